@@ -92,6 +92,14 @@ pub(super) fn abi_type_from_hir_type(context: &Context, typ: &Type) -> AbiType {
             let path = context.fully_qualified_struct_path(context.root_crate_id(), struct_type.id);
             AbiType::Struct { fields, path }
         }
+        Type::Enum(def, args) => {
+            let enum_type = def.borrow();
+            let variants = vecmap(enum_type.get_variants(args), |(name, fields)| {
+                (name, vecmap(fields, |typ| abi_type_from_hir_type(context, &typ)))
+            });
+            let path = context.fully_qualified_enum_path(context.root_crate_id(), enum_type.id);
+            AbiType::Enum { variants, path }
+        }
         Type::Alias(def, args) => abi_type_from_hir_type(context, &def.borrow().get_type(args)),
         Type::Tuple(fields) => {
             let fields = vecmap(fields, |typ| abi_type_from_hir_type(context, typ));
@@ -133,13 +141,15 @@ pub(super) fn compute_function_abi(
 }
 
 /// Attempts to retrieve the name of this parameter. Returns None
-/// if this parameter is a tuple or struct pattern.
+/// if this parameter is a tuple, struct or literal pattern.
 fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Option<&'a str> {
     match pattern {
         HirPattern::Identifier(ident) => Some(interner.definition_name(ident.id)),
         HirPattern::Mutable(pattern, _) => get_param_name(pattern, interner),
         HirPattern::Tuple(_, _) => None,
         HirPattern::Struct(_, _, _) => None,
+        HirPattern::Literal(_, _) => None,
+        HirPattern::Variant(..) => unreachable!("enum variant patterns are refutable"),
     }
 }
 
@@ -15,6 +15,7 @@ use noirc_evaluator::errors::RuntimeError;
 use noirc_evaluator::ssa::SsaProgramArtifact;
 use noirc_frontend::debug::build_debug_crate_file;
 use noirc_frontend::graph::{CrateId, CrateName};
+use noirc_frontend::hir::def_collector::dc_crate::CompilationError;
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
 use noirc_frontend::hir::Context;
 use noirc_frontend::macros_api::MacroProcessor;
@@ -106,6 +107,14 @@ pub struct CompileOptions {
     /// Outputs the paths to any modified artifacts
     #[arg(long, hide = true)]
     pub show_artifact_paths: bool,
+
+    /// Check that the SSA is well-formed after each pass. This is always done in debug builds.
+    #[arg(long, hide = true)]
+    pub verify_ssa: bool,
+
+    /// Leave the SSA pass with this name out of the optimization pipeline, to see what it changes
+    #[arg(long, hide = true)]
+    pub skip_ssa_pass: Vec<String>,
 }
 
 fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::Error> {
@@ -259,11 +268,8 @@ pub fn check_crate(
     disable_macros: bool,
     use_legacy: bool,
 ) -> CompilationResult<()> {
-    let macros: &[&dyn MacroProcessor] =
-        if disable_macros { &[] } else { &[&aztec_macros::AztecMacro as &dyn MacroProcessor] };
-
     let mut errors = vec![];
-    let diagnostics = CrateDefMap::collect_defs(crate_id, context, use_legacy, macros);
+    let diagnostics = collect_crate_errors(context, crate_id, disable_macros, use_legacy);
     errors.extend(diagnostics.into_iter().map(|(error, file_id)| {
         let diagnostic = CustomDiagnostic::from(&error);
         diagnostic.in_file(file_id)
@@ -276,6 +282,20 @@ pub fn check_crate(
     }
 }
 
+/// Run the frontend to check the crate for errors, like [check_crate], but return the errors
+/// as they were found rather than as diagnostics, so that callers can inspect them.
+pub fn collect_crate_errors(
+    context: &mut Context,
+    crate_id: CrateId,
+    disable_macros: bool,
+    use_legacy: bool,
+) -> Vec<(CompilationError, FileId)> {
+    let macros: &[&dyn MacroProcessor] =
+        if disable_macros { &[] } else { &[&aztec_macros::AztecMacro as &dyn MacroProcessor] };
+
+    CrateDefMap::collect_defs(crate_id, context, use_legacy, macros)
+}
+
 pub fn compute_function_abi(
     context: &Context,
     crate_id: &CrateId,
@@ -551,6 +571,8 @@ pub fn compile_no_check(
         options.show_brillig,
         options.force_brillig,
         options.benchmark_codegen,
+        options.verify_ssa || cfg!(debug_assertions),
+        &options.skip_ssa_pass,
     )?;
 
     let abi = abi_gen::gen_abi(context, &main_function, return_visibility, error_types);
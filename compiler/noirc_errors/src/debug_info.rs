@@ -97,6 +97,10 @@ pub struct DebugInfo {
     /// that they should be serialized to/from strings.
     #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
     pub locations: BTreeMap<OpcodeLocation, Vec<Location>>,
+    /// Map the location of each Brillig call opcode into the name of the function it calls
+    #[serde(default)]
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub brillig_function_calls: BTreeMap<OpcodeLocation, String>,
     pub variables: DebugVariables,
     pub functions: DebugFunctions,
     pub types: DebugTypes,
@@ -117,7 +121,7 @@ impl DebugInfo {
         functions: DebugFunctions,
         types: DebugTypes,
     ) -> Self {
-        Self { locations, variables, functions, types }
+        Self { locations, brillig_function_calls: BTreeMap::new(), variables, functions, types }
     }
 
     /// Updates the locations map when the [`Circuit`][acvm::acir::circuit::Circuit] is modified.
@@ -134,6 +138,14 @@ impl DebugInfo {
                 self.locations.insert(new_opcode_location, source_locations.clone());
             });
         }
+
+        let old_function_calls = mem::take(&mut self.brillig_function_calls);
+
+        for (old_opcode_location, name) in old_function_calls {
+            update_map.new_locations(old_opcode_location).for_each(|new_opcode_location| {
+                self.brillig_function_calls.insert(new_opcode_location, name.clone());
+            });
+        }
     }
 
     pub fn opcode_location(&self, loc: &OpcodeLocation) -> Option<Vec<Location>> {
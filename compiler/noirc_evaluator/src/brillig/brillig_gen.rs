@@ -21,11 +21,14 @@ pub(crate) fn convert_ssa_function(
 
     let mut function_context = FunctionContext::new(func);
 
-    brillig_context.enter_context(FunctionContext::function_id_to_function_label(func.id()));
+    let label = FunctionContext::function_id_to_function_label(func.id());
+    brillig_context.enter_context(label.clone());
 
     for block in function_context.blocks.clone() {
         BrilligBlock::compile(&mut function_context, &mut brillig_context, block, &func.dfg);
     }
 
-    brillig_context.artifact()
+    let mut artifact = brillig_context.artifact();
+    artifact.add_function_name(label, func.name().to_string());
+    artifact
 }
@@ -55,6 +55,7 @@ pub(crate) fn directive_invert<F: AcirField>() -> GeneratedBrillig<F> {
         ],
         assert_messages: Default::default(),
         locations: Default::default(),
+        function_calls: Default::default(),
     }
 }
 
@@ -113,6 +114,7 @@ pub(crate) fn directive_quotient<F: AcirField>(bit_size: u32) -> GeneratedBrilli
             ],
             assert_messages: Default::default(),
             locations: Default::default(),
+            function_calls: Default::default(),
         }
     } else {
         // Integer version
@@ -166,6 +168,7 @@ pub(crate) fn directive_quotient<F: AcirField>(bit_size: u32) -> GeneratedBrilli
             ],
             assert_messages: Default::default(),
             locations: Default::default(),
+            function_calls: Default::default(),
         }
     }
 }
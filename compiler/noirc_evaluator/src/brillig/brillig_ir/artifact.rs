@@ -22,6 +22,8 @@ pub(crate) struct GeneratedBrillig<F> {
     pub(crate) byte_code: Vec<BrilligOpcode<F>>,
     pub(crate) locations: BTreeMap<OpcodeLocation, CallStack>,
     pub(crate) assert_messages: BTreeMap<OpcodeLocation, String>,
+    /// A map of the positions of `Call` opcodes to the name of the function they call.
+    pub(crate) function_calls: BTreeMap<OpcodeLocation, String>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -47,6 +49,10 @@ pub(crate) struct BrilligArtifact<F> {
     unresolved_external_call_labels: Vec<(JumpInstructionPosition, UnresolvedJumpLocation)>,
     /// Maps the opcodes that are associated with a callstack to it.
     locations: BTreeMap<OpcodeLocation, CallStack>,
+    /// A map of the positions of `Call` opcodes to the label of the function they call.
+    function_calls: BTreeMap<OpcodeLocation, Label>,
+    /// A map of the labels of the functions in the bytecode to their names.
+    function_names: HashMap<Label, String>,
     /// The current call stack. All opcodes that are pushed will be associated with this call stack.
     call_stack: CallStack,
 }
@@ -77,10 +83,18 @@ impl<F: Clone + std::fmt::Debug> BrilligArtifact<F> {
     /// Resolves all jumps and generates the final bytecode
     pub(crate) fn finish(mut self) -> GeneratedBrillig<F> {
         self.resolve_jumps();
+        let function_calls = self
+            .function_calls
+            .into_iter()
+            .filter_map(|(position, label)| {
+                self.function_names.get(&label).map(|name| (position, name.clone()))
+            })
+            .collect();
         GeneratedBrillig {
             byte_code: self.byte_code,
             locations: self.locations,
             assert_messages: self.assert_messages,
+            function_calls,
         }
     }
 
@@ -151,6 +165,14 @@ impl<F: Clone + std::fmt::Debug> BrilligArtifact<F> {
         for (position_in_bytecode, call_stack) in obj.locations.iter() {
             self.locations.insert(position_in_bytecode + offset, call_stack.clone());
         }
+
+        for (position_in_bytecode, label_id) in &obj.function_calls {
+            self.function_calls.insert(position_in_bytecode + offset, label_id.clone());
+        }
+
+        for (label_id, name) in &obj.function_names {
+            self.function_names.insert(label_id.clone(), name.clone());
+        }
     }
 
     /// Adds a brillig instruction to the brillig byte code
@@ -183,10 +205,18 @@ impl<F: Clone + std::fmt::Debug> BrilligArtifact<F> {
     ) {
         // TODO: Add a check to ensure that the opcode is a call instruction
 
-        self.unresolved_external_call_labels.push((self.index_of_next_opcode(), destination));
+        self.unresolved_external_call_labels
+            .push((self.index_of_next_opcode(), destination.clone()));
+        self.function_calls.insert(self.index_of_next_opcode(), destination);
         self.push_opcode(call_instruction);
     }
 
+    /// Records the name of the function whose code starts at `label`, so that calls to it can be
+    /// attributed to it in the debug info.
+    pub(crate) fn add_function_name(&mut self, label: Label, name: String) {
+        self.function_names.insert(label, name);
+    }
+
     /// Returns true if the opcode is a jump instruction
     fn is_jmp_instruction(instruction: &BrilligOpcode<F>) -> bool {
         matches!(
@@ -12,7 +12,10 @@ use iter_extended::vecmap;
 use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
 use thiserror::Error;
 
-use crate::ssa::ir::{dfg::CallStack, types::NumericType};
+use crate::ssa::{
+    ir::{dfg::CallStack, types::NumericType},
+    pipeline::SsaPass,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, Clone, Error)]
@@ -45,6 +48,8 @@ pub enum RuntimeError {
     UnconstrainedOracleReturnToConstrained { call_stack: CallStack },
     #[error("Could not resolve some references to the array. All references must be resolved at compile time")]
     UnknownReference { call_stack: CallStack },
+    #[error("Cannot skip unknown SSA pass '{name}'")]
+    UnknownSsaPass { name: String, call_stack: CallStack },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,7 +131,8 @@ impl RuntimeError {
             | RuntimeError::BigIntModulus { call_stack, .. }
             | RuntimeError::UnconstrainedSliceReturnToConstrained { call_stack }
             | RuntimeError::UnconstrainedOracleReturnToConstrained { call_stack }
-            | RuntimeError::UnknownReference { call_stack } => call_stack,
+            | RuntimeError::UnknownReference { call_stack }
+            | RuntimeError::UnknownSsaPass { call_stack, .. } => call_stack,
         }
     }
 }
@@ -151,6 +157,14 @@ impl RuntimeError {
                     noirc_errors::Span::inclusive(0, 0)
                 )
             }
+            RuntimeError::UnknownSsaPass { .. } => {
+                let passes = vecmap(SsaPass::all(), SsaPass::name);
+                Diagnostic::simple_error(
+                    self.to_string(),
+                    format!("Known passes are: {}", passes.join(", ")),
+                    noirc_errors::Span::inclusive(0, 0),
+                )
+            }
             RuntimeError::UnknownLoopBound { .. } => {
                 let primary_message = self.to_string();
                 let location =
@@ -32,6 +32,8 @@ use tracing::{span, Level};
 
 use self::{
     acir_gen::{Artifacts, GeneratedAcir},
+    ir::{dfg::CallStack, verify::verify_ssa},
+    pipeline::SsaPass,
     ssa_gen::Ssa,
 };
 
@@ -39,6 +41,8 @@ mod acir_gen;
 pub(super) mod function_builder;
 pub mod ir;
 mod opt;
+mod parser;
+pub mod pipeline;
 pub mod ssa_gen;
 
 /// Optimize the given program by converting it into SSA
@@ -52,37 +56,25 @@ pub(crate) fn optimize_into_acir(
     print_brillig_trace: bool,
     force_brillig_output: bool,
     print_timings: bool,
+    verify_ssa: bool,
+    skip_passes: &[String],
 ) -> Result<Artifacts, RuntimeError> {
+    let unknown_pass = skip_passes
+        .iter()
+        .find(|skipped| SsaPass::all().iter().all(|pass| pass.name() != skipped.as_str()));
+    if let Some(name) = unknown_pass {
+        let call_stack = CallStack::new();
+        return Err(RuntimeError::UnknownSsaPass { name: name.clone(), call_stack });
+    }
+
     let ssa_gen_span = span!(Level::TRACE, "ssa_generation");
     let ssa_gen_span_guard = ssa_gen_span.enter();
-    let ssa = SsaBuilder::new(program, print_passes, force_brillig_output, print_timings)?
-        .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
-        .run_pass(Ssa::remove_paired_rc, "After Removing Paired rc_inc & rc_decs:")
-        .run_pass(Ssa::separate_runtime, "After Runtime Separation:")
-        .run_pass(Ssa::resolve_is_unconstrained, "After Resolving IsUnconstrained:")
-        .run_pass(Ssa::inline_functions, "After Inlining:")
-        // Run mem2reg with the CFG separated into blocks
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::as_slice_optimization, "After `as_slice` optimization")
-        .try_run_pass(Ssa::evaluate_assert_constant, "After Assert Constant:")?
-        .try_run_pass(Ssa::unroll_loops_iteratively, "After Unrolling:")?
-        .run_pass(Ssa::simplify_cfg, "After Simplifying:")
-        .run_pass(Ssa::flatten_cfg, "After Flattening:")
-        .run_pass(Ssa::remove_bit_shifts, "After Removing Bit Shifts:")
-        // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        // Run the inlining pass again to handle functions with `InlineType::NoPredicates`.
-        // Before flattening is run, we treat functions marked with the `InlineType::NoPredicates` as an entry point.
-        // This pass must come immediately following `mem2reg` as the succeeding passes
-        // may create an SSA which inlining fails to handle.
-        .run_pass(Ssa::inline_functions_with_no_predicates, "After Inlining:")
-        .run_pass(Ssa::remove_if_else, "After Remove IfElse:")
-        .run_pass(Ssa::fold_constants, "After Constant Folding:")
-        .run_pass(Ssa::remove_enable_side_effects, "After EnableSideEffects removal:")
-        .run_pass(Ssa::fold_constants_using_constraints, "After Constraint Folding:")
-        .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:")
-        .run_pass(Ssa::array_set_optimization, "After Array Set Optimizations:")
-        .finish();
+    let mut passes = pipeline::default_pipeline();
+    passes.retain(|pass| !skip_passes.iter().any(|skipped| skipped == pass.name()));
+    let ssa =
+        SsaBuilder::new(program, print_passes, force_brillig_output, print_timings, verify_ssa)?
+            .run_passes(&passes)?
+            .finish();
 
     let brillig = time("SSA to Brillig", print_timings, || ssa.to_brillig(print_brillig_trace));
 
@@ -155,6 +147,8 @@ pub fn create_program(
     enable_brillig_logging: bool,
     force_brillig_output: bool,
     print_codegen_timings: bool,
+    verify_ssa: bool,
+    skip_passes: &[String],
 ) -> Result<SsaProgramArtifact, RuntimeError> {
     let debug_variables = program.debug_variables.clone();
     let debug_types = program.debug_types.clone();
@@ -169,6 +163,8 @@ pub fn create_program(
         enable_brillig_logging,
         force_brillig_output,
         print_codegen_timings,
+        verify_ssa,
+        skip_passes,
     )?;
     assert_eq!(
         generated_acirs.len(),
@@ -218,6 +214,7 @@ fn convert_generated_acir_into_circuit(
     let GeneratedAcir {
         return_witnesses,
         locations,
+        brillig_function_calls,
         input_witnesses,
         assertion_payloads: assert_messages,
         warnings,
@@ -249,6 +246,7 @@ fn convert_generated_acir_into_circuit(
         .collect();
 
     let mut debug_info = DebugInfo::new(locations, debug_variables, debug_functions, debug_types);
+    debug_info.brillig_function_calls = brillig_function_calls;
 
     // Perform any ACIR-level optimizations
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
@@ -303,6 +301,8 @@ struct SsaBuilder {
     ssa: Ssa,
     print_ssa_passes: bool,
     print_codegen_timings: bool,
+    /// Whether to check that the SSA is well-formed after each pass
+    verify_ssa: bool,
 }
 
 impl SsaBuilder {
@@ -311,29 +311,34 @@ impl SsaBuilder {
         print_ssa_passes: bool,
         force_brillig_runtime: bool,
         print_codegen_timings: bool,
+        verify_ssa: bool,
     ) -> Result<SsaBuilder, RuntimeError> {
         let ssa = ssa_gen::generate_ssa(program, force_brillig_runtime)?;
-        Ok(SsaBuilder { print_ssa_passes, print_codegen_timings, ssa }.print("Initial SSA:"))
+        let builder = SsaBuilder { print_ssa_passes, print_codegen_timings, verify_ssa, ssa };
+        Ok(builder.print("Initial SSA:").verify("SSA generation"))
     }
 
     fn finish(self) -> Ssa {
         self.ssa
     }
 
-    /// Runs the given SSA pass and prints the SSA afterward if `print_ssa_passes` is true.
-    fn run_pass(mut self, pass: fn(Ssa) -> Ssa, msg: &str) -> Self {
-        self.ssa = time(msg, self.print_codegen_timings, || pass(self.ssa));
-        self.print(msg)
+    /// Runs each of the given SSA passes in turn, printing the SSA after each if `print_ssa_passes` is true.
+    fn run_passes(mut self, passes: &[SsaPass]) -> Result<Self, RuntimeError> {
+        for pass in passes {
+            self.ssa = time(pass.msg(), self.print_codegen_timings, || pass.run(self.ssa))?;
+            self = self.print(pass.msg()).verify(pass.name());
+        }
+        Ok(self)
     }
 
-    /// The same as `run_pass` but for passes that may fail
-    fn try_run_pass(
-        mut self,
-        pass: fn(Ssa) -> Result<Ssa, RuntimeError>,
-        msg: &str,
-    ) -> Result<Self, RuntimeError> {
-        self.ssa = time(msg, self.print_codegen_timings, || pass(self.ssa))?;
-        Ok(self.print(msg))
+    /// Panics if `verify_ssa` is true and the SSA produced by `stage` is malformed.
+    fn verify(self, stage: &str) -> Self {
+        if self.verify_ssa {
+            if let Err(error) = verify_ssa(&self.ssa) {
+                panic!("ICE: SSA is malformed after {stage}: {error}\n{}", self.ssa);
+            }
+        }
+        self
     }
 
     fn print(self, msg: &str) -> Self {
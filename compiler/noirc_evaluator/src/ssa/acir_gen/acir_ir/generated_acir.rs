@@ -52,6 +52,9 @@ pub(crate) struct GeneratedAcir<F: AcirField> {
     /// Correspondence between an opcode index (in opcodes) and the source code call stack which generated it
     pub(crate) locations: BTreeMap<OpcodeLocation, CallStack>,
 
+    /// Correspondence between the location of a Brillig `Call` opcode and the name of the function it calls
+    pub(crate) brillig_function_calls: BTreeMap<OpcodeLocation, String>,
+
     /// Source code location of the current instruction being processed
     /// None if we do not know the location
     pub(crate) call_stack: CallStack,
@@ -604,6 +607,15 @@ impl<F: AcirField> GeneratedAcir<F> {
                 call_stack.clone(),
             );
         }
+        for (brillig_index, name) in generated_brillig.function_calls.iter() {
+            self.brillig_function_calls.insert(
+                OpcodeLocation::Brillig {
+                    acir_index: self.opcodes.len() - 1,
+                    brillig_index: *brillig_index,
+                },
+                name.clone(),
+            );
+        }
         for (brillig_index, message) in generated_brillig.assert_messages.iter() {
             self.assertion_payloads.insert(
                 OpcodeLocation::Brillig {
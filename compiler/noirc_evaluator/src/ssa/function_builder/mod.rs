@@ -37,6 +37,7 @@ pub(crate) struct FunctionBuilder {
     finished_functions: Vec<Function>,
     call_stack: CallStack,
     error_types: BTreeMap<ErrorSelector, ErrorType>,
+    simplify: bool,
 }
 
 impl FunctionBuilder {
@@ -53,9 +54,16 @@ impl FunctionBuilder {
             finished_functions: Vec::new(),
             call_stack: CallStack::new(),
             error_types: BTreeMap::default(),
+            simplify: true,
         }
     }
 
+    /// Sets whether instructions are simplified as they're inserted, which they are by default.
+    /// Turning this off keeps the instructions exactly as they're given, e.g. when parsing SSA.
+    pub(crate) fn set_simplify(&mut self, simplify: bool) {
+        self.simplify = simplify;
+    }
+
     /// Set the runtime of the initial function that is created internally after constructing
     /// the FunctionBuilder. A function's default runtime type is `RuntimeType::Acir(InlineType::Inline)`.
     /// This should only be used immediately following construction of a FunctionBuilder
@@ -165,6 +173,14 @@ impl FunctionBuilder {
         ctrl_typevars: Option<Vec<Type>>,
     ) -> InsertInstructionResult {
         let block = self.current_block();
+        if !self.simplify {
+            return self.current_function.dfg.insert_instruction_without_simplification(
+                instruction,
+                block,
+                ctrl_typevars,
+                self.call_stack.clone(),
+            );
+        }
         self.current_function.dfg.insert_instruction_and_results(
             instruction,
             block,
@@ -280,7 +280,14 @@ impl Instruction {
     }
 
     /// Indicates if the instruction can be safely replaced with the results of another instruction with the same inputs.
-    pub(crate) fn can_be_deduplicated(&self, dfg: &DataFlowGraph) -> bool {
+    ///
+    /// If `deduplicate_with_predicate` is set, the other instruction is known to have been executed under the
+    /// same `EnableSideEffectsIf` predicate, so instructions which depend on the predicate may also be replaced.
+    pub(crate) fn can_be_deduplicated(
+        &self,
+        dfg: &DataFlowGraph,
+        deduplicate_with_predicate: bool,
+    ) -> bool {
         use Instruction::*;
 
         match self {
@@ -294,6 +301,9 @@ impl Instruction {
             | DecrementRc { .. }
             | RangeCheck { .. } => false,
 
+            // An array may be mutated in place by `array_set` in Brillig
+            ArraySet { .. } => false,
+
             Call { func, .. } => match dfg[*func] {
                 Value::Intrinsic(intrinsic) => !intrinsic.has_side_effects(),
                 _ => false,
@@ -303,13 +313,16 @@ impl Instruction {
             // Replacing them with a similar instruction potentially enables replacing an instruction
             // with one that was disabled. See
             // https://github.com/noir-lang/noir/pull/4716#issuecomment-2047846328.
-            Binary(_)
-            | Cast(_, _)
-            | Not(_)
-            | Truncate { .. }
-            | IfElse { .. }
-            | ArrayGet { .. }
-            | ArraySet { .. } => !self.requires_acir_gen_predicate(dfg),
+            Binary(_) | Cast(_, _) | Not(_) | Truncate { .. } | IfElse { .. } => {
+                deduplicate_with_predicate || !self.requires_acir_gen_predicate(dfg)
+            }
+
+            // A disabled read of an array is redirected to a dummy index, so it can only be replaced
+            // by another read under the same predicate. Only reads of known indices are replaced.
+            ArrayGet { index, .. } => {
+                (deduplicate_with_predicate || !self.requires_acir_gen_predicate(dfg))
+                    && dfg.is_constant(*index)
+            }
         }
     }
 
@@ -41,6 +41,15 @@ impl<T> Id<T> {
     }
 }
 
+impl Id<super::function::Function> {
+    /// Creates the Id of a function from the index it was displayed with, e.g. `f1`.
+    /// Unlike other Ids, function Ids aren't bound to a particular map, so this is used
+    /// to keep the Ids of functions which are parsed from their textual form.
+    pub(crate) fn from_displayed_index(index: usize) -> Self {
+        Self::new(index)
+    }
+}
+
 // Need to manually implement most impls on Id.
 // Otherwise rust assumes that Id<T>: Hash only if T: Hash,
 // which isn't true since the T is not used internally.
@@ -6,6 +6,7 @@ use std::{
 
 use acvm::acir::circuit::{ErrorSelector, STRING_ERROR_SELECTOR};
 use acvm::acir::AcirField;
+use acvm::FieldElement;
 use iter_extended::vecmap;
 
 use super::{
@@ -66,17 +67,28 @@ fn value(function: &Function, id: ValueId) -> String {
     let id = function.dfg.resolve(id);
     match &function.dfg[id] {
         Value::NumericConstant { constant, typ } => {
-            format!("{typ} {constant}")
+            format!("{typ} {}", numeric_constant(*constant))
         }
         Value::Function(id) => id.to_string(),
         Value::Intrinsic(intrinsic) => intrinsic.to_string(),
-        Value::Array { array, .. } => {
+        Value::ForeignFunction(name) => name.clone(),
+        Value::Array { array, typ } => {
             let elements = vecmap(array, |element| value(function, *element));
-            format!("[{}]", elements.join(", "))
-        }
-        Value::Param { .. } | Value::Instruction { .. } | Value::ForeignFunction(_) => {
-            id.to_string()
+            format!("[{}] of {typ}", elements.join(", "))
         }
+        Value::Param { .. } | Value::Instruction { .. } => id.to_string(),
+    }
+}
+
+/// Display a constant as a plain integer so that it can be parsed back in. Values which don't fit
+/// in a `u128` are displayed as negative numbers if their negation does, or in hex otherwise.
+fn numeric_constant(constant: FieldElement) -> String {
+    if let Some(constant) = constant.try_into_u128() {
+        constant.to_string()
+    } else if let Some(negated) = (-constant).try_into_u128() {
+        format!("-{negated}")
+    } else {
+        format!("0x{}", constant.to_hex())
     }
 }
 
@@ -95,6 +107,16 @@ fn value_list(function: &Function, values: &[ValueId]) -> String {
     vecmap(values, |id| value(function, *id)).join(", ")
 }
 
+/// Display the types of an instruction's results, e.g. ` -> Field, u32`, for the instructions
+/// whose result types can't be inferred from their operands.
+fn result_types(function: &Function, results: &[ValueId]) -> String {
+    if results.is_empty() {
+        return String::new();
+    }
+    let types = vecmap(results, |id| function.dfg.type_of_value(*id).to_string());
+    format!(" -> {}", types.join(", "))
+}
+
 /// Display a terminator instruction
 pub(crate) fn display_terminator(
     function: &Function,
@@ -135,12 +157,13 @@ pub(crate) fn display_instruction(
         write!(f, "{} = ", value_list(function, results))?;
     }
 
-    display_instruction_inner(function, &function.dfg[instruction], f)
+    display_instruction_inner(function, &function.dfg[instruction], results, f)
 }
 
 fn display_instruction_inner(
     function: &Function,
     instruction: &Instruction,
+    results: &[ValueId],
     f: &mut Formatter,
 ) -> Result {
     let show = |id| value(function, id);
@@ -164,10 +187,13 @@ fn display_instruction_inner(
             }
         }
         Instruction::Call { func, arguments } => {
-            writeln!(f, "call {}({})", show(*func), value_list(function, arguments))
+            let arguments = value_list(function, arguments);
+            writeln!(f, "call {}({arguments}){}", show(*func), result_types(function, results))
+        }
+        Instruction::Allocate => writeln!(f, "allocate{}", result_types(function, results)),
+        Instruction::Load { address } => {
+            writeln!(f, "load {}{}", show(*address), result_types(function, results))
         }
-        Instruction::Allocate => writeln!(f, "allocate"),
-        Instruction::Load { address } => writeln!(f, "load {}", show(*address)),
         Instruction::Store { address, value } => {
             writeln!(f, "store {} at {}", show(*value), show(*address))
         }
@@ -175,7 +201,8 @@ fn display_instruction_inner(
             writeln!(f, "enable_side_effects {}", show(*condition))
         }
         Instruction::ArrayGet { array, index } => {
-            writeln!(f, "array_get {}, index {}", show(*array), show(*index))
+            let results = result_types(function, results);
+            writeln!(f, "array_get {}, index {}{results}", show(*array), show(*index))
         }
         Instruction::ArraySet { array, index, value, mutable } => {
             let array = show(*array);
@@ -241,15 +268,16 @@ fn display_constrain_error(
 ) -> Result {
     match error {
         ConstrainError::Intrinsic(assert_message_string) => {
-            writeln!(f, " '{assert_message_string:?}'")
+            writeln!(f, ", {assert_message_string:?}")
         }
         ConstrainError::UserDefined(selector, values) => {
             if let Some(constant_string) =
                 try_to_extract_string_from_error_payload(*selector, values, &function.dfg)
             {
-                writeln!(f, " '{}'", constant_string)
+                writeln!(f, ", data {constant_string:?}")
             } else {
-                writeln!(f, ", data {}", value_list(function, values))
+                let values = value_list(function, values);
+                writeln!(f, ", data {}: {values}", selector.as_u64())
             }
         }
     }
@@ -0,0 +1,503 @@
+//! Checks that SSA is well-formed, so that a pass which leaves the SSA in a broken state is
+//! caught right after it runs rather than when the SSA is later converted into ACIR or Brillig.
+//!
+//! The checks made on each function are that:
+//! - every block jumped to exists, has a terminator, and is passed an argument for each of
+//!   its parameters,
+//! - the entry block isn't jumped to,
+//! - every value used is defined in a block which dominates the use, and before the use if
+//!   it's defined in the same block,
+//! - the operands of instructions have the types those instructions expect.
+//!
+//! Only blocks which are reachable from the entry block are checked, as unreachable blocks are
+//! never converted to ACIR or Brillig.
+use std::collections::{BTreeSet, HashMap};
+
+use thiserror::Error;
+
+use crate::ssa::ssa_gen::Ssa;
+
+use super::{
+    basic_block::BasicBlockId,
+    dom::DominatorTree,
+    function::{Function, FunctionId},
+    instruction::{BinaryOp, Instruction, InstructionId, TerminatorInstruction},
+    types::Type,
+    value::{Value, ValueId},
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum VerificationError {
+    #[error("main function {0} does not exist")]
+    MissingMain(FunctionId),
+    #[error("function {id} is stored under the id {key}")]
+    MismatchedFunctionId { key: FunctionId, id: FunctionId },
+    #[error("{block} in {function} has no terminator")]
+    MissingTerminator { function: FunctionId, block: BasicBlockId },
+    #[error("{block} in {function} jumps to {destination}, which does not exist")]
+    UnknownBlock { function: FunctionId, block: BasicBlockId, destination: BasicBlockId },
+    #[error("{block} in {function} jumps to the entry block")]
+    JumpToEntryBlock { function: FunctionId, block: BasicBlockId },
+    #[error("{block} in {function} passes {arguments} arguments to {destination}, which has {parameters} parameters")]
+    MismatchedArguments {
+        function: FunctionId,
+        block: BasicBlockId,
+        destination: BasicBlockId,
+        arguments: usize,
+        parameters: usize,
+    },
+    #[error("instruction {index} of {block} in {function} does not exist")]
+    UnknownInstruction { function: FunctionId, block: BasicBlockId, index: usize },
+    #[error("{value} is used in {block} of {function} but does not exist")]
+    UnknownValue { function: FunctionId, block: BasicBlockId, value: ValueId },
+    #[error("{value} is used in {block} of {function} but is not defined in a reachable block")]
+    UndefinedValue { function: FunctionId, block: BasicBlockId, value: ValueId },
+    #[error("{value} is used in {block} of {function} before it's defined")]
+    UseBeforeDefinition { function: FunctionId, block: BasicBlockId, value: ValueId },
+    #[error("{value} is used in {block} of {function} as {expected} but has type {found}")]
+    InvalidOperandType {
+        function: FunctionId,
+        block: BasicBlockId,
+        value: ValueId,
+        expected: &'static str,
+        found: Type,
+    },
+    #[error("{lhs} and {rhs} are used together in {block} of {function} but have types {lhs_type} and {rhs_type}")]
+    MismatchedOperandTypes {
+        function: FunctionId,
+        block: BasicBlockId,
+        lhs: ValueId,
+        rhs: ValueId,
+        lhs_type: Type,
+        rhs_type: Type,
+    },
+}
+
+/// Checks that each function of `ssa` is well-formed, returning the first problem found.
+pub(crate) fn verify_ssa(ssa: &Ssa) -> Result<(), VerificationError> {
+    if !ssa.functions.contains_key(&ssa.main_id) {
+        return Err(VerificationError::MissingMain(ssa.main_id));
+    }
+    for (key, function) in &ssa.functions {
+        if function.id() != *key {
+            return Err(VerificationError::MismatchedFunctionId { key: *key, id: function.id() });
+        }
+        verify_function(function)?;
+    }
+    Ok(())
+}
+
+/// Checks that `function` is well-formed, returning the first problem found.
+pub(crate) fn verify_function(function: &Function) -> Result<(), VerificationError> {
+    let reachable_blocks = check_control_flow(function)?;
+    let mut verifier = FunctionVerifier::new(function, reachable_blocks)?;
+    for block in verifier.reachable_blocks.clone() {
+        verifier.verify_block(block)?;
+    }
+    Ok(())
+}
+
+/// Finds the blocks reachable from the entry block, checking along the way that each one has a
+/// terminator which only jumps to blocks that exist, and never to the entry block.
+///
+/// This is checked before anything else as building a [`DominatorTree`] relies on it.
+fn check_control_flow(function: &Function) -> Result<BTreeSet<BasicBlockId>, VerificationError> {
+    let id = function.id();
+    let dfg = &function.dfg;
+    let entry_block = function.entry_block();
+    let num_blocks = dfg.basic_blocks_iter().len();
+
+    let mut reachable_blocks = BTreeSet::from([entry_block]);
+    let mut stack = vec![entry_block];
+    while let Some(block) = stack.pop() {
+        if dfg[block].terminator().is_none() {
+            return Err(VerificationError::MissingTerminator { function: id, block });
+        }
+        for destination in dfg[block].successors() {
+            if destination.to_usize() >= num_blocks {
+                return Err(VerificationError::UnknownBlock { function: id, block, destination });
+            }
+            if destination == entry_block {
+                return Err(VerificationError::JumpToEntryBlock { function: id, block });
+            }
+            if reachable_blocks.insert(destination) {
+                stack.push(destination);
+            }
+        }
+    }
+    Ok(reachable_blocks)
+}
+
+struct FunctionVerifier<'f> {
+    function: &'f Function,
+    reachable_blocks: BTreeSet<BasicBlockId>,
+    dom_tree: DominatorTree,
+
+    /// The block of each instruction in a reachable block, along with its index in that block
+    definitions: HashMap<InstructionId, (BasicBlockId, usize)>,
+
+    num_values: usize,
+}
+
+impl<'f> FunctionVerifier<'f> {
+    fn new(
+        function: &'f Function,
+        reachable_blocks: BTreeSet<BasicBlockId>,
+    ) -> Result<Self, VerificationError> {
+        let num_instructions = function.dfg.num_instructions();
+        let mut definitions = HashMap::default();
+        for block in &reachable_blocks {
+            for (index, instruction) in function.dfg[*block].instructions().iter().enumerate() {
+                if instruction.to_usize() >= num_instructions {
+                    let function = function.id();
+                    let block = *block;
+                    return Err(VerificationError::UnknownInstruction { function, block, index });
+                }
+                definitions.insert(*instruction, (*block, index));
+            }
+        }
+
+        Ok(FunctionVerifier {
+            function,
+            reachable_blocks,
+            dom_tree: DominatorTree::with_function(function),
+            definitions,
+            num_values: function.dfg.values_iter().len(),
+        })
+    }
+
+    fn verify_block(&mut self, block: BasicBlockId) -> Result<(), VerificationError> {
+        let function = self.function;
+        let dfg = &function.dfg;
+        for (index, instruction) in dfg[block].instructions().iter().enumerate() {
+            let instruction = &dfg[*instruction];
+
+            let mut operands = Vec::new();
+            instruction.for_each_value(|value| operands.push(value));
+            for operand in operands {
+                self.verify_use(operand, block, Some(index))?;
+            }
+            self.verify_operand_types(instruction, block)?;
+        }
+
+        let terminator = dfg[block].unwrap_terminator();
+        let mut operands = Vec::new();
+        terminator.for_each_value(|value| operands.push(value));
+        for operand in operands {
+            self.verify_use(operand, block, None)?;
+        }
+        self.verify_terminator(terminator, block)
+    }
+
+    /// Checks that `value`, used by the instruction at `index` in `block` or by its terminator
+    /// if `index` is `None`, is defined somewhere which dominates that use.
+    fn verify_use(
+        &mut self,
+        value: ValueId,
+        block: BasicBlockId,
+        index: Option<usize>,
+    ) -> Result<(), VerificationError> {
+        let dfg = &self.function.dfg;
+        let function = self.function.id();
+        if value.to_usize() >= self.num_values {
+            return Err(VerificationError::UnknownValue { function, block, value });
+        }
+
+        let definition_block = match &dfg[value] {
+            Value::Instruction { instruction, .. } => {
+                let Some((definition_block, definition_index)) =
+                    self.definitions.get(instruction).copied()
+                else {
+                    let value = dfg.resolve(value);
+                    return Err(VerificationError::UndefinedValue { function, block, value });
+                };
+                let defined_after_use = index.is_some_and(|index| definition_index >= index);
+                if definition_block == block && defined_after_use {
+                    let value = dfg.resolve(value);
+                    return Err(VerificationError::UseBeforeDefinition { function, block, value });
+                }
+                definition_block
+            }
+            Value::Param { block: definition_block, position, .. } => {
+                let is_parameter = self.reachable_blocks.contains(definition_block)
+                    && *position < dfg.block_parameters(*definition_block).len();
+                if !is_parameter {
+                    let value = dfg.resolve(value);
+                    return Err(VerificationError::UndefinedValue { function, block, value });
+                }
+                *definition_block
+            }
+            // An array constant can hold values defined by instructions, which must be usable here
+            Value::Array { array, .. } => {
+                for element in array.clone() {
+                    self.verify_use(element, block, index)?;
+                }
+                return Ok(());
+            }
+            // Other constants and functions can be used anywhere
+            Value::NumericConstant { .. }
+            | Value::Function(_)
+            | Value::Intrinsic(_)
+            | Value::ForeignFunction(_) => return Ok(()),
+        };
+
+        if !self.dom_tree.dominates(definition_block, block) {
+            let value = dfg.resolve(value);
+            return Err(VerificationError::UseBeforeDefinition { function, block, value });
+        }
+        Ok(())
+    }
+
+    fn verify_operand_types(
+        &self,
+        instruction: &Instruction,
+        block: BasicBlockId,
+    ) -> Result<(), VerificationError> {
+        match instruction {
+            Instruction::Binary(binary) => {
+                self.expect_numeric(binary.lhs, block)?;
+                self.expect_numeric(binary.rhs, block)?;
+
+                // This mirrors what ACIR generation accepts: a Field may be combined with any
+                // numeric type, while shifts take their number of bits as a `u8`.
+                let lhs_type = self.function.dfg.type_of_value(binary.lhs);
+                let rhs_type = self.function.dfg.type_of_value(binary.rhs);
+                let is_shift = matches!(binary.operator, BinaryOp::Shl | BinaryOp::Shr);
+                let has_field = lhs_type == Type::field() || rhs_type == Type::field();
+                if lhs_type != rhs_type && !is_shift && !has_field {
+                    let dfg = &self.function.dfg;
+                    return Err(VerificationError::MismatchedOperandTypes {
+                        function: self.function.id(),
+                        block,
+                        lhs: dfg.resolve(binary.lhs),
+                        rhs: dfg.resolve(binary.rhs),
+                        lhs_type,
+                        rhs_type,
+                    });
+                }
+                Ok(())
+            }
+            Instruction::Not(value)
+            | Instruction::Truncate { value, .. }
+            | Instruction::RangeCheck { value, .. }
+            | Instruction::EnableSideEffects { condition: value } => {
+                self.expect_numeric(*value, block)
+            }
+            Instruction::Load { address } | Instruction::Store { address, .. } => {
+                let typ = self.function.dfg.type_of_value(*address);
+                self.expect_type(*address, block, "a reference", matches!(typ, Type::Reference(_)))
+            }
+            Instruction::ArrayGet { array, index } | Instruction::ArraySet { array, index, .. } => {
+                let typ = self.function.dfg.type_of_value(*array);
+                let is_array = matches!(typ, Type::Array(..) | Type::Slice(_));
+                self.expect_type(*array, block, "an array or slice", is_array)?;
+                self.expect_numeric(*index, block)
+            }
+            Instruction::Call { func, .. } => {
+                let typ = self.function.dfg.type_of_value(*func);
+                self.expect_type(*func, block, "a function", typ == Type::Function)
+            }
+            Instruction::Cast(..)
+            | Instruction::Constrain(..)
+            | Instruction::Allocate
+            | Instruction::IncrementRc { .. }
+            | Instruction::DecrementRc { .. }
+            | Instruction::IfElse { .. } => Ok(()),
+        }
+    }
+
+    fn verify_terminator(
+        &self,
+        terminator: &TerminatorInstruction,
+        block: BasicBlockId,
+    ) -> Result<(), VerificationError> {
+        let dfg = &self.function.dfg;
+        let check_arguments = |destination: BasicBlockId, arguments: usize| {
+            let parameters = dfg.block_parameters(destination).len();
+            if arguments == parameters {
+                Ok(())
+            } else {
+                let function = self.function.id();
+                Err(VerificationError::MismatchedArguments {
+                    function,
+                    block,
+                    destination,
+                    arguments,
+                    parameters,
+                })
+            }
+        };
+
+        match terminator {
+            TerminatorInstruction::Jmp { destination, arguments, .. } => {
+                check_arguments(*destination, arguments.len())
+            }
+            TerminatorInstruction::JmpIf { condition, then_destination, else_destination } => {
+                let is_bool = dfg.type_of_value(*condition) == Type::bool();
+                self.expect_type(*condition, block, "a boolean", is_bool)?;
+                check_arguments(*then_destination, 0)?;
+                check_arguments(*else_destination, 0)
+            }
+            TerminatorInstruction::Return { .. } => Ok(()),
+        }
+    }
+
+    fn expect_numeric(&self, value: ValueId, block: BasicBlockId) -> Result<(), VerificationError> {
+        let typ = self.function.dfg.type_of_value(value);
+        self.expect_type(value, block, "a number", matches!(typ, Type::Numeric(_)))
+    }
+
+    /// Returns an error unless `is_expected_type`, which says whether `value` has the type
+    /// described by `expected`.
+    fn expect_type(
+        &self,
+        value: ValueId,
+        block: BasicBlockId,
+        expected: &'static str,
+        is_expected_type: bool,
+    ) -> Result<(), VerificationError> {
+        if is_expected_type {
+            return Ok(());
+        }
+        Err(VerificationError::InvalidOperandType {
+            function: self.function.id(),
+            block,
+            value: self.function.dfg.resolve(value),
+            expected,
+            found: self.function.dfg.type_of_value(value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::ssa::ssa_gen::Ssa;
+
+    use super::{verify_ssa, VerificationError};
+
+    fn verify(src: &str) -> Result<(), VerificationError> {
+        verify_ssa(&Ssa::from_str(src).unwrap())
+    }
+
+    #[test]
+    fn accepts_well_formed_ssa() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field, v1: u1):
+                v2 = allocate -> &mut Field
+                store v0 at v2
+                jmpif v1 then: b1, else: b2
+              b1():
+                v3 = load v2 -> Field
+                jmp b3(v3)
+              b2():
+                jmp b3(v0)
+              b3(v4: Field):
+                v5 = add v4, v0
+                v6 = cast v1 as u32
+                v7 = shl v6, u8 2
+                v8 = mul v7, Field 3
+                return v5, v8
+            }
+            ";
+        assert_eq!(verify(src), Ok(()));
+    }
+
+    #[test]
+    fn rejects_use_of_value_from_non_dominating_block() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field, v1: u1):
+                jmpif v1 then: b1, else: b2
+              b1():
+                v2 = add v0, Field 1
+                jmp b2()
+              b2():
+                return v2
+            }
+            ";
+        let error = verify(src).unwrap_err();
+        assert!(matches!(error, VerificationError::UseBeforeDefinition { .. }));
+    }
+
+    #[test]
+    fn rejects_array_element_from_non_dominating_block() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field, v1: u1):
+                jmpif v1 then: b1, else: b2
+              b1():
+                v2 = add v0, Field 1
+                jmp b2()
+              b2():
+                return [v0, v2] of [Field; 2]
+            }
+            ";
+        let error = verify(src).unwrap_err();
+        assert!(matches!(error, VerificationError::UseBeforeDefinition { .. }));
+    }
+
+    #[test]
+    fn rejects_mismatched_jump_arguments() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                jmp b1(v0, v0)
+              b1(v1: Field):
+                return v1
+            }
+            ";
+        let error = verify(src).unwrap_err();
+        assert!(matches!(
+            error,
+            VerificationError::MismatchedArguments { arguments: 2, parameters: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_jump_to_entry_block() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u1):
+                jmpif v0 then: b1, else: b2
+              b1():
+                jmp b0()
+              b2():
+                return
+            }
+            ";
+        let error = verify(src).unwrap_err();
+        assert!(matches!(error, VerificationError::JumpToEntryBlock { .. }));
+    }
+
+    #[test]
+    fn rejects_mismatched_binary_operands() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: u32, v1: u64):
+                v2 = add v0, v1
+                return v2
+            }
+            ";
+        let error = verify(src).unwrap_err();
+        assert!(matches!(error, VerificationError::MismatchedOperandTypes { .. }));
+    }
+
+    #[test]
+    fn rejects_load_from_non_reference() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                v1 = load v0 -> Field
+                return v1
+            }
+            ";
+        let error = verify(src).unwrap_err();
+        assert!(matches!(
+            error,
+            VerificationError::InvalidOperandType { expected: "a reference", .. }
+        ));
+    }
+}
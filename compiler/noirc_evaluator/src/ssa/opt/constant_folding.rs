@@ -6,8 +6,9 @@
 //!   by the [`DataFlowGraph`] automatically as new instructions are pushed.
 //! - Check whether any input values have been constrained to be equal to a value of a simpler form
 //!   by a [constrain instruction][Instruction::Constrain]. If so, replace the input value with the simpler form.
-//! - Check whether the instruction [can_be_replaced][Instruction::can_be_replaced()]
-//!   by duplicate instruction earlier in the same block.
+//! - Check whether the instruction [can_be_deduplicated][Instruction::can_be_deduplicated()]
+//!   and has a duplicate earlier in a block which dominates it, either earlier in the same block or
+//!   in a block which must have been executed before reaching this one. If so, reuse its results.
 //!
 //! These operations are done in parallel so that they can each benefit from each other
 //! without the need for multiple passes.
@@ -19,6 +20,8 @@
 //!
 //! This is the only pass which removes duplicated pure [`Instruction`]s however and so is needed when
 //! different blocks are merged, i.e. after the [`flatten_cfg`][super::flatten_cfg] pass.
+//! Duplicates across blocks matter for Brillig functions, which are never flattened, and for ACIR
+//! functions before flattening.
 use std::collections::HashSet;
 
 use acvm::{acir::AcirField, FieldElement};
@@ -28,6 +31,7 @@ use crate::ssa::{
     ir::{
         basic_block::BasicBlockId,
         dfg::{DataFlowGraph, InsertInstructionResult},
+        dom::DominatorTree,
         function::Function,
         instruction::{Instruction, InstructionId},
         types::Type,
@@ -66,7 +70,7 @@ impl Ssa {
 /// The structure of this pass is simple:
 /// Go through each block and re-insert all instructions.
 fn constant_fold(function: &mut Function, use_constraint_info: bool) {
-    let mut context = Context { use_constraint_info, ..Default::default() };
+    let mut context = Context::new(function, use_constraint_info);
     context.block_queue.push(function.entry_block());
 
     while let Some(block) = context.block_queue.pop() {
@@ -79,21 +83,39 @@ fn constant_fold(function: &mut Function, use_constraint_info: bool) {
     }
 }
 
-#[derive(Default)]
 struct Context {
     use_constraint_info: bool,
     /// Maps pre-folded ValueIds to the new ValueIds obtained by re-inserting the instruction.
     visited_blocks: HashSet<BasicBlockId>,
     block_queue: Vec<BasicBlockId>,
+
+    /// Cache of instructions without any side-effects along with their outputs.
+    ///
+    /// Blocks are visited after every block which dominates them, so a duplicate of any instruction
+    /// in a dominating block has been cached by the time the block is visited.
+    cached_instruction_results: InstructionResultCache,
+    dom_tree: DominatorTree,
 }
 
+/// Maps each instruction to the predicate it was executed under, if its results depend on the
+/// predicate, and then to the blocks it's in along with its results in each.
+type InstructionResultCache =
+    HashMap<Instruction, HashMap<Option<ValueId>, Vec<(BasicBlockId, Vec<ValueId>)>>>;
+
 impl Context {
+    fn new(function: &Function, use_constraint_info: bool) -> Self {
+        Context {
+            use_constraint_info,
+            visited_blocks: HashSet::new(),
+            block_queue: Vec::new(),
+            cached_instruction_results: HashMap::default(),
+            dom_tree: DominatorTree::with_function(function),
+        }
+    }
+
     fn fold_constants_in_block(&mut self, function: &mut Function, block: BasicBlockId) {
         let instructions = function.dfg[block].take_instructions();
 
-        // Cache of instructions without any side-effects along with their outputs.
-        let mut cached_instruction_results: HashMap<Instruction, Vec<ValueId>> = HashMap::default();
-
         // Contains sets of values which are constrained to be equivalent to each other.
         //
         // The mapping's structure is `side_effects_enabled_var => (constrained_value => simplified_value)`.
@@ -111,7 +133,6 @@ impl Context {
                 &mut function.dfg,
                 block,
                 instruction_id,
-                &mut cached_instruction_results,
                 &mut constraint_simplification_mappings,
                 &mut side_effects_enabled_var,
             );
@@ -120,11 +141,10 @@ impl Context {
     }
 
     fn fold_constants_into_instruction(
-        &self,
+        &mut self,
         dfg: &mut DataFlowGraph,
         block: BasicBlockId,
         id: InstructionId,
-        instruction_result_cache: &mut HashMap<Instruction, Vec<ValueId>>,
         constraint_simplification_mappings: &mut HashMap<ValueId, HashMap<ValueId, ValueId>>,
         side_effects_enabled_var: &mut ValueId,
     ) {
@@ -133,9 +153,11 @@ impl Context {
         let instruction = Self::resolve_instruction(id, dfg, constraint_simplification_mapping);
         let old_results = dfg.instruction_results(id).to_vec();
 
-        // If a copy of this instruction exists earlier in the block, then reuse the previous results.
-        if let Some(cached_results) = instruction_result_cache.get(&instruction) {
-            Self::replace_result_ids(dfg, &old_results, cached_results);
+        // If a copy of this instruction exists earlier in a dominating block, then reuse the previous results.
+        if let Some(cached_results) =
+            self.get_cached_results(&instruction, dfg, block, *side_effects_enabled_var)
+        {
+            Self::replace_result_ids(dfg, &old_results, &cached_results);
             return;
         }
 
@@ -148,7 +170,8 @@ impl Context {
             instruction.clone(),
             new_results,
             dfg,
-            instruction_result_cache,
+            block,
+            *side_effects_enabled_var,
             constraint_simplification_mapping,
         );
 
@@ -220,11 +243,12 @@ impl Context {
     }
 
     fn cache_instruction(
-        &self,
+        &mut self,
         instruction: Instruction,
         instruction_results: Vec<ValueId>,
         dfg: &DataFlowGraph,
-        instruction_result_cache: &mut HashMap<Instruction, Vec<ValueId>>,
+        block: BasicBlockId,
+        side_effects_enabled_var: ValueId,
         constraint_simplification_mapping: &mut HashMap<ValueId, ValueId>,
     ) {
         if self.use_constraint_info {
@@ -256,10 +280,50 @@ impl Context {
             }
         }
 
-        // If the instruction doesn't have side-effects and if it won't interact with enable_side_effects during acir_gen,
-        // we cache the results so we can reuse them if the same instruction appears again later in the block.
-        if instruction.can_be_deduplicated(dfg) {
-            instruction_result_cache.insert(instruction, instruction_results);
+        // If the instruction doesn't have side-effects, we cache the results so we can reuse them if the same
+        // instruction appears again later in the block or in a block it dominates.
+        if let Some(predicate) = Self::cache_predicate(&instruction, dfg, side_effects_enabled_var)
+        {
+            self.cached_instruction_results
+                .entry(instruction)
+                .or_default()
+                .entry(predicate)
+                .or_default()
+                .push((block, instruction_results));
+        }
+    }
+
+    /// Returns the results of a copy of `instruction` in a block which dominates `block`, if one has been cached.
+    fn get_cached_results(
+        &mut self,
+        instruction: &Instruction,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        side_effects_enabled_var: ValueId,
+    ) -> Option<Vec<ValueId>> {
+        let predicate = Self::cache_predicate(instruction, dfg, side_effects_enabled_var)?;
+        let cached = self.cached_instruction_results.get(instruction)?.get(&predicate)?;
+        let (_, results) = cached
+            .iter()
+            .find(|(cached_block, _)| self.dom_tree.dominates(*cached_block, block))?;
+        Some(results.clone())
+    }
+
+    /// Returns the predicate under which the results of `instruction` are cached, or `None` if they can't be reused.
+    ///
+    /// Instructions which won't interact with enable_side_effects during acir_gen are cached regardless of the
+    /// predicate they're executed under. Others can only reuse the results of a copy under the same predicate.
+    fn cache_predicate(
+        instruction: &Instruction,
+        dfg: &DataFlowGraph,
+        side_effects_enabled_var: ValueId,
+    ) -> Option<Option<ValueId>> {
+        if instruction.can_be_deduplicated(dfg, false) {
+            Some(None)
+        } else if instruction.can_be_deduplicated(dfg, true) {
+            Some(Some(side_effects_enabled_var))
+        } else {
+            None
         }
     }
 
@@ -277,7 +341,7 @@ impl Context {
 
 #[cfg(test)]
 mod test {
-    use std::rc::Rc;
+    use std::{rc::Rc, str::FromStr};
 
     use crate::ssa::{
         function_builder::FunctionBuilder,
@@ -287,9 +351,115 @@ mod test {
             types::Type,
             value::{Value, ValueId},
         },
+        opt::assert_normalized_ssa_equals,
+        ssa_gen::Ssa,
     };
     use acvm::acir::AcirField;
 
+    #[test]
+    fn deduplicates_instructions_in_the_same_block() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                v2 = add v0, Field 1
+                v3 = add v0, Field 1
+                v4 = mul v2, v3
+                return v4
+            }
+            ";
+        let expected = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                v2 = add v0, Field 1
+                v3 = mul v2, v2
+                return v3
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().fold_constants();
+        assert_normalized_ssa_equals(ssa, expected);
+    }
+
+    #[test]
+    fn deduplicates_instructions_in_dominated_blocks() {
+        // The truncations in b1 and b2 don't dominate b3, so the one in b3 must remain.
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: u1, v2: [Field; 2]):
+                v3 = mul v0, v0
+                v4 = cast v0 as u32
+                v5 = array_get v2, index u32 1 -> Field
+                jmpif v1 then: b1, else: b2
+              b1():
+                v6 = mul v0, v0
+                v7 = cast v0 as u32
+                v8 = truncate v7 to 8 bits, max_bit_size: 32
+                v9 = array_get v2, index u32 1 -> Field
+                jmp b3(v6, v9)
+              b3(v10: Field, v11: Field):
+                v12 = truncate v4 to 8 bits, max_bit_size: 32
+                return v10, v11, v12
+              b2():
+                v13 = truncate v4 to 8 bits, max_bit_size: 32
+                jmp b3(v3, v5)
+            }
+            ";
+        let expected = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: u1, v2: [Field; 2]):
+                v3 = mul v0, v0
+                v4 = cast v0 as u32
+                v5 = array_get v2, index u32 1 -> Field
+                jmpif v1 then: b1, else: b2
+              b1():
+                v8 = truncate v4 to 8 bits, max_bit_size: 32
+                jmp b3(v3, v5)
+              b3(v10: Field, v11: Field):
+                v12 = truncate v4 to 8 bits, max_bit_size: 32
+                return v10, v11, v12
+              b2():
+                v13 = truncate v4 to 8 bits, max_bit_size: 32
+                jmp b3(v3, v5)
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().fold_constants();
+        assert_normalized_ssa_equals(ssa, expected);
+    }
+
+    #[test]
+    fn deduplicates_array_gets_of_known_indices_under_the_same_predicate() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: u1, v1: [Field; 2], v2: u32):
+                enable_side_effects v0
+                v3 = array_get v1, index u32 1 -> Field
+                v4 = array_get v1, index u32 1 -> Field
+                v5 = array_get v1, index v2 -> Field
+                v6 = array_get v1, index v2 -> Field
+                v7 = not v0
+                enable_side_effects v7
+                v8 = array_get v1, index u32 1 -> Field
+                return v3, v4, v5, v6, v8
+            }
+            ";
+        // The read of an unknown index isn't deduplicated, and nor is the read of index 1 when
+        // the predicate has changed.
+        let expected = "
+            acir(inline) fn main f0 {
+              b0(v0: u1, v1: [Field; 2], v2: u32):
+                enable_side_effects v0
+                v3 = array_get v1, index u32 1 -> Field
+                v5 = array_get v1, index v2 -> Field
+                v6 = array_get v1, index v2 -> Field
+                v7 = not v0
+                enable_side_effects v7
+                v8 = array_get v1, index u32 1 -> Field
+                return v3, v3, v5, v6, v8
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().fold_constants();
+        assert_normalized_ssa_equals(ssa, expected);
+    }
+
     #[test]
     fn simple_constant_fold() {
         // fn main f0 {
@@ -0,0 +1,499 @@
+//! This file contains the loop-invariant code motion pass for the SSA IR.
+//!
+//! Loops in Brillig functions are never unrolled, so any computation inside of them which produces
+//! the same value on every iteration is needlessly repeated. This pass moves such instructions out
+//! of each loop and into its pre-header, the block which jumps into the loop, so that they're
+//! executed once each time the loop is entered.
+//!
+//! The body of a loop may run zero times, so hoisting an instruction means it may be executed
+//! where it previously wasn't. Only instructions which are pure and can never fail at runtime are
+//! therefore hoisted: an unsigned `add` is left in place as Brillig checks it for overflow, as is
+//! an `array_get` whose index may be out of bounds.
+//!
+//! Loops are processed from the innermost outwards, so that an instruction hoisted out of an inner
+//! loop can then be hoisted out of the loops containing it.
+use std::collections::HashSet;
+
+use acvm::acir::AcirField;
+use fxhash::FxHashMap as HashMap;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{BinaryOp, Instruction, TerminatorInstruction},
+        post_order::PostOrder,
+        types::{NumericType, Type},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+use super::unrolling::{find_blocks_in_loop, Loop};
+
+impl Ssa {
+    /// Moves pure instructions whose operands don't change between iterations out of the loops
+    /// of each Brillig function.
+    ///
+    /// ACIR functions are skipped as their loops have already been unrolled.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn loop_invariant_code_motion(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                hoist_loop_invariants(function);
+            }
+        }
+        self
+    }
+}
+
+fn hoist_loop_invariants(function: &mut Function) {
+    let cfg = ControlFlowGraph::with_function(function);
+    let post_order = PostOrder::with_function(function);
+    let mut dom_tree = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+
+    // Blocks in the order they're executed, so that an instruction is visited after the
+    // instructions defining its operands and can be hoisted along with them.
+    let mut reverse_post_order = post_order.into_vec();
+    reverse_post_order.reverse();
+
+    for loop_ in find_loops(function, &cfg, &mut dom_tree) {
+        if let Some(pre_header) = get_pre_header(function, &cfg, &loop_) {
+            let blocks = reverse_post_order.iter().filter(|block| loop_.blocks.contains(block));
+            hoist_out_of_loop(function, &loop_, blocks.copied().collect(), pre_header);
+        }
+    }
+}
+
+/// Finds every loop in the function, ordered so that each loop comes before any loop containing it.
+///
+/// A loop with several back edges is returned once, with the blocks reached from each of them.
+fn find_loops(
+    function: &Function,
+    cfg: &ControlFlowGraph,
+    dom_tree: &mut DominatorTree,
+) -> Vec<Loop> {
+    let mut loops: HashMap<BasicBlockId, Loop> = HashMap::default();
+
+    for (block, _) in function.dfg.basic_blocks_iter() {
+        if dom_tree.is_reachable(block) {
+            for predecessor in cfg.predecessors(block) {
+                if dom_tree.is_reachable(predecessor) && dom_tree.dominates(block, predecessor) {
+                    // predecessor -> block is the back-edge of a loop
+                    let loop_ = find_blocks_in_loop(block, predecessor, cfg);
+                    match loops.get_mut(&block) {
+                        Some(existing) => existing.blocks.extend(loop_.blocks),
+                        None => {
+                            loops.insert(block, loop_);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // An inner loop is a strict subset of the loops containing it so sorting by size puts it first.
+    let mut loops: Vec<_> = loops.into_values().collect();
+    loops.sort_by_key(|loop_| (loop_.blocks.len(), loop_.header));
+    loops
+}
+
+/// Returns the single block outside of the loop which jumps to its header, if there is one.
+///
+/// Hoisted instructions are placed at the end of this block, so it must not jump anywhere else.
+fn get_pre_header(
+    function: &Function,
+    cfg: &ControlFlowGraph,
+    loop_: &Loop,
+) -> Option<BasicBlockId> {
+    let mut entries = cfg.predecessors(loop_.header).filter(|block| !loop_.blocks.contains(block));
+    let pre_header = entries.next()?;
+    if entries.next().is_some() {
+        return None;
+    }
+
+    match function.dfg[pre_header].terminator() {
+        Some(TerminatorInstruction::Jmp { destination, .. }) if *destination == loop_.header => {
+            Some(pre_header)
+        }
+        _ => None,
+    }
+}
+
+/// Moves each loop-invariant instruction in `blocks` to the end of `pre_header`, keeping their
+/// order.
+fn hoist_out_of_loop(
+    function: &mut Function,
+    loop_: &Loop,
+    blocks: Vec<BasicBlockId>,
+    pre_header: BasicBlockId,
+) {
+    // The values which may change from one iteration of the loop to the next
+    let mut defined_in_loop = HashSet::new();
+    for block in &loop_.blocks {
+        defined_in_loop.extend(function.dfg.block_parameters(*block).iter().copied());
+        for instruction in function.dfg[*block].instructions() {
+            defined_in_loop.extend(function.dfg.instruction_results(*instruction).iter().copied());
+        }
+    }
+
+    let mut hoisted = Vec::new();
+    for block in blocks {
+        let dfg = &function.dfg;
+        let mut remaining = Vec::with_capacity(dfg[block].instructions().len());
+
+        for instruction_id in dfg[block].instructions() {
+            let instruction = &dfg[*instruction_id];
+            let mut is_invariant = true;
+            instruction.for_each_value(|value| {
+                is_invariant &= is_loop_invariant(value, dfg, &defined_in_loop);
+            });
+
+            if is_invariant && can_be_hoisted(instruction, dfg) {
+                for result in dfg.instruction_results(*instruction_id) {
+                    defined_in_loop.remove(result);
+                }
+                hoisted.push(*instruction_id);
+            } else {
+                remaining.push(*instruction_id);
+            }
+        }
+
+        *function.dfg[block].instructions_mut() = remaining;
+    }
+
+    function.dfg[pre_header].instructions_mut().extend(hoisted);
+}
+
+/// True if `value` is the same on every iteration of the loop, including each of the elements of
+/// an array constant.
+fn is_loop_invariant(
+    value: ValueId,
+    dfg: &DataFlowGraph,
+    defined_in_loop: &HashSet<ValueId>,
+) -> bool {
+    let value = dfg.resolve(value);
+    match &dfg[value] {
+        Value::Array { array, .. } => {
+            array.iter().all(|element| is_loop_invariant(*element, dfg, defined_in_loop))
+        }
+        _ => !defined_in_loop.contains(&value),
+    }
+}
+
+/// True if `instruction` can be executed before the loop it's in, where it will be executed even
+/// if the loop body never is. It must have no side effects and be unable to fail.
+fn can_be_hoisted(instruction: &Instruction, dfg: &DataFlowGraph) -> bool {
+    match instruction {
+        Instruction::Binary(binary) => {
+            let typ = dfg.type_of_value(binary.lhs);
+            match binary.operator {
+                BinaryOp::Eq | BinaryOp::Lt | BinaryOp::And | BinaryOp::Or | BinaryOp::Xor => true,
+                // Brillig checks unsigned arithmetic for overflow
+                BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => {
+                    !matches!(typ, Type::Numeric(NumericType::Unsigned { .. }))
+                }
+                BinaryOp::Div | BinaryOp::Mod => {
+                    dfg.get_numeric_constant(binary.rhs).map_or(false, |rhs| !rhs.is_zero())
+                }
+                // Shifting by at least the bit size of the operands is an error in Brillig
+                BinaryOp::Shl | BinaryOp::Shr => false,
+            }
+        }
+        Instruction::Cast(..) | Instruction::Not(_) | Instruction::Truncate { .. } => true,
+        Instruction::ArrayGet { array, index } => {
+            // Brillig checks array accesses are in bounds, so only constant indices into arrays
+            // (which have a known length) can be hoisted.
+            let Type::Array(element_types, length) = dfg.type_of_value(*array) else {
+                return false;
+            };
+            let Some(index) = dfg.get_numeric_constant(*index) else {
+                return false;
+            };
+            index
+                .try_to_u64()
+                .map_or(false, |index| (index as usize) < element_types.len() * length)
+        }
+        Instruction::Constrain(..)
+        | Instruction::RangeCheck { .. }
+        | Instruction::Call { .. }
+        | Instruction::Allocate
+        | Instruction::Load { .. }
+        | Instruction::Store { .. }
+        | Instruction::EnableSideEffects { .. }
+        | Instruction::ArraySet { .. }
+        | Instruction::IncrementRc { .. }
+        | Instruction::DecrementRc { .. }
+        | Instruction::IfElse { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, str::FromStr};
+
+    use acvm::{
+        brillig_vm::{VMStatus, VM},
+        FieldElement,
+    };
+    use iter_extended::vecmap;
+
+    use crate::{
+        brillig::{
+            brillig_gen::brillig_fn::FunctionContext,
+            brillig_ir::{tests::DummyBlackBoxSolver, BrilligContext},
+        },
+        ssa::{
+            ir::{cfg::ControlFlowGraph, dom::DominatorTree, post_order::PostOrder},
+            opt::assert_normalized_ssa_equals,
+            ssa_gen::Ssa,
+        },
+    };
+
+    use super::find_loops;
+
+    /// Compiles `ssa` to Brillig and runs its `main` function with `calldata`, returning the values
+    /// it returns and the number of Brillig opcodes which were executed.
+    fn run_brillig(ssa: &Ssa, calldata: Vec<FieldElement>) -> (Vec<FieldElement>, usize) {
+        let brillig = ssa.to_brillig(false);
+        let main = ssa.main();
+        let arguments = vecmap(main.parameters(), |parameter| {
+            FunctionContext::ssa_type_to_parameter(&main.dfg.type_of_value(*parameter))
+        });
+        let mut entry_point = BrilligContext::new_entry_point_artifact(
+            arguments,
+            FunctionContext::return_values(main),
+            FunctionContext::function_id_to_function_label(main.id()),
+        );
+        while let Some(label) = entry_point.first_unresolved_function_call() {
+            entry_point.link_with(brillig.find_by_function_label(label).unwrap());
+        }
+        let bytecode = entry_point.finish().byte_code;
+
+        let mut vm = VM::new(calldata, &bytecode, vec![], &DummyBlackBoxSolver);
+        let mut executed = 0;
+        loop {
+            executed += 1;
+            match vm.process_opcode() {
+                VMStatus::InProgress => {}
+                VMStatus::Finished { return_data_offset, return_data_size } => {
+                    let memory = &vm.get_memory()[return_data_offset..][..return_data_size];
+                    return (vecmap(memory, |value| value.to_field()), executed);
+                }
+                status => panic!("Brillig execution did not finish: {status:?}"),
+            }
+        }
+    }
+
+    /// The number of instructions within the loops of `main`, which are executed on each iteration
+    fn instructions_in_loops(ssa: &Ssa) -> usize {
+        let main = ssa.main();
+        let cfg = ControlFlowGraph::with_function(main);
+        let post_order = PostOrder::with_function(main);
+        let mut dom_tree = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+        let blocks: HashSet<_> = find_loops(main, &cfg, &mut dom_tree)
+            .into_iter()
+            .flat_map(|loop_| loop_.blocks)
+            .collect();
+        blocks.iter().map(|block| main.dfg[*block].instructions().len()).sum()
+    }
+
+    #[test]
+    fn hoists_invariant_instructions_to_pre_header() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: [Field; 3]):
+                jmp b1(u32 0, Field 0)
+              b1(v2: u32, v3: Field):
+                v4 = lt v2, u32 10
+                jmpif v4 then: b3, else: b2
+              b3():
+                v5 = mul v0, Field 2
+                v6 = array_get v1, index u32 1 -> Field
+                v7 = add v5, v6
+                v8 = add v3, v7
+                v9 = add v2, u32 1
+                jmp b1(v9, v8)
+              b2():
+                return v3
+            }
+            ";
+        let expected = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: [Field; 3]):
+                v5 = mul v0, Field 2
+                v6 = array_get v1, index u32 1 -> Field
+                v7 = add v5, v6
+                jmp b1(u32 0, Field 0)
+              b1(v2: u32, v3: Field):
+                v4 = lt v2, u32 10
+                jmpif v4 then: b3, else: b2
+              b3():
+                v8 = add v3, v7
+                v9 = add v2, u32 1
+                jmp b1(v9, v8)
+              b2():
+                return v3
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+        assert_normalized_ssa_equals(ssa, expected);
+    }
+
+    #[test]
+    fn does_not_hoist_instructions_which_may_fail() {
+        // Each of these could fail if the loop body is never executed, e.g. `v0` may be zero or
+        // out of bounds.
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u32, v1: u32, v2: [Field; 3], v3: &mut Field):
+                jmp b1(u32 0)
+              b1(v4: u32):
+                v5 = lt v4, v1
+                jmpif v5 then: b3, else: b2
+              b3():
+                v6 = add v0, u32 1
+                v7 = div u32 10, v0
+                v8 = array_get v2, index v0 -> Field
+                v9 = array_get v2, index u32 3 -> Field
+                v10 = shl v0, u8 2
+                v11 = load v3 -> Field
+                constrain v11 == Field 1
+                v12 = add v4, u32 1
+                jmp b1(v12)
+              b2():
+                return
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+        assert_normalized_ssa_equals(ssa, src);
+    }
+
+    #[test]
+    fn hoists_out_of_nested_loops() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field):
+                jmp b1(u32 0, Field 0)
+              b1(v1: u32, v2: Field):
+                v3 = lt v1, u32 3
+                jmpif v3 then: b3, else: b2
+              b3():
+                v4 = mul v1, u32 2
+                jmp b4(u32 0, v2)
+              b4(v5: u32, v6: Field):
+                v7 = lt v5, u32 3
+                jmpif v7 then: b6, else: b5
+              b6():
+                v8 = mul v0, v0
+                v9 = cast v4 as Field
+                v10 = add v8, v9
+                v11 = add v6, v10
+                v12 = add v5, u32 1
+                jmp b4(v12, v11)
+              b5():
+                v13 = add v1, u32 1
+                jmp b1(v13, v6)
+              b2():
+                return v2
+            }
+            ";
+        // `v4` changes with each iteration of the outer loop, so the instructions using it can only
+        // be hoisted out of the inner loop, while `v0 * v0` can be hoisted out of both.
+        let expected = "
+            brillig fn main f0 {
+              b0(v0: Field):
+                v8 = mul v0, v0
+                jmp b1(u32 0, Field 0)
+              b1(v1: u32, v2: Field):
+                v3 = lt v1, u32 3
+                jmpif v3 then: b3, else: b2
+              b3():
+                v4 = mul v1, u32 2
+                v9 = cast v4 as Field
+                v10 = add v8, v9
+                jmp b4(u32 0, v2)
+              b4(v5: u32, v6: Field):
+                v7 = lt v5, u32 3
+                jmpif v7 then: b6, else: b5
+              b6():
+                v11 = add v6, v10
+                v12 = add v5, u32 1
+                jmp b4(v12, v11)
+              b5():
+                v13 = add v1, u32 1
+                jmp b1(v13, v6)
+              b2():
+                return v2
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+        assert_normalized_ssa_equals(ssa, expected);
+    }
+
+    #[test]
+    fn leaves_acir_functions_unchanged() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                jmp b1(u32 0, Field 0)
+              b1(v1: u32, v2: Field):
+                v3 = lt v1, u32 10
+                jmpif v3 then: b3, else: b2
+              b3():
+                v4 = mul v0, Field 2
+                v5 = add v2, v4
+                v6 = add v1, u32 1
+                jmp b1(v6, v5)
+              b2():
+                return v2
+            }
+            ";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+        assert_normalized_ssa_equals(ssa, src);
+    }
+
+    #[test]
+    fn reduces_instructions_executed() {
+        // A sum over a loop of 10 iterations, each of which computes the same `v0 * 2 + v1[1]`
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: [Field; 3]):
+                jmp b1(u32 0, Field 0)
+              b1(v2: u32, v3: Field):
+                v4 = lt v2, u32 10
+                jmpif v4 then: b3, else: b2
+              b3():
+                v5 = mul v0, Field 2
+                v6 = array_get v1, index u32 1 -> Field
+                v7 = add v5, v6
+                v8 = add v3, v7
+                v9 = add v2, u32 1
+                jmp b1(v9, v8)
+              b2():
+                return v3
+            }
+            ";
+        let calldata = vecmap([3, 1, 2, 3], FieldElement::from);
+
+        let ssa = Ssa::from_str(src).unwrap();
+        let instructions_per_iteration = instructions_in_loops(&ssa);
+        let (returns, executed) = run_brillig(&ssa, calldata.clone());
+
+        let ssa = ssa.loop_invariant_code_motion();
+        let (optimized_returns, optimized_executed) = run_brillig(&ssa, calldata);
+
+        assert_eq!(returns, vec![FieldElement::from(80_u128)]);
+        assert_eq!(optimized_returns, returns);
+
+        assert_eq!(instructions_in_loops(&ssa), instructions_per_iteration - 3);
+        // Each hoisted instruction is compiled to at least one Brillig opcode
+        assert!(
+            optimized_executed + 3 * 10 <= executed,
+            "Expected at least 30 fewer than {executed} opcodes but {optimized_executed} were executed"
+        );
+    }
+}
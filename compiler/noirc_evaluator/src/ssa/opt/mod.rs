@@ -12,6 +12,7 @@ mod defunctionalize;
 mod die;
 pub(crate) mod flatten_cfg;
 mod inlining;
+mod loop_invariant;
 mod mem2reg;
 mod rc;
 mod remove_bit_shifts;
@@ -21,3 +22,51 @@ mod resolve_is_unconstrained;
 mod runtime_separation;
 mod simplify_cfg;
 mod unrolling;
+
+/// Asserts that `ssa` is displayed as `expected` once the values and blocks of both have been
+/// renumbered in the order they appear. This lets a pass be tested against the SSA it should
+/// produce without depending on the ids it happens to allocate.
+#[cfg(test)]
+pub(crate) fn assert_normalized_ssa_equals(ssa: super::ssa_gen::Ssa, expected: &str) {
+    assert_eq!(normalize_ids(&ssa.to_string()), normalize_ids(expected));
+}
+
+/// Renumbers the values (`v1`) and blocks (`b2`) of displayed SSA in the order they first appear,
+/// and trims each line so that indentation doesn't matter.
+#[cfg(test)]
+fn normalize_ids(ssa: &str) -> String {
+    let mut renamed: std::collections::HashMap<String, String> = Default::default();
+    let mut counts = [0; 2];
+    let mut rename = |word: &str| {
+        let prefix_index = match word.split_at(1) {
+            ("v", index) if index.chars().all(|char| char.is_ascii_digit()) => 0,
+            ("b", index) if index.chars().all(|char| char.is_ascii_digit()) => 1,
+            _ => return word.to_string(),
+        };
+        renamed
+            .entry(word.to_string())
+            .or_insert_with(|| {
+                let name = format!("{}{}", &word[..1], counts[prefix_index]);
+                counts[prefix_index] += 1;
+                name
+            })
+            .clone()
+    };
+
+    let mut normalized = String::new();
+    for line in ssa.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut word = String::new();
+        for char in line.chars().chain(std::iter::once('\n')) {
+            if char.is_ascii_alphanumeric() || char == '_' {
+                word.push(char);
+                continue;
+            }
+            if !word.is_empty() {
+                normalized.push_str(&rename(&word));
+                word.clear();
+            }
+            normalized.push(char);
+        }
+    }
+    normalized
+}
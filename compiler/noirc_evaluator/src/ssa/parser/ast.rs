@@ -0,0 +1,152 @@
+use acvm::FieldElement;
+use noirc_errors::Span;
+
+use crate::ssa::ir::{function::RuntimeType, instruction::BinaryOp, types::Type};
+
+/// SSA as it was written, before any of its names have been resolved.
+#[derive(Debug)]
+pub(crate) struct ParsedSsa {
+    pub(crate) functions: Vec<ParsedFunction>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedFunction {
+    pub(crate) runtime_type: RuntimeType,
+    pub(crate) external_name: String,
+    pub(crate) internal_name: Identifier,
+    pub(crate) blocks: Vec<ParsedBlock>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedBlock {
+    pub(crate) name: Identifier,
+    pub(crate) parameters: Vec<ParsedParameter>,
+    pub(crate) instructions: Vec<ParsedInstruction>,
+    pub(crate) terminator: ParsedTerminator,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedParameter {
+    pub(crate) identifier: Identifier,
+    pub(crate) typ: Type,
+}
+
+/// A name along with where it was written, such as `v1`, `b2` or `f3`.
+#[derive(Debug, Clone)]
+pub(crate) struct Identifier {
+    pub(crate) name: String,
+    pub(crate) span: Span,
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedInstruction {
+    Allocate {
+        target: Identifier,
+        typ: Type,
+    },
+    ArrayGet {
+        target: Identifier,
+        element_type: Type,
+        array: ParsedValue,
+        index: ParsedValue,
+    },
+    ArraySet {
+        target: Identifier,
+        array: ParsedValue,
+        index: ParsedValue,
+        value: ParsedValue,
+        mutable: bool,
+    },
+    BinaryOp {
+        target: Identifier,
+        lhs: ParsedValue,
+        op: BinaryOp,
+        rhs: ParsedValue,
+    },
+    Call {
+        targets: Vec<Identifier>,
+        function: ParsedValue,
+        arguments: Vec<ParsedValue>,
+        types: Vec<Type>,
+    },
+    Cast {
+        target: Identifier,
+        lhs: ParsedValue,
+        typ: Type,
+    },
+    Constrain {
+        lhs: ParsedValue,
+        rhs: ParsedValue,
+        error: Option<ParsedConstrainError>,
+    },
+    DecrementRc {
+        value: ParsedValue,
+    },
+    EnableSideEffects {
+        condition: ParsedValue,
+    },
+    IfElse {
+        target: Identifier,
+        then_condition: ParsedValue,
+        then_value: ParsedValue,
+        else_condition: ParsedValue,
+        else_value: ParsedValue,
+    },
+    IncrementRc {
+        value: ParsedValue,
+    },
+    Load {
+        target: Identifier,
+        value: ParsedValue,
+        typ: Type,
+    },
+    Not {
+        target: Identifier,
+        value: ParsedValue,
+    },
+    RangeCheck {
+        value: ParsedValue,
+        max_bit_size: u32,
+    },
+    Store {
+        value: ParsedValue,
+        address: ParsedValue,
+    },
+    Truncate {
+        target: Identifier,
+        value: ParsedValue,
+        bit_size: u32,
+        max_bit_size: u32,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedConstrainError {
+    /// A message generated by the compiler, written as `, "message"`
+    Intrinsic(String),
+    /// A string message given by the user, written as `, data "message"`
+    UserDefinedString(String),
+    /// A payload given by the user, written as `, data <selector>: <values>`
+    UserDefined { selector: u64, values: Vec<ParsedValue> },
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedTerminator {
+    Jmp { destination: Identifier, arguments: Vec<ParsedValue> },
+    Jmpif { condition: ParsedValue, then_block: Identifier, else_block: Identifier },
+    Return(Vec<ParsedValue>),
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedValue {
+    NumericConstant {
+        constant: FieldElement,
+        typ: Type,
+    },
+    Array {
+        values: Vec<ParsedValue>,
+        typ: Type,
+    },
+    /// A value or function, such as `v1` or `f2`, or an intrinsic or foreign function by name
+    Variable(Identifier),
+}
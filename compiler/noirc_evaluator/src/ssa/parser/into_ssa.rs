@@ -0,0 +1,374 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use acvm::acir::circuit::{ErrorSelector, STRING_ERROR_SELECTOR};
+use noirc_errors::Span;
+
+use crate::ssa::{
+    function_builder::FunctionBuilder,
+    ir::{
+        basic_block::BasicBlockId,
+        function::{FunctionId, RuntimeType},
+        instruction::{Binary, ConstrainError, Instruction, Intrinsic},
+        types::Type,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+use super::{
+    ast::{
+        Identifier, ParsedBlock, ParsedConstrainError, ParsedFunction, ParsedInstruction,
+        ParsedSsa, ParsedTerminator, ParsedValue,
+    },
+    is_indexed_name, SsaError,
+};
+
+impl ParsedSsa {
+    pub(crate) fn into_ssa(self) -> Result<Ssa, SsaError> {
+        Translator::translate(self)
+    }
+}
+
+/// Builds the functions of [`ParsedSsa`] one at a time, resolving the names used within each.
+struct Translator {
+    builder: FunctionBuilder,
+
+    /// The functions which have been built so far
+    functions: HashSet<FunctionId>,
+
+    /// Maps the names of the current function's blocks to their ids, along with where each
+    /// block was first referred to
+    blocks: HashMap<String, (BasicBlockId, Span)>,
+
+    /// The blocks of the current function which have been built so far
+    built_blocks: HashSet<BasicBlockId>,
+
+    /// Maps the names of the current function's values to their ids
+    variables: HashMap<String, ValueId>,
+}
+
+impl Translator {
+    fn translate(parsed_ssa: ParsedSsa) -> Result<Ssa, SsaError> {
+        let mut functions = parsed_ssa.functions.into_iter();
+        let main = functions.next().expect("SSA is parsed with at least one function");
+
+        let main_id = function_id(&main.internal_name)?;
+        let mut builder = FunctionBuilder::new(main.external_name.clone(), main_id);
+        builder.set_runtime(main.runtime_type);
+        builder.set_simplify(false);
+
+        let mut translator = Translator {
+            builder,
+            functions: HashSet::from([main_id]),
+            blocks: HashMap::new(),
+            built_blocks: HashSet::new(),
+            variables: HashMap::new(),
+        };
+        translator.translate_function_body(main)?;
+
+        for function in functions {
+            translator.translate_function(function)?;
+        }
+        Ok(translator.builder.finish())
+    }
+
+    fn translate_function(&mut self, function: ParsedFunction) -> Result<(), SsaError> {
+        let id = function_id(&function.internal_name)?;
+        if !self.functions.insert(id) {
+            return Err(SsaError::FunctionAlreadyDefined(function.internal_name));
+        }
+
+        let name = function.external_name.clone();
+        match function.runtime_type {
+            RuntimeType::Acir(inline_type) => self.builder.new_function(name, id, inline_type),
+            RuntimeType::Brillig => self.builder.new_brillig_function(name, id),
+        }
+        self.translate_function_body(function)
+    }
+
+    fn translate_function_body(&mut self, function: ParsedFunction) -> Result<(), SsaError> {
+        self.blocks.clear();
+        self.built_blocks.clear();
+        self.variables.clear();
+
+        let mut blocks = function.blocks.into_iter();
+        let entry_block = blocks.next().expect("a function is parsed with at least one block");
+        let entry_block_id = self.builder.current_block();
+        self.blocks.insert(entry_block.name.name.clone(), (entry_block_id, entry_block.name.span));
+        self.translate_block(entry_block, true)?;
+
+        for block in blocks {
+            self.translate_block(block, false)?;
+        }
+
+        // Any block which was jumped to must have been defined
+        for (name, (block_id, span)) in &self.blocks {
+            if !self.built_blocks.contains(block_id) {
+                let identifier = Identifier { name: name.clone(), span: *span };
+                return Err(SsaError::UnknownBlock(identifier));
+            }
+        }
+        Ok(())
+    }
+
+    fn translate_block(&mut self, block: ParsedBlock, is_entry: bool) -> Result<(), SsaError> {
+        let block_id = self.block_id(&block.name);
+        if !self.built_blocks.insert(block_id) {
+            return Err(SsaError::BlockAlreadyDefined(block.name));
+        }
+        self.builder.switch_to_block(block_id);
+
+        for parameter in block.parameters {
+            let value_id = if is_entry {
+                self.builder.add_parameter(parameter.typ)
+            } else {
+                self.builder.add_block_parameter(block_id, parameter.typ)
+            };
+            self.define_variable(parameter.identifier, value_id)?;
+        }
+
+        for instruction in block.instructions {
+            self.translate_instruction(instruction)?;
+        }
+
+        match block.terminator {
+            ParsedTerminator::Jmp { destination, arguments } => {
+                let destination = self.block_id(&destination);
+                let arguments = self.translate_values(arguments)?;
+                self.builder.terminate_with_jmp(destination, arguments);
+            }
+            ParsedTerminator::Jmpif { condition, then_block, else_block } => {
+                let condition = self.translate_value(condition)?;
+                let then_block = self.block_id(&then_block);
+                let else_block = self.block_id(&else_block);
+                self.builder.terminate_with_jmpif(condition, then_block, else_block);
+            }
+            ParsedTerminator::Return(values) => {
+                let values = self.translate_values(values)?;
+                self.builder.terminate_with_return(values);
+            }
+        }
+        Ok(())
+    }
+
+    fn translate_instruction(&mut self, instruction: ParsedInstruction) -> Result<(), SsaError> {
+        match instruction {
+            ParsedInstruction::Allocate { target, typ } => {
+                let element_type = match typ {
+                    Type::Reference(element_type) => element_type.as_ref().clone(),
+                    found => {
+                        let (expected, span) = ("a reference", target.span);
+                        return Err(SsaError::UnexpectedType { expected, found, span });
+                    }
+                };
+                let value_id = self.builder.insert_allocate(element_type);
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::ArrayGet { target, element_type, array, index } => {
+                let array = self.translate_value(array)?;
+                let index = self.translate_value(index)?;
+                let value_id = self.builder.insert_array_get(array, index, element_type);
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::ArraySet { target, array, index, value, mutable } => {
+                let array = self.translate_value(array)?;
+                let index = self.translate_value(index)?;
+                let value = self.translate_value(value)?;
+                let instruction = Instruction::ArraySet { array, index, value, mutable };
+                let value_id = self.builder.insert_instruction(instruction, None).first();
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::BinaryOp { target, lhs, op, rhs } => {
+                let lhs = self.translate_value(lhs)?;
+                let rhs = self.translate_value(rhs)?;
+                // Operands aren't checked to have the same type here so that ill-typed SSA can
+                // still be read in, e.g. to reproduce a bug.
+                let instruction = Instruction::Binary(Binary { lhs, rhs, operator: op });
+                let value_id = self.builder.insert_instruction(instruction, None).first();
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::Call { targets, function, arguments, types } => {
+                if targets.len() != types.len() {
+                    // Point at the first result, or at the function if the call has none
+                    let span = match (targets.first(), &function) {
+                        (Some(target), _) | (None, ParsedValue::Variable(target)) => target.span,
+                        (None, _) => Span::default(),
+                    };
+                    let (expected, found) = (types.len(), targets.len());
+                    return Err(SsaError::MismatchedCallResults { expected, found, span });
+                }
+
+                let function = self.translate_value(function)?;
+                let arguments = self.translate_values(arguments)?;
+                let results = self.builder.insert_call(function, arguments, types).to_vec();
+                for (target, value_id) in targets.into_iter().zip(results) {
+                    self.define_variable(target, value_id)?;
+                }
+            }
+            ParsedInstruction::Cast { target, lhs, typ } => {
+                let lhs = self.translate_value(lhs)?;
+                let value_id = self.builder.insert_cast(lhs, typ);
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::Constrain { lhs, rhs, error } => {
+                let lhs = self.translate_value(lhs)?;
+                let rhs = self.translate_value(rhs)?;
+                let error = match error {
+                    Some(error) => Some(self.translate_constrain_error(error)?),
+                    None => None,
+                };
+                self.builder.insert_constrain(lhs, rhs, error);
+            }
+            ParsedInstruction::DecrementRc { value } => {
+                let value = self.translate_value(value)?;
+                self.builder.insert_dec_rc(value);
+            }
+            ParsedInstruction::EnableSideEffects { condition } => {
+                let condition = self.translate_value(condition)?;
+                self.builder.insert_enable_side_effects_if(condition);
+            }
+            ParsedInstruction::IfElse {
+                target,
+                then_condition,
+                then_value,
+                else_condition,
+                else_value,
+            } => {
+                let instruction = Instruction::IfElse {
+                    then_condition: self.translate_value(then_condition)?,
+                    then_value: self.translate_value(then_value)?,
+                    else_condition: self.translate_value(else_condition)?,
+                    else_value: self.translate_value(else_value)?,
+                };
+                let value_id = self.builder.insert_instruction(instruction, None).first();
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::IncrementRc { value } => {
+                let value = self.translate_value(value)?;
+                self.builder.insert_inc_rc(value);
+            }
+            ParsedInstruction::Load { target, value, typ } => {
+                let address = self.translate_value(value)?;
+                let value_id = self.builder.insert_load(address, typ);
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::Not { target, value } => {
+                let value = self.translate_value(value)?;
+                let value_id = self.builder.insert_not(value);
+                self.define_variable(target, value_id)?;
+            }
+            ParsedInstruction::RangeCheck { value, max_bit_size } => {
+                let value = self.translate_value(value)?;
+                self.builder.insert_range_check(value, max_bit_size, None);
+            }
+            ParsedInstruction::Store { value, address } => {
+                let value = self.translate_value(value)?;
+                let address = self.translate_value(address)?;
+                self.builder.insert_store(address, value);
+            }
+            ParsedInstruction::Truncate { target, value, bit_size, max_bit_size } => {
+                let value = self.translate_value(value)?;
+                let value_id = self.builder.insert_truncate(value, bit_size, max_bit_size);
+                self.define_variable(target, value_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn translate_constrain_error(
+        &mut self,
+        error: ParsedConstrainError,
+    ) -> Result<ConstrainError, SsaError> {
+        let error = match error {
+            ParsedConstrainError::Intrinsic(message) => ConstrainError::Intrinsic(message),
+            ParsedConstrainError::UserDefinedString(message) => {
+                // String payloads are arrays of their characters, as they're generated from string literals
+                let characters = message
+                    .chars()
+                    .map(|char| self.builder.numeric_constant(char as u128, Type::char()))
+                    .collect::<im::Vector<_>>();
+                let typ = Type::Array(Rc::new(vec![Type::char()]), characters.len());
+                let payload = self.builder.array_constant(characters, typ);
+                ConstrainError::UserDefined(STRING_ERROR_SELECTOR, vec![payload])
+            }
+            ParsedConstrainError::UserDefined { selector, values } => {
+                let values = self.translate_values(values)?;
+                ConstrainError::UserDefined(ErrorSelector::new(selector), values)
+            }
+        };
+        Ok(error)
+    }
+
+    fn translate_values(&mut self, values: Vec<ParsedValue>) -> Result<Vec<ValueId>, SsaError> {
+        values.into_iter().map(|value| self.translate_value(value)).collect()
+    }
+
+    fn translate_value(&mut self, value: ParsedValue) -> Result<ValueId, SsaError> {
+        match value {
+            ParsedValue::NumericConstant { constant, typ } => {
+                Ok(self.builder.numeric_constant(constant, typ))
+            }
+            ParsedValue::Array { values, typ } => {
+                let values = self.translate_values(values)?;
+                Ok(self.builder.array_constant(values.into(), typ))
+            }
+            ParsedValue::Variable(identifier) => self.lookup_variable(identifier),
+        }
+    }
+
+    /// Resolves a name to a value of the current function or, failing that, to a function,
+    /// an intrinsic or a foreign function.
+    fn lookup_variable(&mut self, identifier: Identifier) -> Result<ValueId, SsaError> {
+        if let Some(value_id) = self.variables.get(&identifier.name) {
+            return Ok(*value_id);
+        }
+        if is_indexed_name(&identifier.name, 'f') {
+            let function_id = function_id(&identifier)?;
+            return Ok(self.builder.import_function(function_id));
+        }
+        if let Some(intrinsic) = Intrinsic::lookup(&identifier.name) {
+            return Ok(self.builder.import_intrinsic_id(intrinsic));
+        }
+        if is_indexed_name(&identifier.name, 'v') {
+            return Err(SsaError::UnknownVariable(identifier));
+        }
+        Ok(self.builder.import_foreign_function(&identifier.name))
+    }
+
+    fn define_variable(
+        &mut self,
+        identifier: Identifier,
+        value_id: ValueId,
+    ) -> Result<(), SsaError> {
+        if self.variables.contains_key(&identifier.name) {
+            return Err(SsaError::VariableAlreadyDefined(identifier));
+        }
+        self.variables.insert(identifier.name, value_id);
+        Ok(())
+    }
+
+    /// Returns the id of the block with the given name, creating the block if it's the first
+    /// time it's been referred to.
+    fn block_id(&mut self, identifier: &Identifier) -> BasicBlockId {
+        if let Some((block_id, _)) = self.blocks.get(&identifier.name) {
+            return *block_id;
+        }
+        let block_id = self.builder.insert_block();
+        self.blocks.insert(identifier.name.clone(), (block_id, identifier.span));
+        block_id
+    }
+}
+
+/// Returns the id of the function named `f<index>`
+fn function_id(identifier: &Identifier) -> Result<FunctionId, SsaError> {
+    let index = identifier.name.strip_prefix('f').and_then(|index| index.parse().ok());
+    match index {
+        Some(index) if is_indexed_name(&identifier.name, 'f') => {
+            Ok(FunctionId::from_displayed_index(index))
+        }
+        _ => Err(SsaError::InvalidFunctionId(identifier.clone())),
+    }
+}
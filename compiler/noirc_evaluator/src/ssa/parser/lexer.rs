@@ -0,0 +1,178 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use acvm::FieldElement;
+use noirc_errors::Span;
+use thiserror::Error;
+
+use super::token::{SpannedToken, Token};
+
+/// Splits SSA in its textual form into tokens, skipping whitespace and `//` comments.
+pub(crate) struct Lexer<'a> {
+    source: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum LexerError {
+    #[error("Unexpected character '{char}'")]
+    UnexpectedCharacter { char: char, span: Span },
+    #[error("Invalid integer '{text}'")]
+    InvalidInteger { text: String, span: Span },
+    #[error("Unterminated string")]
+    UnterminatedString { span: Span },
+    #[error("Invalid escape sequence in string")]
+    InvalidEscape { span: Span },
+}
+
+impl LexerError {
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            LexerError::UnexpectedCharacter { span, .. }
+            | LexerError::InvalidInteger { span, .. }
+            | LexerError::UnterminatedString { span }
+            | LexerError::InvalidEscape { span } => *span,
+        }
+    }
+}
+
+impl<'a> Lexer<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Lexer { source, chars: source.char_indices().peekable() }
+    }
+
+    pub(crate) fn next_token(&mut self) -> Result<SpannedToken, LexerError> {
+        self.skip_whitespace_and_comments();
+
+        let Some((start, char)) = self.chars.next() else {
+            let end = self.source.len() as u32;
+            return Ok(SpannedToken { token: Token::Eof, span: Span::from(end..end) });
+        };
+
+        let token = match char {
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            ';' => Token::Semicolon,
+            '&' => Token::Ampersand,
+            '=' if self.next_char_is('=') => Token::Equal,
+            '=' => Token::Assign,
+            '-' if self.next_char_is('>') => Token::Arrow,
+            '-' if self.peek_char().is_some_and(|char| char.is_ascii_digit()) => {
+                return self.lex_integer(start, true);
+            }
+            '"' => return self.lex_string(start),
+            char if char.is_ascii_digit() => return self.lex_integer(start, false),
+            char if char.is_ascii_alphabetic() || char == '_' => {
+                let end = self.eat_while(start, |char| char.is_ascii_alphanumeric() || char == '_');
+                Token::Ident(self.source[start..end].to_string())
+            }
+            char => {
+                let span = Span::from(start as u32..(start + char.len_utf8()) as u32);
+                return Err(LexerError::UnexpectedCharacter { char, span });
+            }
+        };
+        let end = self.chars.peek().map_or(self.source.len(), |(index, _)| *index);
+        Ok(SpannedToken { token, span: Span::from(start as u32..end as u32) })
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match self.chars.peek() {
+                Some((_, char)) if char.is_whitespace() => {
+                    self.chars.next();
+                }
+                Some((index, '/')) if self.source[*index..].starts_with("//") => {
+                    while self.chars.next_if(|(_, char)| *char != '\n').is_some() {}
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn peek_char(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, char)| *char)
+    }
+
+    /// Consumes the next character if it's `expected`
+    fn next_char_is(&mut self, expected: char) -> bool {
+        self.chars.next_if(|(_, char)| *char == expected).is_some()
+    }
+
+    /// Consumes characters while they match `predicate`, returning the end of the text consumed
+    /// so far, which started at `start`.
+    fn eat_while(&mut self, start: usize, predicate: impl Fn(char) -> bool) -> usize {
+        let mut end = start + 1;
+        while let Some((index, char)) = self.chars.next_if(|(_, char)| predicate(*char)) {
+            end = index + char.len_utf8();
+        }
+        end
+    }
+
+    /// Lexes a decimal or `0x` prefixed hex integer, which may be negative if `start` is at a `-`.
+    fn lex_integer(&mut self, start: usize, negative: bool) -> Result<SpannedToken, LexerError> {
+        let end = self.eat_while(start, |char| char.is_ascii_alphanumeric());
+        let span = Span::from(start as u32..end as u32);
+        let text = &self.source[start..end];
+        let digits = if negative { &text[1..] } else { text };
+
+        let is_valid = if let Some(hex) = digits.strip_prefix("0x") {
+            !hex.is_empty() && hex.chars().all(|char| char.is_ascii_hexdigit())
+        } else {
+            digits.chars().all(|char| char.is_ascii_digit())
+        };
+        let value = is_valid.then(|| FieldElement::try_from_str(digits)).flatten();
+        let Some(value) = value else {
+            return Err(LexerError::InvalidInteger { text: text.to_string(), span });
+        };
+        let value = if negative { -value } else { value };
+        Ok(SpannedToken { token: Token::Int(value), span })
+    }
+
+    /// Lexes a string in the form produced by `{:?}`, starting with the `"` at `start`.
+    fn lex_string(&mut self, start: usize) -> Result<SpannedToken, LexerError> {
+        let mut string = String::new();
+        loop {
+            let Some((index, char)) = self.chars.next() else {
+                let span = Span::from(start as u32..self.source.len() as u32);
+                return Err(LexerError::UnterminatedString { span });
+            };
+            match char {
+                '"' => {
+                    let span = Span::from(start as u32..(index + 1) as u32);
+                    return Ok(SpannedToken { token: Token::Str(string), span });
+                }
+                '\\' => string.push(self.lex_escape(index)?),
+                char => string.push(char),
+            }
+        }
+    }
+
+    /// Lexes the escape sequence following the `\` at `start`
+    fn lex_escape(&mut self, start: usize) -> Result<char, LexerError> {
+        let escaped = match self.chars.next() {
+            Some((_, 'n')) => Some('\n'),
+            Some((_, 't')) => Some('\t'),
+            Some((_, 'r')) => Some('\r'),
+            Some((_, '0')) => Some('\0'),
+            Some((_, char @ ('\\' | '"' | '\''))) => Some(char),
+            Some((_, 'u')) if self.next_char_is('{') => {
+                let mut digits = String::new();
+                while let Some((_, char)) = self.chars.next_if(|(_, char)| *char != '}') {
+                    digits.push(char);
+                }
+                self.chars.next();
+                u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)
+            }
+            _ => None,
+        };
+        escaped.ok_or_else(|| {
+            let end = self.chars.peek().map_or(self.source.len(), |(index, _)| *index);
+            LexerError::InvalidEscape { span: Span::from(start as u32..end as u32) }
+        })
+    }
+}
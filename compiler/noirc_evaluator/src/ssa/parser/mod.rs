@@ -0,0 +1,654 @@
+//! This module parses SSA from the textual form produced by [`super::ir::printer`], so that
+//! SSA can be written by hand in tests and SSA printed with `--show-ssa` can be read back in.
+//!
+//! Parsing happens in two steps: the source is first parsed into a [`ParsedSsa`], which
+//! mirrors the text, and is then turned into an [`Ssa`] by resolving the names of its
+//! functions, blocks and values.
+use std::{rc::Rc, str::FromStr};
+
+use acvm::{acir::AcirField, FieldElement};
+use noirc_errors::Span;
+use noirc_frontend::monomorphization::ast::InlineType;
+use thiserror::Error;
+
+use self::{
+    ast::{
+        Identifier, ParsedBlock, ParsedConstrainError, ParsedFunction, ParsedInstruction,
+        ParsedParameter, ParsedSsa, ParsedTerminator, ParsedValue,
+    },
+    lexer::{Lexer, LexerError},
+    token::{SpannedToken, Token},
+};
+
+use super::{
+    ir::{function::RuntimeType, instruction::BinaryOp, types::Type},
+    ssa_gen::Ssa,
+};
+
+mod ast;
+mod into_ssa;
+mod lexer;
+mod token;
+
+#[cfg(test)]
+mod tests;
+
+impl FromStr for Ssa {
+    type Err = SsaErrorWithSource;
+
+    /// Parses SSA in the form it's displayed in. The first function is taken to be `main`.
+    ///
+    /// Instructions are kept as they're written rather than being simplified, although the
+    /// ids of values and blocks are assigned afresh so may differ from those in `src`.
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let with_source = |error: SsaError| SsaErrorWithSource { src: src.to_string(), error };
+        let parsed_ssa =
+            Parser::new(src).parse_ssa().map_err(|error| with_source(SsaError::from(error)))?;
+        parsed_ssa.into_ssa().map_err(with_source)
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum SsaError {
+    #[error("{0}")]
+    ParserError(#[from] ParserError),
+    #[error("Unknown variable '{}'", .0.name)]
+    UnknownVariable(Identifier),
+    #[error("Unknown block '{}'", .0.name)]
+    UnknownBlock(Identifier),
+    #[error("Variable '{}' is already defined", .0.name)]
+    VariableAlreadyDefined(Identifier),
+    #[error("Block '{}' is already defined", .0.name)]
+    BlockAlreadyDefined(Identifier),
+    #[error("Function '{}' is already defined", .0.name)]
+    FunctionAlreadyDefined(Identifier),
+    #[error("'{}' is not a valid function id", .0.name)]
+    InvalidFunctionId(Identifier),
+    #[error("Expected {expected} but found {found}")]
+    UnexpectedType { expected: &'static str, found: Type, span: Span },
+    #[error("Call returns {expected} result(s) but {found} were named")]
+    MismatchedCallResults { expected: usize, found: usize, span: Span },
+}
+
+impl SsaError {
+    fn span(&self) -> Span {
+        match self {
+            SsaError::ParserError(error) => error.span(),
+            SsaError::UnknownVariable(identifier)
+            | SsaError::UnknownBlock(identifier)
+            | SsaError::VariableAlreadyDefined(identifier)
+            | SsaError::BlockAlreadyDefined(identifier)
+            | SsaError::FunctionAlreadyDefined(identifier)
+            | SsaError::InvalidFunctionId(identifier) => identifier.span,
+            SsaError::UnexpectedType { span, .. }
+            | SsaError::MismatchedCallResults { span, .. } => *span,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum ParserError {
+    #[error("{0}")]
+    LexerError(#[from] LexerError),
+    #[error("Expected {expected} but found '{}'", .found.token)]
+    ExpectedToken { expected: String, found: SpannedToken },
+    #[error("'{name}' is not a valid inline type")]
+    InvalidInlineType { name: String, span: Span },
+    #[error("Expected {expected} results but found {found}")]
+    MismatchedResults { expected: usize, found: usize, span: Span },
+    #[error("Expected an integer of at most {max} but found {found}")]
+    IntegerTooLarge { max: u32, found: FieldElement, span: Span },
+}
+
+impl ParserError {
+    fn span(&self) -> Span {
+        match self {
+            ParserError::LexerError(error) => error.span(),
+            ParserError::ExpectedToken { found, .. } => found.span,
+            ParserError::InvalidInlineType { span, .. }
+            | ParserError::MismatchedResults { span, .. }
+            | ParserError::IntegerTooLarge { span, .. } => *span,
+        }
+    }
+}
+
+/// An error along with the SSA it was found in, which is displayed with the line it's on.
+pub(crate) struct SsaErrorWithSource {
+    src: String,
+    pub(crate) error: SsaError,
+}
+
+impl std::fmt::Display for SsaErrorWithSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let start = (self.error.span().start() as usize).min(self.src.len());
+        let line_start = self.src[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = self.src[start..].find('\n').map_or(self.src.len(), |index| start + index);
+        let line_number = self.src[..start].matches('\n').count() + 1;
+        let column = self.src[line_start..start].chars().count();
+
+        writeln!(f, "{} (line {line_number})", self.error)?;
+        writeln!(f, "{}", &self.src[line_start..line_end])?;
+        write!(f, "{}^", " ".repeat(column))
+    }
+}
+
+impl std::fmt::Debug for SsaErrorWithSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+type ParseResult<T> = Result<T, ParserError>;
+
+/// A recursive descent parser with a single token of lookahead.
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    token: SpannedToken,
+    /// An error from lexing the current token, which is reported once the token is needed
+    lexer_error: Option<LexerError>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        let mut lexer = Lexer::new(source);
+        let (token, lexer_error) = Self::lex(&mut lexer);
+        Parser { lexer, token, lexer_error }
+    }
+
+    fn lex(lexer: &mut Lexer<'a>) -> (SpannedToken, Option<LexerError>) {
+        match lexer.next_token() {
+            Ok(token) => (token, None),
+            Err(error) => (SpannedToken { token: Token::Eof, span: error.span() }, Some(error)),
+        }
+    }
+
+    fn parse_ssa(&mut self) -> ParseResult<ParsedSsa> {
+        let mut functions = vec![self.parse_function()?];
+        while self.current()? != &Token::Eof {
+            functions.push(self.parse_function()?);
+        }
+        Ok(ParsedSsa { functions })
+    }
+
+    fn parse_function(&mut self) -> ParseResult<ParsedFunction> {
+        let runtime_type = self.parse_runtime_type()?;
+        self.eat_keyword_or_error("fn")?;
+        let external_name = self.eat_identifier_or_error("a function name")?.name;
+        let internal_name = self.eat_identifier_or_error("a function id")?;
+        self.eat_or_error(Token::LeftBrace, "'{'")?;
+
+        let mut blocks = vec![self.parse_block()?];
+        while !self.eat(Token::RightBrace)? {
+            blocks.push(self.parse_block()?);
+        }
+        Ok(ParsedFunction { runtime_type, external_name, internal_name, blocks })
+    }
+
+    fn parse_runtime_type(&mut self) -> ParseResult<RuntimeType> {
+        if self.eat_keyword("brillig")? {
+            return Ok(RuntimeType::Brillig);
+        }
+        self.eat_keyword_or_error("acir")?;
+        self.eat_or_error(Token::LeftParen, "'('")?;
+        let Identifier { name, span } = self.eat_identifier_or_error("an inline type")?;
+        let inline_type = match name.as_str() {
+            "inline" => InlineType::Inline,
+            "fold" => InlineType::Fold,
+            "no_predicates" => InlineType::NoPredicates,
+            _ => return Err(ParserError::InvalidInlineType { name: name.clone(), span }),
+        };
+        self.eat_or_error(Token::RightParen, "')'")?;
+        Ok(RuntimeType::Acir(inline_type))
+    }
+
+    fn parse_block(&mut self) -> ParseResult<ParsedBlock> {
+        let name = self.eat_identifier_or_error("a block name")?;
+        self.eat_or_error(Token::LeftParen, "'('")?;
+        let mut parameters = Vec::new();
+        if !self.eat(Token::RightParen)? {
+            loop {
+                let identifier = self.eat_identifier_or_error("a parameter")?;
+                self.eat_or_error(Token::Colon, "':'")?;
+                let typ = self.parse_type()?;
+                parameters.push(ParsedParameter { identifier, typ });
+                if !self.eat(Token::Comma)? {
+                    break;
+                }
+            }
+            self.eat_or_error(Token::RightParen, "')'")?;
+        }
+        self.eat_or_error(Token::Colon, "':'")?;
+
+        let mut instructions = Vec::new();
+        loop {
+            if let Some(terminator) = self.parse_terminator()? {
+                return Ok(ParsedBlock { name, parameters, instructions, terminator });
+            }
+            instructions.push(self.parse_instruction()?);
+        }
+    }
+
+    fn parse_terminator(&mut self) -> ParseResult<Option<ParsedTerminator>> {
+        if self.eat_keyword("jmp")? {
+            let destination = self.eat_identifier_or_error("a block name")?;
+            let arguments = self.parse_arguments()?;
+            return Ok(Some(ParsedTerminator::Jmp { destination, arguments }));
+        }
+        if self.eat_keyword("jmpif")? {
+            let condition = self.parse_value()?;
+            self.eat_keyword_or_error("then")?;
+            self.eat_or_error(Token::Colon, "':'")?;
+            let then_block = self.eat_identifier_or_error("a block name")?;
+            self.eat_or_error(Token::Comma, "','")?;
+            self.eat_keyword_or_error("else")?;
+            self.eat_or_error(Token::Colon, "':'")?;
+            let else_block = self.eat_identifier_or_error("a block name")?;
+            return Ok(Some(ParsedTerminator::Jmpif { condition, then_block, else_block }));
+        }
+        if self.eat_keyword("return")? {
+            // A return without values is followed by the end of the function or the next block
+            let returns_values = match self.current()? {
+                Token::RightBrace => false,
+                Token::Ident(name) => !is_indexed_name(name, 'b'),
+                _ => true,
+            };
+            let values = if returns_values { self.parse_value_list()? } else { Vec::new() };
+            return Ok(Some(ParsedTerminator::Return(values)));
+        }
+        Ok(None)
+    }
+
+    fn parse_instruction(&mut self) -> ParseResult<ParsedInstruction> {
+        if self.eat_keyword("constrain")? {
+            let lhs = self.parse_value()?;
+            self.eat_or_error(Token::Equal, "'=='")?;
+            let rhs = self.parse_value()?;
+            let error =
+                if self.eat(Token::Comma)? { Some(self.parse_constrain_error()?) } else { None };
+            return Ok(ParsedInstruction::Constrain { lhs, rhs, error });
+        }
+        if self.eat_keyword("store")? {
+            let value = self.parse_value()?;
+            self.eat_keyword_or_error("at")?;
+            let address = self.parse_value()?;
+            return Ok(ParsedInstruction::Store { value, address });
+        }
+        if self.eat_keyword("enable_side_effects")? {
+            let condition = self.parse_value()?;
+            return Ok(ParsedInstruction::EnableSideEffects { condition });
+        }
+        if self.eat_keyword("inc_rc")? {
+            return Ok(ParsedInstruction::IncrementRc { value: self.parse_value()? });
+        }
+        if self.eat_keyword("dec_rc")? {
+            return Ok(ParsedInstruction::DecrementRc { value: self.parse_value()? });
+        }
+        if self.eat_keyword("range_check")? {
+            let value = self.parse_value()?;
+            self.eat_keyword_or_error("to")?;
+            let max_bit_size = self.eat_u32_or_error()?;
+            self.eat_keyword_or_error("bits")?;
+            return Ok(ParsedInstruction::RangeCheck { value, max_bit_size });
+        }
+        if self.at_keyword("call")? {
+            return self.parse_call(Vec::new());
+        }
+
+        let mut targets = vec![self.eat_identifier_or_error("an instruction")?];
+        while self.eat(Token::Comma)? {
+            targets.push(self.eat_identifier_or_error("a result")?);
+        }
+        self.eat_or_error(Token::Assign, "'='")?;
+        if self.at_keyword("call")? {
+            return self.parse_call(targets);
+        }
+
+        let span = self.token.span;
+        let target = match <[Identifier; 1]>::try_from(targets) {
+            Ok([target]) => target,
+            Err(targets) => {
+                let found = targets.len();
+                return Err(ParserError::MismatchedResults { expected: 1, found, span });
+            }
+        };
+        let instruction = self.eat_identifier_or_error("an instruction")?;
+        let instruction = match instruction.name.as_str() {
+            "allocate" => {
+                self.eat_or_error(Token::Arrow, "'->'")?;
+                ParsedInstruction::Allocate { target, typ: self.parse_type()? }
+            }
+            "array_get" => {
+                let array = self.parse_value()?;
+                self.eat_or_error(Token::Comma, "','")?;
+                self.eat_keyword_or_error("index")?;
+                let index = self.parse_value()?;
+                self.eat_or_error(Token::Arrow, "'->'")?;
+                let element_type = self.parse_type()?;
+                ParsedInstruction::ArrayGet { target, element_type, array, index }
+            }
+            "array_set" => {
+                let mutable = self.eat_keyword("mut")?;
+                let array = self.parse_value()?;
+                self.eat_or_error(Token::Comma, "','")?;
+                self.eat_keyword_or_error("index")?;
+                let index = self.parse_value()?;
+                self.eat_or_error(Token::Comma, "','")?;
+                self.eat_keyword_or_error("value")?;
+                let value = self.parse_value()?;
+                ParsedInstruction::ArraySet { target, array, index, value, mutable }
+            }
+            "cast" => {
+                let lhs = self.parse_value()?;
+                self.eat_keyword_or_error("as")?;
+                ParsedInstruction::Cast { target, lhs, typ: self.parse_type()? }
+            }
+            "if" => {
+                let then_condition = self.parse_value()?;
+                self.eat_keyword_or_error("then")?;
+                let then_value = self.parse_value()?;
+                self.eat_keyword_or_error("else")?;
+                self.eat_keyword_or_error("if")?;
+                let else_condition = self.parse_value()?;
+                self.eat_keyword_or_error("then")?;
+                let else_value = self.parse_value()?;
+                ParsedInstruction::IfElse {
+                    target,
+                    then_condition,
+                    then_value,
+                    else_condition,
+                    else_value,
+                }
+            }
+            "load" => {
+                let value = self.parse_value()?;
+                self.eat_or_error(Token::Arrow, "'->'")?;
+                ParsedInstruction::Load { target, value, typ: self.parse_type()? }
+            }
+            "not" => ParsedInstruction::Not { target, value: self.parse_value()? },
+            "truncate" => {
+                let value = self.parse_value()?;
+                self.eat_keyword_or_error("to")?;
+                let bit_size = self.eat_u32_or_error()?;
+                self.eat_keyword_or_error("bits")?;
+                self.eat_or_error(Token::Comma, "','")?;
+                self.eat_keyword_or_error("max_bit_size")?;
+                self.eat_or_error(Token::Colon, "':'")?;
+                let max_bit_size = self.eat_u32_or_error()?;
+                ParsedInstruction::Truncate { target, value, bit_size, max_bit_size }
+            }
+            name => {
+                let Some(op) = binary_operator(name) else {
+                    let token = Token::Ident(name.to_string());
+                    let found = SpannedToken { token, span: instruction.span };
+                    let expected = "an instruction".to_string();
+                    return Err(ParserError::ExpectedToken { expected, found });
+                };
+                let lhs = self.parse_value()?;
+                self.eat_or_error(Token::Comma, "','")?;
+                let rhs = self.parse_value()?;
+                ParsedInstruction::BinaryOp { target, lhs, op, rhs }
+            }
+        };
+        Ok(instruction)
+    }
+
+    /// Parses a call, with `targets` being the names given to its results
+    fn parse_call(&mut self, targets: Vec<Identifier>) -> ParseResult<ParsedInstruction> {
+        self.eat_keyword_or_error("call")?;
+        let function = self.parse_value()?;
+        let arguments = self.parse_arguments()?;
+
+        let span = self.token.span;
+        let mut types = Vec::new();
+        if self.eat(Token::Arrow)? {
+            types.push(self.parse_type()?);
+            while self.eat(Token::Comma)? {
+                types.push(self.parse_type()?);
+            }
+        }
+        if types.len() != targets.len() {
+            let (expected, found) = (targets.len(), types.len());
+            return Err(ParserError::MismatchedResults { expected, found, span });
+        }
+        Ok(ParsedInstruction::Call { targets, function, arguments, types })
+    }
+
+    fn parse_constrain_error(&mut self) -> ParseResult<ParsedConstrainError> {
+        if let Some(message) = self.eat_string()? {
+            return Ok(ParsedConstrainError::Intrinsic(message));
+        }
+        self.eat_keyword_or_error("data")?;
+        if let Some(message) = self.eat_string()? {
+            return Ok(ParsedConstrainError::UserDefinedString(message));
+        }
+        let Some(selector) = self.eat_int()?.and_then(|selector| selector.try_to_u64()) else {
+            let found = self.token.clone();
+            return Err(ParserError::ExpectedToken { expected: "an error selector".into(), found });
+        };
+        self.eat_or_error(Token::Colon, "':'")?;
+        let values = self.parse_value_list()?;
+        Ok(ParsedConstrainError::UserDefined { selector, values })
+    }
+
+    /// Parses a parenthesized list of values, which may be empty
+    fn parse_arguments(&mut self) -> ParseResult<Vec<ParsedValue>> {
+        self.eat_or_error(Token::LeftParen, "'('")?;
+        if self.eat(Token::RightParen)? {
+            return Ok(Vec::new());
+        }
+        let arguments = self.parse_value_list()?;
+        self.eat_or_error(Token::RightParen, "')'")?;
+        Ok(arguments)
+    }
+
+    /// Parses one or more comma-separated values
+    fn parse_value_list(&mut self) -> ParseResult<Vec<ParsedValue>> {
+        let mut values = vec![self.parse_value()?];
+        while self.eat(Token::Comma)? {
+            values.push(self.parse_value()?);
+        }
+        Ok(values)
+    }
+
+    fn parse_value(&mut self) -> ParseResult<ParsedValue> {
+        if self.eat(Token::LeftBracket)? {
+            let mut values = Vec::new();
+            if !self.eat(Token::RightBracket)? {
+                values = self.parse_value_list()?;
+                self.eat_or_error(Token::RightBracket, "']'")?;
+            }
+            self.eat_keyword_or_error("of")?;
+            let type_start = self.token.clone();
+            let typ = self.parse_type()?;
+            if !matches!(typ, Type::Array(..) | Type::Slice(_)) {
+                let expected = "an array type".to_string();
+                return Err(ParserError::ExpectedToken { expected, found: type_start });
+            }
+            return Ok(ParsedValue::Array { values, typ });
+        }
+
+        let identifier = self.eat_identifier_or_error("a value")?;
+        if let Some(typ) = numeric_type(&identifier.name) {
+            let Some(constant) = self.eat_int()? else {
+                let found = self.token.clone();
+                return Err(ParserError::ExpectedToken { expected: "an integer".into(), found });
+            };
+            return Ok(ParsedValue::NumericConstant { constant, typ });
+        }
+        Ok(ParsedValue::Variable(identifier))
+    }
+
+    fn parse_type(&mut self) -> ParseResult<Type> {
+        if self.eat(Token::Ampersand)? {
+            self.eat_keyword_or_error("mut")?;
+            return Ok(Type::Reference(Rc::new(self.parse_type()?)));
+        }
+        if self.eat(Token::LeftBracket)? {
+            let mut element_types = vec![self.parse_type()?];
+            while self.eat(Token::Comma)? {
+                element_types.push(self.parse_type()?);
+            }
+            let typ = if self.eat(Token::Semicolon)? {
+                let length = self.eat_u32_or_error()?;
+                Type::Array(Rc::new(element_types), length as usize)
+            } else {
+                Type::Slice(Rc::new(element_types))
+            };
+            self.eat_or_error(Token::RightBracket, "']'")?;
+            return Ok(typ);
+        }
+
+        let found = self.token.clone();
+        let typ = match &found.token {
+            Token::Ident(name) if name == "function" => Some(Type::Function),
+            Token::Ident(name) => numeric_type(name),
+            _ => None,
+        };
+        match typ {
+            Some(typ) => {
+                self.bump()?;
+                Ok(typ)
+            }
+            None => Err(ParserError::ExpectedToken { expected: "a type".into(), found }),
+        }
+    }
+
+    fn current(&mut self) -> ParseResult<&Token> {
+        if let Some(error) = self.lexer_error.take() {
+            return Err(error.into());
+        }
+        Ok(&self.token.token)
+    }
+
+    /// Moves on to the next token, returning the current one
+    fn bump(&mut self) -> ParseResult<SpannedToken> {
+        if let Some(error) = self.lexer_error.take() {
+            return Err(error.into());
+        }
+        let (next, lexer_error) = Self::lex(&mut self.lexer);
+        self.lexer_error = lexer_error;
+        Ok(std::mem::replace(&mut self.token, next))
+    }
+
+    fn eat(&mut self, token: Token) -> ParseResult<bool> {
+        if self.current()? == &token {
+            self.bump()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn eat_or_error(&mut self, token: Token, expected: &str) -> ParseResult<()> {
+        if self.eat(token)? {
+            Ok(())
+        } else {
+            let found = self.token.clone();
+            Err(ParserError::ExpectedToken { expected: expected.to_string(), found })
+        }
+    }
+
+    fn at_keyword(&mut self, keyword: &str) -> ParseResult<bool> {
+        Ok(matches!(self.current()?, Token::Ident(name) if name == keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> ParseResult<bool> {
+        if self.at_keyword(keyword)? {
+            self.bump()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn eat_keyword_or_error(&mut self, keyword: &str) -> ParseResult<()> {
+        if self.eat_keyword(keyword)? {
+            Ok(())
+        } else {
+            let found = self.token.clone();
+            Err(ParserError::ExpectedToken { expected: format!("'{keyword}'"), found })
+        }
+    }
+
+    fn eat_identifier_or_error(&mut self, expected: &str) -> ParseResult<Identifier> {
+        if let Token::Ident(_) = self.current()? {
+            let SpannedToken { token: Token::Ident(name), span } = self.bump()? else {
+                unreachable!("the current token is an identifier");
+            };
+            Ok(Identifier { name, span })
+        } else {
+            let found = self.token.clone();
+            Err(ParserError::ExpectedToken { expected: expected.to_string(), found })
+        }
+    }
+
+    fn eat_int(&mut self) -> ParseResult<Option<FieldElement>> {
+        if let Token::Int(int) = self.current()? {
+            let int = *int;
+            self.bump()?;
+            Ok(Some(int))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn eat_u32_or_error(&mut self) -> ParseResult<u32> {
+        let span = self.token.span;
+        let Some(int) = self.eat_int()? else {
+            let found = self.token.clone();
+            return Err(ParserError::ExpectedToken { expected: "an integer".into(), found });
+        };
+        int.try_to_u32().ok_or(ParserError::IntegerTooLarge { max: u32::MAX, found: int, span })
+    }
+
+    fn eat_string(&mut self) -> ParseResult<Option<String>> {
+        if let Token::Str(string) = self.current()? {
+            let string = string.clone();
+            self.bump()?;
+            Ok(Some(string))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the numeric type with the given name, such as `Field`, `u32` or `i8`
+fn numeric_type(name: &str) -> Option<Type> {
+    if name == "Field" {
+        return Some(Type::field());
+    }
+    let bit_size = |bit_size: &str| {
+        let is_digits = !bit_size.is_empty() && bit_size.chars().all(|char| char.is_ascii_digit());
+        is_digits.then(|| bit_size.parse::<u32>().ok()).flatten()
+    };
+    if let Some(bit_size) = name.strip_prefix('u').and_then(bit_size) {
+        Some(Type::unsigned(bit_size))
+    } else {
+        name.strip_prefix('i').and_then(bit_size).map(Type::signed)
+    }
+}
+
+fn binary_operator(name: &str) -> Option<BinaryOp> {
+    let op = match name {
+        "add" => BinaryOp::Add,
+        "sub" => BinaryOp::Sub,
+        "mul" => BinaryOp::Mul,
+        "div" => BinaryOp::Div,
+        "eq" => BinaryOp::Eq,
+        "mod" => BinaryOp::Mod,
+        "lt" => BinaryOp::Lt,
+        "and" => BinaryOp::And,
+        "or" => BinaryOp::Or,
+        "xor" => BinaryOp::Xor,
+        "shl" => BinaryOp::Shl,
+        "shr" => BinaryOp::Shr,
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// Returns whether `name` is `prefix` followed by an index, such as the value `v1` or the block `b2`
+fn is_indexed_name(name: &str, prefix: char) -> bool {
+    name.strip_prefix(prefix)
+        .is_some_and(|index| !index.is_empty() && index.chars().all(|char| char.is_ascii_digit()))
+}
@@ -0,0 +1,261 @@
+use std::str::FromStr;
+
+use noirc_errors::Span;
+use noirc_frontend::monomorphization::ast::InlineType;
+
+use crate::ssa::{
+    ir::{function::RuntimeType, types::Type},
+    opt::assert_normalized_ssa_equals,
+    ssa_gen::Ssa,
+};
+
+use super::{
+    ast::{
+        Identifier, ParsedBlock, ParsedFunction, ParsedInstruction, ParsedParameter, ParsedSsa,
+        ParsedTerminator, ParsedValue,
+    },
+    SsaError,
+};
+
+fn assert_ssa_roundtrip(src: &str) {
+    let ssa = Ssa::from_str(src).unwrap();
+    assert_normalized_ssa_equals(ssa, src);
+}
+
+fn parse_error(src: &str) -> SsaError {
+    match Ssa::from_str(src) {
+        Ok(ssa) => panic!("Expected an error but parsed:\n{ssa}"),
+        Err(error) => error.error,
+    }
+}
+
+#[test]
+fn parses_empty_function() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0():
+            return
+        }
+        ";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn parses_function_with_parameters_of_each_type() {
+    let src = "
+        acir(fold) fn main f0 {
+          b0(v0: Field, v1: u32, v2: i8, v3: [Field; 3], v4: [u8], v5: &mut [Field, u1; 2]):
+            return v0, v1, v2
+        }
+        ";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn parses_constants() {
+    let large = format!("0x{}1{}", "0".repeat(13), "0".repeat(50));
+    let src = format!(
+        "
+        acir(inline) fn main f0 {{
+          b0():
+            return Field 1, u8 255, Field -1, Field {large}, [Field 2, Field 3] of [Field; 2], [] of [u8]
+        }}
+        "
+    );
+    assert_ssa_roundtrip(&src);
+}
+
+#[test]
+fn parses_multiple_functions_and_calls() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0(v0: Field):
+            v2 = call f1(v0) -> Field
+            v4 = call to_le_bits(v0, u32 8) -> [u1; 8]
+            call print(v2)
+            return v2, v4
+        }
+        brillig fn foo f1 {
+          b0(v0: Field):
+            v2 = add v0, Field 1
+            return v2
+        }
+        ";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn parses_control_flow() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0(v0: u1):
+            jmpif v0 then: b1, else: b2
+          b1():
+            jmp b3(Field 1)
+          b3(v1: Field):
+            return v1
+          b2():
+            jmp b3(Field 2)
+        }
+        ";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn parses_instructions() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0(v0: [Field; 2], v1: u32, v2: u1):
+            v3 = array_get v0, index v1 -> Field
+            v4 = array_set v0, index v1, value Field 3
+            v5 = array_set mut v4, index u32 0, value v3
+            v6 = allocate -> &mut Field
+            store v3 at v6
+            v7 = load v6 -> Field
+            v8 = cast v1 as Field
+            v9 = not v2
+            v10 = truncate v8 to 8 bits, max_bit_size: 32
+            range_check v8 to 16 bits
+            inc_rc v0
+            dec_rc v0
+            enable_side_effects v2
+            v11 = if v2 then v7 else if v9 then v10
+            v12 = lt v1, u32 10
+            v13 = shl v1, u8 2
+            return [v3, v7] of [Field; 2], v5, v11, v12, v13
+        }
+        ";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn parses_constrain_errors() {
+    let src = r#"
+        acir(inline) fn main f0 {
+          b0(v0: Field):
+            constrain v0 == Field 1
+            constrain v0 == Field 1, "Index out of bounds"
+            constrain v0 == Field 1, data "a \"quoted\" message"
+            constrain v0 == Field 1, data 1234: v0, Field 2
+            return
+        }
+        "#;
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn skips_comments() {
+    let src = "
+        // The entry point
+        acir(inline) fn main f0 {
+          b0(v0: Field): // the only block
+            return v0
+        }
+        ";
+    let expected = "
+        acir(inline) fn main f0 {
+          b0(v0: Field):
+            return v0
+        }
+        ";
+    assert_normalized_ssa_equals(Ssa::from_str(src).unwrap(), expected);
+}
+
+#[test]
+fn does_not_simplify_instructions() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0():
+            v2 = add Field 1, Field 2
+            return v2
+        }
+        ";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn errors_on_unknown_variable() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0():
+            return v3
+        }
+        ";
+    let error = parse_error(src);
+    assert!(matches!(error, SsaError::UnknownVariable(identifier) if identifier.name == "v3"));
+}
+
+#[test]
+fn errors_on_unknown_block() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0():
+            jmp b1()
+        }
+        ";
+    let error = parse_error(src);
+    assert!(matches!(error, SsaError::UnknownBlock(identifier) if identifier.name == "b1"));
+}
+
+#[test]
+fn errors_on_redefined_variable() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0(v0: Field):
+            v0 = add v0, Field 1
+            return v0
+        }
+        ";
+    assert!(matches!(parse_error(src), SsaError::VariableAlreadyDefined(..)));
+}
+
+#[test]
+fn errors_on_unknown_instruction() {
+    let src = "
+        acir(inline) fn main f0 {
+          b0(v0: Field):
+            v1 = frobnicate v0
+            return v1
+        }
+        ";
+    let error = Ssa::from_str(src).err().expect("Expected an error");
+    assert!(matches!(error.error, SsaError::ParserError(..)));
+    assert_eq!(
+        error.to_string(),
+        "Expected an instruction but found 'frobnicate' (line 4)\n            v1 = frobnicate v0\n                 ^"
+    );
+}
+
+#[test]
+fn errors_on_call_with_more_targets_than_results() {
+    // The parser rejects this itself, so the parsed SSA is built by hand
+    let identifier = |name: &str| Identifier { name: name.to_string(), span: Span::default() };
+    let call = ParsedInstruction::Call {
+        targets: vec![identifier("v1"), identifier("v2")],
+        function: ParsedValue::Variable(identifier("f1")),
+        arguments: vec![ParsedValue::Variable(identifier("v0"))],
+        types: vec![Type::field()],
+    };
+    let block = |instructions, terminator| ParsedBlock {
+        name: identifier("b0"),
+        parameters: vec![ParsedParameter { identifier: identifier("v0"), typ: Type::field() }],
+        instructions,
+        terminator,
+    };
+    let function = |name: &str, id: &str, block| ParsedFunction {
+        runtime_type: RuntimeType::Acir(InlineType::Inline),
+        external_name: name.to_string(),
+        internal_name: identifier(id),
+        blocks: vec![block],
+    };
+    let return_parameter = ParsedTerminator::Return(vec![ParsedValue::Variable(identifier("v0"))]);
+    let parsed_ssa = ParsedSsa {
+        functions: vec![
+            function("main", "f0", block(vec![call], ParsedTerminator::Return(Vec::new()))),
+            function("foo", "f1", block(Vec::new(), return_parameter)),
+        ],
+    };
+
+    let error = parsed_ssa.into_ssa().err().expect("Expected an error");
+    assert!(matches!(error, SsaError::MismatchedCallResults { expected: 1, found: 2, .. }));
+}
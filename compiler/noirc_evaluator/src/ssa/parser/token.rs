@@ -0,0 +1,70 @@
+use acvm::FieldElement;
+use noirc_errors::Span;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SpannedToken {
+    pub(crate) token: Token,
+    pub(crate) span: Span,
+}
+
+/// The tokens of SSA in the textual form produced by [`crate::ssa::ir::printer`].
+///
+/// Keywords such as `fn`, `call` or `Field` are lexed as identifiers, as they're only
+/// meaningful in particular positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    Ident(String),
+    Int(FieldElement),
+    Str(String),
+    /// (
+    LeftParen,
+    /// )
+    RightParen,
+    /// [
+    LeftBracket,
+    /// ]
+    RightBracket,
+    /// {
+    LeftBrace,
+    /// }
+    RightBrace,
+    /// ,
+    Comma,
+    /// :
+    Colon,
+    /// ;
+    Semicolon,
+    /// =
+    Assign,
+    /// ==
+    Equal,
+    /// &
+    Ampersand,
+    /// ->
+    Arrow,
+    Eof,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "{ident}"),
+            Token::Int(int) => write!(f, "{int}"),
+            Token::Str(string) => write!(f, "{string:?}"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::LeftBrace => write!(f, "{{"),
+            Token::RightBrace => write!(f, "}}"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::Semicolon => write!(f, ";"),
+            Token::Assign => write!(f, "="),
+            Token::Equal => write!(f, "=="),
+            Token::Ampersand => write!(f, "&"),
+            Token::Arrow => write!(f, "->"),
+            Token::Eof => write!(f, "end of input"),
+        }
+    }
+}
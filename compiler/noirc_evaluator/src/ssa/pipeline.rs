@@ -0,0 +1,303 @@
+//! The SSA passes which make up the optimization pipeline, along with a way of running any
+//! sequence of them over SSA in its textual form.
+//!
+//! Running passes over textual SSA doesn't require a Noir program, so a miscompilation can be
+//! narrowed down to a single pass by feeding it the SSA printed with `--show-ssa` just before it.
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::errors::RuntimeError;
+
+use super::{ir::verify::verify_ssa, ssa_gen::Ssa};
+
+/// An optimization pass over SSA, which can be selected by its name.
+#[derive(Clone, Copy)]
+pub struct SsaPass {
+    name: &'static str,
+    /// Printed before the SSA produced by this pass when passes are being shown
+    msg: &'static str,
+    run: PassFunction,
+}
+
+#[derive(Clone, Copy)]
+enum PassFunction {
+    Infallible(fn(Ssa) -> Ssa),
+    Fallible(fn(Ssa) -> Result<Ssa, RuntimeError>),
+}
+
+/// Every pass that can be selected by name, in the order they're first run by [`default_pipeline`].
+const PASSES: &[SsaPass] = &[
+    SsaPass::new("defunctionalize", "After Defunctionalization:", Ssa::defunctionalize),
+    SsaPass::new(
+        "remove_paired_rc",
+        "After Removing Paired rc_inc & rc_decs:",
+        Ssa::remove_paired_rc,
+    ),
+    SsaPass::new("separate_runtime", "After Runtime Separation:", Ssa::separate_runtime),
+    SsaPass::new(
+        "resolve_is_unconstrained",
+        "After Resolving IsUnconstrained:",
+        Ssa::resolve_is_unconstrained,
+    ),
+    SsaPass::new("inline_functions", "After Inlining:", Ssa::inline_functions),
+    SsaPass::new("mem2reg", "After Mem2Reg:", Ssa::mem2reg),
+    SsaPass::new(
+        "as_slice_optimization",
+        "After `as_slice` optimization",
+        Ssa::as_slice_optimization,
+    ),
+    SsaPass::fallible(
+        "evaluate_assert_constant",
+        "After Assert Constant:",
+        Ssa::evaluate_assert_constant,
+    ),
+    SsaPass::fallible("unroll", "After Unrolling:", Ssa::unroll_loops_iteratively),
+    SsaPass::new("simplify_cfg", "After Simplifying:", Ssa::simplify_cfg),
+    SsaPass::new("flatten_cfg", "After Flattening:", Ssa::flatten_cfg),
+    SsaPass::new("remove_bit_shifts", "After Removing Bit Shifts:", Ssa::remove_bit_shifts),
+    SsaPass::new(
+        "inline_functions_with_no_predicates",
+        "After Inlining:",
+        Ssa::inline_functions_with_no_predicates,
+    ),
+    SsaPass::new("remove_if_else", "After Remove IfElse:", Ssa::remove_if_else),
+    SsaPass::new("fold_constants", "After Constant Folding:", Ssa::fold_constants),
+    SsaPass::new(
+        "remove_enable_side_effects",
+        "After EnableSideEffects removal:",
+        Ssa::remove_enable_side_effects,
+    ),
+    SsaPass::new(
+        "fold_constants_using_constraints",
+        "After Constraint Folding:",
+        Ssa::fold_constants_using_constraints,
+    ),
+    SsaPass::new(
+        "loop_invariant_code_motion",
+        "After Loop Invariant Code Motion:",
+        Ssa::loop_invariant_code_motion,
+    ),
+    SsaPass::new(
+        "dead_instruction_elimination",
+        "After Dead Instruction Elimination:",
+        Ssa::dead_instruction_elimination,
+    ),
+    SsaPass::new(
+        "array_set_optimization",
+        "After Array Set Optimizations:",
+        Ssa::array_set_optimization,
+    ),
+    SsaPass::new(
+        "bubble_up_constrains",
+        "After Bubbling Up Constrains:",
+        Ssa::bubble_up_constrains,
+    ),
+];
+
+impl SsaPass {
+    const fn new(name: &'static str, msg: &'static str, run: fn(Ssa) -> Ssa) -> Self {
+        SsaPass { name, msg, run: PassFunction::Infallible(run) }
+    }
+
+    const fn fallible(
+        name: &'static str,
+        msg: &'static str,
+        run: fn(Ssa) -> Result<Ssa, RuntimeError>,
+    ) -> Self {
+        SsaPass { name, msg, run: PassFunction::Fallible(run) }
+    }
+
+    /// Every pass that can be selected by name
+    pub fn all() -> &'static [SsaPass] {
+        PASSES
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub(crate) fn msg(&self) -> &'static str {
+        self.msg
+    }
+
+    pub(crate) fn run(&self, ssa: Ssa) -> Result<Ssa, RuntimeError> {
+        match self.run {
+            PassFunction::Infallible(pass) => Ok(pass(ssa)),
+            PassFunction::Fallible(pass) => pass(ssa),
+        }
+    }
+
+    fn lookup(name: &str) -> Option<SsaPass> {
+        PASSES.iter().find(|pass| pass.name == name).copied()
+    }
+}
+
+impl std::fmt::Debug for SsaPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SsaPass({})", self.name)
+    }
+}
+
+/// The passes run when compiling a program, in order.
+pub fn default_pipeline() -> Vec<SsaPass> {
+    let pass = |name| SsaPass::lookup(name).expect("the default pipeline only uses known passes");
+    vec![
+        pass("defunctionalize"),
+        pass("remove_paired_rc"),
+        pass("separate_runtime"),
+        pass("resolve_is_unconstrained"),
+        pass("inline_functions"),
+        // Run mem2reg with the CFG separated into blocks
+        pass("mem2reg"),
+        pass("as_slice_optimization"),
+        pass("evaluate_assert_constant"),
+        pass("unroll"),
+        pass("simplify_cfg"),
+        pass("flatten_cfg"),
+        pass("remove_bit_shifts"),
+        // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
+        pass("mem2reg"),
+        // Run the inlining pass again to handle functions with `InlineType::NoPredicates`.
+        // Before flattening is run, we treat functions marked with the `InlineType::NoPredicates` as an entry point.
+        // This pass must come immediately following `mem2reg` as the succeeding passes
+        // may create an SSA which inlining fails to handle.
+        pass("inline_functions_with_no_predicates"),
+        pass("remove_if_else"),
+        pass("fold_constants"),
+        pass("remove_enable_side_effects"),
+        pass("fold_constants_using_constraints"),
+        // Brillig loops aren't unrolled, so hoist the work repeated on each iteration out of them
+        pass("loop_invariant_code_motion"),
+        pass("dead_instruction_elimination"),
+        pass("array_set_optimization"),
+    ]
+}
+
+/// Parses a comma-separated list of pass names such as `mem2reg,unroll,flatten_cfg`.
+///
+/// The name `default` stands for the whole of the [`default_pipeline`].
+pub fn parse_pipeline(pipeline: &str) -> Result<Vec<SsaPass>, PipelineError> {
+    let mut passes = Vec::new();
+    for name in pipeline.split(',').map(str::trim) {
+        if name == "default" {
+            passes.extend(default_pipeline());
+        } else {
+            let pass = SsaPass::lookup(name)
+                .ok_or_else(|| PipelineError::UnknownPass(name.to_string()))?;
+            passes.push(pass);
+        }
+    }
+    Ok(passes)
+}
+
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("Unknown SSA pass '{0}'")]
+    UnknownPass(String),
+    #[error("Could not parse SSA: {0}")]
+    InvalidSsa(String),
+    #[error("Pass '{pass}' failed: {error}")]
+    PassFailed { pass: &'static str, error: RuntimeError },
+    #[error("Pass '{pass}' produced malformed SSA: {error}\n{ssa}")]
+    MalformedOutput { pass: &'static str, error: String, ssa: String },
+    #[error("Pass '{pass}' produced SSA which could not be parsed back: {error}\n{ssa}")]
+    InvalidOutput { pass: &'static str, error: String, ssa: String },
+}
+
+/// Options for [`run_pipeline`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PipelineOptions {
+    /// Check that the SSA produced by each pass is well-formed and can be parsed back in
+    pub validate: bool,
+}
+
+/// Parses `src` as SSA, runs `passes` over it in order and returns the resulting SSA.
+///
+/// `after_pass` is called with each pass and the SSA it produced, so that the SSA can be shown
+/// after every pass rather than only at the end.
+pub fn run_pipeline(
+    src: &str,
+    passes: &[SsaPass],
+    options: PipelineOptions,
+    mut after_pass: impl FnMut(&SsaPass, &str),
+) -> Result<String, PipelineError> {
+    let mut ssa =
+        Ssa::from_str(src).map_err(|error| PipelineError::InvalidSsa(error.to_string()))?;
+
+    for pass in passes {
+        ssa =
+            pass.run(ssa).map_err(|error| PipelineError::PassFailed { pass: pass.name, error })?;
+        let printed = ssa.to_string();
+        if options.validate {
+            if let Err(error) = verify_ssa(&ssa) {
+                let error = error.to_string();
+                return Err(PipelineError::MalformedOutput {
+                    pass: pass.name,
+                    error,
+                    ssa: printed,
+                });
+            }
+            if let Err(error) = Ssa::from_str(&printed) {
+                let error = error.to_string();
+                return Err(PipelineError::InvalidOutput { pass: pass.name, error, ssa: printed });
+            }
+        }
+        after_pass(pass, &printed);
+    }
+    Ok(ssa.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_pipeline, run_pipeline, PipelineError, PipelineOptions};
+
+    #[test]
+    fn parses_pipelines() {
+        let passes = parse_pipeline("mem2reg, unroll,flatten_cfg,fold_constants").unwrap();
+        let names: Vec<_> = passes.iter().map(|pass| pass.name()).collect();
+        assert_eq!(names, vec!["mem2reg", "unroll", "flatten_cfg", "fold_constants"]);
+
+        let passes = parse_pipeline("default,fold_constants").unwrap();
+        assert_eq!(passes.len(), super::default_pipeline().len() + 1);
+
+        let error = parse_pipeline("mem2reg,frobnicate").unwrap_err();
+        assert!(matches!(error, PipelineError::UnknownPass(name) if name == "frobnicate"));
+    }
+
+    #[test]
+    fn runs_passes_in_order() {
+        let src = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                v1 = allocate -> &mut Field
+                store v0 at v1
+                v2 = load v1 -> Field
+                v3 = add v2, Field 1
+                return v3
+            }
+            ";
+        let passes = parse_pipeline("mem2reg,dead_instruction_elimination").unwrap();
+
+        let mut passes_run = Vec::new();
+        let options = PipelineOptions { validate: true };
+        let ssa =
+            run_pipeline(src, &passes, options, |pass, _| passes_run.push(pass.name())).unwrap();
+        assert_eq!(passes_run, vec!["mem2reg", "dead_instruction_elimination"]);
+
+        let expected = "
+            acir(inline) fn main f0 {
+              b0(v0: Field):
+                v3 = add v0, Field 1
+                return v3
+            }
+            ";
+        crate::ssa::opt::assert_normalized_ssa_equals(ssa.parse().unwrap(), expected);
+    }
+
+    #[test]
+    fn reports_invalid_ssa() {
+        let error = run_pipeline("fn main", &[], PipelineOptions::default(), |_, _| {});
+        assert!(matches!(error, Err(PipelineError::InvalidSsa(_))));
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+use crate::ast::{Ident, UnresolvedGenerics, UnresolvedType};
+
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+/// Ast node for an enum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirEnumeration {
+    pub name: Ident,
+    pub generics: UnresolvedGenerics,
+    /// Each variant along with the types of the values it holds, if any
+    pub variants: Vec<(Ident, Vec<UnresolvedType>)>,
+    pub span: Span,
+}
+
+impl Display for NoirEnumeration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let generics = vecmap(&self.generics, |generic| generic.to_string());
+        let generics =
+            if generics.is_empty() { "".into() } else { format!("<{}>", generics.join(", ")) };
+
+        writeln!(f, "enum {}{} {{", self.name, generics)?;
+
+        for (name, parameters) in self.variants.iter() {
+            if parameters.is_empty() {
+                writeln!(f, "    {name},")?;
+            } else {
+                let parameters = vecmap(parameters, ToString::to_string);
+                writeln!(f, "    {name}({}),", parameters.join(", "))?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
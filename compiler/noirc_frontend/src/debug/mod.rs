@@ -404,6 +404,12 @@ impl DebugInstrumenter {
                     self.walk_expr(alt);
                 }
             }
+            ast::ExpressionKind::Match(match_expr) => {
+                self.walk_expr(&mut match_expr.expression);
+                match_expr.rules.iter_mut().for_each(|(_pattern, ref mut branch)| {
+                    self.walk_expr(branch);
+                });
+            }
             ast::ExpressionKind::Tuple(exprs) => {
                 exprs.iter_mut().for_each(|ref mut expr| {
                     self.walk_expr(expr);
@@ -459,6 +465,13 @@ impl DebugInstrumenter {
             ast::StatementKind::For(ref mut for_stmt) => {
                 self.walk_for(for_stmt);
             }
+            ast::StatementKind::While(ref mut while_stmt) => {
+                self.walk_expr(&mut while_stmt.condition);
+                self.walk_expr(&mut while_stmt.block);
+            }
+            ast::StatementKind::Loop(ref mut block) => {
+                self.walk_expr(block);
+            }
             _ => {} // Constrain, Error
         }
     }
@@ -677,6 +690,10 @@ fn pattern_vars(pattern: &ast::Pattern) -> Vec<(ast::Ident, bool)> {
                 stack.extend(pids.iter().map(|(_, pattern)| (pattern, is_mut)));
                 vars.extend(pids.iter().map(|(id, _)| (id.clone(), false)));
             }
+            ast::Pattern::Variant(_, fields, _) => {
+                stack.extend(fields.iter().map(|pattern| (pattern, is_mut)));
+            }
+            ast::Pattern::Literal(..) => {}
         }
     }
     vars
@@ -703,6 +720,8 @@ fn pattern_to_string(pattern: &ast::Pattern) -> String {
                     .join(", "),
             )
         }
+        ast::Pattern::Literal(literal, _) => literal.to_string(),
+        ast::Pattern::Variant(..) => pattern.to_string(),
     }
 }
 
@@ -0,0 +1,380 @@
+//! Exhaustiveness and reachability checking for `match` expressions.
+//!
+//! This follows the "usefulness" algorithm from Luc Maranget's
+//! "Warnings for pattern matching". Patterns are first lowered into a simplified
+//! form made of constructors and wildcards, guided by the type being matched on so
+//! that patterns which failed to type check can't produce malformed rows. A pattern
+//! is then useful with respect to the rows above it if some value matches it
+//! but none of those rows. An arm whose pattern is not useful is unreachable, and
+//! a match is exhaustive if a wildcard is not useful after all of its arms.
+use std::collections::BTreeSet;
+
+use acvm::{AcirField, FieldElement};
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+use crate::{
+    ast::Signedness,
+    hir::type_check::TypeCheckError,
+    hir_def::{expr::HirLiteral, stmt::HirPattern},
+    macros_api::HirExpression,
+    node_interner::ExprId,
+    Type,
+};
+
+use super::Elaborator;
+
+/// Integer types up to this many bits are small enough for a match to list every value.
+const MAX_ENUMERABLE_BIT_SIZE: u32 = 16;
+
+#[derive(Debug, Clone)]
+enum Constructor {
+    Bool(bool),
+    /// An integer value along with whether it is negative
+    Integer(FieldElement, bool),
+    /// The only constructor of a tuple or struct
+    Product,
+    /// The enum variant with the given index
+    Variant(usize),
+}
+
+impl PartialEq for Constructor {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Constructor::Bool(a), Constructor::Bool(b)) => a == b,
+            (Constructor::Integer(a, a_negative), Constructor::Integer(b, b_negative)) => {
+                // -0 and 0 are the same value
+                a == b && (a_negative == b_negative || a.is_zero())
+            }
+            (Constructor::Product, Constructor::Product) => true,
+            (Constructor::Variant(a), Constructor::Variant(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Wildcard,
+    Constructor(Constructor, Vec<Pattern>),
+}
+
+impl Pattern {
+    fn wildcards(count: usize) -> Vec<Pattern> {
+        vec![Pattern::Wildcard; count]
+    }
+}
+
+impl<'context> Elaborator<'context> {
+    /// Issues an error if the `rules` of a match on a value of type `typ` don't cover every value,
+    /// and a warning for each rule which can never be reached because earlier rules cover it.
+    pub(super) fn check_match_exhaustiveness(
+        &mut self,
+        typ: &Type,
+        rules: &[(HirPattern, ExprId)],
+        span: Span,
+    ) {
+        if matches!(typ.follow_bindings(), Type::Error) {
+            return;
+        }
+
+        let types = [typ.clone()];
+        let mut rows: Vec<Vec<Pattern>> = Vec::with_capacity(rules.len());
+
+        for (pattern, _) in rules {
+            let row = vec![self.lower_pattern(pattern, typ)];
+            if find_witness(&rows, &row, &types).is_none() {
+                self.push_err(TypeCheckError::UnreachableMatchArm { span: pattern.span() });
+            }
+            rows.push(row);
+        }
+
+        if let Some(witness) = find_witness(&rows, &[Pattern::Wildcard], &types) {
+            let missing = display_pattern(&witness[0], typ);
+            self.push_err(TypeCheckError::NonExhaustiveMatch { missing, span });
+        }
+    }
+
+    /// Lowers a pattern matching values of type `typ`. Anything which doesn't fit the type
+    /// has already been reported as a type error and is treated as a wildcard here.
+    fn lower_pattern(&self, pattern: &HirPattern, typ: &Type) -> Pattern {
+        match (pattern, typ.follow_bindings()) {
+            (HirPattern::Identifier(_), _) => Pattern::Wildcard,
+            (HirPattern::Mutable(pattern, _), _) => self.lower_pattern(pattern, typ),
+            (HirPattern::Tuple(fields, _), Type::Tuple(field_types))
+                if fields.len() == field_types.len() =>
+            {
+                let fields = fields
+                    .iter()
+                    .zip(&field_types)
+                    .map(|(field, field_type)| self.lower_pattern(field, field_type));
+                Pattern::Constructor(Constructor::Product, fields.collect())
+            }
+            (HirPattern::Struct(_, fields, _), Type::Struct(struct_type, generics)) => {
+                let field_types = struct_type.borrow().get_fields(&generics);
+                let fields = vecmap(field_types, |(name, field_type)| {
+                    fields
+                        .iter()
+                        .find(|(field_name, _)| field_name.0.contents == name)
+                        .map_or(Pattern::Wildcard, |(_, field)| {
+                            self.lower_pattern(field, &field_type)
+                        })
+                });
+                Pattern::Constructor(Constructor::Product, fields)
+            }
+            (HirPattern::Variant(_, index, fields, _), Type::Enum(enum_type, generics)) => {
+                let field_types = enum_type.borrow().get_variant(*index, &generics);
+                if fields.len() != field_types.len() {
+                    return Pattern::Wildcard;
+                }
+                let fields = fields
+                    .iter()
+                    .zip(&field_types)
+                    .map(|(field, field_type)| self.lower_pattern(field, field_type));
+                Pattern::Constructor(Constructor::Variant(*index), fields.collect())
+            }
+            (HirPattern::Literal(expr_id, _), typ) => {
+                match (self.interner.expression(expr_id), typ) {
+                    (HirExpression::Literal(HirLiteral::Bool(value)), Type::Bool) => {
+                        Pattern::Constructor(Constructor::Bool(value), Vec::new())
+                    }
+                    (
+                        HirExpression::Literal(HirLiteral::Integer(value, negative)),
+                        Type::FieldElement | Type::Integer(..) | Type::TypeVariable(..),
+                    ) => Pattern::Constructor(Constructor::Integer(value, negative), Vec::new()),
+                    _ => Pattern::Wildcard,
+                }
+            }
+            _ => Pattern::Wildcard,
+        }
+    }
+}
+
+/// Returns a list of patterns, one for each of `types`, matching a value that is matched by
+/// `vector` but none of the `rows`. Returns `None` if there is no such value, in which case
+/// `vector` is useless after `rows`.
+fn find_witness(rows: &[Vec<Pattern>], vector: &[Pattern], types: &[Type]) -> Option<Vec<Pattern>> {
+    let Some((first, rest)) = vector.split_first() else {
+        return rows.is_empty().then(Vec::new);
+    };
+    let typ = &types[0];
+
+    match first {
+        Pattern::Constructor(constructor, fields) => {
+            find_witness_for_constructor(rows, constructor, fields.clone(), rest, types)
+        }
+        Pattern::Wildcard => {
+            let mut used = Vec::new();
+            for row in rows {
+                if let Pattern::Constructor(constructor, _) = &row[0] {
+                    if !used.contains(constructor) {
+                        used.push(constructor.clone());
+                    }
+                }
+            }
+
+            if let Some(all_constructors) = complete_signature(typ, &used) {
+                // Every value starts with one of these constructors, so the wildcard is
+                // useful if it is useful for any one of them.
+                all_constructors.into_iter().find_map(|constructor| {
+                    let fields = Pattern::wildcards(arity(&constructor, typ));
+                    find_witness_for_constructor(rows, &constructor, fields, rest, types)
+                })
+            } else {
+                // Some constructor isn't listed so only rows starting with a wildcard matter
+                let default_rows: Vec<_> = rows
+                    .iter()
+                    .filter(|row| matches!(row[0], Pattern::Wildcard))
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+
+                let mut witness = find_witness(&default_rows, rest, &types[1..])?;
+                witness.insert(0, missing_constructor(typ, &used));
+                Some(witness)
+            }
+        }
+    }
+}
+
+/// Specializes `rows` and the vector `constructor(fields), rest...` to the values starting
+/// with `constructor` and looks for a witness among them.
+fn find_witness_for_constructor(
+    rows: &[Vec<Pattern>],
+    constructor: &Constructor,
+    fields: Vec<Pattern>,
+    rest: &[Pattern],
+    types: &[Type],
+) -> Option<Vec<Pattern>> {
+    let field_count = fields.len();
+    let specialized_rows: Vec<_> =
+        rows.iter().filter_map(|row| specialize(row, constructor, field_count)).collect();
+
+    let mut vector = fields;
+    vector.extend_from_slice(rest);
+
+    let mut field_types = constructor_field_types(constructor, &types[0], field_count);
+    field_types.extend_from_slice(&types[1..]);
+
+    let mut witness = find_witness(&specialized_rows, &vector, &field_types)?;
+    let rest = witness.split_off(field_count);
+    let mut result = vec![Pattern::Constructor(constructor.clone(), witness)];
+    result.extend(rest);
+    Some(result)
+}
+
+/// Keeps a row only if its first pattern can match a value starting with `constructor`,
+/// replacing that pattern with the patterns of the constructor's fields.
+fn specialize(
+    row: &[Pattern],
+    constructor: &Constructor,
+    field_count: usize,
+) -> Option<Vec<Pattern>> {
+    let mut specialized = match &row[0] {
+        Pattern::Wildcard => Pattern::wildcards(field_count),
+        Pattern::Constructor(other, fields) if other == constructor => fields.clone(),
+        Pattern::Constructor(..) => return None,
+    };
+    specialized.extend_from_slice(&row[1..]);
+    Some(specialized)
+}
+
+/// Returns every constructor of `typ` if `used` contains all of them.
+fn complete_signature(typ: &Type, used: &[Constructor]) -> Option<Vec<Constructor>> {
+    match typ.follow_bindings() {
+        Type::Bool => {
+            let all = vec![Constructor::Bool(false), Constructor::Bool(true)];
+            all.iter().all(|constructor| used.contains(constructor)).then_some(all)
+        }
+        Type::Tuple(_) | Type::Struct(..) => Some(vec![Constructor::Product]),
+        Type::Enum(enum_type, _) => {
+            let count = enum_type.borrow().num_variants();
+            let complete = (0..count).all(|index| used.contains(&Constructor::Variant(index)));
+            complete.then(|| (0..count).map(Constructor::Variant).collect())
+        }
+        Type::Integer(signedness, bit_size) => {
+            let bit_size: u32 = bit_size.into();
+            if bit_size > MAX_ENUMERABLE_BIT_SIZE {
+                return None;
+            }
+
+            // Literals outside of the type's range don't match any value so they can't count
+            // towards covering it, and neither can two literals for the same value.
+            let (min, max) = match signedness {
+                Signedness::Unsigned => (0, (1i128 << bit_size) - 1),
+                Signedness::Signed => (-(1i128 << (bit_size - 1)), (1i128 << (bit_size - 1)) - 1),
+            };
+            let mut values = BTreeSet::new();
+            let in_range = used.iter().filter(|constructor| {
+                let Constructor::Integer(value, negative) = constructor else {
+                    return false;
+                };
+                let Some(value) = value.try_to_u64() else {
+                    return false;
+                };
+                let value = if *negative { -i128::from(value) } else { i128::from(value) };
+                (min..=max).contains(&value) && values.insert(value)
+            });
+            let in_range: Vec<_> = in_range.cloned().collect();
+
+            (in_range.len() as i128 == max - min + 1).then_some(in_range)
+        }
+        _ => None,
+    }
+}
+
+fn arity(constructor: &Constructor, typ: &Type) -> usize {
+    match (constructor, typ.follow_bindings()) {
+        (Constructor::Product, Type::Tuple(fields)) => fields.len(),
+        (Constructor::Product, Type::Struct(struct_type, _)) => struct_type.borrow().num_fields(),
+        (Constructor::Variant(index), Type::Enum(enum_type, generics)) => {
+            enum_type.borrow().get_variant(*index, &generics).len()
+        }
+        _ => 0,
+    }
+}
+
+fn constructor_field_types(constructor: &Constructor, typ: &Type, field_count: usize) -> Vec<Type> {
+    match (constructor, typ.follow_bindings()) {
+        (_, Type::Tuple(fields)) => fields,
+        (_, Type::Struct(struct_type, generics)) => {
+            vecmap(struct_type.borrow().get_fields(&generics), |(_, typ)| typ)
+        }
+        (Constructor::Variant(index), Type::Enum(enum_type, generics)) => {
+            enum_type.borrow().get_variant(*index, &generics)
+        }
+        _ => vec![Type::Error; field_count],
+    }
+}
+
+/// A pattern for values of `typ` which don't start with any of the `used` constructors
+fn missing_constructor(typ: &Type, used: &[Constructor]) -> Pattern {
+    match typ.follow_bindings() {
+        Type::Bool if !used.is_empty() => {
+            let value = !used.contains(&Constructor::Bool(true));
+            Pattern::Constructor(Constructor::Bool(value), Vec::new())
+        }
+        Type::Integer(Signedness::Unsigned, _) | Type::FieldElement | Type::TypeVariable(..)
+            if !used.is_empty() =>
+        {
+            // Point out the smallest value that isn't matched. One of the first `used.len() + 1`
+            // values has to be missing.
+            let missing = (0..=used.len() as u128)
+                .map(|value| Constructor::Integer(FieldElement::from(value), false))
+                .find(|constructor| !used.contains(constructor));
+            missing.map_or(Pattern::Wildcard, |constructor| {
+                Pattern::Constructor(constructor, Vec::new())
+            })
+        }
+        Type::Enum(enum_type, generics) if !used.is_empty() => {
+            // Point out the first variant that isn't matched, with a wildcard for each value
+            let enum_type = enum_type.borrow();
+            let missing = (0..enum_type.num_variants())
+                .find(|index| !used.contains(&Constructor::Variant(*index)));
+            missing.map_or(Pattern::Wildcard, |index| {
+                let field_count = enum_type.get_variant(index, &generics).len();
+                Pattern::Constructor(Constructor::Variant(index), Pattern::wildcards(field_count))
+            })
+        }
+        _ => Pattern::Wildcard,
+    }
+}
+
+fn display_pattern(pattern: &Pattern, typ: &Type) -> String {
+    let Pattern::Constructor(constructor, fields) = pattern else {
+        return "_".to_string();
+    };
+
+    match (constructor, typ.follow_bindings()) {
+        (Constructor::Bool(value), _) => value.to_string(),
+        (Constructor::Integer(value, negative), _) => {
+            let sign = if *negative { "-" } else { "" };
+            format!("{sign}{}", value.to_u128())
+        }
+        (Constructor::Product, Type::Tuple(field_types)) => {
+            let fields =
+                vecmap(fields.iter().zip(&field_types), |(field, typ)| display_pattern(field, typ));
+            format!("({})", fields.join(", "))
+        }
+        (Constructor::Product, Type::Struct(struct_type, generics)) => {
+            let struct_type = struct_type.borrow();
+            let field_types = struct_type.get_fields(&generics);
+            let fields = vecmap(fields.iter().zip(&field_types), |(field, (name, typ))| {
+                format!("{name}: {}", display_pattern(field, typ))
+            });
+            format!("{} {{ {} }}", struct_type.name, fields.join(", "))
+        }
+        (Constructor::Variant(index), Type::Enum(enum_type, generics)) => {
+            let enum_type = enum_type.borrow();
+            let name = format!("{}::{}", enum_type.name, enum_type.variant_name(*index));
+            if fields.is_empty() {
+                return name;
+            }
+
+            let field_types = enum_type.get_variant(*index, &generics);
+            let fields =
+                vecmap(fields.iter().zip(&field_types), |(field, typ)| display_pattern(field, typ));
+            format!("{name}({})", fields.join(", "))
+        }
+        (Constructor::Product | Constructor::Variant(_), _) => "_".to_string(),
+    }
+}
@@ -6,7 +6,7 @@ use rustc_hash::FxHashSet as HashSet;
 use crate::{
     ast::{
         ArrayLiteral, ConstructorExpression, IfExpression, InfixExpression, Lambda,
-        UnresolvedTypeExpression,
+        MatchExpression, UnresolvedTypeExpression,
     },
     hir::{
         comptime::{self, Interpreter, InterpreterError},
@@ -16,9 +16,9 @@ use crate::{
     hir_def::{
         expr::{
             HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCastExpression,
-            HirConstructorExpression, HirIfExpression, HirIndexExpression, HirInfixExpression,
-            HirLambda, HirMemberAccess, HirMethodCallExpression, HirMethodReference,
-            HirPrefixExpression, HirQuoted,
+            HirConstructorExpression, HirEnumConstructorExpression, HirIfExpression,
+            HirIndexExpression, HirInfixExpression, HirLambda, HirMatchExpression, HirMemberAccess,
+            HirMethodCallExpression, HirMethodReference, HirPrefixExpression, HirQuoted,
         },
         traits::TraitConstraint,
     },
@@ -27,8 +27,8 @@ use crate::{
         HirLiteral, HirStatement, Ident, IndexExpression, Literal, MemberAccessExpression,
         MethodCallExpression, PrefixExpression,
     },
-    node_interner::{DefinitionKind, ExprId, FuncId},
-    QuotedType, Shared, StructType, Type,
+    node_interner::{DefinitionKind, ExprId, FuncId, ReferenceId},
+    EnumType, QuotedType, Shared, StructType, Type,
 };
 
 use super::Elaborator;
@@ -49,11 +49,19 @@ impl<'context> Elaborator<'context> {
             ExpressionKind::Cast(cast) => self.elaborate_cast(*cast, expr.span),
             ExpressionKind::Infix(infix) => return self.elaborate_infix(*infix, expr.span),
             ExpressionKind::If(if_) => self.elaborate_if(*if_),
+            ExpressionKind::Match(match_) => self.elaborate_match(*match_, expr.span),
             ExpressionKind::Variable(variable, generics) => {
-                let generics = generics.map(|option_inner| {
-                    option_inner.into_iter().map(|generic| self.resolve_type(generic)).collect()
-                });
-                return self.elaborate_variable(variable, generics);
+                let variant =
+                    if generics.is_none() { self.lookup_enum_variant(&variable) } else { None };
+
+                if let Some((enum_type, variant_index)) = variant {
+                    self.elaborate_enum_constructor(enum_type, variant_index, Vec::new(), expr.span)
+                } else {
+                    let generics = generics.map(|option_inner| {
+                        option_inner.into_iter().map(|generic| self.resolve_type(generic)).collect()
+                    });
+                    return self.elaborate_variable(variable, generics);
+                }
             }
             ExpressionKind::Tuple(tuple) => self.elaborate_tuple(tuple),
             ExpressionKind::Lambda(lambda) => self.elaborate_lambda(*lambda),
@@ -277,6 +285,17 @@ impl<'context> Elaborator<'context> {
     }
 
     fn elaborate_call(&mut self, call: CallExpression, span: Span) -> (HirExpression, Type) {
+        if let ExpressionKind::Variable(path, None) = &call.func.kind {
+            if let Some((enum_type, variant_index)) = self.lookup_enum_variant(path) {
+                return self.elaborate_enum_constructor(
+                    enum_type,
+                    variant_index,
+                    call.arguments,
+                    span,
+                );
+            }
+        }
+
         let (func, func_type) = self.elaborate_expression(*call.func);
 
         let mut arguments = Vec::with_capacity(call.arguments.len());
@@ -332,6 +351,9 @@ impl<'context> Elaborator<'context> {
                 };
 
                 if func_id != FuncId::dummy_id() {
+                    let name_location = Location::new(method_call.method_name.span(), self.file);
+                    self.interner.add_reference(ReferenceId::Function(func_id), name_location);
+
                     let function_type = self.interner.function_meta(&func_id).typ.clone();
                     self.try_add_mutable_reference_to_object(
                         &function_type,
@@ -423,6 +445,48 @@ impl<'context> Elaborator<'context> {
         (expr, Type::Struct(struct_type, generics))
     }
 
+    /// Elaborates a call to an enum variant, or a variant's name on its own if the variant
+    /// holds no values.
+    fn elaborate_enum_constructor(
+        &mut self,
+        enum_type: Shared<EnumType>,
+        variant_index: usize,
+        arguments: Vec<Expression>,
+        span: Span,
+    ) -> (HirExpression, Type) {
+        let enum_generics = enum_type.borrow().instantiate(self.interner);
+        let parameter_types = enum_type.borrow().get_variant(variant_index, &enum_generics);
+
+        if arguments.len() != parameter_types.len() {
+            let (expected, found) = (parameter_types.len(), arguments.len());
+            self.push_err(TypeCheckError::ArityMisMatch { expected, found, span });
+        }
+
+        let arguments = vecmap(arguments.into_iter().enumerate(), |(i, argument)| {
+            let argument_span = argument.span;
+            let (argument, argument_type) = self.elaborate_expression(argument);
+            let expected_type = parameter_types.get(i).unwrap_or(&Type::Error);
+
+            self.unify_with_coercions(&argument_type, expected_type, argument, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: expected_type.to_string(),
+                    expr_typ: argument_type.to_string(),
+                    expr_span: argument_span,
+                }
+            });
+            argument
+        });
+
+        let typ = Type::Enum(enum_type.clone(), enum_generics.clone());
+        let expr = HirExpression::EnumConstructor(HirEnumConstructorExpression {
+            r#type: enum_type,
+            enum_generics,
+            variant_index,
+            arguments,
+        });
+        (expr, typ)
+    }
+
     /// Resolve all the fields of a struct constructor expression.
     /// Ensures all fields are present, none are repeated, and all
     /// are part of the struct.
@@ -448,6 +512,8 @@ impl<'context> Elaborator<'context> {
                 unseen_fields.remove(&field_name);
                 seen_fields.insert(field_name.clone());
 
+                self.add_struct_member_reference(&struct_type.borrow(), &field_name);
+
                 self.unify_with_coercions(&field_type, expected_type, resolved, || {
                     TypeCheckError::TypeMismatch {
                         expected_typ: expected_type.to_string(),
@@ -594,6 +660,46 @@ impl<'context> Elaborator<'context> {
         (HirExpression::If(if_expr), ret_type)
     }
 
+    fn elaborate_match(
+        &mut self,
+        match_expr: MatchExpression,
+        span: Span,
+    ) -> (HirExpression, Type) {
+        let (expression, expression_type) = self.elaborate_expression(match_expr.expression);
+        let mut ret_type = None;
+
+        let rules = vecmap(match_expr.rules, |(pattern, branch)| {
+            // Each arm gets its own scope for the variables its pattern binds
+            self.push_scope();
+            let pattern = self.elaborate_pattern(
+                pattern,
+                expression_type.clone(),
+                DefinitionKind::Local(None),
+            );
+
+            let expr_span = branch.span;
+            let (branch, branch_type) = self.elaborate_expression(branch);
+            match &ret_type {
+                None => ret_type = Some(branch_type),
+                Some(ret_type) => self.unify(ret_type, &branch_type, || {
+                    TypeCheckError::TypeMismatch {
+                        expected_typ: ret_type.to_string(),
+                        expr_typ: branch_type.to_string(),
+                        expr_span,
+                    }
+                    .add_context("Expected the types of all match arms to be equal")
+                }),
+            }
+            self.pop_scope();
+            (pattern, branch)
+        });
+
+        self.check_match_exhaustiveness(&expression_type, &rules, span);
+
+        let match_expr = HirMatchExpression { expression, rules };
+        (HirExpression::Match(match_expr), ret_type.unwrap_or(Type::Unit))
+    }
+
     fn elaborate_tuple(&mut self, tuple: Vec<Expression>) -> (HirExpression, Type) {
         let mut element_ids = Vec::with_capacity(tuple.len());
         let mut element_types = Vec::with_capacity(tuple.len());
@@ -9,8 +9,8 @@ use crate::{
         comptime::{self, Interpreter, Value},
         def_collector::{
             dc_crate::{
-                filter_literal_globals, CompilationError, ImplMap, UnresolvedGlobal,
-                UnresolvedStruct, UnresolvedTypeAlias,
+                filter_literal_globals, CompilationError, ImplMap, UnresolvedEnum,
+                UnresolvedGlobal, UnresolvedStruct, UnresolvedTypeAlias,
             },
             errors::DuplicateType,
         },
@@ -28,7 +28,8 @@ use crate::{
         SecondaryAttribute, StructId,
     },
     node_interner::{
-        DefinitionId, DefinitionKind, DependencyId, ExprId, FuncId, GlobalId, TraitId, TypeAliasId,
+        DefinitionId, DefinitionKind, DependencyId, EnumId, ExprId, FuncId, GlobalId, TraitId,
+        TypeAliasId,
     },
     Shared, Type, TypeVariable,
 };
@@ -55,6 +56,7 @@ use crate::{
     macros_api::ItemVisibility,
 };
 
+mod exhaustiveness;
 mod expressions;
 mod lints;
 mod patterns;
@@ -244,6 +246,7 @@ impl<'context> Elaborator<'context> {
 
         // Must resolve structs before we resolve globals.
         self.collect_struct_definitions(items.types);
+        self.collect_enum_definitions(items.enums);
 
         // Before we resolve any function symbols we must go through our impls and
         // re-collect the methods within into their proper module. This cannot be
@@ -649,6 +652,8 @@ impl<'context> Elaborator<'context> {
             .map(|(name, typevar, _span)| (name.clone(), typevar.clone()))
             .collect();
 
+        // Checked before the statements are moved out of the function below
+        let has_body = !func.def.body.is_empty();
         let statements = std::mem::take(&mut func.def.body.statements);
         let body = BlockExpression { statements };
 
@@ -664,7 +669,7 @@ impl<'context> Elaborator<'context> {
             parameter_idents,
             return_type: func.def.return_type.clone(),
             return_visibility: func.def.return_visibility,
-            has_body: !func.def.body.is_empty(),
+            has_body,
             trait_constraints,
             is_entry_point,
             is_trait_function,
@@ -892,20 +897,29 @@ impl<'context> Elaborator<'context> {
 
         let function_ids = functions.function_ids();
 
-        if let Type::Struct(struct_type, _) = &self_type {
-            let struct_ref = struct_type.borrow();
+        let type_module = match self_type {
+            Type::Struct(struct_type, _) => {
+                let struct_ref = struct_type.borrow();
+                Some((struct_ref.id.module_id(), struct_ref.name.to_string()))
+            }
+            Type::Enum(enum_type, _) => {
+                let enum_ref = enum_type.borrow();
+                Some((enum_ref.id.module_id(), enum_ref.name.to_string()))
+            }
+            _ => None,
+        };
 
+        if let Some((module_id, type_name)) = type_module {
             // `impl`s are only allowed on types defined within the current crate
-            if !is_trait_impl && struct_ref.id.krate() != self.crate_id {
-                let type_name = struct_ref.name.to_string();
+            if !is_trait_impl && module_id.krate != self.crate_id {
                 self.push_err(DefCollectorErrorKind::ForeignImpl { span, type_name });
                 return;
             }
 
-            // Grab the module defined by the struct type. Note that impls are a case
+            // Grab the module defined by the struct or enum type. Note that impls are a case
             // where the module the methods are added to is not the same as the module
             // they are resolved in.
-            let module = Self::get_module_mut(self.def_maps, struct_ref.id.module_id());
+            let module = Self::get_module_mut(self.def_maps, module_id);
 
             for (_, method_id, method) in &functions.functions {
                 // If this method was already declared, remove it from the module so it cannot
@@ -1065,6 +1079,7 @@ impl<'context> Elaborator<'context> {
 
         let object_crate = match &trait_impl.resolved_object_type {
             Some(Type::Struct(struct_type, _)) => struct_type.borrow().id.krate(),
+            Some(Type::Enum(enum_type, _)) => enum_type.borrow().id.krate(),
             _ => CrateId::Dummy,
         };
 
@@ -1131,6 +1146,29 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    fn collect_enum_definitions(&mut self, enums: BTreeMap<EnumId, UnresolvedEnum>) {
+        // Each enum should already be present in the NodeInterner after def collection.
+        for (type_id, typ) in enums {
+            self.file = typ.file_id;
+            self.local_module = typ.module_id;
+
+            let (generics, variants) = self.recover_generics(|this| {
+                let generics = this.add_generics(&typ.enum_def.generics);
+                this.current_item = Some(DependencyId::Enum(type_id));
+
+                let variants = vecmap(typ.enum_def.variants, |(name, parameters)| {
+                    (name, vecmap(parameters, |typ| this.resolve_type(typ)))
+                });
+                (generics, variants)
+            });
+
+            self.interner.update_enum(type_id, |enum_def| {
+                enum_def.set_variants(variants);
+                enum_def.generics = generics;
+            });
+        }
+    }
+
     fn run_comptime_attributes_on_struct(
         &mut self,
         attributes: Vec<SecondaryAttribute>,
@@ -1345,6 +1383,7 @@ impl<'context> Elaborator<'context> {
         let comptime = CollectedItems {
             functions: comptime_function_sets,
             types: BTreeMap::new(),
+            enums: BTreeMap::new(),
             type_aliases: BTreeMap::new(),
             traits: BTreeMap::new(),
             trait_impls: Vec::new(),
@@ -14,8 +14,10 @@ use crate::{
         function::FunctionBody,
         stmt::HirPattern,
     },
-    macros_api::{HirExpression, Ident, Path, Pattern},
-    node_interner::{DefinitionId, DefinitionKind, DependencyId, ExprId, GlobalId, TraitImplKind},
+    macros_api::{Expression, ExpressionKind, HirExpression, Ident, Path, Pattern},
+    node_interner::{
+        DefinitionId, DefinitionKind, DependencyId, ExprId, GlobalId, ReferenceId, TraitImplKind,
+    },
     Shared, StructType, Type, TypeBindings,
 };
 
@@ -144,9 +146,90 @@ impl<'context> Elaborator<'context> {
                 mutable,
                 new_definitions,
             ),
+            Pattern::Literal(literal, span) => {
+                let literal = Expression::new(ExpressionKind::Literal(literal), span);
+                let (expr_id, actual_type) = self.elaborate_expression(literal);
+                self.unify(&actual_type, &expected_type, || TypeCheckError::TypeMismatch {
+                    expected_typ: expected_type.to_string(),
+                    expr_typ: actual_type.to_string(),
+                    expr_span: span,
+                });
+                HirPattern::Literal(expr_id, Location::new(span, self.file))
+            }
+            Pattern::Variant(name, fields, span) => self.elaborate_variant_pattern(
+                name,
+                fields,
+                span,
+                expected_type,
+                definition,
+                mutable,
+                new_definitions,
+            ),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn elaborate_variant_pattern(
+        &mut self,
+        name: Path,
+        fields: Vec<Pattern>,
+        span: Span,
+        expected_type: Type,
+        definition: DefinitionKind,
+        mutable: Option<Span>,
+        new_definitions: &mut Vec<HirIdent>,
+    ) -> HirPattern {
+        let location = Location::new(span, self.file);
+
+        let Some((enum_type, variant_index)) = self.lookup_enum_variant(&name) else {
+            self.push_err(ResolverError::ExpectedEnumVariant { path: name });
+
+            // Still define any variables in the sub-patterns so that uses of them
+            // in the arm don't produce more errors.
+            let fields = vecmap(fields, |field| {
+                self.elaborate_pattern_mut(
+                    field,
+                    Type::Error,
+                    definition.clone(),
+                    mutable,
+                    new_definitions,
+                    None,
+                )
+            });
+            return HirPattern::Tuple(fields, location);
+        };
+
+        let generics = enum_type.borrow().instantiate(self.interner);
+        let field_types = enum_type.borrow().get_variant(variant_index, &generics);
+        let actual_type = Type::Enum(enum_type, generics);
+
+        self.unify(&actual_type, &expected_type, || TypeCheckError::TypeMismatchWithSource {
+            expected: expected_type.clone(),
+            actual: actual_type.clone(),
+            span,
+            source: Source::Assignment,
+        });
+
+        if fields.len() != field_types.len() {
+            let (expected, found) = (field_types.len(), fields.len());
+            self.push_err(TypeCheckError::ArityMisMatch { expected, found, span });
+        }
+
+        let fields = vecmap(fields.into_iter().enumerate(), |(i, field)| {
+            let field_type = field_types.get(i).cloned().unwrap_or(Type::Error);
+            self.elaborate_pattern_mut(
+                field,
+                field_type,
+                definition.clone(),
+                mutable,
+                new_definitions,
+                None,
+            )
+        });
+
+        HirPattern::Variant(actual_type, variant_index, fields, location)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn elaborate_struct_pattern(
         &mut self,
@@ -232,6 +315,7 @@ impl<'context> Elaborator<'context> {
             if unseen_fields.contains(&field) {
                 unseen_fields.remove(&field);
                 seen_fields.insert(field.clone());
+                self.add_struct_member_reference(&struct_type.borrow(), &field);
             } else if seen_fields.contains(&field) {
                 // duplicate field
                 self.push_err(ResolverError::DuplicateField { field: field.clone() });
@@ -359,6 +443,14 @@ impl<'context> Elaborator<'context> {
         ident
     }
 
+    /// Records a use of the given field of a struct, e.g. in a constructor or a struct pattern
+    pub(super) fn add_struct_member_reference(&mut self, struct_type: &StructType, field: &Ident) {
+        if let Some(index) = struct_type.field_index(&field.0.contents) {
+            let location = Location::new(field.span(), self.file);
+            self.interner.add_reference(ReferenceId::StructMember(struct_type.id, index), location);
+        }
+    }
+
     /// Lookup and use the specified variable.
     /// This will increment its use counter by one and return the variable if found.
     /// If the variable is not found, an error is returned.
@@ -374,6 +466,7 @@ impl<'context> Elaborator<'context> {
         if let Some((variable_found, scope)) = variable {
             variable_found.num_times_used += 1;
             let id = variable_found.ident.id;
+            self.interner.add_definition_reference(id, location);
             Ok((HirIdent::non_trait_method(id, location), scope))
         } else {
             Err(ResolverError::VariableNotDeclared {
@@ -399,9 +492,13 @@ impl<'context> Elaborator<'context> {
 
     fn resolve_variable(&mut self, path: Path) -> HirIdent {
         if let Some((method, constraint, assumed)) = self.resolve_trait_generic_path(&path) {
+            let id = self.interner.trait_method_id(method);
+            let name_location = Location::new(path.last_segment().span(), self.file);
+            self.interner.add_definition_reference(id, name_location);
+
             HirIdent {
                 location: Location::new(path.span, self.file),
-                id: self.interner.trait_method_id(method),
+                id,
                 impl_kind: ImplKind::TraitMethod(method, constraint, assumed),
             }
         } else {
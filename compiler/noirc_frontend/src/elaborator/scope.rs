@@ -1,4 +1,4 @@
-use noirc_errors::Spanned;
+use noirc_errors::{Location, Spanned};
 
 use crate::ast::ERROR_IDENT;
 use crate::hir::def_map::{LocalModuleId, ModuleId};
@@ -17,7 +17,7 @@ use crate::{
     },
     macros_api::{Path, StructId},
     node_interner::{DefinitionId, TraitId},
-    Shared, StructType,
+    EnumType, Shared, StructType,
 };
 use crate::{Type, TypeAlias};
 
@@ -44,12 +44,18 @@ impl<'context> Elaborator<'context> {
 
     pub(super) fn resolve_path(&mut self, path: Path) -> Result<ModuleDefId, ResolverError> {
         let resolver = StandardPathResolver::new(self.module_id());
+        let name_span = path.segments.last().map(|segment| segment.span());
         let path_resolution = resolver.resolve(self.def_maps, path)?;
 
         if let Some(error) = path_resolution.error {
             self.push_err(error);
         }
 
+        if let Some(span) = name_span {
+            let location = Location::new(span, self.file);
+            self.interner.add_module_def_id_reference(path_resolution.module_def_id, location);
+        }
+
         Ok(path_resolution.module_def_id)
     }
 
@@ -197,4 +203,26 @@ impl<'context> Elaborator<'context> {
     pub fn lookup_type_alias(&mut self, path: Path) -> Option<Shared<TypeAlias>> {
         self.lookup(path).ok().map(|id| self.interner.get_type_alias(id))
     }
+
+    pub fn lookup_enum(&mut self, path: Path) -> Option<Shared<EnumType>> {
+        self.lookup(path).ok().map(|id| self.interner.get_enum(id))
+    }
+
+    /// Looks up a path of the form `Enum::Variant`, returning the enum and the variant's index.
+    /// Returns `None` without issuing any errors if the path names something else, such as
+    /// a method in an impl on the enum.
+    pub(super) fn lookup_enum_variant(&mut self, path: &Path) -> Option<(Shared<EnumType>, usize)> {
+        let (variant, enum_segments) = path.segments.split_last()?;
+        if enum_segments.is_empty() {
+            return None;
+        }
+
+        let enum_path = Path { segments: enum_segments.to_vec(), kind: path.kind, span: path.span };
+        let resolver = StandardPathResolver::new(self.module_id());
+        let enum_id = resolver.resolve(self.def_maps, enum_path).ok()?.module_def_id.as_enum()?;
+
+        let enum_type = self.interner.get_enum(enum_id);
+        let index = enum_type.borrow().variant_index(&variant.0.contents)?;
+        Some((enum_type, index))
+    }
 }
@@ -1,7 +1,7 @@
 use noirc_errors::{Location, Span};
 
 use crate::{
-    ast::{AssignStatement, ConstrainStatement, LValue},
+    ast::{AssignStatement, ConstrainStatement, Expression, LValue, WhileStatement},
     hir::{
         comptime::Interpreter,
         resolution::errors::ResolverError,
@@ -11,6 +11,7 @@ use crate::{
         expr::HirIdent,
         stmt::{
             HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
+            HirWhileStatement,
         },
     },
     macros_api::{
@@ -29,6 +30,8 @@ impl<'context> Elaborator<'context> {
             StatementKind::Constrain(constrain) => self.elaborate_constrain(constrain),
             StatementKind::Assign(assign) => self.elaborate_assign(assign),
             StatementKind::For(for_stmt) => self.elaborate_for(for_stmt),
+            StatementKind::While(while_stmt) => self.elaborate_while(while_stmt),
+            StatementKind::Loop(block) => self.elaborate_loop(block, statement.span),
             StatementKind::Break => self.elaborate_jump(true, statement.span),
             StatementKind::Continue => self.elaborate_jump(false, statement.span),
             StatementKind::Comptime(statement) => self.elaborate_comptime_statement(*statement),
@@ -205,6 +208,40 @@ impl<'context> Elaborator<'context> {
         (statement, Type::Unit)
     }
 
+    fn elaborate_while(&mut self, while_loop: WhileStatement) -> (HirStatement, Type) {
+        if !self.in_unconstrained_fn {
+            let span = while_loop.span;
+            self.push_err(ResolverError::LoopInConstrainedFn { is_while: true, span });
+        }
+
+        let expr_span = while_loop.condition.span;
+        let (condition, condition_type) = self.elaborate_expression(while_loop.condition);
+
+        self.unify(&condition_type, &Type::Bool, || TypeCheckError::TypeMismatch {
+            expected_typ: Type::Bool.to_string(),
+            expr_typ: condition_type.to_string(),
+            expr_span,
+        });
+
+        self.nested_loops += 1;
+        let (block, _block_type) = self.elaborate_expression(while_loop.block);
+        self.nested_loops -= 1;
+
+        (HirStatement::While(HirWhileStatement { condition, block }), Type::Unit)
+    }
+
+    fn elaborate_loop(&mut self, block: Expression, span: Span) -> (HirStatement, Type) {
+        if !self.in_unconstrained_fn {
+            self.push_err(ResolverError::LoopInConstrainedFn { is_while: false, span });
+        }
+
+        self.nested_loops += 1;
+        let (block, _block_type) = self.elaborate_expression(block);
+        self.nested_loops -= 1;
+
+        (HirStatement::Loop(block), Type::Unit)
+    }
+
     fn elaborate_jump(&mut self, is_break: bool, span: noirc_errors::Span) -> (HirStatement, Type) {
         if !self.in_unconstrained_fn {
             self.push_err(ResolverError::JumpInConstrainedFn { is_break, span });
@@ -279,9 +316,18 @@ impl<'context> Elaborator<'context> {
                 };
 
                 let name = &field_name.0.contents;
-                let (object_type, field_index) = self
-                    .check_field_access(&lhs_type, name, field_name.span(), Some(dereference_lhs))
-                    .unwrap_or((Type::Error, 0));
+                let (object_type, field_index) = match self.check_field_access(
+                    &lhs_type,
+                    name,
+                    field_name.span(),
+                    Some(dereference_lhs),
+                ) {
+                    Some((object_type, field_index)) => {
+                        self.add_field_access_reference(&lhs_type, field_index, field_name.span());
+                        (object_type, field_index)
+                    }
+                    None => (Type::Error, 0),
+                };
 
                 let field_index = Some(field_index);
                 let typ = object_type.clone();
@@ -27,7 +27,10 @@ use crate::{
         HirExpression, HirLiteral, HirStatement, Path, PathKind, SecondaryAttribute, Signedness,
         UnaryOp, UnresolvedType, UnresolvedTypeData,
     },
-    node_interner::{DefinitionKind, ExprId, GlobalId, TraitId, TraitImplKind, TraitMethodId},
+    node_interner::{
+        DefinitionKind, DependencyId, ExprId, GlobalId, ReferenceId, TraitId, TraitImplKind,
+        TraitMethodId,
+    },
     Generics, Type, TypeBinding, TypeVariable, TypeVariableKind,
 };
 
@@ -181,6 +184,25 @@ impl<'context> Elaborator<'context> {
             return Type::Alias(alias, args);
         }
 
+        if let Some(enum_type) = self.lookup_enum(path.clone()) {
+            let id = enum_type.borrow().id;
+            if self.current_item == Some(DependencyId::Enum(id)) {
+                self.push_err(ResolverError::SelfReferentialEnum { span });
+                return Type::Error;
+            }
+
+            let expected_generic_count = enum_type.borrow().generics.len();
+            self.verify_generics_count(expected_generic_count, &mut args, span, || {
+                enum_type.borrow().to_string()
+            });
+
+            if let Some(current_item) = self.current_item {
+                self.interner.add_enum_dependency(current_item, id);
+            }
+
+            return Type::Enum(enum_type, args);
+        }
+
         match self.lookup_struct_or_error(path) {
             Some(struct_type) => {
                 if self.resolving_ids.contains(&struct_type.borrow().id) {
@@ -1038,6 +1060,8 @@ impl<'context> Elaborator<'context> {
         match self.check_field_access(&lhs_type, &access.rhs.0.contents, span, dereference_lhs) {
             Some((element_type, index)) => {
                 self.interner.set_field_index(expr_id, index);
+                self.add_field_access_reference(&lhs_type, index, access.rhs.span());
+
                 // We must update `access` in case we added any dereferences to it
                 self.interner.replace_expr(&expr_id, HirExpression::MemberAccess(access));
                 element_type
@@ -1046,6 +1070,20 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// Records a use of the field at `index` of `lhs_type` if it is a struct,
+    /// possibly behind one or more mutable references.
+    pub(super) fn add_field_access_reference(&mut self, lhs_type: &Type, index: usize, span: Span) {
+        let mut lhs_type = lhs_type.follow_bindings();
+        while let Type::MutableReference(element) = lhs_type {
+            lhs_type = element.follow_bindings();
+        }
+
+        if let Type::Struct(struct_type, _) = lhs_type {
+            let referenced = ReferenceId::StructMember(struct_type.borrow().id, index);
+            self.interner.add_reference(referenced, Location::new(span, self.file));
+        }
+    }
+
     pub(super) fn lookup_method(
         &mut self,
         object_type: &Type,
@@ -1442,6 +1480,17 @@ impl<'context> Elaborator<'context> {
                     }
                 }
             }
+            Type::Enum(enum_type, generics) => {
+                for (i, generic) in generics.iter().enumerate() {
+                    if let Type::NamedGeneric(type_variable, name) = generic {
+                        if enum_type.borrow().generic_is_numeric(i) {
+                            found.insert(name.to_string(), type_variable.clone());
+                        }
+                    } else {
+                        Self::find_numeric_generics_in_type(generic, found);
+                    }
+                }
+            }
             Type::Alias(alias, generics) => {
                 for (i, generic) in generics.iter().enumerate() {
                     if let Type::NamedGeneric(type_variable, name) = generic {
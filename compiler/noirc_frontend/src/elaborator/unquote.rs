@@ -1,7 +1,7 @@
 use crate::{
     ast::{
         ArrayLiteral, AssignStatement, ConstrainStatement, ConstructorExpression, IfExpression,
-        InfixExpression, Lambda,
+        InfixExpression, Lambda, MatchExpression,
     },
     macros_api::{
         BlockExpression, CallExpression, CastExpression, Expression, ExpressionKind,
@@ -41,6 +41,11 @@ impl<'a> Elaborator<'a> {
                 self.find_unquoted_exprs_in_assign(assign, unquoted_exprs);
             }
             StatementKind::For(for_) => self.find_unquoted_exprs_in_for(for_, unquoted_exprs),
+            StatementKind::While(while_) => {
+                self.find_unquoted_exprs_in_expr(&mut while_.condition, unquoted_exprs);
+                self.find_unquoted_exprs_in_expr(&mut while_.block, unquoted_exprs);
+            }
+            StatementKind::Loop(block) => self.find_unquoted_exprs_in_expr(block, unquoted_exprs),
             StatementKind::Break => (),
             StatementKind::Continue => (),
             StatementKind::Comptime(comptime) => {
@@ -128,6 +133,9 @@ impl<'a> Elaborator<'a> {
                 self.find_unquoted_exprs_in_infix(infix, unquoted_exprs);
             }
             ExpressionKind::If(if_) => self.find_unquoted_exprs_in_if(if_, unquoted_exprs),
+            ExpressionKind::Match(match_) => {
+                self.find_unquoted_exprs_in_match(match_, unquoted_exprs);
+            }
             ExpressionKind::Variable(_, _) => (),
             ExpressionKind::Tuple(tuple) => {
                 self.find_unquoted_exprs_in_tuple(tuple, unquoted_exprs);
@@ -271,6 +279,18 @@ impl<'a> Elaborator<'a> {
         }
     }
 
+    fn find_unquoted_exprs_in_match(
+        &mut self,
+        match_: &mut MatchExpression,
+        unquoted_exprs: &mut Vec<ExprId>,
+    ) {
+        self.find_unquoted_exprs_in_expr(&mut match_.expression, unquoted_exprs);
+
+        for (_, branch) in &mut match_.rules {
+            self.find_unquoted_exprs_in_expr(branch, unquoted_exprs);
+        }
+    }
+
     fn find_unquoted_exprs_in_tuple(
         &mut self,
         tuple: &mut [Expression],
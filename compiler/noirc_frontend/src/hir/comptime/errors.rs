@@ -14,6 +14,8 @@ pub enum InterpreterError {
     ErrorNodeEncountered { location: Location },
     NonFunctionCalled { value: Value, location: Location },
     NonBoolUsedInIf { value: Value, location: Location },
+    NonBoolUsedInWhile { value: Value, location: Location },
+    NoMatchingArm { value: Value, location: Location },
     NonBoolUsedInConstrain { value: Value, location: Location },
     FailingConstraint { message: Option<Value>, location: Location },
     NoMethodFound { name: String, typ: Type, location: Location },
@@ -33,6 +35,7 @@ pub enum InterpreterError {
     CastToNonNumericType { typ: Type, location: Location },
     QuoteInRuntimeCode { location: Location },
     NonStructInConstructor { typ: Type, location: Location },
+    NonEnumInConstructor { typ: Type, location: Location },
     CannotInlineMacro { value: Value, location: Location },
     UnquoteFoundDuringEvaluation { location: Location },
 
@@ -73,6 +76,8 @@ impl InterpreterError {
             | InterpreterError::ErrorNodeEncountered { location, .. }
             | InterpreterError::NonFunctionCalled { location, .. }
             | InterpreterError::NonBoolUsedInIf { location, .. }
+            | InterpreterError::NonBoolUsedInWhile { location, .. }
+            | InterpreterError::NoMatchingArm { location, .. }
             | InterpreterError::NonBoolUsedInConstrain { location, .. }
             | InterpreterError::FailingConstraint { location, .. }
             | InterpreterError::NoMethodFound { location, .. }
@@ -92,6 +97,7 @@ impl InterpreterError {
             | InterpreterError::CastToNonNumericType { location, .. }
             | InterpreterError::QuoteInRuntimeCode { location, .. }
             | InterpreterError::NonStructInConstructor { location, .. }
+            | InterpreterError::NonEnumInConstructor { location, .. }
             | InterpreterError::CannotInlineMacro { location, .. }
             | InterpreterError::UnquoteFoundDuringEvaluation { location, .. }
             | InterpreterError::Unimplemented { location, .. }
@@ -152,6 +158,15 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
                 let secondary = "If conditions must be a boolean value".to_string();
                 CustomDiagnostic::simple_error(msg, secondary, location.span)
             }
+            InterpreterError::NonBoolUsedInWhile { value, location } => {
+                let msg = format!("Expected a `bool` but found `{}`", value.get_type());
+                let secondary = "While conditions must be a boolean value".to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
+            InterpreterError::NoMatchingArm { value, location } => {
+                let msg = format!("No arm of this match matches the value `{value:?}`");
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
             InterpreterError::NonBoolUsedInConstrain { value, location } => {
                 let msg = format!("Expected a `bool` but found `{}`", value.get_type());
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
@@ -248,6 +263,10 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
                 let msg = format!("`{typ}` is not a struct type");
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
             }
+            InterpreterError::NonEnumInConstructor { typ, location } => {
+                let msg = format!("`{typ}` is not an enum type");
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
             InterpreterError::CannotInlineMacro { value, location } => {
                 let msg = "Cannot inline value into runtime code if it contains references".into();
                 let secondary = format!("Cannot inline value {value:?}");
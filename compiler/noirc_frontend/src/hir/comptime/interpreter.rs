@@ -12,13 +12,13 @@ use crate::{
     hir_def::{
         expr::{
             HirArrayLiteral, HirBlockExpression, HirCallExpression, HirCastExpression,
-            HirConstructorExpression, HirIdent, HirIfExpression, HirIndexExpression,
-            HirInfixExpression, HirLambda, HirMemberAccess, HirMethodCallExpression,
-            HirPrefixExpression,
+            HirConstructorExpression, HirEnumConstructorExpression, HirIdent, HirIfExpression,
+            HirIndexExpression, HirInfixExpression, HirLambda, HirMatchExpression, HirMemberAccess,
+            HirMethodCallExpression, HirPrefixExpression,
         },
         stmt::{
             HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
-            HirPattern,
+            HirPattern, HirWhileStatement,
         },
     },
     macros_api::{HirExpression, HirLiteral, HirStatement, NodeInterner},
@@ -251,6 +251,82 @@ impl<'a> Interpreter<'a> {
                 self.pop_scope();
                 res
             }
+            HirPattern::Variant(_, index, pattern_fields, _) => {
+                match (argument, typ.follow_bindings()) {
+                    (Value::Enum(variant, fields, _), Type::Enum(enum_type, generics))
+                        if variant == *index && fields.len() == pattern_fields.len() =>
+                    {
+                        let field_types = enum_type.borrow().get_variant(variant, &generics);
+                        for ((pattern, typ), field) in
+                            pattern_fields.iter().zip(&field_types).zip(fields)
+                        {
+                            self.define_pattern(pattern, typ, field, location)?;
+                        }
+                        Ok(())
+                    }
+                    (value, _) => Err(InterpreterError::TypeMismatch {
+                        expected: typ.clone(),
+                        value,
+                        location,
+                    }),
+                }
+            }
+            // Literal patterns bind nothing. Whether they match is checked by `pattern_matches`.
+            HirPattern::Literal(..) => Ok(()),
+        }
+    }
+
+    /// True if `value` matches `pattern`. Only literal patterns can reject a well-typed value.
+    fn pattern_matches(
+        &mut self,
+        pattern: &HirPattern,
+        value: &Value,
+        location: Location,
+    ) -> IResult<bool> {
+        match (pattern, value) {
+            (HirPattern::Identifier(_), _) => Ok(true),
+            (HirPattern::Mutable(pattern, _), value) => {
+                self.pattern_matches(pattern, value, location)
+            }
+            (HirPattern::Tuple(patterns, _), Value::Tuple(fields)) => {
+                for (pattern, field) in patterns.iter().zip(fields) {
+                    if !self.pattern_matches(pattern, field, location)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            (HirPattern::Struct(_, patterns, _), Value::Struct(fields, _)) => {
+                for (field_name, pattern) in patterns {
+                    let field = fields.get(&field_name.0.contents).ok_or_else(|| {
+                        InterpreterError::ExpectedStructToHaveField {
+                            value: value.clone(),
+                            field_name: field_name.0.contents.clone(),
+                            location,
+                        }
+                    })?;
+                    if !self.pattern_matches(pattern, field, location)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            (HirPattern::Variant(_, index, patterns, _), Value::Enum(variant, fields, _)) => {
+                if index != variant {
+                    return Ok(false);
+                }
+                for (pattern, field) in patterns.iter().zip(fields) {
+                    if !self.pattern_matches(pattern, field, location)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            (HirPattern::Literal(literal, _), value) => Ok(self.evaluate(*literal)? == *value),
+            // The value doesn't have the pattern's type, `define_pattern` will report this
+            (HirPattern::Tuple(..) | HirPattern::Struct(..) | HirPattern::Variant(..), _) => {
+                Ok(true)
+            }
         }
     }
 
@@ -336,11 +412,15 @@ impl<'a> Interpreter<'a> {
             HirExpression::Infix(infix) => self.evaluate_infix(infix, id),
             HirExpression::Index(index) => self.evaluate_index(index, id),
             HirExpression::Constructor(constructor) => self.evaluate_constructor(constructor, id),
+            HirExpression::EnumConstructor(constructor) => {
+                self.evaluate_enum_constructor(constructor, id)
+            }
             HirExpression::MemberAccess(access) => self.evaluate_access(access, id),
             HirExpression::Call(call) => self.evaluate_call(call, id),
             HirExpression::MethodCall(call) => self.evaluate_method_call(call, id),
             HirExpression::Cast(cast) => self.evaluate_cast(&cast, id),
             HirExpression::If(if_) => self.evaluate_if(if_, id),
+            HirExpression::Match(match_) => self.evaluate_match(match_, id),
             HirExpression::Tuple(tuple) => self.evaluate_tuple(tuple),
             HirExpression::Lambda(lambda) => self.evaluate_lambda(lambda, id),
             HirExpression::Quote(block) => self.evaluate_quote(block, id),
@@ -933,6 +1013,16 @@ impl<'a> Interpreter<'a> {
         Ok(Value::Struct(fields, typ))
     }
 
+    fn evaluate_enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: ExprId,
+    ) -> IResult<Value> {
+        let fields = try_vecmap(constructor.arguments, |argument| self.evaluate(argument))?;
+        let typ = self.interner.id_type(id);
+        Ok(Value::Enum(constructor.variant_index, fields, typ))
+    }
+
     fn evaluate_access(&mut self, access: HirMemberAccess, id: ExprId) -> IResult<Value> {
         let (fields, struct_type) = match self.evaluate(access.lhs)? {
             Value::Struct(fields, typ) => (fields, typ),
@@ -1122,6 +1212,25 @@ impl<'a> Interpreter<'a> {
         result
     }
 
+    fn evaluate_match(&mut self, match_: HirMatchExpression, id: ExprId) -> IResult<Value> {
+        let value = self.evaluate(match_.expression)?;
+        let typ = self.interner.id_type(match_.expression);
+        let location = self.interner.expr_location(&id);
+
+        for (pattern, branch) in &match_.rules {
+            if self.pattern_matches(pattern, &value, location)? {
+                self.push_scope();
+                let result = self
+                    .define_pattern(pattern, &typ, value, location)
+                    .and_then(|()| self.evaluate(*branch));
+                self.pop_scope();
+                return result;
+            }
+        }
+
+        Err(InterpreterError::NoMatchingArm { value, location })
+    }
+
     fn evaluate_tuple(&mut self, tuple: Vec<ExprId>) -> IResult<Value> {
         let fields = try_vecmap(tuple, |field| self.evaluate(field))?;
         Ok(Value::Tuple(fields))
@@ -1151,6 +1260,8 @@ impl<'a> Interpreter<'a> {
             HirStatement::Constrain(constrain) => self.evaluate_constrain(constrain),
             HirStatement::Assign(assign) => self.evaluate_assign(assign),
             HirStatement::For(for_) => self.evaluate_for(for_),
+            HirStatement::While(while_) => self.evaluate_while(while_),
+            HirStatement::Loop(block) => self.evaluate_loop(block),
             HirStatement::Break => self.evaluate_break(statement),
             HirStatement::Continue => self.evaluate_continue(statement),
             HirStatement::Expression(expression) => self.evaluate(expression),
@@ -1315,6 +1426,47 @@ impl<'a> Interpreter<'a> {
         Ok(Value::Unit)
     }
 
+    fn evaluate_while(&mut self, while_: HirWhileStatement) -> IResult<Value> {
+        let was_in_loop = std::mem::replace(&mut self.in_loop, true);
+
+        loop {
+            match self.evaluate(while_.condition)? {
+                Value::Bool(true) => (),
+                Value::Bool(false) => break,
+                value => {
+                    let location = self.interner.expr_location(&while_.condition);
+                    return Err(InterpreterError::NonBoolUsedInWhile { value, location });
+                }
+            }
+
+            match self.evaluate(while_.block) {
+                Ok(_) => (),
+                Err(InterpreterError::Break) => break,
+                Err(InterpreterError::Continue) => continue,
+                Err(other) => return Err(other),
+            }
+        }
+
+        self.in_loop = was_in_loop;
+        Ok(Value::Unit)
+    }
+
+    fn evaluate_loop(&mut self, block: ExprId) -> IResult<Value> {
+        let was_in_loop = std::mem::replace(&mut self.in_loop, true);
+
+        loop {
+            match self.evaluate(block) {
+                Ok(_) => (),
+                Err(InterpreterError::Break) => break,
+                Err(InterpreterError::Continue) => continue,
+                Err(other) => return Err(other),
+            }
+        }
+
+        self.in_loop = was_in_loop;
+        Ok(Value::Unit)
+    }
+
     fn evaluate_break(&mut self, id: StmtId) -> IResult<Value> {
         if self.in_loop {
             Err(InterpreterError::Break)
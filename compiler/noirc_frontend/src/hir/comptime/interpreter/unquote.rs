@@ -4,7 +4,7 @@ use noirc_errors::Location;
 use crate::{
     ast::{
         ArrayLiteral, AssignStatement, ConstrainStatement, ConstructorExpression, IfExpression,
-        InfixExpression, Lambda,
+        InfixExpression, Lambda, MatchExpression,
     },
     hir::comptime::{errors::IResult, Value},
     macros_api::{
@@ -46,6 +46,11 @@ impl<'a> Interpreter<'a> {
             StatementKind::Expression(expr) => self.substitute_unquoted_into_expr(expr, args),
             StatementKind::Assign(assign) => self.substitute_unquoted_into_assign(assign, args),
             StatementKind::For(for_) => self.substitute_unquoted_into_for(for_, args),
+            StatementKind::While(while_) => {
+                self.substitute_unquoted_into_expr(&mut while_.condition, args)?;
+                self.substitute_unquoted_into_expr(&mut while_.block, args)
+            }
+            StatementKind::Loop(block) => self.substitute_unquoted_into_expr(block, args),
             StatementKind::Break => Ok(()),
             StatementKind::Continue => Ok(()),
             StatementKind::Comptime(comptime) => {
@@ -126,6 +131,7 @@ impl<'a> Interpreter<'a> {
             ExpressionKind::Cast(cast) => self.substitute_unquoted_into_cast(cast, args),
             ExpressionKind::Infix(infix) => self.substitute_unquoted_into_infix(infix, args),
             ExpressionKind::If(if_) => self.substitute_unquoted_into_if(if_, args),
+            ExpressionKind::Match(match_) => self.substitute_unquoted_into_match(match_, args),
             ExpressionKind::Variable(_, _) => Ok(()),
             ExpressionKind::Tuple(tuple) => self.substitute_unquoted_into_tuple(tuple, args),
             ExpressionKind::Lambda(lambda) => self.substitute_unquoted_into_lambda(lambda, args),
@@ -266,6 +272,19 @@ impl<'a> Interpreter<'a> {
         Ok(())
     }
 
+    fn substitute_unquoted_into_match(
+        &mut self,
+        match_: &mut MatchExpression,
+        args: &UnquoteArgs,
+    ) -> IResult<()> {
+        self.substitute_unquoted_into_expr(&mut match_.expression, args)?;
+
+        for (_, branch) in &mut match_.rules {
+            self.substitute_unquoted_into_expr(branch, args)?;
+        }
+        Ok(())
+    }
+
     fn substitute_unquoted_into_tuple(
         &mut self,
         tuple: &mut [Expression],
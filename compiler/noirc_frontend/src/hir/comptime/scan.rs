@@ -15,7 +15,7 @@ use crate::{
         expr::{
             HirArrayLiteral, HirBlockExpression, HirCallExpression, HirConstructorExpression,
             HirIdent, HirIfExpression, HirIndexExpression, HirInfixExpression, HirLambda,
-            HirMethodCallExpression,
+            HirMatchExpression, HirMethodCallExpression,
         },
         stmt::HirForStatement,
     },
@@ -71,11 +71,13 @@ impl<'interner> Interpreter<'interner> {
             HirExpression::Infix(infix) => self.scan_infix(infix),
             HirExpression::Index(index) => self.scan_index(index),
             HirExpression::Constructor(constructor) => self.scan_constructor(constructor),
+            HirExpression::EnumConstructor(constructor) => self.scan_tuple(constructor.arguments),
             HirExpression::MemberAccess(member_access) => self.scan_expression(member_access.lhs),
             HirExpression::Call(call) => self.scan_call(call),
             HirExpression::MethodCall(method_call) => self.scan_method_call(method_call),
             HirExpression::Cast(cast) => self.scan_expression(cast.lhs),
             HirExpression::If(if_) => self.scan_if(if_),
+            HirExpression::Match(match_) => self.scan_match(match_),
             HirExpression::Tuple(tuple) => self.scan_tuple(tuple),
             HirExpression::Lambda(lambda) => self.scan_lambda(lambda),
             HirExpression::Comptime(block) => {
@@ -204,6 +206,17 @@ impl<'interner> Interpreter<'interner> {
         Ok(())
     }
 
+    fn scan_match(&mut self, match_: HirMatchExpression) -> IResult<()> {
+        self.scan_expression(match_.expression)?;
+
+        for (_, branch) in match_.rules {
+            self.push_scope();
+            self.scan_expression(branch)?;
+            self.pop_scope();
+        }
+        Ok(())
+    }
+
     fn scan_tuple(&mut self, tuple: Vec<ExprId>) -> IResult<()> {
         for field in tuple {
             self.scan_expression(field)?;
@@ -221,6 +234,11 @@ impl<'interner> Interpreter<'interner> {
             HirStatement::Constrain(constrain) => self.scan_expression(constrain.0),
             HirStatement::Assign(assign) => self.scan_expression(assign.expression),
             HirStatement::For(for_) => self.scan_for(for_),
+            HirStatement::While(while_) => {
+                self.scan_expression(while_.condition)?;
+                self.scan_loop_body(while_.block)
+            }
+            HirStatement::Loop(block) => self.scan_loop_body(block),
             HirStatement::Break => Ok(()),
             HirStatement::Continue => Ok(()),
             HirStatement::Expression(expression) => self.scan_expression(expression),
@@ -238,11 +256,15 @@ impl<'interner> Interpreter<'interner> {
     }
 
     fn scan_for(&mut self, for_: HirForStatement) -> IResult<()> {
+        self.scan_loop_body(for_.block)
+    }
+
+    fn scan_loop_body(&mut self, block: ExprId) -> IResult<()> {
         // We don't need to set self.in_loop since we're not actually evaluating this loop.
         // We just need to push a scope so that if there's a `comptime { .. }` expr inside this
         // loop, any variables it defines aren't accessible outside of it.
         self.push_scope();
-        self.scan_expression(for_.block)?;
+        self.scan_expression(block)?;
         self.pop_scope();
         Ok(())
     }
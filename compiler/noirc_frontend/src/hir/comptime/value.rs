@@ -9,7 +9,10 @@ use crate::{
     ast::{
         ArrayLiteral, BlockExpression, ConstructorExpression, Ident, IntegerBitSize, Signedness,
     },
-    hir_def::expr::{HirArrayLiteral, HirConstructorExpression, HirIdent, HirLambda, ImplKind},
+    hir_def::expr::{
+        HirArrayLiteral, HirConstructorExpression, HirEnumConstructorExpression, HirIdent,
+        HirLambda, ImplKind,
+    },
     macros_api::{
         Expression, ExpressionKind, HirExpression, HirLiteral, Literal, NodeInterner, Path,
         StructId,
@@ -39,6 +42,8 @@ pub enum Value {
     Closure(HirLambda, Vec<Value>, Type),
     Tuple(Vec<Value>),
     Struct(HashMap<Rc<String>, Value>, Type),
+    /// The index of an enum's variant along with the values it holds
+    Enum(usize, Vec<Value>, Type),
     Pointer(Shared<Value>),
     Array(Vector<Value>, Type),
     Slice(Vector<Value>, Type),
@@ -70,6 +75,7 @@ impl Value {
                 Type::Tuple(vecmap(fields, |field| field.get_type().into_owned()))
             }
             Value::Struct(_, typ) => return Cow::Borrowed(typ),
+            Value::Enum(_, _, typ) => return Cow::Borrowed(typ),
             Value::Array(_, typ) => return Cow::Borrowed(typ),
             Value::Slice(_, typ) => return Cow::Borrowed(typ),
             Value::Code(_) => Type::Quoted(QuotedType::Expr),
@@ -164,6 +170,11 @@ impl Value {
                     struct_type,
                 }))
             }
+            // The variant's path may not be in scope where the value is inlined,
+            // so it is resolved up front instead
+            value @ Value::Enum(..) => {
+                ExpressionKind::Resolved(value.into_hir_expression(interner, location)?)
+            }
             Value::Array(elements, _) => {
                 let elements =
                     try_vecmap(elements, |element| element.into_expression(interner, location))?;
@@ -263,6 +274,22 @@ impl Value {
                     fields,
                 })
             }
+            Value::Enum(variant_index, fields, typ) => {
+                let arguments =
+                    try_vecmap(fields, |field| field.into_hir_expression(interner, location))?;
+
+                let (r#type, enum_generics) = match typ.follow_bindings() {
+                    Type::Enum(def, generics) => (def, generics),
+                    _ => return Err(InterpreterError::NonEnumInConstructor { typ, location }),
+                };
+
+                HirExpression::EnumConstructor(HirEnumConstructorExpression {
+                    r#type,
+                    enum_generics,
+                    variant_index,
+                    arguments,
+                })
+            }
             Value::Array(elements, _) => {
                 let elements = try_vecmap(elements, |element| {
                     element.into_hir_expression(interner, location)
@@ -342,6 +369,21 @@ impl Display for Value {
                 let fields = vecmap(fields, |(name, value)| format!("{}: {}", name, value));
                 write!(f, "{typename} {{ {} }}", fields.join(", "))
             }
+            Value::Enum(index, fields, typ) => {
+                let name = match typ.follow_bindings() {
+                    Type::Enum(def, _) => {
+                        let def = def.borrow();
+                        format!("{}::{}", def.name, def.variant_name(*index))
+                    }
+                    other => other.to_string(),
+                };
+                if fields.is_empty() {
+                    write!(f, "{name}")
+                } else {
+                    let fields = vecmap(fields, ToString::to_string);
+                    write!(f, "{name}({})", fields.join(", "))
+                }
+            }
             Value::Pointer(value) => write!(f, "&mut {}", value.borrow()),
             Value::Array(values, _) => {
                 let values = vecmap(values, ToString::to_string);
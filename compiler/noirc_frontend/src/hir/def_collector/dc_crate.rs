@@ -20,17 +20,18 @@ use crate::hir::Context;
 
 use crate::macros_api::{MacroError, MacroProcessor};
 use crate::node_interner::{
-    FuncId, GlobalId, NodeInterner, StructId, TraitId, TraitImplId, TypeAliasId,
+    EnumId, FuncId, GlobalId, NodeInterner, StructId, TraitId, TraitImplId, TypeAliasId,
 };
 
 use crate::ast::{
-    ExpressionKind, Ident, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTypeAlias, Path, PathKind, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+    ExpressionKind, Ident, LetStatement, Literal, NoirEnumeration, NoirFunction, NoirStruct,
+    NoirTrait, NoirTypeAlias, Path, PathKind, UnresolvedGenerics, UnresolvedTraitConstraint,
+    UnresolvedType,
 };
 use crate::parser::{ParserError, SortedModule};
 use fm::FileId;
 use iter_extended::vecmap;
-use noirc_errors::{CustomDiagnostic, Span};
+use noirc_errors::{CustomDiagnostic, Location, Span};
 use std::collections::{BTreeMap, HashMap};
 
 use std::rc::Rc;
@@ -101,6 +102,12 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedEnum {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub enum_def: NoirEnumeration,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedTrait {
     pub file_id: FileId,
@@ -157,6 +164,7 @@ pub struct DefCollector {
 pub struct CollectedItems {
     pub(crate) functions: Vec<UnresolvedFunctions>,
     pub(crate) types: BTreeMap<StructId, UnresolvedStruct>,
+    pub(crate) enums: BTreeMap<EnumId, UnresolvedEnum>,
     pub(crate) type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
     pub(crate) traits: BTreeMap<TraitId, UnresolvedTrait>,
     pub(crate) globals: Vec<UnresolvedGlobal>,
@@ -239,6 +247,7 @@ impl DefCollector {
             items: CollectedItems {
                 functions: vec![],
                 types: BTreeMap::new(),
+                enums: BTreeMap::new(),
                 type_aliases: BTreeMap::new(),
                 traits: BTreeMap::new(),
                 impls: HashMap::new(),
@@ -327,6 +336,16 @@ impl DefCollector {
                     // Populate module namespaces according to the imports used
                     let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
 
+                    // Remember which items each (non-prelude) import refers to
+                    let name_span = collected_import.path.segments.last().map(|name| name.span());
+                    if let (Some(span), false) = (name_span, resolved_import.is_prelude) {
+                        let file_id = current_def_map.file_id(collected_import.module_id);
+                        let location = Location::new(span, file_id);
+                        for ns in resolved_import.resolved_namespace.iter_defs() {
+                            context.def_interner.add_module_def_id_reference(ns, location);
+                        }
+                    }
+
                     let name = resolved_import.name;
                     for ns in resolved_import.resolved_namespace.iter_defs() {
                         let result = current_def_map.modules[resolved_import.module_scope.0]
@@ -357,6 +376,12 @@ impl DefCollector {
             return errors;
         }
 
+        for (_, unresolved) in def_collector.items.enums {
+            let span = unresolved.enum_def.name.span();
+            let error = ResolverError::EnumInLegacyResolver { span };
+            errors.push((error.into(), unresolved.file_id));
+        }
+
         let mut resolved_module = ResolvedModule { errors, ..Default::default() };
 
         // We must first resolve and intern the globals before we can resolve any stmts inside each function.
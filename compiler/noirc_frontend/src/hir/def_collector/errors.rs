@@ -14,6 +14,7 @@ pub enum DuplicateType {
     Module,
     Global,
     TypeDefinition,
+    EnumVariant,
     Import,
     Trait,
     TraitImplementation,
@@ -89,6 +90,7 @@ impl fmt::Display for DuplicateType {
             DuplicateType::Module => write!(f, "module"),
             DuplicateType::Global => write!(f, "global"),
             DuplicateType::TypeDefinition => write!(f, "type definition"),
+            DuplicateType::EnumVariant => write!(f, "enum variant"),
             DuplicateType::Trait => write!(f, "trait definition"),
             DuplicateType::TraitImplementation => write!(f, "trait implementation"),
             DuplicateType::Import => write!(f, "import"),
@@ -143,6 +143,11 @@ impl CrateDefMap {
         &mut self.modules
     }
 
+    /// Returns the name and root module of each dependency of this crate
+    pub fn extern_prelude(&self) -> &BTreeMap<String, ModuleId> {
+        &self.extern_prelude
+    }
+
     pub fn krate(&self) -> CrateId {
         self.krate
     }
@@ -4,7 +4,7 @@ use noirc_errors::Location;
 
 use super::{ItemScope, LocalModuleId, ModuleDefId, ModuleId, PerNs};
 use crate::ast::{Ident, ItemVisibility};
-use crate::node_interner::{FuncId, GlobalId, StructId, TraitId, TypeAliasId};
+use crate::node_interner::{EnumId, FuncId, GlobalId, StructId, TraitId, TypeAliasId};
 
 /// Contains the actual contents of a module: its parent (if one exists),
 /// children, and scope with all definitions defined within the scope.
@@ -38,7 +38,7 @@ impl ModuleData {
         }
     }
 
-    pub(crate) fn scope(&self) -> &ItemScope {
+    pub fn scope(&self) -> &ItemScope {
         &self.scope
     }
 
@@ -87,6 +87,10 @@ impl ModuleData {
         self.declare(name, ItemVisibility::Public, ModuleDefId::TypeId(id), None)
     }
 
+    pub fn declare_enum(&mut self, name: Ident, id: EnumId) -> Result<(), (Ident, Ident)> {
+        self.declare(name, ItemVisibility::Public, id.into(), None)
+    }
+
     pub fn declare_type_alias(
         &mut self,
         name: Ident,
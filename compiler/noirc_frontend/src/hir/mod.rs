@@ -8,7 +8,7 @@ pub mod type_check;
 use crate::debug::DebugInstrumenter;
 use crate::graph::{CrateGraph, CrateId};
 use crate::hir_def::function::FuncMeta;
-use crate::node_interner::{FuncId, NodeInterner, StructId};
+use crate::node_interner::{EnumId, FuncId, NodeInterner, StructId};
 use crate::parser::ParserError;
 use crate::ParsedModule;
 use def_map::{Contract, CrateDefMap};
@@ -139,7 +139,20 @@ impl Context<'_, '_> {
     /// For example, if you project contains a `main.nr` and `foo.nr` and you provide the `main_crate_id` and the
     /// `bar_struct_id` where the `Bar` struct is inside `foo.nr`, this function would return `foo::Bar` as a [String].
     pub fn fully_qualified_struct_path(&self, crate_id: &CrateId, id: StructId) -> String {
-        let module_id = id.module_id();
+        self.fully_qualified_type_path(crate_id, id.module_id())
+    }
+
+    /// Returns a fully-qualified path to the given [EnumId] from the given [CrateId].
+    /// See [Context::fully_qualified_struct_path] for more details.
+    pub fn fully_qualified_enum_path(&self, crate_id: &CrateId, id: EnumId) -> String {
+        self.fully_qualified_type_path(crate_id, id.module_id())
+    }
+
+    fn fully_qualified_type_path(
+        &self,
+        crate_id: &CrateId,
+        module_id: def_map::ModuleId,
+    ) -> String {
         let child_id = module_id.local_id.0;
         let def_map =
             self.def_map(&module_id.krate).expect("The local crate should be analyzed already");
@@ -153,7 +166,7 @@ impl Context<'_, '_> {
         } else {
             let crates = self
                 .find_dependencies(crate_id, &module_id.krate)
-                .expect("The Struct was supposed to be defined in a dependency");
+                .expect("The type was supposed to be defined in a dependency");
             crates.join("::") + "::" + &module_path
         }
     }
@@ -90,6 +90,8 @@ pub enum ResolverError {
     JumpInConstrainedFn { is_break: bool, span: Span },
     #[error("break/continue are only allowed within loops")]
     JumpOutsideLoop { is_break: bool, span: Span },
+    #[error("while/loop are only allowed in unconstrained functions")]
+    LoopInConstrainedFn { is_while: bool, span: Span },
     #[error("Only `comptime` globals can be mutable")]
     MutableGlobal { span: Span },
     #[error("Self-referential structs are not supported")]
@@ -110,6 +112,14 @@ pub enum ResolverError {
     NonFunctionInAnnotation { span: Span },
     #[error("Unknown annotation")]
     UnknownAnnotation { span: Span },
+    #[error("`match` expressions are not supported by the legacy resolver")]
+    MatchInLegacyResolver { span: Span },
+    #[error("Enums are not supported by the legacy resolver")]
+    EnumInLegacyResolver { span: Span },
+    #[error("Self-referential enums are not allowed")]
+    SelfReferentialEnum { span: Span },
+    #[error("`{path}` is not an enum variant")]
+    ExpectedEnumVariant { path: crate::ast::Path },
 }
 
 impl ResolverError {
@@ -354,6 +364,14 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     *span,
                 )
             },
+            ResolverError::LoopInConstrainedFn { is_while, span } => {
+                let item = if *is_while { "while" } else { "loop" };
+                Diagnostic::simple_error(
+                    format!("`{item}` is only allowed in unconstrained functions"),
+                    "Constrained code must always have a known number of loop iterations, consider using a `for` loop instead".into(),
+                    *span,
+                )
+            },
             ResolverError::JumpOutsideLoop { is_break, span } => {
                 let item = if *is_break { "break" } else { "continue" };
                 Diagnostic::simple_error(
@@ -436,6 +454,34 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     *span,
                 )
             },
+            ResolverError::MatchInLegacyResolver { span } => {
+                Diagnostic::simple_error(
+                    "`match` expressions are not supported by the legacy resolver".into(),
+                    "Compile without `--use-legacy` to use `match`".into(),
+                    *span,
+                )
+            },
+            ResolverError::EnumInLegacyResolver { span } => {
+                Diagnostic::simple_error(
+                    "Enums are not supported by the legacy resolver".into(),
+                    "Compile without `--use-legacy` to use enums".into(),
+                    *span,
+                )
+            },
+            ResolverError::ExpectedEnumVariant { path } => {
+                Diagnostic::simple_error(
+                    format!("`{path}` is not an enum variant"),
+                    "Patterns in a match may only refer to enum variants by their path".into(),
+                    path.span(),
+                )
+            },
+            ResolverError::SelfReferentialEnum { span } => {
+                Diagnostic::simple_error(
+                    "Self-referential enums are not allowed".into(),
+                    "A variant can't hold a value of the enum it belongs to".into(),
+                    *span,
+                )
+            },
         }
     }
 }
@@ -42,7 +42,9 @@ use crate::hir::{
     def_map::CrateDefMap,
     resolution::path_resolver::PathResolver,
 };
-use crate::hir_def::stmt::{HirAssignStatement, HirForStatement, HirLValue, HirPattern};
+use crate::hir_def::stmt::{
+    HirAssignStatement, HirForStatement, HirLValue, HirPattern, HirWhileStatement,
+};
 use crate::node_interner::{
     DefinitionId, DefinitionKind, DependencyId, ExprId, FuncId, GlobalId, NodeInterner, StmtId,
     StructId, TraitId, TraitImplId, TraitMethodId, TypeAliasId,
@@ -1154,6 +1156,9 @@ impl<'a> Resolver<'a> {
             | Type::Quoted(_)
             | Type::Forall(_, _) => (),
 
+            // Enums are rejected by the legacy resolver
+            Type::Enum(..) => (),
+
             Type::TraitAsType(_, _, args) => {
                 for arg in args {
                     Self::find_numeric_generics_in_type(arg, found);
@@ -1322,6 +1327,30 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
+            StatementKind::While(while_loop) => {
+                if !self.in_unconstrained_fn {
+                    let span = while_loop.span;
+                    self.push_err(ResolverError::LoopInConstrainedFn { is_while: true, span });
+                }
+
+                let condition = self.resolve_expression(while_loop.condition);
+                self.nested_loops += 1;
+                let block = self.resolve_expression(while_loop.block);
+                self.nested_loops -= 1;
+
+                HirStatement::While(HirWhileStatement { condition, block })
+            }
+            StatementKind::Loop(block) => {
+                if !self.in_unconstrained_fn {
+                    self.push_err(ResolverError::LoopInConstrainedFn { is_while: false, span });
+                }
+
+                self.nested_loops += 1;
+                let block = self.resolve_expression(block);
+                self.nested_loops -= 1;
+
+                HirStatement::Loop(block)
+            }
             StatementKind::Break => {
                 self.check_break_continue(true, span);
                 HirStatement::Break
@@ -1562,6 +1591,10 @@ impl<'a> Resolver<'a> {
                 consequence: self.resolve_expression(if_expr.consequence),
                 alternative: if_expr.alternative.map(|e| self.resolve_expression(e)),
             }),
+            ExpressionKind::Match(_) => {
+                self.push_err(ResolverError::MatchInLegacyResolver { span: expr.span });
+                HirExpression::Error
+            }
             ExpressionKind::Index(indexed_expr) => HirExpression::Index(HirIndexExpression {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
@@ -1727,6 +1760,14 @@ impl<'a> Resolver<'a> {
                 let location = Location::new(span, self.file);
                 HirPattern::Struct(typ, fields, location)
             }
+            Pattern::Literal(literal, span) => {
+                let literal = Expression::new(ExpressionKind::Literal(literal), span);
+                let literal = self.resolve_expression(literal);
+                HirPattern::Literal(literal, Location::new(span, self.file))
+            }
+            Pattern::Variant(..) => {
+                unreachable!("variant patterns are only allowed in match arms, which are rejected by the legacy resolver")
+            }
         }
     }
 
@@ -145,6 +145,10 @@ pub enum TypeCheckError {
     StringIndexAssign { span: Span },
     #[error("Macro calls may only return Expr values")]
     MacroReturningNonExpr { typ: Type, span: Span },
+    #[error("Non-exhaustive match, pattern `{missing}` is not covered")]
+    NonExhaustiveMatch { missing: String, span: Span },
+    #[error("Unreachable match arm")]
+    UnreachableMatchArm { span: Span },
 }
 
 impl TypeCheckError {
@@ -342,6 +346,16 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
                 "Macro calls must return quoted expressions, otherwise there is no code to insert".into(),
                 *span,
                 ),
+            TypeCheckError::NonExhaustiveMatch { missing, span } => Diagnostic::simple_error(
+                error.to_string(),
+                format!("pattern `{missing}` not covered"),
+                *span,
+            ),
+            TypeCheckError::UnreachableMatchArm { span } => Diagnostic::simple_warning(
+                error.to_string(),
+                "Every value matching this pattern is matched by an earlier arm".into(),
+                *span,
+            ),
         }
     }
 }
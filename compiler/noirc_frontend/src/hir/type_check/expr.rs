@@ -280,6 +280,12 @@ impl<'interner> TypeChecker<'interner> {
             HirExpression::Constructor(constructor) => self.check_constructor(constructor, expr_id),
             HirExpression::MemberAccess(access) => self.check_member_access(access, *expr_id),
             HirExpression::Error => Type::Error,
+            HirExpression::Match(_) => {
+                unreachable!("match expressions are rejected by the legacy resolver")
+            }
+            HirExpression::EnumConstructor(_) => {
+                unreachable!("enums are rejected by the legacy resolver")
+            }
             HirExpression::Tuple(elements) => {
                 Type::Tuple(vecmap(&elements, |elem| self.check_expression(elem)))
             }
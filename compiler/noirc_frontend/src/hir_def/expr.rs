@@ -8,7 +8,7 @@ use crate::Shared;
 
 use super::stmt::HirPattern;
 use super::traits::TraitConstraint;
-use super::types::{StructType, Type};
+use super::types::{EnumType, StructType, Type};
 
 /// A HirExpression is the result of an Expression in the AST undergoing
 /// name resolution. It is almost identical to the Expression AST node, but
@@ -26,11 +26,13 @@ pub enum HirExpression {
     Infix(HirInfixExpression),
     Index(HirIndexExpression),
     Constructor(HirConstructorExpression),
+    EnumConstructor(HirEnumConstructorExpression),
     MemberAccess(HirMemberAccess),
     Call(HirCallExpression),
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
     If(HirIfExpression),
+    Match(HirMatchExpression),
     Tuple(Vec<ExprId>),
     Lambda(HirLambda),
     Quote(HirQuoted),
@@ -161,6 +163,14 @@ pub struct HirIfExpression {
     pub alternative: Option<ExprId>,
 }
 
+/// `match expression { pattern => branch, ... }`. The rules are tried in order
+/// and the branch of the first pattern matching the expression is evaluated.
+#[derive(Debug, Clone)]
+pub struct HirMatchExpression {
+    pub expression: ExprId,
+    pub rules: Vec<(HirPattern, ExprId)>,
+}
+
 // `lhs as type` in the source code
 #[derive(Debug, Clone)]
 pub struct HirCastExpression {
@@ -251,6 +261,16 @@ pub struct HirConstructorExpression {
     pub fields: Vec<(Ident, ExprId)>,
 }
 
+/// A call to one of an enum's variants, e.g. `Shape::Circle(radius)`,
+/// or the name of a variant holding no values, e.g. `Color::Red`.
+#[derive(Debug, Clone)]
+pub struct HirEnumConstructorExpression {
+    pub r#type: Shared<EnumType>,
+    pub enum_generics: Vec<Type>,
+    pub variant_index: usize,
+    pub arguments: Vec<ExprId>,
+}
+
 /// Indexing, as in `array[index]`
 #[derive(Debug, Clone)]
 pub struct HirIndexExpression {
@@ -16,6 +16,8 @@ pub enum HirStatement {
     Constrain(HirConstrainStatement),
     Assign(HirAssignStatement),
     For(HirForStatement),
+    While(HirWhileStatement),
+    Loop(ExprId),
     Break,
     Continue,
     Expression(ExprId),
@@ -50,6 +52,12 @@ pub struct HirForStatement {
     pub block: ExprId,
 }
 
+#[derive(Debug, Clone)]
+pub struct HirWhileStatement {
+    pub condition: ExprId,
+    pub block: ExprId,
+}
+
 /// Corresponds to `lvalue = expression;` in the source code
 #[derive(Debug, Clone)]
 pub struct HirAssignStatement {
@@ -70,15 +78,22 @@ pub enum HirPattern {
     Mutable(Box<HirPattern>, Location),
     Tuple(Vec<HirPattern>, Location),
     Struct(Type, Vec<(Ident, HirPattern)>, Location),
+    /// An integer or boolean literal in a `match` arm. The ExprId refers to
+    /// the literal itself, typed the same as the value being matched on.
+    Literal(ExprId, Location),
+    /// An enum variant along with patterns for each of the values it holds.
+    /// The type is the enum being matched on.
+    Variant(Type, usize, Vec<HirPattern>, Location),
 }
 
 impl HirPattern {
     pub fn field_count(&self) -> usize {
         match self {
-            HirPattern::Identifier(_) => 0,
+            HirPattern::Identifier(_) | HirPattern::Literal(..) => 0,
             HirPattern::Mutable(pattern, _) => pattern.field_count(),
             HirPattern::Tuple(fields, _) => fields.len(),
             HirPattern::Struct(_, fields, _) => fields.len(),
+            HirPattern::Variant(_, _, fields, _) => fields.len(),
         }
     }
 
@@ -101,7 +116,9 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location.span,
             HirPattern::Mutable(_, location)
             | HirPattern::Tuple(_, location)
-            | HirPattern::Struct(_, _, location) => location.span,
+            | HirPattern::Struct(_, _, location)
+            | HirPattern::Literal(_, location)
+            | HirPattern::Variant(_, _, _, location) => location.span,
         }
     }
 
@@ -110,7 +127,9 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location,
             HirPattern::Mutable(_, location)
             | HirPattern::Tuple(_, location)
-            | HirPattern::Struct(_, _, location) => *location,
+            | HirPattern::Struct(_, _, location)
+            | HirPattern::Literal(_, location)
+            | HirPattern::Variant(_, _, _, location) => *location,
         }
     }
 }
@@ -16,7 +16,7 @@ use noirc_printable_type::PrintableType;
 
 use crate::{
     ast::{Ident, Signedness},
-    node_interner::StructId,
+    node_interner::{EnumId, StructId},
 };
 
 use super::expr::{HirCallExpression, HirExpression, HirIdent};
@@ -59,6 +59,10 @@ pub enum Type {
     /// represents the generic arguments (if any) to this struct type.
     Struct(Shared<StructType>, Vec<Type>),
 
+    /// A user-defined enum type. Like a Struct, this refers to the shared definition of the
+    /// enum along with the generic arguments (if any) it was given.
+    Enum(Shared<EnumType>, Vec<Type>),
+
     /// A user-defined alias to another type. Similar to a Struct, this carries a shared
     /// reference to the definition of the alias along with any generics that may have
     /// been applied to the alias.
@@ -131,6 +135,14 @@ impl Type {
                 let fields = struct_type.get_fields(args);
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
+            Type::Enum(def, args) => {
+                // The tag followed by the values of every variant
+                let variants = def.borrow().get_variants(args);
+                variants
+                    .iter()
+                    .flat_map(|(_, parameters)| parameters)
+                    .fold(1, |acc, typ| acc + typ.field_count())
+            }
             Type::Alias(def, generics) => def.borrow().get_type(generics).field_count(),
             Type::Tuple(fields) => {
                 fields.iter().fold(0, |acc, field_typ| acc + field_typ.field_count())
@@ -174,6 +186,10 @@ impl Type {
                 }
                 false
             }
+            Type::Enum(enum_type, generics) => {
+                let variants = enum_type.borrow().get_variants(generics);
+                variants.iter().flat_map(|(_, parameters)| parameters).any(Type::contains_slice)
+            }
             Type::Tuple(types) => {
                 for typ in types.iter() {
                     if typ.contains_slice() {
@@ -295,6 +311,17 @@ impl StructType {
         })
     }
 
+    /// Returns the fields of this struct as they were declared, in declaration order.
+    /// Unlike `get_fields`, generic fields are left as-is instead of being instantiated.
+    pub fn get_fields_as_written(&self) -> &[(Ident, Type)] {
+        &self.fields
+    }
+
+    /// Returns the position of the field with the given name, if any
+    pub fn field_index(&self, field_name: &str) -> Option<usize> {
+        self.fields.iter().position(|(name, _)| name.0.contents == field_name)
+    }
+
     pub fn field_names(&self) -> BTreeSet<Ident> {
         self.fields.iter().map(|(name, _)| name.clone()).collect()
     }
@@ -314,6 +341,116 @@ impl StructType {
     }
 }
 
+/// Represents an enum type in the type system. As with structs, each instance of
+/// this rust struct is shared across all Type::Enum variants referring to the same enum.
+#[derive(Eq)]
+pub struct EnumType {
+    /// A unique id representing this enum type. Used to check if two
+    /// enum types are equal.
+    pub id: EnumId,
+
+    pub name: Ident,
+
+    /// Variants are ordered and private, they should only be accessed through
+    /// get_variant() or get_variants() which apply any generic arguments to them.
+    /// A variant's position in this list is the tag it is represented with at runtime.
+    variants: Vec<(Ident, Vec<Type>)>,
+
+    pub generics: Generics,
+    pub location: Location,
+}
+
+impl std::hash::Hash for EnumType {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialEq for EnumType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl EnumType {
+    pub fn new(id: EnumId, name: Ident, location: Location, generics: Generics) -> EnumType {
+        EnumType { id, name, variants: Vec::new(), generics, location }
+    }
+
+    /// Like a struct's fields, an enum's variants may refer to other types which refer
+    /// back to the enum so they are only set once every type has been created.
+    pub fn set_variants(&mut self, variants: Vec<(Ident, Vec<Type>)>) {
+        assert!(self.variants.is_empty());
+        self.variants = variants;
+    }
+
+    pub fn num_variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Returns the index of the variant with the given name, which is also its tag.
+    pub fn variant_index(&self, variant_name: &str) -> Option<usize> {
+        self.variants.iter().position(|(name, _)| name.0.contents == variant_name)
+    }
+
+    pub fn variant_name(&self, index: usize) -> &Ident {
+        &self.variants[index].0
+    }
+
+    /// Returns the types of the values held by the variant at the given index,
+    /// after being applied to the given generic arguments.
+    pub fn get_variant(&self, index: usize, generic_args: &[Type]) -> Vec<Type> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants[index].1, |typ| typ.substitute(&substitutions))
+    }
+
+    /// Returns every variant of this enum, after being applied to the given generic arguments.
+    pub fn get_variants(&self, generic_args: &[Type]) -> Vec<(String, Vec<Type>)> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants, |(name, parameters)| {
+            let parameters = vecmap(parameters, |typ| typ.substitute(&substitutions));
+            (name.0.contents.clone(), parameters)
+        })
+    }
+
+    /// Returns the variants of this enum as they were declared, in declaration order.
+    pub fn get_variants_as_written(&self) -> &[(Ident, Vec<Type>)] {
+        &self.variants
+    }
+
+    fn substitutions(&self, generic_args: &[Type]) -> TypeBindings {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        self.generics
+            .iter()
+            .zip(generic_args)
+            .map(|(old, new)| (old.id(), (old.clone(), new.clone())))
+            .collect()
+    }
+
+    /// True if the given index is the same index as a generic type of this enum
+    /// which is expected to be a numeric generic.
+    pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
+        let target_id = self.generics[index_of_generic].0;
+        self.variants
+            .iter()
+            .flat_map(|(_, parameters)| parameters)
+            .any(|typ| typ.contains_numeric_typevar(target_id))
+    }
+
+    /// Instantiate this enum type, returning a Vec of the new generic args (in
+    /// the same order as self.generics)
+    pub fn instantiate(&self, interner: &mut NodeInterner) -> Vec<Type> {
+        vecmap(&self.generics, |_| interner.next_type_variable())
+    }
+}
+
+impl std::fmt::Display for EnumType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 impl std::fmt::Display for StructType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name)
@@ -678,6 +815,13 @@ impl Type {
                     }
                 })
             }
+            Type::Enum(enum_type, generics) => generics.iter().enumerate().any(|(i, generic)| {
+                if named_generic_id_matches_target(generic) {
+                    enum_type.borrow().generic_is_numeric(i)
+                } else {
+                    generic.contains_numeric_typevar(target_id)
+                }
+            }),
             Type::Alias(alias, generics) => generics.iter().enumerate().any(|(i, generic)| {
                 if named_generic_id_matches_target(generic) {
                     alias.borrow().generic_is_numeric(i)
@@ -737,6 +881,12 @@ impl Type {
                 .get_fields(generics)
                 .into_iter()
                 .all(|(_, field)| field.is_valid_for_program_input()),
+            Type::Enum(definition, generics) => definition
+                .borrow()
+                .get_variants(generics)
+                .iter()
+                .flat_map(|(_, parameters)| parameters)
+                .all(Type::is_valid_for_program_input),
         }
     }
 
@@ -785,6 +935,12 @@ impl Type {
                 .get_fields(generics)
                 .into_iter()
                 .all(|(_, field)| field.is_valid_non_inlined_function_input()),
+            Type::Enum(definition, generics) => definition
+                .borrow()
+                .get_variants(generics)
+                .iter()
+                .flat_map(|(_, parameters)| parameters)
+                .all(Type::is_valid_non_inlined_function_input),
         }
     }
 
@@ -880,6 +1036,14 @@ impl std::fmt::Display for Type {
                     write!(f, "{}<{}>", s.borrow(), args.join(", "))
                 }
             }
+            Type::Enum(e, args) => {
+                let args = vecmap(args, |arg| arg.to_string());
+                if args.is_empty() {
+                    write!(f, "{}", e.borrow())
+                } else {
+                    write!(f, "{}<{}>", e.borrow(), args.join(", "))
+                }
+            }
             Type::Alias(alias, args) => {
                 let args = vecmap(args, |arg| arg.to_string());
                 if args.is_empty() {
@@ -1269,6 +1433,17 @@ impl Type {
                 }
             }
 
+            (Enum(id_a, args_a), Enum(id_b, args_b)) => {
+                if id_a == id_b && args_a.len() == args_b.len() {
+                    for (a, b) in args_a.iter().zip(args_b) {
+                        a.try_unify(b, bindings)?;
+                    }
+                    Ok(())
+                } else {
+                    Err(UnificationError)
+                }
+            }
+
             (NamedGeneric(binding, _), other) | (other, NamedGeneric(binding, _))
                 if !binding.borrow().is_unbound() =>
             {
@@ -1617,6 +1792,12 @@ impl Type {
                 });
                 Type::Struct(fields.clone(), args)
             }
+            Type::Enum(variants, args) => {
+                let args = vecmap(args, |arg| {
+                    arg.substitute_helper(type_bindings, substitute_bound_typevars)
+                });
+                Type::Enum(variants.clone(), args)
+            }
             Type::Alias(alias, args) => {
                 let args = vecmap(args, |arg| {
                     arg.substitute_helper(type_bindings, substitute_bound_typevars)
@@ -1679,6 +1860,7 @@ impl Type {
                 len_occurs || field_occurs
             }
             Type::Struct(_, generic_args)
+            | Type::Enum(_, generic_args)
             | Type::Alias(_, generic_args)
             | Type::TraitAsType(_, _, generic_args) => {
                 generic_args.iter().any(|arg| arg.occurs(target_id))
@@ -1733,6 +1915,10 @@ impl Type {
                 let args = vecmap(args, |arg| arg.follow_bindings());
                 Struct(def.clone(), args)
             }
+            Enum(def, args) => {
+                let args = vecmap(args, |arg| arg.follow_bindings());
+                Enum(def.clone(), args)
+            }
             Alias(def, args) => {
                 // We don't need to vecmap(args, follow_bindings) since we're recursively
                 // calling follow_bindings here already.
@@ -1885,6 +2071,13 @@ impl From<&Type> for PrintableType {
                 let fields = vecmap(fields, |(name, typ)| (name, typ.into()));
                 PrintableType::Struct { fields, name: struct_type.name.to_string() }
             }
+            Type::Enum(def, ref args) => {
+                let enum_type = def.borrow();
+                let variants = vecmap(enum_type.get_variants(args), |(name, parameters)| {
+                    (name, vecmap(parameters, |typ| typ.into()))
+                });
+                PrintableType::Enum { variants, name: enum_type.name.to_string() }
+            }
             Type::Alias(alias, args) => alias.borrow().get_type(args).into(),
             Type::TraitAsType(_, _, _) => unreachable!(),
             Type::Tuple(types) => PrintableType::Tuple { types: vecmap(types, |typ| typ.into()) },
@@ -1938,6 +2131,14 @@ impl std::fmt::Debug for Type {
                     write!(f, "{}<{}>", s.borrow(), args.join(", "))
                 }
             }
+            Type::Enum(e, args) => {
+                let args = vecmap(args, |arg| format!("{:?}", arg));
+                if args.is_empty() {
+                    write!(f, "{}", e.borrow())
+                } else {
+                    write!(f, "{}<{}>", e.borrow(), args.join(", "))
+                }
+            }
             Type::Alias(alias, args) => {
                 let args = vecmap(args, |arg| format!("{:?}", arg));
                 if args.is_empty() {
@@ -2011,3 +2212,9 @@ impl std::fmt::Debug for StructType {
         write!(f, "{}", self.name)
     }
 }
+
+impl std::fmt::Debug for EnumType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
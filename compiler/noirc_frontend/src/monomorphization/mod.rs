@@ -8,7 +8,7 @@
 //!
 //! The entry point to this pass is the `monomorphize` function which, starting from a given
 //! function, will monomorphize the entire reachable program.
-use crate::ast::{FunctionKind, IntegerBitSize, Signedness, UnaryOp, Visibility};
+use crate::ast::{BinaryOpKind, FunctionKind, IntegerBitSize, Signedness, UnaryOp, Visibility};
 use crate::{
     debug::DebugInstrumenter,
     hir_def::{
@@ -391,6 +391,9 @@ impl<'interner> Monomorphizer<'interner> {
                     self.parameter(field, &field_type, new_params)?;
                 }
             }
+            HirPattern::Literal(..) | HirPattern::Variant(..) => {
+                unreachable!("Literal and variant patterns are only allowed in match arms")
+            }
         }
         Ok(())
     }
@@ -517,12 +520,16 @@ impl<'interner> Monomorphizer<'interner> {
                 let typ = Self::convert_type(&self.interner.id_type(expr), location)?;
                 ast::Expression::If(ast::If { condition, consequence, alternative: else_, typ })
             }
+            HirExpression::Match(match_expr) => self.match_expression(match_expr, expr)?,
 
             HirExpression::Tuple(fields) => {
                 let fields = try_vecmap(fields, |id| self.expr(id))?;
                 ast::Expression::Tuple(fields)
             }
             HirExpression::Constructor(constructor) => self.constructor(constructor, expr)?,
+            HirExpression::EnumConstructor(constructor) => {
+                self.enum_constructor(constructor, expr)?
+            }
 
             HirExpression::Lambda(lambda) => self.lambda(lambda, expr)?,
 
@@ -637,6 +644,12 @@ impl<'interner> Monomorphizer<'interner> {
                     block,
                 }))
             }
+            HirStatement::While(while_loop) => {
+                let condition = Box::new(self.expr(while_loop.condition)?);
+                let body = Box::new(self.expr(while_loop.block)?);
+                Ok(ast::Expression::While(ast::While { condition, body }))
+            }
+            HirStatement::Loop(block) => Ok(ast::Expression::Loop(Box::new(self.expr(block)?))),
             HirStatement::Expression(expr) => self.expr(expr),
             HirStatement::Semi(expr) => {
                 self.expr(expr).map(|expr| ast::Expression::Semi(Box::new(expr)))
@@ -709,6 +722,37 @@ impl<'interner> Monomorphizer<'interner> {
         Ok(ast::Expression::Block(new_exprs))
     }
 
+    /// An enum value is a tuple of its variant's tag followed by a tuple for each variant
+    /// holding that variant's values. Only the payload of the constructed variant is
+    /// meaningful, the payloads of the other variants are zeroed.
+    fn enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: node_interner::ExprId,
+    ) -> Result<ast::Expression, MonomorphizationError> {
+        let location = self.interner.expr_location(&id);
+        let typ = Self::convert_type(&self.interner.id_type(id), location)?;
+        let ast::Type::Tuple(payload_types) = typ else {
+            unreachable!("Expected an enum to be represented by a tuple, found {typ}")
+        };
+
+        let tag = constructor.variant_index as u128;
+        let tag =
+            ast::Expression::Literal(ast::Literal::Integer(tag.into(), ast::Type::Field, location));
+
+        let mut arguments = try_vecmap(constructor.arguments, |id| self.expr(id))?;
+        let mut fields = vec![tag];
+        for (index, payload_type) in payload_types.iter().skip(1).enumerate() {
+            let payload = if index == constructor.variant_index {
+                ast::Expression::Tuple(std::mem::take(&mut arguments))
+            } else {
+                self.zeroed_value_of_type(payload_type, location)
+            };
+            fields.push(payload);
+        }
+        Ok(ast::Expression::Tuple(fields))
+    }
+
     fn block(
         &mut self,
         statement_ids: Vec<StmtId>,
@@ -756,6 +800,13 @@ impl<'interner> Monomorphizer<'interner> {
 
                 self.unpack_tuple_pattern(value, patterns_iter)
             }
+            HirPattern::Variant(_, index, patterns, _) => {
+                let fields = unwrap_enum_variant_type(typ, index);
+                let payload = ast::Expression::ExtractTupleField(Box::new(value), index + 1);
+                self.unpack_tuple_pattern(payload, patterns.into_iter().zip(fields))
+            }
+            // Literals don't bind anything, they are checked by `pattern_condition` instead
+            HirPattern::Literal(..) => Ok(ast::Expression::Block(Vec::new())),
         }
     }
 
@@ -792,6 +843,168 @@ impl<'interner> Monomorphizer<'interner> {
         Ok(ast::Expression::Block(definitions))
     }
 
+    /// Lowers a match into a chain of `if`s, one for each arm whose pattern can fail to match:
+    ///
+    /// {
+    ///     let value = expression;
+    ///     if pattern1_matches(value) {
+    ///         let pattern1_bindings = value;
+    ///         branch1
+    ///     } else if ... {
+    ///         ...
+    ///     } else {
+    ///         let last_pattern_bindings = value;
+    ///         last_branch
+    ///     }
+    /// }
+    ///
+    /// The final arm doesn't need a condition since the match was checked to be exhaustive. If its
+    /// pattern can fail to match it's asserted to match instead, as an enum which came from an input
+    /// may have a tag which isn't one of its variants.
+    fn match_expression(
+        &mut self,
+        match_expr: HirMatchExpression,
+        expr: node_interner::ExprId,
+    ) -> Result<ast::Expression, MonomorphizationError> {
+        let location = self.interner.expr_location(&expr);
+        let typ = Self::convert_type(&self.interner.id_type(expr), location)?;
+
+        let value_type = self.interner.id_type(match_expr.expression);
+        let value_location = self.interner.expr_location(&match_expr.expression);
+        let value = self.expr(match_expr.expression)?;
+        let value_id = self.next_local_id();
+        let name = "match_value".to_string();
+
+        let value_ident = ast::Expression::Ident(ast::Ident {
+            location: Some(value_location),
+            definition: Definition::Local(value_id),
+            mutable: false,
+            name: name.clone(),
+            typ: Self::convert_type(&value_type, value_location)?,
+        });
+
+        // Build the chain from the last arm backwards, so each arm can use the chain
+        // of the arms after it as its alternative
+        let mut chain = None;
+        for (pattern, branch) in match_expr.rules.into_iter().rev() {
+            let condition = self.pattern_condition(&pattern, value_ident.clone(), &value_type)?;
+            let bindings = self.unpack_pattern(pattern, value_ident.clone(), &value_type)?;
+            let branch = match bindings {
+                // Nothing to bind for a literal pattern
+                ast::Expression::Block(bindings) if bindings.is_empty() => self.expr(branch)?,
+                bindings => ast::Expression::Block(vec![bindings, self.expr(branch)?]),
+            };
+
+            chain = Some(match (condition, chain) {
+                (Some(condition), Some(alternative)) => ast::Expression::If(ast::If {
+                    condition: Box::new(condition),
+                    consequence: Box::new(branch),
+                    alternative: Some(Box::new(alternative)),
+                    typ: typ.clone(),
+                }),
+                // The match was checked to be exhaustive so the last arm matches any well-formed value,
+                // but nothing else stops an enum input from having a tag which isn't one of its variants.
+                (Some(condition), None) => {
+                    let message = "Match value does not match any pattern";
+                    let message_type =
+                        HirType::String(Box::new(HirType::Constant(message.len() as u32)));
+                    let message = ast::Expression::Literal(ast::Literal::Str(message.to_string()));
+                    let assertion = ast::Expression::Constrain(
+                        Box::new(condition),
+                        location,
+                        Some(Box::new((message, message_type))),
+                    );
+                    ast::Expression::Block(vec![assertion, branch])
+                }
+                // Any arms after an irrefutable pattern are unreachable
+                (None, _) => branch,
+            });
+        }
+
+        let mut block = vec![ast::Expression::Let(ast::Let {
+            id: value_id,
+            mutable: false,
+            name,
+            expression: Box::new(value),
+        })];
+        block.extend(chain);
+        Ok(ast::Expression::Block(block))
+    }
+
+    /// Returns a boolean expression which is true if `value` matches `pattern`,
+    /// or None if the pattern matches any value of its type.
+    fn pattern_condition(
+        &mut self,
+        pattern: &HirPattern,
+        value: ast::Expression,
+        typ: &HirType,
+    ) -> Result<Option<ast::Expression>, MonomorphizationError> {
+        let mut value = value;
+        let mut condition: Option<ast::Expression> = None;
+
+        let fields: Vec<(&HirPattern, HirType)> = match pattern {
+            HirPattern::Identifier(_) => return Ok(None),
+            HirPattern::Mutable(pattern, _) => return self.pattern_condition(pattern, value, typ),
+            HirPattern::Literal(literal, location) => {
+                let literal = self.expr(*literal)?;
+                return Ok(Some(ast::Expression::Binary(ast::Binary {
+                    lhs: Box::new(value),
+                    operator: BinaryOpKind::Equal,
+                    rhs: Box::new(literal),
+                    location: *location,
+                })));
+            }
+            HirPattern::Tuple(patterns, _) => patterns.iter().zip(unwrap_tuple_type(typ)).collect(),
+            HirPattern::Struct(_, patterns, _) => {
+                // Fields are extracted by their position in the struct type
+                let patterns =
+                    btree_map(patterns, |(name, pattern)| (name.0.contents.clone(), pattern));
+                vecmap(unwrap_struct_type(typ), |(field_name, field_type)| {
+                    (patterns[&field_name], field_type)
+                })
+            }
+            HirPattern::Variant(_, index, patterns, location) => {
+                // Check the tag first, then match the variant's values within its payload
+                let tag = ast::Expression::ExtractTupleField(Box::new(value.clone()), 0);
+                let index_literal = ast::Expression::Literal(ast::Literal::Integer(
+                    (*index as u128).into(),
+                    ast::Type::Field,
+                    *location,
+                ));
+                condition = Some(ast::Expression::Binary(ast::Binary {
+                    lhs: Box::new(tag),
+                    operator: BinaryOpKind::Equal,
+                    rhs: Box::new(index_literal),
+                    location: *location,
+                }));
+
+                value = ast::Expression::ExtractTupleField(Box::new(value), index + 1);
+                patterns.iter().zip(unwrap_enum_variant_type(typ, *index)).collect()
+            }
+        };
+
+        for (i, (field_pattern, field_type)) in fields.into_iter().enumerate() {
+            let field = ast::Expression::ExtractTupleField(Box::new(value.clone()), i);
+            let location = field_pattern.location();
+            let Some(field_condition) =
+                self.pattern_condition(field_pattern, field, &field_type)?
+            else {
+                continue;
+            };
+
+            condition = Some(match condition {
+                Some(condition) => ast::Expression::Binary(ast::Binary {
+                    lhs: Box::new(condition),
+                    operator: BinaryOpKind::And,
+                    rhs: Box::new(field_condition),
+                    location,
+                }),
+                None => field_condition,
+            });
+        }
+        Ok(condition)
+    }
+
     /// Find a captured variable in the innermost closure, and construct an expression
     fn lookup_captured_expr(&mut self, id: node_interner::DefinitionId) -> Option<ast::Expression> {
         let ctx = self.lambda_envs_stack.last()?;
@@ -977,6 +1190,16 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Type::Tuple(fields)
             }
 
+            HirType::Enum(def, args) => {
+                let mut fields = vec![ast::Type::Field];
+                for (_, parameters) in def.borrow().get_variants(args) {
+                    let parameters =
+                        try_vecmap(parameters, |typ| Self::convert_type(&typ, location))?;
+                    fields.push(ast::Type::Tuple(parameters));
+                }
+                ast::Type::Tuple(fields)
+            }
+
             HirType::Alias(def, args) => {
                 Self::convert_type(&def.borrow().get_type(args), location)?
             }
@@ -1813,6 +2036,14 @@ fn unwrap_struct_type(typ: &HirType) -> Vec<(String, HirType)> {
     }
 }
 
+/// Returns the types of the values held by the variant at `index` of the given enum type
+fn unwrap_enum_variant_type(typ: &HirType, index: usize) -> Vec<HirType> {
+    match typ.follow_bindings() {
+        HirType::Enum(def, args) => def.borrow().get_variant(index, &args),
+        other => unreachable!("unwrap_enum_variant_type: expected enum, found {:?}", other),
+    }
+}
+
 fn perform_instantiation_bindings(bindings: &TypeBindings) {
     for (var, binding) in bindings.values() {
         var.force_bind(binding.clone());
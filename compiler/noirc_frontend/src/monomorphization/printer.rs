@@ -49,6 +49,15 @@ impl AstPrinter {
                 write!(f, " as {})", cast.r#type)
             }
             Expression::For(for_expr) => self.print_for(for_expr, f),
+            Expression::While(while_expr) => {
+                write!(f, "while ")?;
+                self.print_expr(&while_expr.condition, f)?;
+                self.print_loop_body(&while_expr.body, f)
+            }
+            Expression::Loop(body) => {
+                write!(f, "loop")?;
+                self.print_loop_body(body, f)
+            }
             Expression::If(if_expr) => self.print_if(if_expr, f),
             Expression::Tuple(tuple) => self.print_tuple(tuple, f),
             Expression::ExtractTupleField(expr, index) => {
@@ -207,6 +216,19 @@ impl AstPrinter {
         write!(f, "}}")
     }
 
+    fn print_loop_body(
+        &mut self,
+        body: &Expression,
+        f: &mut Formatter,
+    ) -> Result<(), std::fmt::Error> {
+        write!(f, " {{")?;
+        self.indent_level += 1;
+        self.print_expr_expect_block(body, f)?;
+        self.indent_level -= 1;
+        self.next_line(f)?;
+        write!(f, "}}")
+    }
+
     fn print_if(
         &mut self,
         if_expr: &super::ast::If,
@@ -15,8 +15,10 @@ use crate::ast::Ident;
 use crate::graph::CrateId;
 use crate::hir::comptime;
 use crate::hir::def_collector::dc_crate::CompilationError;
-use crate::hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias};
-use crate::hir::def_map::{LocalModuleId, ModuleId};
+use crate::hir::def_collector::dc_crate::{
+    UnresolvedEnum, UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias,
+};
+use crate::hir::def_map::{LocalModuleId, ModuleDefId, ModuleId};
 use crate::QuotedType;
 
 use crate::ast::{BinaryOpKind, FunctionDefinition, ItemVisibility};
@@ -25,7 +27,7 @@ use crate::hir_def::expr::HirIdent;
 use crate::hir_def::stmt::HirLetStatement;
 use crate::hir_def::traits::TraitImpl;
 use crate::hir_def::traits::{Trait, TraitConstraint};
-use crate::hir_def::types::{StructType, Type};
+use crate::hir_def::types::{EnumType, StructType, Type};
 use crate::hir_def::{
     expr::HirExpression,
     function::{FuncMeta, HirFunction},
@@ -75,7 +77,7 @@ pub struct NodeInterner {
     pub(crate) id_to_location: HashMap<Index, Location>,
 
     // Maps each DefinitionId to a DefinitionInfo.
-    definitions: Vec<DefinitionInfo>,
+    pub(crate) definitions: Vec<DefinitionInfo>,
 
     // Type checking map
     //
@@ -92,10 +94,16 @@ pub struct NodeInterner {
     // Each struct definition is possibly shared across multiple type nodes.
     // It is also mutated through the RefCell during name resolution to append
     // methods from impls to the type.
-    structs: HashMap<StructId, Shared<StructType>>,
+    pub(crate) structs: HashMap<StructId, Shared<StructType>>,
 
     struct_attributes: HashMap<StructId, StructAttributes>,
 
+    // Enum map.
+    //
+    // Like structs, each enum definition is shared across all the types referring to it
+    // and its variants are filled in during name resolution.
+    pub(crate) enums: HashMap<EnumId, Shared<EnumType>>,
+
     // Maps TypeAliasId -> Shared<TypeAlias>
     //
     // Map type aliases to the actual type.
@@ -176,6 +184,10 @@ pub struct NodeInterner {
 
     /// Stores the [Location] of a [Type] reference
     pub(crate) type_ref_locations: Vec<(Type, Location)>,
+
+    /// Every place an item is used in the source program, as recorded during elaboration.
+    /// Searched by LSP to find all references to an item, which is needed to rename it.
+    pub(crate) references: Vec<(ReferenceId, Location)>,
 }
 
 /// A dependency in the dependency graph may be a type or a definition.
@@ -191,11 +203,27 @@ pub struct NodeInterner {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum DependencyId {
     Struct(StructId),
+    Enum(EnumId),
     Global(GlobalId),
     Function(FuncId),
     Alias(TypeAliasId),
 }
 
+/// An item that a [Location] in the source program may refer to, either because the
+/// item is defined there or because it is used there.
+///
+/// This is used by the LSP to find out what the user is pointing at.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ReferenceId {
+    Struct(StructId),
+    StructMember(StructId, usize),
+    Trait(TraitId),
+    Global(GlobalId),
+    Function(FuncId),
+    Alias(TypeAliasId),
+    Local(DefinitionId),
+}
+
 /// A trait implementation is either a normal implementation that is present in the source
 /// program via an `impl` block, or it is assumed to exist from a `where` clause or similar.
 #[derive(Debug, Clone)]
@@ -271,7 +299,7 @@ impl FunctionModifiers {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub struct DefinitionId(usize);
+pub struct DefinitionId(pub(crate) usize);
 
 impl DefinitionId {
     //dummy id for error reporting
@@ -353,6 +381,28 @@ impl StructId {
     }
 }
 
+/// Like [StructId], an enum is identified by the module holding its methods.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub struct EnumId(ModuleId);
+
+impl EnumId {
+    pub fn dummy_id() -> EnumId {
+        EnumId(ModuleId { krate: CrateId::dummy_id(), local_id: LocalModuleId::dummy_id() })
+    }
+
+    pub fn module_id(self) -> ModuleId {
+        self.0
+    }
+
+    pub fn krate(self) -> CrateId {
+        self.0.krate
+    }
+
+    pub fn local_module_id(self) -> LocalModuleId {
+        self.0.local_id
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
 pub struct TypeAliasId(pub usize);
 
@@ -489,6 +539,7 @@ impl Default for NodeInterner {
             definition_to_type: HashMap::new(),
             structs: HashMap::new(),
             struct_attributes: HashMap::new(),
+            enums: HashMap::new(),
             type_aliases: Vec::new(),
             traits: HashMap::new(),
             trait_implementations: HashMap::new(),
@@ -506,6 +557,7 @@ impl Default for NodeInterner {
             primitive_methods: HashMap::new(),
             type_alias_ref: Vec::new(),
             type_ref_locations: Vec::new(),
+            references: Vec::new(),
         };
 
         // An empty block expression is used often, we add this into the `node` on startup
@@ -600,6 +652,25 @@ impl NodeInterner {
         struct_id
     }
 
+    pub fn new_enum(
+        &mut self,
+        typ: &UnresolvedEnum,
+        krate: CrateId,
+        local_id: LocalModuleId,
+        file_id: FileId,
+    ) -> EnumId {
+        let enum_id = EnumId(ModuleId { krate, local_id });
+        let name = typ.enum_def.name.clone();
+
+        // Variants and generics are filled in later, see `new_struct`
+        let generics = vecmap(&typ.enum_def.generics, |_| TypeVariable::unbound(TypeVariableId(0)));
+
+        let location = Location::new(typ.enum_def.span, file_id);
+        let new_enum = EnumType::new(enum_id, name, location, generics);
+        self.enums.insert(enum_id, Shared::new(new_enum));
+        enum_id
+    }
+
     pub fn push_type_alias(&mut self, typ: &UnresolvedTypeAlias) -> TypeAliasId {
         let type_id = TypeAliasId(self.type_aliases.len());
 
@@ -624,6 +695,11 @@ impl NodeInterner {
         f(&mut value);
     }
 
+    pub fn update_enum(&mut self, type_id: EnumId, f: impl FnOnce(&mut EnumType)) {
+        let mut value = self.enums.get_mut(&type_id).unwrap().borrow_mut();
+        f(&mut value);
+    }
+
     pub fn update_trait(&mut self, trait_id: TraitId, f: impl FnOnce(&mut Trait)) {
         let value = self.traits.get_mut(&trait_id).unwrap();
         f(value);
@@ -663,6 +739,36 @@ impl NodeInterner {
         self.type_ref_locations.push((typ, location));
     }
 
+    /// Records that the item `referenced` is used at the given [Location]
+    pub fn add_reference(&mut self, referenced: ReferenceId, location: Location) {
+        self.references.push((referenced, location));
+    }
+
+    /// Records a use of the given definition, unless it is a generic type
+    pub fn add_definition_reference(&mut self, id: DefinitionId, location: Location) {
+        let referenced = match self.definition(id).kind {
+            DefinitionKind::Function(func_id) => ReferenceId::Function(func_id),
+            DefinitionKind::Global(global_id) => ReferenceId::Global(global_id),
+            DefinitionKind::Local(_) => ReferenceId::Local(id),
+            DefinitionKind::GenericType(_) => return,
+        };
+        self.add_reference(referenced, location);
+    }
+
+    /// Records a use of the item a path resolved to. Uses of modules are not recorded.
+    pub fn add_module_def_id_reference(&mut self, id: ModuleDefId, location: Location) {
+        let referenced = match id {
+            ModuleDefId::FunctionId(func_id) => ReferenceId::Function(func_id),
+            ModuleDefId::TypeId(struct_id) => ReferenceId::Struct(struct_id),
+            ModuleDefId::TypeAliasId(alias_id) => ReferenceId::Alias(alias_id),
+            ModuleDefId::TraitId(trait_id) => ReferenceId::Trait(trait_id),
+            ModuleDefId::GlobalId(global_id) => ReferenceId::Global(global_id),
+            // Enums aren't tracked by the LSP yet
+            ModuleDefId::ModuleId(_) | ModuleDefId::EnumId(_) => return,
+        };
+        self.add_reference(referenced, location);
+    }
+
     fn push_global(
         &mut self,
         ident: Ident,
@@ -826,6 +932,15 @@ impl NodeInterner {
         self.function_modules[&func]
     }
 
+    /// Returns every function with a body which was defined in the given crate, including methods
+    pub fn functions_in_crate(&self, crate_id: CrateId) -> impl Iterator<Item = FuncId> + '_ {
+        self.func_meta.iter().filter_map(move |(func_id, meta)| {
+            let module = self.function_modules.get(func_id)?;
+            let has_body = meta.has_body && !meta.is_trait_function;
+            (module.krate == crate_id && has_body).then_some(*func_id)
+        })
+    }
+
     /// Returns the [`FuncId`] corresponding to the function referred to by `expr_id`
     pub fn lookup_function_from_expr(&self, expr: &ExprId) -> Option<FuncId> {
         if let HirExpression::Ident(HirIdent { id, .. }, _) = self.expression(expr) {
@@ -985,6 +1100,10 @@ impl NodeInterner {
         self.structs[&id].clone()
     }
 
+    pub fn get_enum(&self, id: EnumId) -> Shared<EnumType> {
+        self.enums[&id].clone()
+    }
+
     pub fn get_struct_methods(&self, id: StructId) -> Vec<Methods> {
         self.struct_methods
             .keys()
@@ -1178,6 +1297,11 @@ impl NodeInterner {
         self.trait_implementations[&id].clone()
     }
 
+    /// Returns every trait impl written in the program, in no particular order.
+    pub fn all_trait_implementations(&self) -> impl Iterator<Item = &Shared<TraitImpl>> {
+        self.trait_implementations.values()
+    }
+
     /// Given a `ObjectType: TraitId` pair, try to find an existing impl that satisfies the
     /// constraint. If an impl cannot be found, this will return a vector of each constraint
     /// in the path to get to the failing constraint. Usually this is just the single failing
@@ -1520,6 +1644,69 @@ impl NodeInterner {
         self.lookup_primitive_method(&typ, method_name)
     }
 
+    /// Returns every method which can be called on a value of the given type along with its
+    /// name, including the methods of trait impls for that type and of impls for all types `T`.
+    ///
+    /// Unlike `lookup_method`, this does not bind any type variables in `typ`.
+    pub fn get_type_methods(&self, typ: &Type) -> Vec<(&str, FuncId)> {
+        let typ = match typ.follow_bindings() {
+            Type::MutableReference(element) => *element,
+            other => other,
+        };
+
+        let mut candidates: Vec<(&str, &Methods)> = Vec::new();
+        if let Type::Struct(struct_type, _) = &typ {
+            let id = struct_type.borrow().id;
+            candidates.extend(
+                self.struct_methods
+                    .iter()
+                    .filter(|((struct_id, _), _)| *struct_id == id)
+                    .map(|((_, name), methods)| (name.as_str(), methods)),
+            );
+        } else if let Some(key) = get_type_method_key(&typ) {
+            candidates.extend(
+                self.primitive_methods
+                    .iter()
+                    .filter(|((method_key, _), _)| *method_key == key)
+                    .map(|((_, name), methods)| (name.as_str(), methods)),
+            );
+        }
+        candidates.extend(
+            self.primitive_methods
+                .iter()
+                .filter(|((method_key, _), _)| *method_key == TypeMethodKey::Generic)
+                .map(|((_, name), methods)| (name.as_str(), methods)),
+        );
+
+        let object_type_matches = |method: &FuncId| {
+            let Type::Function(args, _, _) = self.function_meta(method).typ.instantiate(self).0
+            else {
+                return false;
+            };
+            let Some(object) = args.first() else {
+                return false;
+            };
+            let object = match object {
+                Type::MutableReference(element) => element.as_ref(),
+                other => other,
+            };
+            object.try_unify(&typ, &mut TypeBindings::new()).is_ok()
+        };
+
+        let mut methods = Vec::new();
+        for (name, candidate_methods) in candidates {
+            if methods.iter().any(|(method_name, _)| *method_name == name) {
+                continue;
+            }
+            if let Some(method) =
+                candidate_methods.iter().find(|method| object_type_matches(method))
+            {
+                methods.push((name, method));
+            }
+        }
+        methods
+    }
+
     /// Returns what the next trait impl id is expected to be.
     pub fn next_trait_impl_id(&mut self) -> TraitImplId {
         let next_id = self.next_trait_implementation_id;
@@ -1636,6 +1823,10 @@ impl NodeInterner {
         self.add_dependency(dependent, DependencyId::Struct(dependency));
     }
 
+    pub fn add_enum_dependency(&mut self, dependent: DependencyId, dependency: EnumId) {
+        self.add_dependency(dependent, DependencyId::Enum(dependency));
+    }
+
     pub fn add_global_dependency(&mut self, dependent: DependencyId, dependency: GlobalId) {
         self.add_dependency(dependent, DependencyId::Global(dependency));
     }
@@ -1686,6 +1877,12 @@ impl NodeInterner {
                             push_error(struct_type.name.to_string(), &scc, i, struct_type.location);
                             break;
                         }
+                        DependencyId::Enum(enum_id) => {
+                            let enum_type = self.get_enum(enum_id);
+                            let enum_type = enum_type.borrow();
+                            push_error(enum_type.name.to_string(), &scc, i, enum_type.location);
+                            break;
+                        }
                         DependencyId::Global(global_id) => {
                             let global = self.get_global(global_id);
                             let name = global.ident.to_string();
@@ -1719,6 +1916,7 @@ impl NodeInterner {
     fn get_cycle_error_string(&self, scc: &[PetGraphIndex], start_index: usize) -> String {
         let index_to_string = |index: PetGraphIndex| match self.dependency_graph[index] {
             DependencyId::Struct(id) => Cow::Owned(self.get_struct(id).borrow().name.to_string()),
+            DependencyId::Enum(id) => Cow::Owned(self.get_enum(id).borrow().name.to_string()),
             DependencyId::Function(id) => Cow::Borrowed(self.function_name(&id)),
             DependencyId::Alias(id) => {
                 Cow::Owned(self.get_type_alias(id).borrow().name.to_string())
@@ -1807,6 +2005,7 @@ enum TypeMethodKey {
     Function,
     Generic,
     Quoted(QuotedType),
+    Enum(EnumId),
 }
 
 fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
@@ -1827,6 +2026,7 @@ fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
         Type::Function(_, _, _) => Some(Function),
         Type::NamedGeneric(_, _) => Some(Generic),
         Type::Quoted(quoted) => Some(Quoted(*quoted)),
+        Type::Enum(enum_type, _) => Some(Enum(enum_type.borrow().id)),
         Type::MutableReference(element) => get_type_method_key(element),
         Type::Alias(alias, _) => get_type_method_key(&alias.borrow().typ),
 
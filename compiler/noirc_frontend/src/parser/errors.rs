@@ -42,6 +42,10 @@ pub enum ParserErrorReason {
     AssertMessageNotString,
     #[error("Integer bit size {0} isn't supported")]
     InvalidBitSize(u32),
+    #[error("Only integer and boolean literals can be used as patterns")]
+    InvalidLiteralPattern,
+    #[error("Expected a , separating these two match arms")]
+    MissingMatchArmComma,
     #[error("{0}")]
     Lexer(LexerErrorKind),
 }
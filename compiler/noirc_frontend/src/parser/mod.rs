@@ -12,8 +12,9 @@ mod labels;
 mod parser;
 
 use crate::ast::{
-    Expression, Ident, ImportStatement, LetStatement, ModuleDeclaration, NoirFunction, NoirStruct,
-    NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable, StatementKind, TypeImpl, UseTree,
+    Expression, Ident, ImportStatement, LetStatement, ModuleDeclaration, NoirEnumeration,
+    NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable, StatementKind,
+    TypeImpl, UseTree,
 };
 use crate::token::{Keyword, Token};
 
@@ -30,6 +31,7 @@ pub(crate) enum TopLevelStatement {
     Module(ModuleDeclaration),
     Import(UseTree),
     Struct(NoirStruct),
+    Enum(NoirEnumeration),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -179,11 +181,15 @@ where
         })
 }
 
-/// Recovery strategy for statements: If a statement fails to parse skip until the next ';' or fail
-/// if we find a '}' first.
+/// Recovery strategy for statements: If a statement fails to parse skip until the next ';'.
+/// If we find a '}' first the statement is the last one of its block, e.g. one which is still
+/// being written, so we skip up to that '}' instead to keep the rest of the block.
 fn statement_recovery() -> impl NoirParser<StatementKind> {
     use Token::*;
-    try_skip_until([Semicolon, RightBrace], RightBrace)
+    try_skip_until([Semicolon, RightBrace], RightBrace).or(none_of([RightBrace, EOF])
+        .repeated()
+        .at_least(1)
+        .map(|_| StatementKind::Error))
 }
 
 fn parameter_recovery<T: Recoverable + Clone>() -> impl NoirParser<T> {
@@ -213,6 +219,7 @@ pub struct SortedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnumeration>,
     pub traits: Vec<NoirTrait>,
     pub trait_impls: Vec<NoirTraitImpl>,
     pub impls: Vec<TypeImpl>,
@@ -244,6 +251,10 @@ impl std::fmt::Display for SortedModule {
             write!(f, "{type_}")?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{enum_}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
@@ -279,6 +290,7 @@ impl ParsedModule {
                 ItemKind::Import(import) => module.push_import(import),
                 ItemKind::Function(func) => module.push_function(func),
                 ItemKind::Struct(typ) => module.push_type(typ),
+                ItemKind::Enum(typ) => module.push_enum(typ),
                 ItemKind::Trait(noir_trait) => module.push_trait(noir_trait),
                 ItemKind::TraitImpl(trait_impl) => module.push_trait_impl(trait_impl),
                 ItemKind::Impl(r#impl) => module.push_impl(r#impl),
@@ -304,6 +316,7 @@ pub enum ItemKind {
     Import(UseTree),
     Function(NoirFunction),
     Struct(NoirStruct),
+    Enum(NoirEnumeration),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -360,6 +373,10 @@ impl SortedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnumeration) {
+        self.enums.push(typ);
+    }
+
     fn push_trait(&mut self, noir_trait: NoirTrait) {
         self.traits.push(noir_trait);
     }
@@ -479,6 +496,7 @@ impl std::fmt::Display for TopLevelStatement {
             TopLevelStatement::Trait(t) => t.fmt(f),
             TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
@@ -35,9 +35,10 @@ use super::{
 use super::{spanned, Item, ItemKind};
 use crate::ast::{
     BinaryOp, BinaryOpKind, BlockExpression, ForLoopStatement, ForRange, Ident, IfExpression,
-    InfixExpression, LValue, Literal, ModuleDeclaration, NoirTypeAlias, Param, Path, Pattern,
-    Recoverable, Statement, TraitBound, TypeImpl, UnaryRhsMemberAccess, UnaryRhsMethodCall,
-    UnresolvedTraitConstraint, UseTree, UseTreeKind, Visibility,
+    InfixExpression, LValue, Literal, MatchExpression, ModuleDeclaration, NoirTypeAlias, Param,
+    Path, Pattern, Recoverable, Statement, TraitBound, TypeImpl, UnaryRhsMemberAccess,
+    UnaryRhsMethodCall, UnresolvedTraitConstraint, UseTree, UseTreeKind, Visibility,
+    WhileStatement,
 };
 use crate::ast::{
     Expression, ExpressionKind, LetStatement, StatementKind, UnresolvedType, UnresolvedTypeData,
@@ -53,6 +54,7 @@ use noirc_errors::{Span, Spanned};
 
 mod assertion;
 mod attributes;
+mod enums;
 mod function;
 mod lambdas;
 mod literals;
@@ -178,6 +180,7 @@ fn module() -> impl NoirParser<ParsedModule> {
                     TopLevelStatement::Module(m) => push_item(ItemKind::ModuleDecl(m)),
                     TopLevelStatement::Import(i) => push_item(ItemKind::Import(i)),
                     TopLevelStatement::Struct(s) => push_item(ItemKind::Struct(s)),
+                    TopLevelStatement::Enum(e) => push_item(ItemKind::Enum(e)),
                     TopLevelStatement::Trait(t) => push_item(ItemKind::Trait(t)),
                     TopLevelStatement::TraitImpl(t) => push_item(ItemKind::TraitImpl(t)),
                     TopLevelStatement::Impl(i) => push_item(ItemKind::Impl(i)),
@@ -193,6 +196,7 @@ fn module() -> impl NoirParser<ParsedModule> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | enum_definition
 ///                    | trait_definition
 ///                    | implementation
 ///                    | submodule
@@ -205,6 +209,7 @@ fn top_level_statement(
     choice((
         function::function_definition(false).map(TopLevelStatement::Function),
         structs::struct_definition(),
+        enums::enum_definition(),
         traits::trait_definition(),
         traits::trait_implementation(),
         implementation(),
@@ -499,6 +504,8 @@ where
             declaration(expr_parser.clone()),
             assignment(expr_parser.clone()),
             for_loop(expr_no_constructors.clone(), statement.clone()),
+            while_loop(expr_no_constructors.clone(), statement.clone()),
+            loop_statement(statement.clone()),
             break_statement(),
             continue_statement(),
             return_statement(expr_parser.clone()),
@@ -565,7 +572,17 @@ where
 }
 
 fn pattern() -> impl NoirParser<Pattern> {
-    recursive(|pattern| {
+    pattern_with_refutable(false)
+}
+
+/// The patterns of a `match` arm, which unlike other patterns may also be literals or
+/// enum variants since they are allowed to fail to match
+fn match_pattern() -> impl NoirParser<Pattern> {
+    pattern_with_refutable(true)
+}
+
+fn pattern_with_refutable(allow_refutable: bool) -> impl NoirParser<Pattern> {
+    recursive(move |pattern| {
         let ident_pattern = ident().map(Pattern::Identifier).map_err(|mut error| {
             if matches!(error.found(), Token::IntType(..)) {
                 error = ParserError::with_reason(
@@ -594,11 +611,63 @@ fn pattern() -> impl NoirParser<Pattern> {
             .map_with_span(|(typename, fields), span| Pattern::Struct(typename, fields, span));
 
         let tuple_pattern = pattern
+            .clone()
             .separated_by(just(Token::Comma))
             .delimited_by(just(Token::LeftParen), just(Token::RightParen))
             .map_with_span(Pattern::Tuple);
 
-        choice((mut_pattern, tuple_pattern, struct_pattern, ident_pattern))
+        let literal_pattern = just(Token::Minus)
+            .or_not()
+            .then(token_kind(TokenKind::Literal))
+            .validate(move |(minus, token), span, emit| {
+                let literal = match (minus, token) {
+                    (minus, Token::Int(value)) => Literal::Integer(value, minus.is_some()),
+                    (None, Token::Bool(value)) => Literal::Bool(value),
+                    _ => {
+                        emit(ParserError::with_reason(
+                            ParserErrorReason::InvalidLiteralPattern,
+                            span,
+                        ));
+                        return Pattern::error(span);
+                    }
+                };
+                Pattern::Literal(literal, span)
+            });
+
+        let variant_fields = pattern
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+        let variant_pattern = path()
+            .then(variant_fields)
+            .map_with_span(|(path, fields), span| Pattern::Variant(path, fields, span));
+
+        // A lone identifier is a variable binding rather than a variant without fields
+        let unit_variant_pattern = path().try_map(|path, span| match path.as_ident() {
+            Some(ident) => {
+                let found = Token::Ident(ident.to_string());
+                Err(ParserError::expected_label(ParsingRuleLabel::Pattern, found, span))
+            }
+            None => Ok(Pattern::Variant(path, Vec::new(), span)),
+        });
+
+        // Refutable patterns are left out of other patterns entirely rather than rejected
+        // afterwards so that error recovery can't mistake something like `let = 1` for one
+        if allow_refutable {
+            choice((
+                mut_pattern,
+                tuple_pattern,
+                struct_pattern,
+                variant_pattern,
+                unit_variant_pattern,
+                ident_pattern,
+                literal_pattern,
+            ))
+            .boxed()
+        } else {
+            choice((mut_pattern, tuple_pattern, struct_pattern, ident_pattern)).boxed()
+        }
     })
     .labelled(ParsingRuleLabel::Pattern)
 }
@@ -955,6 +1024,51 @@ where
     })
 }
 
+/// match expr { pattern => expr, ... }
+///
+/// The comma after an arm may be left out when its expression is a block.
+fn match_expr<'a, P, P2, S>(
+    expr_parser: P,
+    expr_no_constructors: P2,
+    statement: S,
+) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+    P2: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    let block_branch =
+        block_expr(statement).then_ignore(just(Token::Comma).or_not()).map(|block| (block, true));
+    let expression_branch = expr_parser
+        .then(just(Token::Comma).or_not())
+        .map(|(expression, comma)| (expression, comma.is_some()));
+
+    let rule = match_pattern()
+        .then_ignore(just(Token::FatArrow))
+        .then(block_branch.or(expression_branch))
+        .map_with_span(|rule, span| (rule, span));
+
+    let rules = rule
+        .repeated()
+        .validate(|rules, _span, emit| {
+            let last = rules.len().saturating_sub(1);
+            let iter = rules.into_iter().enumerate();
+            vecmap(iter, |(i, ((pattern, (branch, separated)), span))| {
+                if !separated && i != last {
+                    emit(ParserError::with_reason(ParserErrorReason::MissingMatchArmComma, span));
+                }
+                (pattern, branch)
+            })
+        })
+        .delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
+
+    keyword(Keyword::Match).ignore_then(expr_no_constructors).then(rules).map(
+        |(expression, rules)| {
+            ExpressionKind::Match(Box::new(MatchExpression { expression, rules }))
+        },
+    )
+}
+
 fn for_loop<'a, P, S>(expr_no_constructors: P, statement: S) -> impl NoirParser<StatementKind> + 'a
 where
     P: ExprParser + 'a,
@@ -970,6 +1084,29 @@ where
         })
 }
 
+fn while_loop<'a, P, S>(
+    expr_no_constructors: P,
+    statement: S,
+) -> impl NoirParser<StatementKind> + 'a
+where
+    P: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::While)
+        .ignore_then(expr_no_constructors)
+        .then(block_expr(statement))
+        .map_with_span(|(condition, block), span| {
+            StatementKind::While(WhileStatement { condition, block, span })
+        })
+}
+
+fn loop_statement<'a, S>(statement: S) -> impl NoirParser<StatementKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::Loop).ignore_then(block_expr(statement)).map(StatementKind::Loop)
+}
+
 /// The 'range' of a for loop. Either an actual range `start .. end` or an array expression.
 fn for_range<P>(expr_no_constructors: P) -> impl NoirParser<ForRange>
 where
@@ -1064,7 +1201,8 @@ where
     S: NoirParser<StatementKind> + 'a,
 {
     choice((
-        if_expr(expr_no_constructors, statement.clone()),
+        if_expr(expr_no_constructors.clone(), statement.clone()),
+        match_expr(expr_parser.clone(), expr_no_constructors, statement.clone()),
         slice_expr(expr_parser.clone()),
         array_expr(expr_parser.clone()),
         if allow_constructors {
@@ -1353,6 +1491,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_block_with_incomplete_last_statement() {
+        let (block, errors) = parse_recover(block(fresh_statement()), "{ let x = 1; x. }");
+        assert!(!errors.is_empty());
+
+        // Only the part which couldn't be parsed is lost, not the whole block
+        let statements = block.expect("Expected the block to be recovered").statements;
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(statements[0].kind, StatementKind::Let(_)));
+        assert!(matches!(statements[1].kind, StatementKind::Expression(_)));
+        assert!(matches!(statements[2].kind, StatementKind::Error));
+    }
+
     /// Extract an Statement::Expression from a statement or panic
     fn unwrap_expr(stmt: &StatementKind) -> &ExpressionKind {
         match stmt {
@@ -1380,6 +1531,28 @@ mod test {
         parse_all_failing(fresh_statement(), vec!["pub x = y", "pub x : pub Field = y"]);
     }
 
+    #[test]
+    fn parse_while_loop() {
+        parse_all(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec!["while x < y {}", "while true { foo; break; }", "while (a == b) { continue; }"],
+        );
+
+        parse_all_failing(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec![
+                "while x < y",    // The body must be a block
+                "while { foo; }", // A condition is required
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_loop() {
+        parse_all(loop_statement(fresh_statement()), vec!["loop {}", "loop { foo; break; }"]);
+        parse_all_failing(loop_statement(fresh_statement()), vec!["loop", "loop x"]);
+    }
+
     #[test]
     fn parse_for_loop() {
         parse_all(
@@ -1427,6 +1600,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_match_expr() {
+        parse_all(
+            match_expr(expression(), expression_no_constructors(expression()), fresh_statement()),
+            vec![
+                "match x {}",
+                "match x { 0 => a, 1 => b, _ => c }",
+                "match x { -1 => a, y => y, }",
+                "match (a, b) { (true, y) => { y } (false, _) => 0 }",
+                "match point { Point { x: 0, y } => y, Point { x, y: _ } => x }",
+                "match shape { Shape::Circle(r) => r, Shape::Square(_, (0, s)) => s }",
+                "match color { Color::Red => 1, crate::Color::Green => 2, _ => 3 }",
+            ],
+        );
+
+        parse_all_failing(
+            match_expr(expression(), expression_no_constructors(expression()), fresh_statement()),
+            vec![
+                "match x { 0 => a 1 => b }", // Arms that aren't blocks need a comma after them
+                "match x { \"a\" => b }",    // Only integer and boolean literals are patterns
+                "match x { 0 -> a }",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_refutable_pattern_outside_match() {
+        parse_all_failing(
+            declaration(expression()),
+            vec!["let 1 = x", "let (a, true) = x", "let Option::Some(y) = x", "let Color::Red = x"],
+        );
+    }
+
     #[test]
     fn parse_module_declaration() {
         parse_with(module_declaration(), "mod foo").unwrap();
@@ -0,0 +1,73 @@
+use chumsky::prelude::*;
+
+use crate::ast::{Ident, NoirEnumeration, UnresolvedType};
+use crate::{
+    parser::{
+        parser::{
+            function, parse_type,
+            primitives::{ident, keyword},
+        },
+        NoirParser, TopLevelStatement,
+    },
+    token::{Keyword, Token},
+};
+
+/// enum_definition: 'enum' ident generics '{' enum_variants '}'
+pub(super) fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Enum;
+    use Token::*;
+
+    let variants = enum_variants().delimited_by(just(LeftBrace), just(RightBrace)).recover_with(
+        nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ),
+    );
+
+    keyword(Enum).ignore_then(ident()).then(function::generics()).then(variants).map_with_span(
+        |((name, generics), variants), span| {
+            TopLevelStatement::Enum(NoirEnumeration { name, generics, variants, span })
+        },
+    )
+}
+
+/// enum_variants: ( ident ( '(' type, ... ')' )? ),*
+fn enum_variants() -> impl NoirParser<Vec<(Ident, Vec<UnresolvedType>)>> {
+    let parameters = parse_type()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+    ident()
+        .then(parameters.or_not().map(Option::unwrap_or_default))
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::parser::test_helpers::*;
+
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo { }",
+            "enum Color { Red, Green, Blue }",
+            "enum Shape { Circle(Field), Rectangle(Field, Field), }",
+            "enum Option<T> { Some(T), None }",
+            "enum Foo { Bar(u8, [Field; 2]), Baz }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing = vec![
+            "enum {  }",
+            "enum Foo;",
+            "enum Foo { Bar: Field }",
+            "enum Foo { Bar(pub Field) }",
+        ];
+        parse_all_failing(enum_definition(), failing);
+    }
+}
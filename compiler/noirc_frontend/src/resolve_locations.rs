@@ -1,10 +1,14 @@
+use fm::FileId;
+use iter_extended::vecmap;
 use noirc_arena::Index;
-use noirc_errors::Location;
+use noirc_errors::{Location, Span};
 
 use crate::hir_def::expr::HirExpression;
 use crate::hir_def::types::Type;
 
-use crate::node_interner::{DefinitionKind, Node, NodeInterner};
+use crate::node_interner::{
+    DefinitionId, DefinitionInfo, DefinitionKind, FuncId, Node, NodeInterner, ReferenceId,
+};
 
 impl NodeInterner {
     /// Scans the interner for the item which is located at that [Location]
@@ -46,6 +50,138 @@ impl NodeInterner {
             .or_else(|| self.try_resolve_type_alias(location))
     }
 
+    /// Returns the item referred to by whatever is found at the given [Location], which can
+    /// either be a use of that item (a variable, a call, a member access, a type...) or the
+    /// place where the item is defined.
+    pub fn find_referenced(&self, location: Location) -> Option<ReferenceId> {
+        self.find_reference_at(location)
+            .map(|(referenced, _)| referenced)
+            .or_else(|| {
+                self.find_location_index(location)
+                    .and_then(|index| self.resolve_reference(index, location))
+            })
+            .or_else(|| self.find_definition_at(location))
+            .or_else(|| self.find_type_ref_at(location))
+    }
+
+    /// Returns the recorded use of an item that contains the given [Location], if any,
+    /// together with the [Location] of that use.
+    /// If several uses overlap, the smallest one is returned.
+    pub fn find_reference_at(&self, location: Location) -> Option<(ReferenceId, Location)> {
+        let mut candidate: Option<&(ReferenceId, Location)> = None;
+
+        for reference in &self.references {
+            if reference.1.contains(&location) {
+                if let Some((_, current_location)) = candidate {
+                    if reference.1.span.is_smaller(&current_location.span) {
+                        candidate = Some(reference);
+                    }
+                } else {
+                    candidate = Some(reference);
+                }
+            }
+        }
+        candidate.copied()
+    }
+
+    /// Returns the [Location] of every use of the given item, plus the location where it is
+    /// declared if `include_declaration` is true. Locations are sorted and unique.
+    ///
+    /// Trait methods and the methods implementing them in trait impls are treated as the
+    /// same item, since renaming one of them requires renaming all of them.
+    pub fn find_all_references(
+        &self,
+        referenced: ReferenceId,
+        include_declaration: bool,
+    ) -> Vec<Location> {
+        let items = match referenced {
+            ReferenceId::Function(func_id) => {
+                vecmap(self.related_trait_methods(func_id), ReferenceId::Function)
+            }
+            other => vec![other],
+        };
+
+        let mut locations: Vec<Location> = self
+            .references
+            .iter()
+            .filter(|(item, _)| items.contains(item))
+            .map(|(_, location)| *location)
+            .collect();
+
+        if include_declaration {
+            locations.extend(items.iter().map(|item| self.reference_location(*item)));
+        }
+
+        locations.sort_by_key(|location| (location.file, location.span));
+        locations.dedup();
+        locations
+    }
+
+    /// If the given function is a trait method, or a method in a trait impl, returns the
+    /// trait method along with every method implementing it. Otherwise returns just `func_id`.
+    fn related_trait_methods(&self, func_id: FuncId) -> Vec<FuncId> {
+        let trait_method = self.traits.values().find_map(|trait_| {
+            trait_.method_ids.iter().find(|(_, id)| **id == func_id).map(|(name, _)| (trait_, name))
+        });
+
+        let trait_method = trait_method.or_else(|| {
+            let (_, trait_id) = self.get_function_trait(&func_id)?;
+            let trait_ = self.get_trait(trait_id);
+            let name = self.function_name(&func_id);
+            trait_.method_ids.get_key_value(name).map(|(name, _)| (trait_, name))
+        });
+
+        let Some((trait_, name)) = trait_method else {
+            return vec![func_id];
+        };
+
+        let mut methods = vec![trait_.method_ids[name]];
+        if let Some(index) =
+            trait_.methods.iter().position(|method| &method.name.0.contents == name)
+        {
+            for trait_impl in self.trait_implementations.values() {
+                let trait_impl = trait_impl.borrow();
+                if trait_impl.trait_id == trait_.id {
+                    methods.extend(trait_impl.methods.get(index).copied());
+                }
+            }
+        }
+        if !methods.contains(&func_id) {
+            methods.push(func_id);
+        }
+        methods
+    }
+
+    /// Returns the [Location] where the given [ReferenceId] is defined.
+    /// For functions, structs, traits and the like this is the location of the item's name.
+    pub fn reference_location(&self, reference: ReferenceId) -> Location {
+        match reference {
+            ReferenceId::Struct(id) => {
+                let struct_type = self.get_struct(id);
+                let struct_type = struct_type.borrow();
+                Location::new(struct_type.name.span(), struct_type.location.file)
+            }
+            ReferenceId::StructMember(id, field_index) => {
+                let struct_type = self.get_struct(id);
+                let struct_type = struct_type.borrow();
+                let (field_name, _) = &struct_type.get_fields_as_written()[field_index];
+                Location::new(field_name.span(), struct_type.location.file)
+            }
+            ReferenceId::Trait(id) => {
+                let trait_ = self.get_trait(id);
+                Location::new(trait_.name.span(), trait_.location.file)
+            }
+            ReferenceId::Global(id) => self.get_global(id).location,
+            ReferenceId::Function(id) => self.function_meta(&id).name.location,
+            ReferenceId::Alias(id) => {
+                let alias = self.get_type_alias(id);
+                let alias = alias.borrow();
+                Location::new(alias.name.span(), alias.location.file)
+            }
+            ReferenceId::Local(id) => self.definition(id).location,
+        }
+    }
+
     pub fn get_declaration_location_from(&self, location: Location) -> Option<Location> {
         self.try_resolve_trait_method_declaration(location).or_else(|| {
             self.find_location_index(location)
@@ -81,6 +217,225 @@ impl NodeInterner {
         }
     }
 
+    /// Resolves the item referred to by the node at the given [Index].
+    /// `location` is used to tell apart the different parts of a node, such as the field
+    /// names of a constructor expression.
+    fn resolve_reference(
+        &self,
+        index: impl Into<Index>,
+        location: Location,
+    ) -> Option<ReferenceId> {
+        let node = self.nodes.get(index.into())?;
+
+        let Node::Expression(expression) = node else {
+            return None;
+        };
+
+        match expression {
+            HirExpression::Ident(ident, _) => match self.definition(ident.id).kind {
+                DefinitionKind::Function(func_id) => Some(ReferenceId::Function(func_id)),
+                DefinitionKind::Global(global_id) => Some(ReferenceId::Global(global_id)),
+                DefinitionKind::Local(_) => Some(ReferenceId::Local(ident.id)),
+                DefinitionKind::GenericType(_) => None,
+            },
+            HirExpression::Constructor(expr) => {
+                let struct_type = expr.r#type.borrow();
+                let field =
+                    expr.fields.iter().find(|(name, _)| name.span().contains(&location.span));
+                match field {
+                    Some((name, _)) => struct_type
+                        .get_fields_as_written()
+                        .iter()
+                        .position(|(field_name, _)| field_name.0.contents == name.0.contents)
+                        .map(|field_index| ReferenceId::StructMember(struct_type.id, field_index)),
+                    None => Some(ReferenceId::Struct(struct_type.id)),
+                }
+            }
+            HirExpression::MemberAccess(member_access) => {
+                let lhs_type = match self.id_type(member_access.lhs).follow_bindings() {
+                    Type::MutableReference(element) => *element,
+                    other => other,
+                };
+                let Type::Struct(struct_type, _) = lhs_type else {
+                    return None;
+                };
+                let struct_type = struct_type.borrow();
+                struct_type
+                    .get_fields_as_written()
+                    .iter()
+                    .position(|(field_name, _)| {
+                        field_name.0.contents == member_access.rhs.0.contents
+                    })
+                    .map(|field_index| ReferenceId::StructMember(struct_type.id, field_index))
+            }
+            HirExpression::Call(call) => self.resolve_reference(call.func, location),
+            _ => None,
+        }
+    }
+
+    /// Returns the type of the outermost expression ending right where the given [Location]
+    /// ends, if there is one. E.g. for the location of `bar` in `foo.bar` this is the type of
+    /// the whole `foo.bar` expression.
+    pub fn type_of_expression_ending_at(&self, location: Location) -> Option<Type> {
+        let mut candidate: Option<(&Index, &Location)> = None;
+
+        for (index, interned_location) in self.id_to_location.iter() {
+            if interned_location.file != location.file
+                || interned_location.span.end() != location.span.end()
+                || !matches!(self.nodes.get(*index), Some(Node::Expression(_)))
+            {
+                continue;
+            }
+
+            match candidate {
+                Some((_, current_location))
+                    if current_location.span.start() <= interned_location.span.start() => {}
+                _ => candidate = Some((index, interned_location)),
+            }
+        }
+
+        candidate.map(|(index, _)| self.id_type(*index)).filter(|typ| !matches!(typ, Type::Error))
+    }
+
+    /// Returns the local variables declared in the given file within the given [Span],
+    /// in the order they were declared.
+    pub fn local_definitions_in(
+        &self,
+        file: FileId,
+        span: Span,
+    ) -> impl Iterator<Item = (DefinitionId, &DefinitionInfo)> {
+        self.definitions.iter().enumerate().filter_map(move |(index, definition)| {
+            let is_local = matches!(definition.kind, DefinitionKind::Local(_));
+            let location = definition.location;
+            (is_local && location.file == file && span.contains(&location.span))
+                .then_some((DefinitionId(index), definition))
+        })
+    }
+
+    /// Returns every use of an item in the given file, including types written in signatures.
+    /// Uses may overlap, e.g. a type annotation `Foo<Bar>` contains a use of `Bar`.
+    pub fn references_in_file(&self, file: FileId) -> Vec<(ReferenceId, Location)> {
+        let struct_refs = self.type_ref_locations.iter().filter_map(|(typ, location)| match typ {
+            Type::Struct(struct_type, _) => {
+                Some((ReferenceId::Struct(struct_type.borrow().id), *location))
+            }
+            _ => None,
+        });
+        let alias_refs = self
+            .type_alias_ref
+            .iter()
+            .map(|(type_alias_id, location)| (ReferenceId::Alias(*type_alias_id), *location));
+
+        self.references
+            .iter()
+            .copied()
+            .chain(struct_refs)
+            .chain(alias_refs)
+            .filter(|(_, location)| location.file == file)
+            .collect()
+    }
+
+    /// Returns every item declared in the given file, along with the [Location] of its name.
+    pub fn declarations_in_file(&self, file: FileId) -> Vec<(ReferenceId, Location)> {
+        let mut declarations: Vec<ReferenceId> = Vec::new();
+
+        declarations.extend(self.func_meta.keys().copied().map(ReferenceId::Function));
+        for struct_type in self.structs.values() {
+            let struct_type = struct_type.borrow();
+            declarations.push(ReferenceId::Struct(struct_type.id));
+            let field_count = struct_type.get_fields_as_written().len();
+            declarations.extend(
+                (0..field_count).map(|index| ReferenceId::StructMember(struct_type.id, index)),
+            );
+        }
+        declarations.extend(self.traits.keys().copied().map(ReferenceId::Trait));
+        declarations
+            .extend(self.get_all_globals().iter().map(|global| ReferenceId::Global(global.id)));
+        declarations
+            .extend(self.type_aliases.iter().map(|alias| ReferenceId::Alias(alias.borrow().id)));
+        declarations.extend(
+            self.local_definitions_in(file, Span::from(0..u32::MAX))
+                .map(|(definition_id, _)| ReferenceId::Local(definition_id)),
+        );
+
+        declarations
+            .into_iter()
+            .map(|declaration| (declaration, self.reference_location(declaration)))
+            .filter(|(_, location)| location.file == file)
+            .collect()
+    }
+
+    /// Finds the item whose name is declared at the given [Location].
+    pub fn find_definition_at(&self, location: Location) -> Option<ReferenceId> {
+        let contains =
+            |span: noirc_errors::Span, file| file == location.file && span.contains(&location.span);
+
+        for (func_id, func_meta) in &self.func_meta {
+            if func_meta.name.location.contains(&location) {
+                return Some(ReferenceId::Function(*func_id));
+            }
+        }
+
+        for struct_type in self.structs.values() {
+            let struct_type = struct_type.borrow();
+            let file = struct_type.location.file;
+            if contains(struct_type.name.span(), file) {
+                return Some(ReferenceId::Struct(struct_type.id));
+            }
+
+            let fields = struct_type.get_fields_as_written();
+            if let Some(field_index) =
+                fields.iter().position(|(field_name, _)| contains(field_name.span(), file))
+            {
+                return Some(ReferenceId::StructMember(struct_type.id, field_index));
+            }
+        }
+
+        for trait_ in self.traits.values() {
+            if contains(trait_.name.span(), trait_.location.file) {
+                return Some(ReferenceId::Trait(trait_.id));
+            }
+        }
+
+        for global in self.get_all_globals() {
+            if global.location.contains(&location) {
+                return Some(ReferenceId::Global(global.id));
+            }
+        }
+
+        for alias in &self.type_aliases {
+            let alias = alias.borrow();
+            if contains(alias.name.span(), alias.location.file) {
+                return Some(ReferenceId::Alias(alias.id));
+            }
+        }
+
+        self.definitions
+            .iter()
+            .position(|definition| {
+                matches!(definition.kind, DefinitionKind::Local(_))
+                    && definition.location.contains(&location)
+            })
+            .map(|index| ReferenceId::Local(DefinitionId(index)))
+    }
+
+    /// Finds the struct or type alias named by a type written at the given [Location].
+    fn find_type_ref_at(&self, location: Location) -> Option<ReferenceId> {
+        self.type_ref_locations
+            .iter()
+            .find(|(_typ, type_ref_location)| type_ref_location.contains(&location))
+            .and_then(|(typ, _)| match typ {
+                Type::Struct(struct_type, _) => Some(ReferenceId::Struct(struct_type.borrow().id)),
+                _ => None,
+            })
+            .or_else(|| {
+                self.type_alias_ref
+                    .iter()
+                    .find(|(_, alias_location)| alias_location.contains(&location))
+                    .map(|(type_alias_id, _)| ReferenceId::Alias(*type_alias_id))
+            })
+    }
+
     fn get_type_location_from_index(&self, index: impl Into<Index>) -> Option<Location> {
         match self.id_type(index.into()) {
             Type::Struct(struct_type, _) => Some(struct_type.borrow().location),
@@ -854,6 +854,8 @@ fn find_lambda_captures(stmts: &[StmtId], interner: &NodeInterner, result: &mut
             HirStatement::Constrain(constr_stmt) => constr_stmt.0,
             HirStatement::Semi(semi_expr) => semi_expr,
             HirStatement::For(for_loop) => for_loop.block,
+            HirStatement::While(while_loop) => while_loop.block,
+            HirStatement::Loop(block) => block,
             HirStatement::Error => panic!("Invalid HirStatement!"),
             HirStatement::Break => panic!("Unexpected break"),
             HirStatement::Continue => panic!("Unexpected continue"),
@@ -1312,6 +1314,66 @@ fn break_and_continue_outside_loop() {
     assert_eq!(get_program_errors(src).len(), 2);
 }
 
+#[test]
+fn while_and_loop_in_unconstrained_fn() {
+    let src = r#"
+        unconstrained fn main(mut x: u32) -> pub u32 {
+            while x < 10 {
+                if x == 3 {
+                    x += 2;
+                    continue;
+                }
+                x += 1;
+            }
+            loop {
+                x += 1;
+                if x > 20 {
+                    break;
+                }
+            }
+            x
+        }
+    "#;
+    assert_eq!(get_program_errors(src).len(), 0);
+}
+
+#[test]
+fn while_and_loop_in_constrained_fn() {
+    let src = r#"
+        fn main(mut x: u32) {
+            while x < 10 {
+                x += 1;
+            }
+            loop {}
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 2, "Expected two errors, got: {errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::LoopInConstrainedFn { is_while: true, .. })
+    ));
+    assert!(matches!(
+        errors[1].0,
+        CompilationError::ResolverError(ResolverError::LoopInConstrainedFn { is_while: false, .. })
+    ));
+}
+
+#[test]
+fn while_condition_must_be_bool() {
+    let src = r#"
+        unconstrained fn main(x: u32) {
+            while x {}
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+    ));
+}
+
 // Regression for #2540
 #[test]
 fn for_loop_over_array() {
@@ -1444,3 +1506,315 @@ fn specify_method_types_with_turbofish() {
     let errors = get_program_errors(src);
     assert_eq!(errors.len(), 0);
 }
+
+#[test]
+fn match_on_integers_tuples_and_structs() {
+    let src = r#"
+        struct Point {
+            x: Field,
+            y: Field,
+        }
+
+        fn main(x: u8, flags: (bool, bool), point: Point) -> pub Field {
+            let a = match x {
+                0 => 10,
+                1 => 20,
+                other => other as Field,
+            };
+            let b = match flags {
+                (true, true) => 1,
+                (true, false) => 2,
+                (false, _) => 3,
+            };
+            let c = match point {
+                Point { x: 0, y } => y,
+                Point { x, y: _ } => x,
+            };
+            a + b + c
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+}
+
+#[test]
+fn match_arms_must_have_the_same_type() {
+    let src = r#"
+        fn main(x: Field) -> pub Field {
+            match x {
+                0 => 1,
+                _ => true,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    assert!(matches!(
+        &errors[0].0,
+        CompilationError::TypeError(TypeCheckError::Context { err, .. })
+            if matches!(err.as_ref(), TypeCheckError::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn non_exhaustive_match_on_integers() {
+    let src = r#"
+        fn main(x: u8) -> pub Field {
+            match x {
+                0 => 1,
+                1 => 2,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    let CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0);
+    };
+    assert_eq!(missing, "2");
+}
+
+#[test]
+fn non_exhaustive_match_reports_missing_pattern() {
+    let src = r#"
+        struct Flags {
+            a: bool,
+            b: bool,
+        }
+
+        fn main(flags: Flags) -> pub Field {
+            match flags {
+                Flags { a: true, b: _ } => 1,
+                Flags { a: false, b: true } => 2,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    let CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0);
+    };
+    assert_eq!(missing, "Flags { a: false, b: false }");
+}
+
+#[test]
+fn match_listing_every_value_of_a_small_integer_is_exhaustive() {
+    let src = r#"
+        fn main(x: u1) -> pub Field {
+            match x {
+                0 => 1,
+                1 => 2,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+}
+
+#[test]
+fn out_of_range_literals_do_not_make_a_match_exhaustive() {
+    let src = r#"
+        fn main(x: u1) -> pub Field {
+            match x {
+                0 => 1,
+                2 => 2,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    let CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0);
+    };
+    assert_eq!(missing, "1");
+}
+
+#[test]
+fn unreachable_match_arm() {
+    let src = r#"
+        fn main(x: (bool, Field)) -> pub Field {
+            match x {
+                (true, y) => y,
+                (_, 0) => 1,
+                (true, 1) => 2,
+                _ => 3,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one warning, got: {errors:?}");
+    let CompilationError::TypeError(TypeCheckError::UnreachableMatchArm { span }) = &errors[0].0
+    else {
+        panic!("Expected an unreachable match arm warning, got {:?}", errors[0].0);
+    };
+    assert_eq!(&src[span.start() as usize..span.end() as usize], "(true, 1)");
+}
+
+#[test]
+fn match_lowers_to_if_chain() {
+    let src = r#"
+    fn main(x: Field) -> pub Field {
+        match x {
+            0 => 1,
+            y => y,
+        }
+    }
+    "#;
+    let expected_rewrite = r#"fn main$f0(x$l0: Field) -> Field {
+    {
+        let match_value$1 = x$l0;
+        if (match_value$l1 == 0) {
+            1
+        } else {
+            let y$2 = match_value$l1;
+            y$l2
+        }
+    }
+}
+"#;
+    check_rewrite(src, expected_rewrite);
+}
+
+#[test]
+fn construct_and_match_on_enums() {
+    let src = r#"
+        enum Shape {
+            Circle(Field),
+            Rectangle(Field, Field),
+            Empty,
+        }
+
+        enum Maybe<T> {
+            Just(T),
+            Nothing,
+        }
+
+        fn area(shape: Shape) -> Field {
+            match shape {
+                Shape::Circle(radius) => 3 * radius * radius,
+                Shape::Rectangle(width, height) => width * height,
+                Shape::Empty => 0,
+            }
+        }
+
+        fn main(x: Field) -> pub Field {
+            let maybe: Maybe<Field> = Maybe::Just(x);
+            let value = match maybe {
+                Maybe::Just(value) => value,
+                Maybe::Nothing => 0,
+            };
+            area(Shape::Rectangle(value, 2)) + area(Shape::Empty)
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+}
+
+#[test]
+fn enum_constructor_with_wrong_number_of_arguments() {
+    let src = r#"
+        enum Shape {
+            Rectangle(Field, Field),
+        }
+
+        fn main() {
+            let _ = Shape::Rectangle(1);
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::ArityMisMatch { expected: 2, found: 1, .. })
+    ));
+}
+
+#[test]
+fn non_exhaustive_match_on_enum_reports_missing_variant() {
+    let src = r#"
+        enum Shape {
+            Circle(Field),
+            Rectangle(Field, Field),
+        }
+
+        fn main(x: Field) -> pub Field {
+            match Shape::Circle(x) {
+                Shape::Circle(radius) => radius,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    let CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0);
+    };
+    assert_eq!(missing, "Shape::Rectangle(_, _)");
+}
+
+#[test]
+fn duplicate_enum_variant() {
+    let src = r#"
+        enum Foo {
+            Bar,
+            Bar(Field),
+        }
+
+        fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::DefinitionError(DefCollectorErrorKind::Duplicate {
+            typ: DuplicateType::EnumVariant,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn self_referential_enum() {
+    let src = r#"
+        enum List {
+            Cons(Field, List),
+            Nil,
+        }
+
+        fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::SelfReferentialEnum { .. })
+    ));
+}
+
+#[test]
+fn pattern_must_name_an_enum_variant() {
+    let src = r#"
+        enum Shape {
+            Circle(Field),
+        }
+
+        fn main(x: Field) -> pub Field {
+            match Shape::Circle(x) {
+                Shape::Square(side) => side,
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "Expected one error, got: {errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::ExpectedEnumVariant { .. })
+    ));
+}
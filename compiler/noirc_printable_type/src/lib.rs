@@ -33,6 +33,12 @@ pub enum PrintableType {
         name: String,
         fields: Vec<(String, PrintableType)>,
     },
+    /// An enum is laid out as its tag followed by the values of every variant,
+    /// of which only those of the variant selected by the tag are meaningful.
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<PrintableType>)>,
+    },
     String {
         length: u32,
     },
@@ -56,6 +62,7 @@ pub enum PrintableValue<F> {
     String(String),
     Vec { array_elements: Vec<PrintableValue<F>>, is_slice: bool },
     Struct(BTreeMap<String, PrintableValue<F>>),
+    Variant { index: usize, values: Vec<PrintableValue<F>> },
     Other,
 }
 
@@ -227,6 +234,21 @@ fn to_string<F: AcirField>(value: &PrintableValue<F>, typ: &PrintableType) -> Op
             output.push_str(" }");
         }
 
+        (PrintableValue::Variant { index, values }, PrintableType::Enum { name, variants }) => {
+            let Some((variant_name, types)) = variants.get(*index) else {
+                output.push_str(&format!("{name}::<invalid variant>"));
+                return Some(output);
+            };
+            output.push_str(&format!("{name}::{variant_name}"));
+
+            if !values.is_empty() {
+                let values = values.iter().zip(types).map(|(value, typ)| {
+                    PrintableValueDisplay::Plain(value.clone(), typ.clone()).to_string()
+                });
+                output.push_str(&format!("({})", values.collect::<Vec<_>>().join(", ")));
+            }
+        }
+
         (PrintableValue::Vec { array_elements, .. }, PrintableType::Tuple { types }) => {
             output.push('(');
             let mut elems = array_elements.iter().zip(types).peekable();
@@ -359,6 +381,23 @@ pub fn decode_value<F: AcirField>(
 
             PrintableValue::Struct(struct_map)
         }
+        PrintableType::Enum { variants, .. } => {
+            let tag = field_iterator.next().unwrap();
+
+            // Every variant's values are present so they all have to be decoded
+            let mut values = vecmap(variants, |(_, types)| {
+                vecmap(types, |typ| decode_value(field_iterator, typ))
+            });
+
+            // Unconstrained code can produce any tag, so one which isn't a variant is kept
+            // out of range to be printed as invalid rather than panicking here.
+            match tag.try_to_u64().and_then(|tag| usize::try_from(tag).ok()) {
+                Some(index) if index < values.len() => {
+                    PrintableValue::Variant { index, values: values.swap_remove(index) }
+                }
+                _ => PrintableValue::Variant { index: variants.len(), values: Vec::new() },
+            }
+        }
         PrintableType::Function { env, .. } => {
             let field_element = field_iterator.next().unwrap();
             let func_ref = PrintableValue::Field(field_element);
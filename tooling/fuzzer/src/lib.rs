@@ -3,45 +3,145 @@
 //!
 //! Code is used under the MIT license.
 
-use acvm::{blackbox_solver::StubbedBlackBoxSolver, FieldElement};
+use std::{
+    cell::{Cell, RefCell},
+    time::Instant,
+};
+
+use acvm::{acir::native_types::WitnessStack, BlackBoxFunctionSolver, FieldElement};
 use noirc_abi::InputMap;
 use proptest::test_runner::{TestCaseError, TestError, TestRunner};
 
+mod persistence;
+mod printer;
 mod strategies;
 mod types;
 
+pub use persistence::FileRegressionPersistence;
+pub use printer::format_arguments;
+
 use types::{CaseOutcome, CounterExampleOutcome, FuzzOutcome, FuzzTestResult};
 
 use noirc_artifacts::program::ProgramArtifact;
 
-use nargo::ops::{execute_program, DefaultForeignCallExecutor};
+use nargo::{
+    errors::ExecutionLimit,
+    ops::{
+        execute_program_with_coverage, execute_program_with_limits, DefaultForeignCallExecutor,
+        ExecutionLimits, OpcodeCoverage,
+    },
+    NargoError,
+};
+
+/// The result of executing a program with a single input
+pub type ExecutionResult = Result<WitnessStack<FieldElement>, NargoError<FieldElement>>;
 
 /// An executor for Noir programs which which provides fuzzing support using [`proptest`].
 ///
 /// After instantiation, calling `fuzz` will proceed to hammer the program with
 /// inputs, until it finds a counterexample. The provided [`TestRunner`] contains all the
 /// configuration which can be overridden via [environment variables](proptest::test_runner::Config)
-pub struct FuzzedExecutor {
+pub struct FuzzedExecutor<B> {
     /// The program to be fuzzed
     program: ProgramArtifact,
 
     /// The fuzzer
     runner: TestRunner,
+
+    /// The solver for the black box functions the program calls
+    blackbox_solver: B,
+
+    /// Whether the output of the program's `print` calls is shown
+    show_output: bool,
+
+    /// The JSON-RPC server resolving the program's other foreign calls, if any
+    foreign_call_resolver_url: Option<String>,
+
+    /// The timeout applies to the fuzzing run as a whole, the step limit to each input separately
+    limits: ExecutionLimits,
+
+    /// Whether to record which opcodes are executed across all inputs
+    collect_coverage: bool,
 }
 
-impl FuzzedExecutor {
+impl<B: BlackBoxFunctionSolver<FieldElement>> FuzzedExecutor<B> {
     /// Instantiates a fuzzed executor given a testrunner
-    pub fn new(program: ProgramArtifact, runner: TestRunner) -> Self {
-        Self { program, runner }
+    pub fn new(program: ProgramArtifact, runner: TestRunner, blackbox_solver: B) -> Self {
+        Self {
+            program,
+            runner,
+            blackbox_solver,
+            show_output: false,
+            foreign_call_resolver_url: None,
+            limits: ExecutionLimits::default(),
+            collect_coverage: false,
+        }
+    }
+
+    /// Shows the output of the program's `print` calls if `show_output` is true, and resolves its
+    /// other foreign calls with the JSON-RPC server at `foreign_call_resolver_url`, if given.
+    pub fn with_foreign_calls(
+        mut self,
+        show_output: bool,
+        foreign_call_resolver_url: Option<&str>,
+    ) -> Self {
+        self.show_output = show_output;
+        self.foreign_call_resolver_url = foreign_call_resolver_url.map(str::to_string);
+        self
+    }
+
+    /// Stops fuzzing once the run takes longer than `limits.timeout`, or an input makes the program
+    /// execute more than `limits.max_steps` steps.
+    pub fn with_execution_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Records how many times each opcode is executed over every input tried, including while
+    /// shrinking a counterexample.
+    pub fn with_coverage(mut self) -> Self {
+        self.collect_coverage = true;
+        self
     }
 
     /// Fuzzes the provided program.
-    pub fn fuzz(&self) -> FuzzTestResult {
+    ///
+    /// `check_result` decides whether executing the program with an input passes the test,
+    /// returning the reason why it doesn't otherwise.
+    pub fn fuzz(
+        &self,
+        check_result: impl Fn(ExecutionResult) -> Result<(), String>,
+    ) -> FuzzTestResult {
         let strategy = strategies::arb_input_map(&self.program.abi);
 
+        let deadline = self.limits.timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        // Once a limit has been exceeded there's nothing to be learned from shrinking the input
+        // which exceeded it, so every later case is passed without running it.
+        let exceeded_limit = Cell::new(None);
+        let coverage = RefCell::new(self.collect_coverage.then(OpcodeCoverage::default));
+
         let run_result: Result<(), TestError<InputMap>> =
             self.runner.clone().run(&strategy, |input_map| {
-                let fuzz_res = self.single_fuzz(input_map)?;
+                if exceeded_limit.get().is_some() {
+                    return Ok(());
+                }
+                let timed_out = ExecutionLimit::Timeout(self.limits.timeout.unwrap_or_default());
+                let remaining_time = match deadline {
+                    Some(deadline) if Instant::now() >= deadline => {
+                        exceeded_limit.set(Some(timed_out));
+                        return Err(TestCaseError::fail(timed_out.to_string()));
+                    }
+                    Some(deadline) => Some(deadline - Instant::now()),
+                    None => None,
+                };
+                let limits = ExecutionLimits { timeout: remaining_time, ..self.limits };
+
+                let fuzz_res = self.single_fuzz(
+                    input_map,
+                    limits,
+                    coverage.borrow_mut().as_mut(),
+                    &check_result,
+                )?;
 
                 match fuzz_res {
                     FuzzOutcome::Case(_) => Ok(()),
@@ -49,43 +149,92 @@ impl FuzzedExecutor {
                         exit_reason: status,
                         ..
                     }) => Err(TestCaseError::fail(status)),
+                    FuzzOutcome::LimitExceeded(limit) => {
+                        // Report the timeout of the whole run rather than the time which was left for this input.
+                        let limit = match limit {
+                            ExecutionLimit::Timeout(_) => timed_out,
+                            ExecutionLimit::Steps(_) => limit,
+                        };
+                        exceeded_limit.set(Some(limit));
+                        Err(TestCaseError::fail(limit.to_string()))
+                    }
                 }
             });
 
+        let coverage = coverage.into_inner();
         match run_result {
-            Ok(()) => FuzzTestResult { success: true, reason: None, counterexample: None },
+            Ok(()) => FuzzTestResult {
+                success: true,
+                reason: None,
+                counterexample: None,
+                exceeded_limit: None,
+                coverage,
+            },
 
             Err(TestError::Abort(reason)) => FuzzTestResult {
                 success: false,
                 reason: Some(reason.to_string()),
                 counterexample: None,
+                exceeded_limit: None,
+                coverage,
             },
             Err(TestError::Fail(reason, counterexample)) => {
                 let reason = reason.to_string();
                 let reason = if reason.is_empty() { None } else { Some(reason) };
 
-                FuzzTestResult { success: false, reason, counterexample: Some(counterexample) }
+                FuzzTestResult {
+                    success: false,
+                    reason,
+                    counterexample: Some(counterexample),
+                    exceeded_limit: exceeded_limit.get(),
+                    coverage,
+                }
             }
         }
     }
 
-    /// Granular and single-step function that runs only one fuzz and returns either a `CaseOutcome`
-    /// or a `CounterExampleOutcome`
-    pub fn single_fuzz(&self, input_map: InputMap) -> Result<FuzzOutcome, TestCaseError> {
+    /// Granular and single-step function that runs only one fuzz and returns either a `CaseOutcome`,
+    /// a `CounterExampleOutcome` or the execution limit which the input exceeded.
+    /// The opcodes executed are added to `coverage`, if given.
+    pub fn single_fuzz(
+        &self,
+        input_map: InputMap,
+        limits: ExecutionLimits,
+        coverage: Option<&mut OpcodeCoverage>,
+        check_result: impl Fn(ExecutionResult) -> Result<(), String>,
+    ) -> Result<FuzzOutcome, TestCaseError> {
         let initial_witness = self.program.abi.encode(&input_map, None).unwrap();
-        let result = execute_program(
-            &self.program.bytecode,
-            initial_witness,
-            &StubbedBlackBoxSolver,
-            &mut DefaultForeignCallExecutor::<FieldElement>::new(false, None),
+        let mut foreign_call_executor = DefaultForeignCallExecutor::<FieldElement>::new(
+            self.show_output,
+            self.foreign_call_resolver_url.as_deref(),
         );
+        let result = match coverage {
+            Some(coverage) => execute_program_with_coverage(
+                &self.program.bytecode,
+                initial_witness,
+                &self.blackbox_solver,
+                &mut foreign_call_executor,
+                limits,
+                coverage,
+            ),
+            None => execute_program_with_limits(
+                &self.program.bytecode,
+                initial_witness,
+                &self.blackbox_solver,
+                &mut foreign_call_executor,
+                limits,
+            ),
+        };
 
         // TODO: Add handling for `vm.assume` equivalent
 
-        match result {
-            Ok(_) => Ok(FuzzOutcome::Case(CaseOutcome { case: input_map })),
-            Err(err) => Ok(FuzzOutcome::CounterExample(CounterExampleOutcome {
-                exit_reason: err.to_string(),
+        if let Err(NargoError::ExecutionLimitExceeded(limit)) = result {
+            return Ok(FuzzOutcome::LimitExceeded(limit));
+        }
+        match check_result(result) {
+            Ok(()) => Ok(FuzzOutcome::Case(CaseOutcome { case: input_map })),
+            Err(reason) => Ok(FuzzOutcome::CounterExample(CounterExampleOutcome {
+                exit_reason: reason,
                 counterexample: input_map,
             })),
         }
@@ -0,0 +1,71 @@
+use std::{
+    any::Any,
+    fmt::Debug,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use proptest::test_runner::{FailurePersistence, PersistedSeed};
+
+/// Stores the seeds of failing fuzz runs in a file so that the [`TestRunner`][proptest::test_runner::TestRunner]
+/// replays them before generating any new inputs.
+///
+/// The file uses the same format as proptest's own regression files: one `cc <seed>` line per failure
+/// followed by a comment containing the shrunk inputs. Lines starting with `#` are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileRegressionPersistence {
+    path: PathBuf,
+}
+
+impl FileRegressionPersistence {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl FailurePersistence for FileRegressionPersistence {
+    fn load_persisted_failures2(&self, _source_file: Option<&'static str>) -> Vec<PersistedSeed> {
+        let Ok(contents) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+
+        contents
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| line.parse().ok())
+            .collect()
+    }
+
+    fn save_persisted_failure2(
+        &mut self,
+        _source_file: Option<&'static str>,
+        seed: PersistedSeed,
+        shrunken_value: &dyn Debug,
+    ) {
+        if self.load_persisted_failures2(None).iter().any(|persisted| persisted == &seed) {
+            return;
+        }
+
+        if let Some(parent) = self.path.parent() {
+            // Failing to persist a seed shouldn't fail the test run, we'll just be unable to replay it.
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+            let _ = writeln!(file, "{seed} # shrinks to {shrunken_value:?}");
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn FailurePersistence> {
+        Box::new(self.clone())
+    }
+
+    fn eq(&self, other: &dyn FailurePersistence) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|other| self == other)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,172 @@
+use acvm::{AcirField, FieldElement};
+use iter_extended::vecmap;
+use noirc_abi::{input_parser::InputValue, Abi, AbiType, InputMap, Sign};
+
+/// Renders the arguments of a fuzzed function call as Noir source, e.g. `1, [2, 3], Foo { x: true }`,
+/// so that a counterexample can be pasted straight into a regular `#[test]`.
+pub fn format_arguments(abi: &Abi, input_map: &InputMap) -> String {
+    let arguments = vecmap(&abi.parameters, |parameter| match input_map.get(&parameter.name) {
+        Some(value) => format_value(value, &parameter.typ),
+        None => "_".to_string(),
+    });
+    arguments.join(", ")
+}
+
+fn format_value(value: &InputValue, typ: &AbiType) -> String {
+    match (value, typ) {
+        (InputValue::Field(field), AbiType::Field) => format_field(*field),
+        (InputValue::Field(field), AbiType::Integer { sign, width }) => {
+            format_integer(*field, *sign, *width)
+        }
+        (InputValue::Field(field), AbiType::Boolean) => field.is_one().to_string(),
+        (InputValue::String(string), AbiType::String { .. }) => format!("{string:?}"),
+        (InputValue::Vec(elements), AbiType::Array { typ, .. }) => {
+            let elements = vecmap(elements, |element| format_value(element, typ));
+            format!("[{}]", elements.join(", "))
+        }
+        (InputValue::Vec(elements), AbiType::Tuple { fields }) => {
+            let elements =
+                vecmap(elements.iter().zip(fields), |(element, typ)| format_value(element, typ));
+            if elements.len() == 1 {
+                format!("({},)", elements[0])
+            } else {
+                format!("({})", elements.join(", "))
+            }
+        }
+        (InputValue::Struct(map), AbiType::Struct { path, fields }) => {
+            let fields = vecmap(fields, |(name, typ)| match map.get(name) {
+                Some(value) => format!("{name}: {}", format_value(value, typ)),
+                None => format!("{name}: _"),
+            });
+            format!("{} {{ {} }}", type_name(path), fields.join(", "))
+        }
+        (InputValue::Struct(map), AbiType::Enum { path, variants }) => {
+            let Some((variant, InputValue::Vec(values))) = map.iter().next() else {
+                return format!("{value:?}");
+            };
+            let fields = variants
+                .iter()
+                .find_map(|(name, fields)| (name == variant).then_some(fields.as_slice()))
+                .unwrap_or_default();
+
+            if values.is_empty() {
+                format!("{}::{variant}", type_name(path))
+            } else {
+                let values =
+                    vecmap(values.iter().zip(fields), |(value, typ)| format_value(value, typ));
+                format!("{}::{variant}({})", type_name(path), values.join(", "))
+            }
+        }
+        // The fuzzer only generates values which match the ABI, so this is just a fallback.
+        _ => format!("{value:?}"),
+    }
+}
+
+fn format_field(field: FieldElement) -> String {
+    if field.num_bits() <= 64 {
+        field.to_u128().to_string()
+    } else {
+        format!("0x{}", field.to_hex().trim_start_matches('0'))
+    }
+}
+
+fn format_integer(field: FieldElement, sign: Sign, width: u32) -> String {
+    let value = field.to_u128();
+    match sign {
+        Sign::Signed if width > 0 && width < 128 && value >= 1 << (width - 1) => {
+            format!("-{}", (1u128 << width) - value)
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Structs and enums are printed with the last segment of their path as that is how they
+/// would be named in a test living next to their definition.
+fn type_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::{AcirField, FieldElement};
+    use noirc_abi::{input_parser::InputValue, Abi, AbiParameter, AbiType, AbiVisibility, Sign};
+
+    use super::format_arguments;
+
+    fn parameter(name: &str, typ: AbiType) -> AbiParameter {
+        AbiParameter { name: name.to_string(), typ, visibility: AbiVisibility::Private }
+    }
+
+    #[test]
+    fn formats_arguments_as_noir_expressions() {
+        let point = AbiType::Struct {
+            path: "foo::Point".to_string(),
+            fields: vec![("x".to_string(), AbiType::Field), ("y".to_string(), AbiType::Boolean)],
+        };
+        let shape = AbiType::Enum {
+            path: "foo::Shape".to_string(),
+            variants: vec![
+                ("Square".to_string(), vec![AbiType::Integer { sign: Sign::Unsigned, width: 8 }]),
+                ("Empty".to_string(), vec![]),
+            ],
+        };
+        let abi = Abi {
+            parameters: vec![
+                parameter("a", AbiType::Integer { sign: Sign::Signed, width: 8 }),
+                parameter(
+                    "b",
+                    AbiType::Array {
+                        length: 2,
+                        typ: Box::new(AbiType::Integer { sign: Sign::Unsigned, width: 32 }),
+                    },
+                ),
+                parameter("c", point),
+                parameter("d", AbiType::String { length: 2 }),
+                parameter("e", shape.clone()),
+                parameter("f", shape),
+            ],
+            return_type: None,
+            error_types: BTreeMap::new(),
+        };
+
+        let input_map = BTreeMap::from([
+            ("a".to_string(), InputValue::Field(FieldElement::from(255u128))),
+            (
+                "b".to_string(),
+                InputValue::Vec(vec![
+                    InputValue::Field(FieldElement::from(1u128)),
+                    InputValue::Field(FieldElement::from(2u128)),
+                ]),
+            ),
+            (
+                "c".to_string(),
+                InputValue::Struct(BTreeMap::from([
+                    ("x".to_string(), InputValue::Field(FieldElement::from(3u128))),
+                    ("y".to_string(), InputValue::Field(FieldElement::one())),
+                ])),
+            ),
+            ("d".to_string(), InputValue::String("hi".to_string())),
+            (
+                "e".to_string(),
+                InputValue::Struct(BTreeMap::from([(
+                    "Square".to_string(),
+                    InputValue::Vec(vec![InputValue::Field(FieldElement::from(4u128))]),
+                )])),
+            ),
+            (
+                "f".to_string(),
+                InputValue::Struct(BTreeMap::from([(
+                    "Empty".to_string(),
+                    InputValue::Vec(vec![]),
+                )])),
+            ),
+        ]);
+
+        assert_eq!(
+            format_arguments(&abi, &input_map),
+            r#"-1, [1, 2], Point { x: 3, y: true }, "hi", Shape::Square(4), Shape::Empty"#
+        );
+    }
+}
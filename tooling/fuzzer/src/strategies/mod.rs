@@ -78,6 +78,26 @@ pub(super) fn arb_value_from_abi_type(abi_type: &AbiType) -> SBoxedStrategy<Inpu
             let fields: Vec<_> = fields.iter().map(arb_value_from_abi_type).collect();
             fields.prop_map(InputValue::Vec).sboxed()
         }
+
+        AbiType::Enum { variants, .. } => {
+            let variants: Vec<SBoxedStrategy<InputValue>> = variants
+                .iter()
+                .map(|(name, fields)| {
+                    let name = name.clone();
+                    let fields: Vec<_> = fields.iter().map(arb_value_from_abi_type).collect();
+                    fields
+                        .prop_map(move |fields| {
+                            InputValue::Struct(BTreeMap::from([(
+                                name.clone(),
+                                InputValue::Vec(fields),
+                            )]))
+                        })
+                        .sboxed()
+                })
+                .collect();
+
+            proptest::strategy::Union::new(variants).sboxed()
+        }
     }
 }
 
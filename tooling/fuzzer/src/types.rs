@@ -1,3 +1,4 @@
+use nargo::{errors::ExecutionLimit, ops::OpcodeCoverage};
 use noirc_abi::InputMap;
 
 type CounterExample = InputMap;
@@ -16,6 +17,12 @@ pub struct FuzzTestResult {
 
     /// Minimal reproduction test case for failing fuzz tests
     pub counterexample: Option<CounterExample>,
+
+    /// The execution limit which stopped fuzzing early, if any
+    pub exceeded_limit: Option<ExecutionLimit>,
+
+    /// How many times each opcode was executed over all inputs, if coverage was requested
+    pub coverage: Option<OpcodeCoverage>,
 }
 
 /// Returned by a single fuzz in the case of a successful run
@@ -39,4 +46,5 @@ pub struct CounterExampleOutcome {
 pub enum FuzzOutcome {
     Case(CaseOutcome),
     CounterExample(CounterExampleOutcome),
+    LimitExceeded(ExecutionLimit),
 }
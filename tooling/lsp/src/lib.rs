@@ -22,15 +22,21 @@ use fm::{codespan_files as files, FileManager};
 use fxhash::FxHashSet;
 use lsp_types::CodeLens;
 use nargo::{
+    insert_all_files_for_workspace_into_file_manager_with_overrides,
     package::{Package, PackageType},
     parse_all,
     workspace::Workspace,
 };
-use nargo_toml::{find_file_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{
+    find_file_manifest, get_package_manifest, resolve_workspace_from_toml, PackageSelection,
+};
 use noirc_driver::{file_manager_with_stdlib, prepare_crate, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::{
     graph::{CrateId, CrateName},
-    hir::{def_map::parse_file, Context, FunctionNameMatch, ParsedFiles},
+    hir::{
+        def_map::{parse_file, CrateDefMap},
+        Context, FunctionNameMatch, ParsedFiles,
+    },
     node_interner::NodeInterner,
     parser::ParserError,
     ParsedModule,
@@ -42,9 +48,12 @@ use notifications::{
     on_did_open_text_document, on_did_save_text_document, on_exit, on_initialized,
 };
 use requests::{
-    on_code_lens_request, on_formatting, on_goto_declaration_request, on_goto_definition_request,
-    on_goto_type_definition_request, on_initialize, on_profile_run_request, on_shutdown,
-    on_test_run_request, on_tests_request,
+    on_code_action_request, on_code_lens_request, on_completion_request,
+    on_document_symbol_request, on_formatting, on_goto_declaration_request,
+    on_goto_definition_request, on_goto_type_definition_request, on_hover_request, on_initialize,
+    on_inlay_hint_request, on_prepare_rename_request, on_profile_run_request,
+    on_references_request, on_rename_request, on_semantic_tokens_full_request, on_shutdown,
+    on_test_run_request, on_tests_request, on_workspace_symbol_request,
 };
 use serde_json::Value as JsonValue;
 use thiserror::Error;
@@ -55,6 +64,9 @@ mod requests;
 mod solver;
 mod types;
 
+#[cfg(test)]
+mod test_utils;
+
 use solver::WrapperSolver;
 use types::{notification, request, NargoTest, NargoTestId, Position, Range, Url};
 
@@ -74,6 +86,7 @@ pub struct LspState {
     input_files: HashMap<String, String>,
     cached_lenses: HashMap<String, Vec<CodeLens>>,
     cached_definitions: HashMap<String, NodeInterner>,
+    cached_def_maps: HashMap<String, BTreeMap<CrateId, CrateDefMap>>,
     cached_parsed_files: HashMap<PathBuf, (usize, (ParsedModule, Vec<ParserError>))>,
     parsing_cache_enabled: bool,
 }
@@ -90,6 +103,7 @@ impl LspState {
             input_files: HashMap::new(),
             cached_lenses: HashMap::new(),
             cached_definitions: HashMap::new(),
+            cached_def_maps: HashMap::new(),
             open_documents_count: 0,
             cached_parsed_files: HashMap::new(),
             parsing_cache_enabled: true,
@@ -119,6 +133,16 @@ impl NargoLspService {
             .request::<request::GotoDefinition, _>(on_goto_definition_request)
             .request::<request::GotoDeclaration, _>(on_goto_declaration_request)
             .request::<request::GotoTypeDefinition, _>(on_goto_type_definition_request)
+            .request::<request::HoverRequest, _>(on_hover_request)
+            .request::<request::References, _>(on_references_request)
+            .request::<request::Completion, _>(on_completion_request)
+            .request::<request::PrepareRenameRequest, _>(on_prepare_rename_request)
+            .request::<request::Rename, _>(on_rename_request)
+            .request::<request::DocumentSymbolRequest, _>(on_document_symbol_request)
+            .request::<request::WorkspaceSymbolRequest, _>(on_workspace_symbol_request)
+            .request::<request::SemanticTokensFullRequest, _>(on_semantic_tokens_full_request)
+            .request::<request::InlayHintRequest, _>(on_inlay_hint_request)
+            .request::<request::CodeActionRequest, _>(on_code_action_request)
             .notification::<notification::Initialized>(on_initialized)
             .notification::<notification::DidChangeConfiguration>(on_did_change_configuration)
             .notification::<notification::DidOpenTextDocument>(on_did_open_text_document)
@@ -221,10 +245,17 @@ fn byte_span_to_range<'a, F: files::Files<'a> + ?Sized>(
 
 pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Workspace, LspError> {
     if let Some(toml_path) = find_file_manifest(file_path) {
+        // If the package is a member of a workspace we want to see the whole workspace,
+        // so that e.g. references to an item can be found in every package using it.
+        if let Some(workspace) = find_enclosing_workspace(&toml_path, file_path) {
+            return Ok(workspace);
+        }
+
         resolve_workspace_from_toml(
             &toml_path,
             PackageSelection::All,
             Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+            false,
         )
         .map_err(|err| LspError::WorkspaceResolutionError(err.to_string()))
     } else {
@@ -247,6 +278,7 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
             name: CrateName::from_str(parent_folder)
                 .map_err(|err| LspError::WorkspaceResolutionError(err.to_string()))?,
             dependencies: BTreeMap::new(),
+            test_config: Default::default(),
         };
         let workspace = Workspace {
             root_dir: PathBuf::from(parent_folder),
@@ -258,6 +290,25 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
     }
 }
 
+/// Looks for a workspace manifest above the package manifest at `toml_path` which has
+/// the package containing `file_path` as one of its members.
+fn find_enclosing_workspace(toml_path: &Path, file_path: &Path) -> Option<Workspace> {
+    let package_dir = toml_path.parent()?;
+    package_dir.ancestors().skip(1).find_map(|directory| {
+        let workspace_toml_path = get_package_manifest(directory).ok()?;
+        let workspace = resolve_workspace_from_toml(
+            &workspace_toml_path,
+            PackageSelection::All,
+            Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+            false,
+        )
+        .ok()?;
+        let is_member =
+            workspace.members.iter().any(|package| file_path.starts_with(&package.root_dir));
+        (workspace.members.len() > 1 && is_member).then_some(workspace)
+    })
+}
+
 /// Prepares a package from a source string
 /// This is useful for situations when we don't need dependencies
 /// and just need to operate on single file.
@@ -280,6 +331,29 @@ fn prepare_source(source: String, state: &mut LspState) -> (Context<'static, 'st
     (context, root_crate_id)
 }
 
+/// Creates a file manager with every file in `workspace` and the stdlib. The documents open in
+/// the editor are added with their current contents, which may not have been saved to disk yet.
+fn workspace_file_manager(workspace: &Workspace, state: &LspState) -> FileManager {
+    let open_documents: Vec<(PathBuf, &str)> = state
+        .input_files
+        .iter()
+        .filter_map(|(uri, source)| {
+            let file_path = Url::parse(uri).ok()?.to_file_path().ok()?;
+            Some((file_path, source.as_str()))
+        })
+        .collect();
+    let overrides: HashMap<&Path, &str> =
+        open_documents.iter().map(|(file_path, source)| (file_path.as_path(), *source)).collect();
+
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    insert_all_files_for_workspace_into_file_manager_with_overrides(
+        workspace,
+        &mut workspace_file_manager,
+        &overrides,
+    );
+    workspace_file_manager
+}
+
 fn parse_diff(file_manager: &FileManager, state: &mut LspState) -> ParsedFiles {
     if state.parsing_cache_enabled {
         let noir_file_hashes: Vec<_> = file_manager
@@ -1,8 +1,8 @@
 use std::ops::ControlFlow;
 
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
-use nargo::{insert_all_files_for_workspace_into_file_manager, prepare_package};
-use noirc_driver::{check_crate, file_manager_with_stdlib};
+use nargo::prepare_package;
+use noirc_driver::check_crate;
 use noirc_errors::{DiagnosticKind, FileDiagnostic};
 
 use crate::requests::collect_lenses_for_package;
@@ -14,7 +14,7 @@ use crate::types::{
 
 use crate::{
     byte_span_to_range, get_package_tests_in_crate, parse_diff, prepare_source,
-    resolve_workspace_for_source_path, LspState,
+    resolve_workspace_for_source_path, workspace_file_manager, LspState,
 };
 
 pub(super) fn on_initialized(
@@ -85,6 +85,14 @@ pub(super) fn on_did_change_text_document(
 
     state.cached_lenses.insert(params.text_document.uri.to_string(), lenses);
 
+    // The results of type-checking the workspace no longer match the document, so they have
+    // to be computed again by the next request needing them
+    for package in &workspace.members {
+        let package_root_dir: String = package.root_dir.as_os_str().to_string_lossy().into();
+        state.cached_definitions.remove(&package_root_dir);
+        state.cached_def_maps.remove(&package_root_dir);
+    }
+
     ControlFlow::Continue(())
 }
 
@@ -99,6 +107,7 @@ pub(super) fn on_did_close_text_document(
 
     if state.open_documents_count == 0 {
         state.cached_definitions.clear();
+        state.cached_def_maps.clear();
     }
 
     ControlFlow::Continue(())
@@ -128,8 +137,7 @@ fn process_noir_document(
         ResponseError::new(ErrorCode::REQUEST_FAILED, lsp_error.to_string())
     })?;
 
-    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
-    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let workspace_file_manager = workspace_file_manager(&workspace, state);
 
     let parsed_files = parse_diff(&workspace_file_manager, state);
 
@@ -163,7 +171,8 @@ fn process_noir_document(
             );
             state.cached_lenses.insert(document_uri.to_string(), collected_lenses);
 
-            state.cached_definitions.insert(package_root_dir, context.def_interner);
+            state.cached_definitions.insert(package_root_dir.clone(), context.def_interner);
+            state.cached_def_maps.insert(package_root_dir, context.def_maps);
 
             let fm = &context.file_manager;
             let files = fm.as_file_map();
@@ -0,0 +1,552 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::{FileId, FileMap, PathString};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Diagnostic, Range, TextEdit,
+    Url, WorkspaceEdit,
+};
+use nargo::insert_all_files_for_workspace_into_file_manager;
+use noirc_driver::{collect_crate_errors, file_manager_with_stdlib};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    ast::ItemVisibility,
+    graph::CrateId,
+    hir::{
+        def_collector::dc_crate::CompilationError,
+        def_map::{CrateDefMap, LocalModuleId, ModuleDefId},
+        resolution::{errors::ResolverError, import::PathResolutionError},
+    },
+    macros_api::NodeInterner,
+    node_interner::ReferenceId,
+    parser::{Item, ItemKind},
+    token::{Keyword, Token},
+    Type,
+};
+
+use crate::{
+    byte_span_to_range, parse_diff, resolve_workspace_for_source_path, types::CodeActionResult,
+    LspState,
+};
+
+use super::{
+    completion::{byte_index_in_source, lex},
+    package_for_file,
+};
+
+pub(crate) fn on_code_action_request(
+    state: &mut LspState,
+    params: CodeActionParams,
+) -> impl Future<Output = Result<CodeActionResult, ResponseError>> {
+    let result = on_code_action_inner(state, params);
+    future::ready(result)
+}
+
+fn on_code_action_inner(
+    state: &mut LspState,
+    params: CodeActionParams,
+) -> Result<CodeActionResult, ResponseError> {
+    // Every action we offer is a quick-fix, so there's nothing to do if the client asked
+    // for other kinds of actions only
+    if let Some(only) = &params.context.only {
+        if !only.iter().any(|kind| CodeActionKind::QUICKFIX.as_str().starts_with(kind.as_str())) {
+            return Ok(None);
+        }
+    }
+
+    let uri = params.text_document.uri;
+    let file_path = uri.to_file_path().map_err(|_| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
+    })?;
+
+    let workspace = resolve_workspace_for_source_path(file_path.as_path())
+        .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?;
+    let package = package_for_file(&workspace, &file_path).ok_or_else(|| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "Workspace has no packages")
+    })?;
+
+    // The edits we return apply to the document as the client currently has it, which may
+    // not have been saved yet.
+    let source = match state.input_files.get(&uri.to_string()) {
+        Some(source) => source.clone(),
+        None => std::fs::read_to_string(&file_path)
+            .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?,
+    };
+    let start = byte_index_in_source(&source, &params.range.start).unwrap_or(source.len());
+    let end = byte_index_in_source(&source, &params.range.end).unwrap_or(source.len());
+
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    workspace_file_manager.add_file_with_source(&file_path, source.clone());
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_diff(&workspace_file_manager, state);
+
+    let (mut context, crate_id) =
+        nargo::prepare_package(&workspace_file_manager, &parsed_files, package);
+    let errors = collect_crate_errors(&mut context, crate_id, false, false);
+
+    let files = workspace_file_manager.as_file_map();
+    let Some(file_id) = files.get_file_id(&PathString::from(file_path)) else {
+        return Ok(None);
+    };
+    let Some((parsed_module, _)) = parsed_files.get(&file_id) else {
+        return Ok(None);
+    };
+
+    let finder = QuickFixFinder {
+        uri,
+        source: &source,
+        tokens: lex(&source),
+        items: &parsed_module.items,
+        files,
+        file_id,
+        interner: &context.def_interner,
+        def_maps: &context.def_maps,
+        crate_id,
+        range: Span::from(start as u32..end as u32),
+        diagnostics: &params.context.diagnostics,
+    };
+
+    let mut actions = Vec::new();
+    for (error, error_file_id) in &errors {
+        if let (CompilationError::ResolverError(error), true) = (error, *error_file_id == file_id) {
+            actions.extend(finder.quick_fixes(error).into_iter().map(CodeActionOrCommand::from));
+        }
+    }
+
+    Ok((!actions.is_empty()).then_some(actions))
+}
+
+/// Turns the errors found in a file into the edits that would fix them
+struct QuickFixFinder<'a> {
+    uri: Url,
+    source: &'a str,
+    tokens: Vec<(Token, Span)>,
+    items: &'a [Item],
+    files: &'a FileMap,
+    file_id: FileId,
+    interner: &'a NodeInterner,
+    def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+    crate_id: CrateId,
+    /// The part of the document actions were requested for
+    range: Span,
+    /// The diagnostics the client sent along with the request
+    diagnostics: &'a [Diagnostic],
+}
+
+impl<'a> QuickFixFinder<'a> {
+    fn quick_fixes(&self, error: &ResolverError) -> Vec<CodeAction> {
+        let span = match error {
+            ResolverError::PathResolutionError(PathResolutionError::Unresolved(ident))
+            | ResolverError::NecessaryPub { ident }
+            | ResolverError::OracleMarkedAsConstrained { ident } => ident.span(),
+            ResolverError::VariableNotDeclared { span, .. }
+            | ResolverError::MissingFields { span, .. } => *span,
+            _ => return Vec::new(),
+        };
+        if span.start() > self.range.end() || self.range.start() > span.end() {
+            return Vec::new();
+        }
+
+        match error {
+            ResolverError::PathResolutionError(PathResolutionError::Unresolved(ident)) => {
+                self.import_item(&ident.0.contents, span)
+            }
+            ResolverError::VariableNotDeclared { name, .. } => self.import_item(name, span),
+            ResolverError::NecessaryPub { .. } => self.make_return_value_public(span),
+            ResolverError::OracleMarkedAsConstrained { .. } => self.mark_unconstrained(span),
+            ResolverError::MissingFields { missing_fields, .. } => {
+                self.fill_missing_fields(span, missing_fields)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Offers a `use` for every item named `name` that can be imported into this crate
+    fn import_item(&self, name: &str, span: Span) -> Vec<CodeAction> {
+        // Only the first segment of a path can be brought into scope by an import
+        if matches!(self.token_before(span.start()), Some(Token::DoubleColon)) {
+            return Vec::new();
+        }
+
+        let Some((byte_index, indentation)) = self.import_position(span) else {
+            return Vec::new();
+        };
+        let Some(range) = self.range(Span::from(byte_index..byte_index)) else {
+            return Vec::new();
+        };
+
+        self.import_candidates(name)
+            .into_iter()
+            .map(|path| {
+                let new_text = format!("use {path};\n{indentation}");
+                self.action(format!("Import {path}"), span, vec![TextEdit { range, new_text }])
+            })
+            .collect()
+    }
+
+    /// The full paths of the items named `name`, starting with those in the current crate
+    fn import_candidates(&self, name: &str) -> Vec<String> {
+        let mut paths = Vec::new();
+        for (crate_id, def_map) in self.def_maps {
+            let crate_name = if *crate_id == self.crate_id {
+                "crate".to_string()
+            } else {
+                let dependencies = self.def_maps[&self.crate_id].extern_prelude();
+                match dependencies.iter().find(|(_, module_id)| module_id.krate == *crate_id) {
+                    Some((dependency_name, _)) => dependency_name.clone(),
+                    None => continue,
+                }
+            };
+
+            for (index, module) in def_map.modules().iter() {
+                if !is_module_path(def_map, LocalModuleId(index)) {
+                    continue;
+                }
+
+                let definitions: Vec<ModuleDefId> =
+                    module.type_definitions().chain(module.value_definitions()).collect();
+                let scope = module.scope();
+                for (ident, per_trait) in scope.types().iter().chain(scope.values()) {
+                    if ident.0.contents != name {
+                        continue;
+                    }
+
+                    for (module_def_id, visibility, is_prelude) in per_trait.values() {
+                        let is_visible =
+                            *crate_id == self.crate_id || *visibility == ItemVisibility::Public;
+                        if *is_prelude || !is_visible || !definitions.contains(module_def_id) {
+                            continue;
+                        }
+
+                        let module_path =
+                            def_map.get_module_path_with_separator(index, module.parent, "::");
+                        let path = if module_path.is_empty() {
+                            format!("{crate_name}::{name}")
+                        } else {
+                            format!("{crate_name}::{module_path}::{name}")
+                        };
+                        paths.push((*crate_id != self.crate_id, path));
+                    }
+                }
+            }
+        }
+
+        paths.sort();
+        paths.dedup();
+        paths.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// Where a `use` for the module containing `span` goes: right before the first item of
+    /// that module, with the same indentation.
+    fn import_position(&self, span: Span) -> Option<(u32, &'a str)> {
+        let mut items = self.items;
+        'descend: loop {
+            for item in items {
+                if let ItemKind::Submodules(submodule) = &item.kind {
+                    if item.span.start() <= span.start() && span.end() <= item.span.end() {
+                        items = &submodule.contents.items;
+                        continue 'descend;
+                    }
+                }
+            }
+            break;
+        }
+
+        let byte_index = items.first()?.span.start();
+        let line_start =
+            self.source[..byte_index as usize].rfind('\n').map_or(0, |index| index + 1);
+        let indentation = &self.source[line_start..byte_index as usize];
+        let indentation = if indentation.trim().is_empty() { indentation } else { "" };
+        Some((byte_index, indentation))
+    }
+
+    /// Adds `pub` to the return type of the function named at `name_span`
+    fn make_return_value_public(&self, name_span: Span) -> Vec<CodeAction> {
+        let mut depth = 0;
+        let tokens = self.tokens.iter().skip_while(|(_, span)| span.start() < name_span.end());
+        let mut tokens = tokens.skip_while(|(token, _)| {
+            match token {
+                Token::LeftParen => depth += 1,
+                Token::RightParen => depth -= 1,
+                _ => (),
+            }
+            depth > 0 || *token != Token::Arrow
+        });
+
+        let (Some(_arrow), Some((_, return_type_span))) = (tokens.next(), tokens.next()) else {
+            return Vec::new();
+        };
+        let Some(range) =
+            self.range(Span::from(return_type_span.start()..return_type_span.start()))
+        else {
+            return Vec::new();
+        };
+
+        let edit = TextEdit { range, new_text: "pub ".to_string() };
+        vec![self.action("Make the return value public".to_string(), name_span, vec![edit])]
+    }
+
+    /// Adds `unconstrained` to the function named at `name_span`
+    fn mark_unconstrained(&self, name_span: Span) -> Vec<CodeAction> {
+        let fn_keyword = self.tokens.iter().rev().find(|(_, span)| span.end() <= name_span.start());
+        let Some((Token::Keyword(Keyword::Fn), fn_span)) = fn_keyword else {
+            return Vec::new();
+        };
+        let Some(range) = self.range(Span::from(fn_span.start()..fn_span.start())) else {
+            return Vec::new();
+        };
+
+        let edit = TextEdit { range, new_text: "unconstrained ".to_string() };
+        vec![self.action("Mark the function as unconstrained".to_string(), name_span, vec![edit])]
+    }
+
+    /// Adds the fields missing from the constructor whose type is written at `type_span`.
+    /// Each field is given a placeholder value of its type.
+    fn fill_missing_fields(&self, type_span: Span, missing_fields: &[String]) -> Vec<CodeAction> {
+        // Find the braces of the constructor and what comes right before the closing one
+        let mut tokens = self.tokens.iter().skip_while(|(_, span)| span.start() < type_span.end());
+        let Some((Token::LeftBrace, _)) = tokens.next() else {
+            return Vec::new();
+        };
+        let mut depth = 0;
+        let mut previous = Token::LeftBrace;
+        let mut insertion_point = None;
+        let mut closing_brace = None;
+        for (token, span) in tokens {
+            match token {
+                Token::LeftBrace => depth += 1,
+                Token::RightBrace if depth == 0 => {
+                    closing_brace = Some(span.start());
+                    break;
+                }
+                Token::RightBrace => depth -= 1,
+                _ => (),
+            }
+            previous = token.clone();
+            insertion_point = Some(span.end());
+        }
+        let Some(closing_brace) = closing_brace else {
+            return Vec::new();
+        };
+        let insertion_point = insertion_point.unwrap_or_else(|| {
+            let open_brace = self.source[..closing_brace as usize].rfind('{').unwrap_or(0);
+            open_brace as u32 + 1
+        });
+
+        let location = Location::new(Span::single_char(type_span.end() - 1), self.file_id);
+        // Fields are added in the order they are declared in, when the struct is known
+        let fields: Vec<String> = match self.interner.find_referenced(location) {
+            Some(ReferenceId::Struct(struct_id)) => {
+                let struct_type = self.interner.get_struct(struct_id);
+                let struct_type = struct_type.borrow();
+                struct_type
+                    .get_fields_as_written()
+                    .iter()
+                    .filter(|(name, _)| missing_fields.contains(&name.0.contents))
+                    .map(|(name, typ)| format!("{name}: {}", placeholder_value(Some(typ))))
+                    .collect()
+            }
+            _ => missing_fields
+                .iter()
+                .map(|name| format!("{name}: {}", placeholder_value(None)))
+                .collect(),
+        };
+        let separator =
+            if matches!(previous, Token::LeftBrace | Token::Comma) { " " } else { ", " };
+        let mut new_text = format!("{separator}{}", fields.join(", "));
+        if insertion_point == closing_brace {
+            new_text.push(' ');
+        }
+
+        let Some(range) = self.range(Span::from(insertion_point..insertion_point)) else {
+            return Vec::new();
+        };
+        let edit = TextEdit { range, new_text };
+        vec![self.action("Fill in missing fields".to_string(), type_span, vec![edit])]
+    }
+
+    fn token_before(&self, byte_index: u32) -> Option<&Token> {
+        let index = self.tokens.partition_point(|(_, span)| span.end() <= byte_index);
+        index.checked_sub(1).map(|index| &self.tokens[index].0)
+    }
+
+    fn range(&self, span: Span) -> Option<Range> {
+        byte_span_to_range(self.files, self.file_id, span.into())
+    }
+
+    fn action(&self, title: String, error_span: Span, edits: Vec<TextEdit>) -> CodeAction {
+        // Link the action to the diagnostics the client has for the error, if it sent any
+        let diagnostics = self.range(error_span).map(|error_range| {
+            self.diagnostics
+                .iter()
+                .filter(|diagnostic| overlaps(&diagnostic.range, &error_range))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+
+        CodeAction {
+            title,
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: diagnostics.filter(|diagnostics| !diagnostics.is_empty()),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(self.uri.clone(), edits)])),
+                document_changes: None,
+                change_annotations: None,
+            }),
+            command: None,
+            is_preferred: None,
+            disabled: None,
+            data: None,
+        }
+    }
+}
+
+/// True if the module can be named in a path. Structs and traits get a module of their
+/// own to hold their methods, which can't be imported from.
+fn is_module_path(def_map: &CrateDefMap, module_id: LocalModuleId) -> bool {
+    let modules = def_map.modules();
+    let Some(parent) = modules[module_id.0].parent else {
+        return true;
+    };
+    let parent = &modules[parent.0];
+    parent.children.iter().any(|(name, child)| {
+        *child == module_id
+            && matches!(
+                parent.scope().find_name(name).types,
+                Some((ModuleDefId::ModuleId(child), _, _)) if child.local_id == module_id
+            )
+    })
+}
+
+fn placeholder_value(typ: Option<&Type>) -> &'static str {
+    match typ {
+        Some(Type::FieldElement | Type::Integer(..)) => "0",
+        Some(Type::Bool) => "false",
+        _ => "std::unsafe::zeroed()",
+    }
+}
+
+fn overlaps(range: &Range, other: &Range) -> bool {
+    let start_of = |range: &Range| (range.start.line, range.start.character);
+    let end_of = |range: &Range| (range.end.line, range.end.character);
+    start_of(range) <= end_of(other) && start_of(other) <= end_of(range)
+}
+
+#[cfg(test)]
+mod code_action_tests {
+    use lsp_types::{
+        CodeActionContext, PartialResultParams, Position, TextDocumentIdentifier,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    async fn code_actions_at(position: Position) -> (String, Vec<CodeAction>) {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("code_action").await;
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+            range: Range { start: position, end: position },
+            context: CodeActionContext { diagnostics: Vec::new(), only: None, trigger_kind: None },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+
+        let response = on_code_action_request(&mut state, params)
+            .await
+            .expect("Could not execute on_code_action_request")
+            .unwrap_or_default();
+        let actions = response
+            .into_iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => action,
+                CodeActionOrCommand::Command(command) => {
+                    panic!("Expected a code action, got {:?}", command)
+                }
+            })
+            .collect();
+
+        let path = noir_text_document.to_file_path().unwrap();
+        (std::fs::read_to_string(path).unwrap(), actions)
+    }
+
+    /// Applies the edits of `action` to `source` and returns the lines that changed
+    fn apply(source: &str, action: &CodeAction) -> Vec<String> {
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        let mut edits: Vec<_> = changes.values().flatten().collect();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+
+        let mut lines: Vec<String> = source.lines().map(String::from).collect();
+        for edit in edits {
+            assert_eq!(edit.range.start, edit.range.end, "Expected an insertion");
+            let line = &mut lines[edit.range.start.line as usize];
+            line.insert_str(edit.range.start.character as usize, &edit.new_text);
+        }
+
+        let new_source = lines.join("\n");
+        let old_lines: Vec<&str> = source.lines().collect();
+        new_source.lines().filter(|line| !old_lines.contains(line)).map(String::from).collect()
+    }
+
+    #[test]
+    async fn test_import_unresolved_struct() {
+        let (source, actions) = code_actions_at(Position { line: 6, character: 17 }).await;
+
+        // The stdlib also has structs named `Point`, but those in the crate come first
+        let first = actions.first().expect("Expected code actions");
+        assert_eq!(first.title, "Import crate::things::Point");
+        assert_eq!(apply(&source, first), vec!["use crate::things::Point;"]);
+        assert!(actions.iter().any(|action| action.title.starts_with("Import std::")));
+    }
+
+    #[test]
+    async fn test_import_unresolved_function() {
+        let (source, actions) = code_actions_at(Position { line: 7, character: 24 }).await;
+
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(titles, vec!["Import crate::things::origin"]);
+        assert_eq!(apply(&source, &actions[0]), vec!["use crate::things::origin;"]);
+    }
+
+    #[test]
+    async fn test_make_return_value_public() {
+        let (source, actions) = code_actions_at(Position { line: 5, character: 4 }).await;
+
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(titles, vec!["Make the return value public"]);
+        assert_eq!(apply(&source, &actions[0]), vec!["fn main(x: Field) -> pub Field {"]);
+    }
+
+    #[test]
+    async fn test_mark_oracle_unconstrained() {
+        let (source, actions) = code_actions_at(Position { line: 3, character: 4 }).await;
+
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(titles, vec!["Mark the function as unconstrained"]);
+        assert_eq!(apply(&source, &actions[0]), vec!["unconstrained fn get_number() -> Field {}"]);
+    }
+
+    #[test]
+    async fn test_fill_missing_fields() {
+        let (source, actions) = code_actions_at(Position { line: 12, character: 13 }).await;
+
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(titles, vec!["Fill in missing fields"]);
+        assert_eq!(
+            apply(&source, &actions[0]),
+            vec!["    things::Point { x, y: 0, valid: false }"]
+        );
+    }
+
+    #[test]
+    async fn test_no_actions_away_from_errors() {
+        let (_, actions) = code_actions_at(Position { line: 8, character: 4 }).await;
+        assert!(actions.is_empty());
+    }
+}
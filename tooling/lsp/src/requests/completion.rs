@@ -0,0 +1,994 @@
+use std::{
+    collections::BTreeMap,
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::FileId;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse, Position,
+};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    ast::{
+        ArrayLiteral, BlockExpression, Expression, ExpressionKind, ForRange, Ident,
+        ItemVisibility, Literal, NoirFunction, Path, PathKind, Statement, StatementKind,
+        TraitImplItem,
+    },
+    graph::CrateId,
+    hir::{
+        def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId},
+        resolution::import::{resolve_import, ImportDirective},
+    },
+    hir_def::{function::FuncMeta, stmt::HirPattern},
+    lexer::Lexer,
+    macros_api::NodeInterner,
+    node_interner::{DefinitionId, DefinitionInfo},
+    parser::{Item, ItemKind},
+    token::{Keyword, Token},
+    ParsedModule, Type,
+};
+
+use crate::{types::CompletionResult, LspState};
+
+use super::{character_to_line_offset, process_request};
+
+pub(crate) fn on_completion_request(
+    state: &mut LspState,
+    params: CompletionParams,
+) -> impl Future<Output = Result<CompletionResult, ResponseError>> {
+    let result = on_completion_inner(state, params);
+    future::ready(result)
+}
+
+fn on_completion_inner(
+    state: &mut LspState,
+    params: CompletionParams,
+) -> Result<CompletionResult, ResponseError> {
+    let text_document_position = params.text_document_position;
+    let uri = &text_document_position.text_document.uri;
+    let file_path = uri.to_file_path().map_err(|_| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
+    })?;
+
+    // Completion is requested while the document is being edited, so we look at its latest
+    // contents instead of what was last saved to disk, like the type-checked workspace does.
+    let source = match state.input_files.get(&uri.to_string()) {
+        Some(source) => source.clone(),
+        None => std::fs::read_to_string(&file_path)
+            .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?,
+    };
+    let byte_index =
+        byte_index_in_source(&source, &text_document_position.position).ok_or_else(|| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, "Position is outside of the document")
+        })?;
+
+    // The statement being written usually doesn't parse yet, but the parser recovers from it
+    // so the rest of the document is still available.
+    let (parsed_module, _) = noirc_frontend::parse_program(&source);
+
+    process_request(state, text_document_position, |args| {
+        let file_id = args.location.file;
+        let crate_id = *args.def_maps.keys().find(|crate_id| crate_id.is_root())?;
+        let def_map = &args.def_maps[&crate_id];
+        let module_id = module_at(def_map, file_id, &parsed_module, byte_index as u32)?;
+
+        let finder = CompletionFinder {
+            interner: args.interner,
+            def_maps: args.def_maps,
+            crate_id,
+            module_id,
+            file_id,
+            function: function_at(&parsed_module.items, byte_index as u32),
+            byte_index: byte_index as u32,
+        };
+        let items = finder.find(&source[..byte_index]);
+
+        (!items.is_empty()).then_some(CompletionResponse::Array(items))
+    })
+}
+
+/// Converts an LSP position into a byte index into `source`
+pub(super) fn byte_index_in_source(source: &str, position: &Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        line_start += source[line_start..].find('\n')? + 1;
+    }
+    let line_end = source[line_start..].find('\n').map_or(source.len(), |end| line_start + end);
+    let line = &source[line_start..line_end];
+    character_to_line_offset(line, position.character).ok().map(|offset| line_start + offset)
+}
+
+/// What is being completed, as found by looking at the tokens right before the cursor.
+#[derive(Debug, PartialEq)]
+enum CompletionContext {
+    /// `a::b::pre`, where `segments` are `a` and `b`.
+    /// `in_use` is true if this path is part of a `use` statement.
+    Path { kind: PathKind, segments: Vec<String>, prefix: String, in_use: bool },
+    /// `receiver.pre`, where `receiver` ends at `receiver_end`.
+    /// `receiver_names` holds the identifiers in `receiver` if it is a chain like `a.b.c`.
+    Member { receiver_end: u32, receiver_names: Option<Vec<String>>, prefix: String },
+    /// `Foo { a: 1, pre`, where `struct_path` is `Foo` and `existing_fields` holds `a`.
+    ConstructorField { struct_path: Vec<String>, existing_fields: Vec<String>, prefix: String },
+    /// Just a name, like `pre`.
+    Name { prefix: String },
+}
+
+struct CompletionFinder<'a> {
+    interner: &'a NodeInterner,
+    def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+    crate_id: CrateId,
+    module_id: LocalModuleId,
+    file_id: FileId,
+    /// The function the cursor is in, if any
+    function: Option<&'a NoirFunction>,
+    byte_index: u32,
+}
+
+impl<'a> CompletionFinder<'a> {
+    fn find(&self, source_before_cursor: &str) -> Vec<CompletionItem> {
+        let mut items = match completion_context(source_before_cursor) {
+            CompletionContext::Path { kind, segments, prefix, in_use } => {
+                self.complete_path(kind, segments, &prefix, in_use)
+            }
+            CompletionContext::Member { receiver_end, receiver_names, prefix } => {
+                self.complete_member(receiver_end, receiver_names, &prefix)
+            }
+            CompletionContext::ConstructorField { struct_path, existing_fields, prefix } => {
+                self.complete_constructor_field(struct_path, &existing_fields, &prefix)
+            }
+            CompletionContext::Name { prefix } => self.complete_name(&prefix),
+        };
+
+        items.sort_by(|item1, item2| item1.label.cmp(&item2.label));
+        items.dedup_by(|item1, item2| item1.label == item2.label);
+        items
+    }
+
+    /// Completes the last segment of a path, e.g. in `use std::hash::pe` or `dep::`
+    fn complete_path(
+        &self,
+        kind: PathKind,
+        segments: Vec<String>,
+        prefix: &str,
+        in_use: bool,
+    ) -> Vec<CompletionItem> {
+        let def_map = &self.def_maps[&self.crate_id];
+
+        if segments.is_empty() {
+            return match kind {
+                PathKind::Dep => def_map
+                    .extern_prelude()
+                    .keys()
+                    .filter(|name| name.starts_with(prefix))
+                    .map(|name| simple_item(name, CompletionItemKind::MODULE, None))
+                    .collect(),
+                PathKind::Crate => self.module_items(self.root_module(), prefix),
+                PathKind::Plain => {
+                    let mut items = self.module_items(self.current_module(), prefix);
+                    if in_use {
+                        // Only modules and crates can start an import path
+                        items.retain(|item| item.kind == Some(CompletionItemKind::MODULE));
+                        for keyword in ["crate", "dep"] {
+                            if keyword.starts_with(prefix) {
+                                items.push(simple_item(keyword, CompletionItemKind::KEYWORD, None));
+                            }
+                        }
+                    }
+                    items.extend(self.crate_names(prefix));
+                    items
+                }
+            };
+        }
+
+        let path = Path {
+            segments: segments
+                .into_iter()
+                .map(|segment| Ident::new(segment, Span::default()))
+                .collect(),
+            kind,
+            span: Span::default(),
+        };
+        let import =
+            ImportDirective { module_id: self.module_id, path, alias: None, is_prelude: false };
+        let Ok(resolved_import) = resolve_import(self.crate_id, &import, self.def_maps) else {
+            return Vec::new();
+        };
+
+        match resolved_import.resolved_namespace.types {
+            Some((ModuleDefId::ModuleId(module_id), _, _)) => self.module_items(module_id, prefix),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Completes the fields and methods of `receiver` in `receiver.pre`
+    fn complete_member(
+        &self,
+        receiver_end: u32,
+        receiver_names: Option<Vec<String>>,
+        prefix: &str,
+    ) -> Vec<CompletionItem> {
+        let location =
+            Location::new(Span::single_char(receiver_end.saturating_sub(1)), self.file_id);
+        let receiver_type = self
+            .interner
+            .type_of_expression_ending_at(location)
+            .or_else(|| self.type_of_receiver_names(receiver_names?));
+        let Some(receiver_type) = receiver_type else {
+            return Vec::new();
+        };
+
+        let mut items = Vec::new();
+
+        if let Type::Struct(struct_type, generics) = dereference(receiver_type.clone()) {
+            for (name, typ) in struct_type.borrow().get_fields(&generics) {
+                if name.starts_with(prefix) {
+                    let detail = typ.to_string();
+                    items.push(simple_item(&name, CompletionItemKind::FIELD, Some(detail)));
+                }
+            }
+        }
+
+        for (name, func_id) in self.interner.get_type_methods(&receiver_type) {
+            let func_meta = self.interner.function_meta(&func_id);
+            if name.starts_with(prefix) && has_self_parameter(self.interner, func_meta) {
+                let detail = function_detail(func_meta);
+                items.push(simple_item(name, CompletionItemKind::METHOD, Some(detail)));
+            }
+        }
+
+        items
+    }
+
+    /// Completes the fields missing from a constructor expression, like `Foo { a: 1, pre`
+    fn complete_constructor_field(
+        &self,
+        struct_path: Vec<String>,
+        existing_fields: &[String],
+        prefix: &str,
+    ) -> Vec<CompletionItem> {
+        let segments = struct_path.into_iter().map(|segment| Ident::new(segment, Span::default()));
+        let path =
+            Path { segments: segments.collect(), kind: PathKind::Plain, span: Span::default() };
+        let import =
+            ImportDirective { module_id: self.module_id, path, alias: None, is_prelude: false };
+        let Ok(resolved_import) = resolve_import(self.crate_id, &import, self.def_maps) else {
+            return Vec::new();
+        };
+        let Some((ModuleDefId::TypeId(struct_id), _, _)) = resolved_import.resolved_namespace.types
+        else {
+            return Vec::new();
+        };
+
+        let struct_type = self.interner.get_struct(struct_id);
+        let struct_type = struct_type.borrow();
+        struct_type
+            .get_fields_as_written()
+            .iter()
+            .filter(|(name, _)| {
+                name.0.contents.starts_with(prefix) && !existing_fields.contains(&name.0.contents)
+            })
+            .map(|(name, typ)| {
+                simple_item(&name.0.contents, CompletionItemKind::FIELD, Some(typ.to_string()))
+            })
+            .collect()
+    }
+
+    /// Completes a name: a local variable, an item in scope or the name of a crate
+    fn complete_name(&self, prefix: &str) -> Vec<CompletionItem> {
+        let mut items = Vec::new();
+
+        for (id, definition) in self.locals_in_scope() {
+            if definition.name.starts_with(prefix) {
+                let detail = self.interner.definition_type(id).to_string();
+                let kind = CompletionItemKind::VARIABLE;
+                items.push(simple_item(&definition.name, kind, Some(detail)));
+            }
+        }
+
+        items.extend(self.module_items(self.current_module(), prefix));
+        items.extend(self.crate_names(prefix));
+        items
+    }
+
+    /// Returns the local variables which are in scope at the cursor, in the order they were
+    /// declared. Those declared in a block, closure, loop or match arm which the cursor isn't
+    /// in are left out.
+    fn locals_in_scope(&self) -> Vec<(DefinitionId, &'a DefinitionInfo)> {
+        let Some(function) = self.function else {
+            return Vec::new();
+        };
+
+        let mut closed_scopes = ClosedScopes { byte_index: self.byte_index, spans: Vec::new() };
+        closed_scopes.collect_block(&function.def.body);
+
+        let span = Span::from(function_span(function).start()..self.byte_index);
+        self.interner
+            .local_definitions_in(self.file_id, span)
+            .filter(|(_, definition)| {
+                let location = definition.location.span;
+                !closed_scopes.spans.iter().any(|scope| scope.contains(&location))
+            })
+            .collect()
+    }
+
+    /// Returns the items visible in the given module whose name starts with `prefix`.
+    /// Private items are only included if the module is part of the current crate, and
+    /// items from the prelude only if it is the current module.
+    fn module_items(&self, module_id: ModuleId, prefix: &str) -> Vec<CompletionItem> {
+        let Some(def_map) = self.def_maps.get(&module_id.krate) else {
+            return Vec::new();
+        };
+        let module = &def_map.modules()[module_id.local_id.0];
+        let scope = module.scope();
+
+        let mut items = Vec::new();
+        for (name, per_trait) in scope.types().iter().chain(scope.values()) {
+            let name = &name.0.contents;
+            if !name.starts_with(prefix) {
+                continue;
+            }
+
+            for (module_def_id, visibility, is_prelude) in per_trait.values() {
+                if module_id.krate != self.crate_id && *visibility != ItemVisibility::Public {
+                    continue;
+                }
+                if *is_prelude && module_id != self.current_module() {
+                    continue;
+                }
+                items.push(self.module_def_item(name, *module_def_id));
+            }
+        }
+        items
+    }
+
+    fn module_def_item(&self, name: &str, module_def_id: ModuleDefId) -> CompletionItem {
+        match module_def_id {
+            ModuleDefId::ModuleId(_) => simple_item(name, CompletionItemKind::MODULE, None),
+            ModuleDefId::FunctionId(func_id) => {
+                let detail = function_detail(self.interner.function_meta(&func_id));
+                simple_item(name, CompletionItemKind::FUNCTION, Some(detail))
+            }
+            ModuleDefId::TypeId(_) => simple_item(name, CompletionItemKind::STRUCT, None),
+            ModuleDefId::EnumId(_) => simple_item(name, CompletionItemKind::ENUM, None),
+            ModuleDefId::TypeAliasId(type_alias_id) => {
+                let detail = self.interner.get_type_alias(type_alias_id).borrow().typ.to_string();
+                simple_item(name, CompletionItemKind::STRUCT, Some(detail))
+            }
+            ModuleDefId::TraitId(_) => simple_item(name, CompletionItemKind::INTERFACE, None),
+            ModuleDefId::GlobalId(global_id) => {
+                let global = self.interner.get_global(global_id);
+                let detail = self.interner.definition_type(global.definition_id).to_string();
+                simple_item(name, CompletionItemKind::CONSTANT, Some(detail))
+            }
+        }
+    }
+
+    /// The names of the dependencies of the current crate, which can start a path
+    fn crate_names(&self, prefix: &str) -> Vec<CompletionItem> {
+        self.def_maps[&self.crate_id]
+            .extern_prelude()
+            .keys()
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| simple_item(name, CompletionItemKind::MODULE, None))
+            .collect()
+    }
+
+    /// Finds the type of a receiver like `a.b.c` by looking up the local variable `a`
+    /// and then the types of its fields. This is needed when the statement the receiver
+    /// is in could not be parsed, so it was never elaborated.
+    fn type_of_receiver_names(&self, names: Vec<String>) -> Option<Type> {
+        let (first, fields) = names.split_first()?;
+
+        let (id, _) = self
+            .locals_in_scope()
+            .into_iter()
+            .filter(|(_, definition)| &definition.name == first)
+            .last()?;
+
+        let mut typ = self.interner.definition_type(id);
+        for field in fields {
+            let Type::Struct(struct_type, generics) = dereference(typ) else {
+                return None;
+            };
+            let (field_type, _) = struct_type.borrow().get_field(field, &generics)?;
+            typ = field_type;
+        }
+        Some(typ)
+    }
+
+    fn current_module(&self) -> ModuleId {
+        ModuleId { krate: self.crate_id, local_id: self.module_id }
+    }
+
+    fn root_module(&self) -> ModuleId {
+        ModuleId { krate: self.crate_id, local_id: self.def_maps[&self.crate_id].root() }
+    }
+}
+
+/// Figures out what is being completed by lexing the source code right before the cursor.
+/// This works regardless of whether the statement being written can be parsed yet.
+fn completion_context(source_before_cursor: &str) -> CompletionContext {
+    let mut tokens = lex(source_before_cursor);
+
+    let prefix = match tokens.last() {
+        Some((Token::Ident(name), span)) if span.end() as usize == source_before_cursor.len() => {
+            let name = name.clone();
+            tokens.pop();
+            name
+        }
+        _ => String::new(),
+    };
+
+    match tokens.last() {
+        Some((Token::DoubleColon, _)) => {
+            tokens.pop();
+            let (kind, segments, before_path) = path_before(&tokens);
+            let in_use = matches!(before_path, Some(Token::Keyword(Keyword::Use)));
+            CompletionContext::Path { kind, segments, prefix, in_use }
+        }
+        Some((Token::Keyword(Keyword::Use), _)) => CompletionContext::Path {
+            kind: PathKind::Plain,
+            segments: Vec::new(),
+            prefix,
+            in_use: true,
+        },
+        Some((Token::Dot, dot_span)) => {
+            let receiver_end = dot_span.start();
+            tokens.pop();
+            let receiver_names = names_chain_before(&tokens);
+            CompletionContext::Member { receiver_end, receiver_names, prefix }
+        }
+        Some((Token::LeftBrace | Token::Comma, _)) => match constructor_before(&tokens) {
+            Some((struct_path, existing_fields)) => {
+                CompletionContext::ConstructorField { struct_path, existing_fields, prefix }
+            }
+            None => CompletionContext::Name { prefix },
+        },
+        _ => CompletionContext::Name { prefix },
+    }
+}
+
+/// Given tokens ending in a path like `a::b` (without a trailing `::`), returns the path's
+/// kind, its segments and the token right before the path.
+fn path_before(tokens: &[(Token, Span)]) -> (PathKind, Vec<String>, Option<&Token>) {
+    let mut segments = Vec::new();
+    let mut kind = PathKind::Plain;
+    let mut index = tokens.len();
+
+    while index > 0 {
+        match &tokens[index - 1].0 {
+            Token::Ident(name) => segments.push(name.clone()),
+            Token::Keyword(Keyword::Crate) => kind = PathKind::Crate,
+            Token::Keyword(Keyword::Dep) => kind = PathKind::Dep,
+            _ => break,
+        }
+        index -= 1;
+
+        if kind != PathKind::Plain || index == 0 || tokens[index - 1].0 != Token::DoubleColon {
+            break;
+        }
+        index -= 1;
+    }
+
+    segments.reverse();
+    let before_path = index.checked_sub(1).map(|index| &tokens[index].0);
+    (kind, segments, before_path)
+}
+
+/// Given tokens ending in a chain of identifiers like `a.b.c`, returns those identifiers
+fn names_chain_before(tokens: &[(Token, Span)]) -> Option<Vec<String>> {
+    let mut names = Vec::new();
+    let mut index = tokens.len();
+
+    loop {
+        let Some((Token::Ident(name), _)) = index.checked_sub(1).map(|index| &tokens[index]) else {
+            return None;
+        };
+        names.push(name.clone());
+        index -= 1;
+
+        if index == 0 || tokens[index - 1].0 != Token::Dot {
+            break;
+        }
+        index -= 1;
+    }
+
+    // A chain preceded by `::` is part of a path, which we can't resolve
+    if index > 0 && tokens[index - 1].0 == Token::DoubleColon {
+        return None;
+    }
+
+    names.reverse();
+    Some(names)
+}
+
+/// If `tokens` end inside a constructor expression right where a field name is expected,
+/// like `Foo { a: 1,`, returns the path of the struct and the fields already given.
+fn constructor_before(tokens: &[(Token, Span)]) -> Option<(Vec<String>, Vec<String>)> {
+    let mut depth = 0;
+    let mut existing_fields = Vec::new();
+    let mut index = tokens.len();
+
+    // Find the unmatched `{` this field belongs to
+    loop {
+        index = index.checked_sub(1)?;
+        match &tokens[index].0 {
+            Token::RightBrace | Token::RightParen | Token::RightBracket => depth += 1,
+            Token::LeftBrace if depth == 0 => break,
+            Token::LeftBrace | Token::LeftParen | Token::LeftBracket => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            Token::Semicolon if depth == 0 => return None,
+            Token::Colon if depth == 0 => {
+                if let Some((Token::Ident(name), _)) = index.checked_sub(1).map(|i| &tokens[i]) {
+                    existing_fields.push(name.clone());
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let struct_path = constructor_path_before(&tokens[..index])?;
+    Some((struct_path, existing_fields))
+}
+
+/// If `tokens` come right before the `{` of a constructor expression, like `Foo {`,
+/// returns the path of the struct being constructed.
+fn constructor_path_before(tokens: &[(Token, Span)]) -> Option<Vec<String>> {
+    let (_, segments, before_path) = path_before(tokens);
+    if segments.is_empty() {
+        return None;
+    }
+
+    // Rule out blocks that follow a name but aren't constructors, e.g. `if x {` or `fn foo() -> Bar {`
+    let is_constructor = !matches!(
+        before_path,
+        Some(
+            Token::Keyword(
+                Keyword::Struct
+                    | Keyword::Impl
+                    | Keyword::Trait
+                    | Keyword::Fn
+                    | Keyword::Mod
+                    | Keyword::Contract
+                    | Keyword::For
+                    | Keyword::In
+                    | Keyword::If
+                    | Keyword::While
+                    | Keyword::Where
+            ) | Token::Arrow
+                | Token::Colon
+                | Token::Plus
+                | Token::Greater
+        )
+    );
+    is_constructor.then_some(segments)
+}
+
+pub(super) fn lex(source: &str) -> Vec<(Token, Span)> {
+    let (tokens, _) = Lexer::lex(source);
+    tokens
+        .0
+        .into_iter()
+        .map(|token| (token.token().clone(), token.to_span()))
+        .filter(|(token, _)| {
+            !matches!(token, Token::EOF | Token::LineComment(..) | Token::BlockComment(..))
+        })
+        .collect()
+}
+
+/// Finds the module the cursor is in: the module of the file, or one of the
+/// `mod foo { ... }` modules written inside it.
+fn module_at(
+    def_map: &CrateDefMap,
+    file_id: FileId,
+    parsed_module: &ParsedModule,
+    byte_index: u32,
+) -> Option<LocalModuleId> {
+    let modules = def_map.modules();
+    let (index, _) = modules.iter().find(|(_, module)| {
+        module.location.file == file_id
+            && module.parent.map_or(true, |parent| modules[parent.0].location.file != file_id)
+    })?;
+
+    let mut module_id = LocalModuleId(index);
+    let mut items = &parsed_module.items;
+    'descend: loop {
+        for item in items {
+            if let ItemKind::Submodules(submodule) = &item.kind {
+                if item.span.start() <= byte_index && byte_index <= item.span.end() {
+                    let Some(child) = modules[module_id.0].children.get(&submodule.name) else {
+                        break 'descend;
+                    };
+                    module_id = *child;
+                    items = &submodule.contents.items;
+                    continue 'descend;
+                }
+            }
+        }
+        break;
+    }
+    Some(module_id)
+}
+
+/// Returns the function the cursor is in
+fn function_at(items: &[Item], byte_index: u32) -> Option<&NoirFunction> {
+    let mut functions = Vec::new();
+    collect_functions(items, &mut functions);
+    functions.into_iter().find(|function| {
+        let span = function_span(function);
+        span.start() <= byte_index && byte_index <= span.end()
+    })
+}
+
+fn collect_functions<'a>(items: &'a [Item], functions: &mut Vec<&'a NoirFunction>) {
+    for item in items {
+        match &item.kind {
+            ItemKind::Function(function) => functions.push(function),
+            ItemKind::Impl(type_impl) => {
+                functions.extend(type_impl.methods.iter().map(|(method, _)| method));
+            }
+            ItemKind::TraitImpl(trait_impl) => {
+                functions.extend(trait_impl.items.iter().filter_map(|item| match item {
+                    TraitImplItem::Function(method) => Some(method),
+                    _ => None,
+                }));
+            }
+            ItemKind::Submodules(submodule) => {
+                collect_functions(&submodule.contents.items, functions);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// The span of a function from its name to the end of its body, which covers its parameters
+fn function_span(function: &NoirFunction) -> Span {
+    Span::from(function.def.name.span().start()..function.def.span.end())
+}
+
+/// Collects the spans of the scopes in a function which the cursor is not in. Local variables
+/// declared in any of them can't be used at the cursor.
+struct ClosedScopes {
+    byte_index: u32,
+    spans: Vec<Span>,
+}
+
+impl ClosedScopes {
+    fn collect_block(&mut self, block: &BlockExpression) {
+        for statement in &block.statements {
+            self.collect_statement(statement);
+        }
+    }
+
+    fn collect_statement(&mut self, statement: &Statement) {
+        match &statement.kind {
+            StatementKind::Let(let_statement) => self.collect_expression(&let_statement.expression),
+            StatementKind::Constrain(constrain) => {
+                self.collect_expression(&constrain.0);
+                if let Some(message) = &constrain.1 {
+                    self.collect_expression(message);
+                }
+            }
+            StatementKind::Expression(expression)
+            | StatementKind::Semi(expression)
+            | StatementKind::Loop(expression) => self.collect_expression(expression),
+            StatementKind::Assign(assign) => self.collect_expression(&assign.expression),
+            StatementKind::For(for_loop) => {
+                match &for_loop.range {
+                    ForRange::Range(start, end) => {
+                        self.collect_expression(start);
+                        self.collect_expression(end);
+                    }
+                    ForRange::Array(array) => self.collect_expression(array),
+                }
+
+                // The loop variable is declared before the loop's body but only in scope inside it
+                let span = Span::from(for_loop.identifier.span().start()..for_loop.span.end());
+                self.collect_scope(for_loop.block.span, span, |this| {
+                    this.collect_expression(&for_loop.block);
+                });
+            }
+            StatementKind::While(while_statement) => {
+                self.collect_expression(&while_statement.condition);
+                self.collect_expression(&while_statement.block);
+            }
+            StatementKind::Comptime(statement) => self.collect_statement(statement),
+            StatementKind::Break | StatementKind::Continue | StatementKind::Error => (),
+        }
+    }
+
+    fn collect_expression(&mut self, expression: &Expression) {
+        let span = expression.span;
+        match &expression.kind {
+            ExpressionKind::Block(block) | ExpressionKind::Comptime(block, _) => {
+                self.collect_scope(span, span, |this| this.collect_block(block));
+            }
+            ExpressionKind::Lambda(lambda) => {
+                self.collect_scope(span, span, |this| this.collect_expression(&lambda.body));
+            }
+            ExpressionKind::Match(match_expression) => {
+                self.collect_expression(&match_expression.expression);
+                for (pattern, branch) in &match_expression.rules {
+                    let span = Span::from(pattern.span().start()..branch.span.end());
+                    self.collect_scope(span, span, |this| this.collect_expression(branch));
+                }
+            }
+            ExpressionKind::Literal(Literal::Array(array) | Literal::Slice(array)) => match array {
+                ArrayLiteral::Standard(elements) => self.collect_expressions(elements),
+                ArrayLiteral::Repeated { repeated_element, length } => {
+                    self.collect_expression(repeated_element);
+                    self.collect_expression(length);
+                }
+            },
+            ExpressionKind::Prefix(prefix) => self.collect_expression(&prefix.rhs),
+            ExpressionKind::Index(index) => {
+                self.collect_expression(&index.collection);
+                self.collect_expression(&index.index);
+            }
+            ExpressionKind::Call(call) => {
+                self.collect_expression(&call.func);
+                self.collect_expressions(&call.arguments);
+            }
+            ExpressionKind::MethodCall(call) => {
+                self.collect_expression(&call.object);
+                self.collect_expressions(&call.arguments);
+            }
+            ExpressionKind::Constructor(constructor) => {
+                for (_, field) in &constructor.fields {
+                    self.collect_expression(field);
+                }
+            }
+            ExpressionKind::MemberAccess(access) => self.collect_expression(&access.lhs),
+            ExpressionKind::Cast(cast) => self.collect_expression(&cast.lhs),
+            ExpressionKind::Infix(infix) => {
+                self.collect_expression(&infix.lhs);
+                self.collect_expression(&infix.rhs);
+            }
+            ExpressionKind::If(if_expression) => {
+                self.collect_expression(&if_expression.condition);
+                self.collect_expression(&if_expression.consequence);
+                if let Some(alternative) = &if_expression.alternative {
+                    self.collect_expression(alternative);
+                }
+            }
+            ExpressionKind::Tuple(elements) => self.collect_expressions(elements),
+            ExpressionKind::Parenthesized(expression) | ExpressionKind::Unquote(expression) => {
+                self.collect_expression(expression);
+            }
+            ExpressionKind::Literal(_)
+            | ExpressionKind::Variable(..)
+            | ExpressionKind::Quote(..)
+            | ExpressionKind::UnquoteMarker(_)
+            | ExpressionKind::Resolved(_)
+            | ExpressionKind::Error => (),
+        }
+    }
+
+    fn collect_expressions(&mut self, expressions: &[Expression]) {
+        for expression in expressions {
+            self.collect_expression(expression);
+        }
+    }
+
+    /// If the cursor is in `body_span` this looks for the closed scopes inside of it with
+    /// `collect_inner`. Otherwise the whole `scope_span` is a closed scope.
+    fn collect_scope(
+        &mut self,
+        body_span: Span,
+        scope_span: Span,
+        collect_inner: impl FnOnce(&mut Self),
+    ) {
+        if body_span.start() <= self.byte_index && self.byte_index <= body_span.end() {
+            collect_inner(self);
+        } else {
+            self.spans.push(scope_span);
+        }
+    }
+}
+
+/// True if the function's first parameter is `self`, meaning it can be called as a method
+pub(super) fn has_self_parameter(interner: &NodeInterner, func_meta: &FuncMeta) -> bool {
+    let Some((pattern, _, _)) = func_meta.parameters.0.first() else {
+        return false;
+    };
+    let pattern = match pattern {
+        HirPattern::Mutable(pattern, _) => pattern.as_ref(),
+        other => other,
+    };
+    matches!(pattern, HirPattern::Identifier(ident) if interner.definition_name(ident.id) == "self")
+}
+
+fn dereference(typ: Type) -> Type {
+    match typ.follow_bindings() {
+        Type::MutableReference(element) => dereference(*element),
+        other => other,
+    }
+}
+
+fn function_detail(func_meta: &FuncMeta) -> String {
+    let (_, typ) = func_meta.typ.unwrap_forall();
+    typ.to_string()
+}
+
+fn simple_item(label: &str, kind: CompletionItemKind, detail: Option<String>) -> CompletionItem {
+    CompletionItem { label: label.to_string(), kind: Some(kind), detail, ..Default::default() }
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use lsp_types::{
+        PartialResultParams, Position, TextDocumentIdentifier, TextDocumentPositionParams,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    /// Requests completions for `src`, which replaces the contents of the `completion`
+    /// test program's main.nr. The cursor is placed where `>|<` is written.
+    async fn completion_labels(src: &str) -> Vec<String> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("completion").await;
+
+        let (line, line_contents) =
+            src.lines().enumerate().find(|(_, line)| line.contains(">|<")).expect("Missing >|<");
+        let character = line_contents.find(">|<").unwrap();
+        let src = src.replace(">|<", "");
+        state.input_files.insert(noir_text_document.to_string(), src);
+
+        let params = CompletionParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                position: Position { line: line as u32, character: character as u32 },
+            },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+            context: None,
+        };
+
+        let response = on_completion_request(&mut state, params)
+            .await
+            .expect("Could not execute on_completion_request");
+
+        match response {
+            Some(CompletionResponse::Array(items)) => {
+                items.into_iter().map(|item| item.label).collect()
+            }
+            Some(CompletionResponse::List(list)) => {
+                list.items.into_iter().map(|item| item.label).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    #[test]
+    async fn test_completes_module_items_in_use() {
+        let src = r#"
+            mod things;
+            use things::>|<
+            fn main() {}
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["Describe", "Point", "origin"]);
+    }
+
+    #[test]
+    async fn test_completes_crate_names_after_dep() {
+        let src = r#"
+            use dep::>|<
+            fn main() {}
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["std"]);
+    }
+
+    #[test]
+    async fn test_completes_stdlib_modules_with_prefix() {
+        let src = r#"
+            use std::coll>|<
+            fn main() {}
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["collections"]);
+    }
+
+    #[test]
+    async fn test_completes_stdlib_functions() {
+        let src = r#"
+            fn main() {
+                let _ = std::hash::pedersen_h>|<
+            }
+        "#;
+        assert_eq!(
+            completion_labels(src).await,
+            vec!["pedersen_hash", "pedersen_hash_with_separator"]
+        );
+    }
+
+    #[test]
+    async fn test_completes_fields_and_methods_on_incomplete_statement() {
+        let src = r#"
+            mod things;
+            use things::Point;
+            fn main() {
+                let point = Point { x: 1, y: 2 };
+                let _ = point.>|<
+            }
+        "#;
+        // `into` comes from the stdlib's blanket `impl<T, U> Into<T> for U`
+        assert_eq!(completion_labels(src).await, vec!["describe", "into", "sum", "x", "y"]);
+    }
+
+    #[test]
+    async fn test_completes_methods_with_prefix_on_field_chain() {
+        let src = r#"
+            mod things;
+            use things::Point;
+            struct Line { start: Point, end: Point }
+            fn main() {
+                let line = Line { start: things::origin(), end: things::origin() };
+                line.start.s>|<
+            }
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["sum"]);
+    }
+
+    #[test]
+    async fn test_completes_missing_constructor_fields() {
+        let src = r#"
+            mod things;
+            use things::Point;
+            fn main() {
+                let point = Point { x: 1, >|<
+            }
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["y"]);
+    }
+
+    #[test]
+    async fn test_completes_members_on_incomplete_multi_line_statement() {
+        let src = r#"
+            mod things;
+            use things::Point;
+            fn main() {
+                let point = Point { x: 1, y: 2 };
+                if point.x == 1 {
+                    let _ = point
+                        .s>|<
+                }
+            }
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["sum"]);
+    }
+
+    #[test]
+    async fn test_does_not_complete_local_variables_out_of_scope() {
+        let src = r#"
+            fn main() {
+                let some_value = 1;
+                {
+                    let some_inner_value = 2;
+                }
+                let some_closure = |some_parameter| some_parameter;
+                for some_index in 0..2 {}
+                let _ = some>|<
+            }
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["some_closure", "some_value"]);
+    }
+
+    #[test]
+    async fn test_completes_local_variables_and_items() {
+        let src = r#"
+            fn some_function() {}
+            fn main() {
+                let some_value = 1;
+                let _ = some>|<
+            }
+        "#;
+        assert_eq!(completion_labels(src).await, vec!["some_function", "some_value"]);
+    }
+}
@@ -0,0 +1,453 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::{FileId, FileMap};
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, Position, Range, SymbolKind,
+    TextDocumentPositionParams,
+};
+use noirc_errors::Span;
+use noirc_frontend::{
+    hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId},
+    macros_api::NodeInterner,
+    node_interner::FuncId,
+};
+
+use crate::{byte_span_to_range, types::DocumentSymbolResult, LspState};
+
+use super::process_request;
+
+pub(crate) fn on_document_symbol_request(
+    state: &mut LspState,
+    params: DocumentSymbolParams,
+) -> impl Future<Output = Result<DocumentSymbolResult, ResponseError>> {
+    // The symbols don't depend on a position, but we still need one to process the request
+    let text_document_position_params = TextDocumentPositionParams {
+        text_document: params.text_document,
+        position: Position::default(),
+    };
+
+    let result = process_request(state, text_document_position_params, |args| {
+        let file_id = args.location.file;
+        let symbols = args
+            .def_maps
+            .values()
+            .flat_map(|def_map| {
+                let builder = DocumentSymbolBuilder {
+                    interner: args.interner,
+                    def_map,
+                    files: args.files,
+                    file_id,
+                };
+                modules_in_file(def_map, file_id)
+                    .flat_map(|module_id| builder.module_contents(module_id))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Some(DocumentSymbolResponse::Nested(symbols))
+    });
+    future::ready(result)
+}
+
+/// Returns the modules whose contents start at the top of the given file: the root module of
+/// a crate, or a module declared with `mod foo;` in another file.
+pub(super) fn modules_in_file(
+    def_map: &CrateDefMap,
+    file_id: FileId,
+) -> impl Iterator<Item = LocalModuleId> + '_ {
+    let modules = def_map.modules();
+    modules.iter().filter_map(move |(index, module)| {
+        let is_file_module = module.location.file == file_id
+            && module.parent.map_or(true, |parent| modules[parent.0].location.file != file_id);
+        is_file_module.then_some(LocalModuleId(index))
+    })
+}
+
+/// Builds the outline of a file from the items the def collector found in its modules.
+/// This includes any item generated by macros, as long as it has a location in the file.
+pub(super) struct DocumentSymbolBuilder<'a> {
+    pub(super) interner: &'a NodeInterner,
+    pub(super) def_map: &'a CrateDefMap,
+    pub(super) files: &'a FileMap,
+    pub(super) file_id: FileId,
+}
+
+impl<'a> DocumentSymbolBuilder<'a> {
+    /// Returns a symbol for each item declared in the given module, plus one for each impl
+    /// written in it. Nested modules written in the same file include their own items.
+    pub(super) fn module_contents(&self, module_id: LocalModuleId) -> Vec<DocumentSymbol> {
+        let module = &self.def_map.modules()[module_id.0];
+
+        let definitions = module.type_definitions().chain(module.value_definitions());
+        let mut symbols: Vec<DocumentSymbol> = definitions
+            .filter_map(|module_def_id| self.definition_symbol(module_id, module_def_id))
+            .collect();
+        symbols.extend(self.impl_symbols(module_id));
+
+        sort_symbols(&mut symbols);
+        symbols
+    }
+
+    fn definition_symbol(
+        &self,
+        module_id: LocalModuleId,
+        module_def_id: ModuleDefId,
+    ) -> Option<DocumentSymbol> {
+        match module_def_id {
+            ModuleDefId::ModuleId(child_id) => {
+                let modules = self.def_map.modules();
+                let child = &modules[child_id.local_id.0];
+                let (name, _) = modules[module_id.0]
+                    .children
+                    .iter()
+                    .find(|(_, local_id)| **local_id == child_id.local_id)?;
+
+                // A `mod foo;` declaration: the contents of the module live in another file
+                if child.location.file != self.file_id {
+                    let range = self.range(child.location.span)?;
+                    return Some(new_symbol(
+                        &name.0.contents,
+                        SymbolKind::MODULE,
+                        None,
+                        range,
+                        range,
+                    ));
+                }
+
+                let selection_range = self.range(child.location.span)?;
+                let children = self.module_contents(child_id.local_id);
+                let detail = child.is_contract.then(|| "contract".to_string());
+                let mut symbol = new_symbol(
+                    &name.0.contents,
+                    SymbolKind::MODULE,
+                    detail,
+                    selection_range,
+                    selection_range,
+                );
+                symbol.range = enclosing_range(symbol.range, &children);
+                symbol.children = Some(children);
+                Some(symbol)
+            }
+            ModuleDefId::FunctionId(func_id) => self.function_symbol(func_id, SymbolKind::FUNCTION),
+            ModuleDefId::TypeId(struct_id) => {
+                let struct_type = self.interner.get_struct(struct_id);
+                let struct_type = struct_type.borrow();
+                if struct_type.location.file != self.file_id {
+                    return None;
+                }
+
+                let fields = struct_type.get_fields_as_written();
+                let children: Vec<DocumentSymbol> = fields
+                    .iter()
+                    .filter_map(|(name, typ)| {
+                        let range = self.range(name.span())?;
+                        let detail = Some(typ.to_string());
+                        Some(new_symbol(&name.0.contents, SymbolKind::FIELD, detail, range, range))
+                    })
+                    .collect();
+
+                let selection_range = self.range(struct_type.name.span())?;
+                let mut symbol = new_symbol(
+                    &struct_type.name.0.contents,
+                    SymbolKind::STRUCT,
+                    None,
+                    selection_range,
+                    selection_range,
+                );
+                symbol.range = enclosing_range(symbol.range, &children);
+                symbol.children = Some(children);
+                Some(symbol)
+            }
+            ModuleDefId::EnumId(enum_id) => {
+                let enum_type = self.interner.get_enum(enum_id);
+                let enum_type = enum_type.borrow();
+                if enum_type.location.file != self.file_id {
+                    return None;
+                }
+
+                let variants = enum_type.get_variants_as_written();
+                let children: Vec<DocumentSymbol> = variants
+                    .iter()
+                    .filter_map(|(name, fields)| {
+                        let range = self.range(name.span())?;
+                        let detail = (!fields.is_empty()).then(|| {
+                            let fields: Vec<_> = fields.iter().map(ToString::to_string).collect();
+                            format!("({})", fields.join(", "))
+                        });
+                        Some(new_symbol(
+                            &name.0.contents,
+                            SymbolKind::ENUM_MEMBER,
+                            detail,
+                            range,
+                            range,
+                        ))
+                    })
+                    .collect();
+
+                let selection_range = self.range(enum_type.name.span())?;
+                let mut symbol = new_symbol(
+                    &enum_type.name.0.contents,
+                    SymbolKind::ENUM,
+                    None,
+                    selection_range,
+                    selection_range,
+                );
+                symbol.range = enclosing_range(symbol.range, &children);
+                symbol.children = Some(children);
+                Some(symbol)
+            }
+            ModuleDefId::TraitId(trait_id) => {
+                let trait_ = self.interner.get_trait(trait_id);
+                if trait_.location.file != self.file_id {
+                    return None;
+                }
+
+                let children: Vec<DocumentSymbol> = trait_
+                    .methods
+                    .iter()
+                    .filter_map(|method| {
+                        let range = self.range(method.location.span)?;
+                        let detail = Some(method.typ.to_string());
+                        Some(new_symbol(
+                            &method.name.0.contents,
+                            SymbolKind::METHOD,
+                            detail,
+                            range,
+                            range,
+                        ))
+                    })
+                    .collect();
+
+                let selection_range = self.range(trait_.name.span())?;
+                let mut symbol = new_symbol(
+                    &trait_.name.0.contents,
+                    SymbolKind::INTERFACE,
+                    None,
+                    selection_range,
+                    selection_range,
+                );
+                symbol.range = enclosing_range(symbol.range, &children);
+                symbol.children = Some(children);
+                Some(symbol)
+            }
+            ModuleDefId::TypeAliasId(type_alias_id) => {
+                let type_alias = self.interner.get_type_alias(type_alias_id);
+                let type_alias = type_alias.borrow();
+                if type_alias.location.file != self.file_id {
+                    return None;
+                }
+
+                let range = self.range(type_alias.name.span())?;
+                let detail = Some(type_alias.typ.to_string());
+                Some(new_symbol(
+                    &type_alias.name.0.contents,
+                    SymbolKind::TYPE_PARAMETER,
+                    detail,
+                    range,
+                    range,
+                ))
+            }
+            ModuleDefId::GlobalId(global_id) => {
+                let global = self.interner.get_global(global_id);
+                if global.location.file != self.file_id {
+                    return None;
+                }
+
+                let range = self.range(global.location.span)?;
+                let detail = Some(self.interner.definition_type(global.definition_id).to_string());
+                Some(new_symbol(
+                    &global.ident.0.contents,
+                    SymbolKind::CONSTANT,
+                    detail,
+                    range,
+                    range,
+                ))
+            }
+        }
+    }
+
+    /// Returns a symbol for each type with methods defined in the given module, named like
+    /// the `impl` they come from. All `impl` blocks for the same type and trait are merged.
+    fn impl_symbols(&self, module_id: LocalModuleId) -> Vec<DocumentSymbol> {
+        let is_in_module = |func_id: &FuncId| {
+            let module = self.interner.function_module(*func_id);
+            module.krate == self.def_map.krate() && module.local_id == module_id
+        };
+
+        let mut impls: Vec<(String, Vec<FuncId>)> = Vec::new();
+
+        let struct_ids = self.def_map.modules().iter().flat_map(|(_, module)| {
+            module.type_definitions().filter_map(|module_def_id| match module_def_id {
+                ModuleDefId::TypeId(struct_id) => Some(struct_id),
+                _ => None,
+            })
+        });
+        for struct_id in struct_ids {
+            let methods: Vec<FuncId> = self
+                .interner
+                .get_struct_methods(struct_id)
+                .into_iter()
+                .flat_map(|methods| methods.direct)
+                .filter(is_in_module)
+                .collect();
+            if !methods.is_empty() {
+                let struct_type = self.interner.get_struct(struct_id);
+                let name = format!("impl {}", struct_type.borrow().name);
+                impls.push((name, methods));
+            }
+        }
+
+        for trait_impl in self.interner.all_trait_implementations() {
+            let trait_impl = trait_impl.borrow();
+            if trait_impl.file != self.file_id || !trait_impl.methods.iter().all(is_in_module) {
+                continue;
+            }
+            let name = format!("impl {} for {}", trait_impl.ident, trait_impl.typ);
+            impls.push((name, trait_impl.methods.clone()));
+        }
+
+        impls
+            .into_iter()
+            .filter_map(|(name, methods)| {
+                let mut children: Vec<DocumentSymbol> = methods
+                    .into_iter()
+                    .filter_map(|func_id| self.function_symbol(func_id, SymbolKind::METHOD))
+                    .collect();
+                sort_symbols(&mut children);
+
+                let first_method = children.first()?;
+                let mut symbol = new_symbol(
+                    &name,
+                    SymbolKind::OBJECT,
+                    None,
+                    first_method.range,
+                    first_method.selection_range,
+                );
+                symbol.range = enclosing_range(symbol.range, &children);
+                symbol.children = Some(children);
+                Some(symbol)
+            })
+            .collect()
+    }
+
+    fn function_symbol(&self, func_id: FuncId, kind: SymbolKind) -> Option<DocumentSymbol> {
+        let func_meta = self.interner.function_meta(&func_id);
+        if func_meta.name.location.file != self.file_id {
+            return None;
+        }
+
+        // The function's definition location covers its body, so the symbol spans from
+        // the name to the end of the body.
+        let name_span = func_meta.name.location.span;
+        let definition_id = self.interner.function_definition_id(func_id);
+        let body_location = self.interner.definition(definition_id).location;
+        let span = if body_location.file == self.file_id {
+            Span::from(name_span.start()..body_location.span.end().max(name_span.end()))
+        } else {
+            name_span
+        };
+
+        let name = self.interner.function_name(&func_id);
+        let (_, typ) = func_meta.typ.unwrap_forall();
+        let selection_range = self.range(name_span)?;
+        let range = self.range(span)?;
+        Some(new_symbol(name, kind, Some(typ.to_string()), range, selection_range))
+    }
+
+    fn range(&self, span: Span) -> Option<Range> {
+        byte_span_to_range(self.files, self.file_id, span.into())
+    }
+}
+
+#[allow(deprecated)]
+fn new_symbol(
+    name: &str,
+    kind: SymbolKind,
+    detail: Option<String>,
+    range: Range,
+    selection_range: Range,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: name.to_string(),
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: None,
+    }
+}
+
+/// Returns `range` extended to cover the ranges of all of the given symbols
+fn enclosing_range(range: Range, symbols: &[DocumentSymbol]) -> Range {
+    let position_key = |position: &Position| (position.line, position.character);
+    symbols.iter().fold(range, |range, symbol| Range {
+        start: std::cmp::min_by_key(range.start, symbol.range.start, position_key),
+        end: std::cmp::max_by_key(range.end, symbol.range.end, position_key),
+    })
+}
+
+fn sort_symbols(symbols: &mut [DocumentSymbol]) {
+    symbols.sort_by_key(|symbol| (symbol.range.start.line, symbol.range.start.character));
+}
+
+#[cfg(test)]
+mod document_symbol_tests {
+    use lsp_types::{PartialResultParams, TextDocumentIdentifier, WorkDoneProgressParams};
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    /// Returns each symbol as `name (kind)`, indented according to how deeply it is nested
+    fn outline(symbols: &[DocumentSymbol], depth: usize, lines: &mut Vec<String>) {
+        for symbol in symbols {
+            lines.push(format!("{}{} ({:?})", "  ".repeat(depth), symbol.name, symbol.kind));
+            outline(symbol.children.as_deref().unwrap_or_default(), depth + 1, lines);
+        }
+    }
+
+    #[test]
+    async fn test_document_symbols() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+
+        let response = on_document_symbol_request(&mut state, params)
+            .await
+            .expect("Could not execute on_document_symbol_request");
+        let Some(DocumentSymbolResponse::Nested(symbols)) = response else {
+            panic!("Expected nested document symbols, got {:?}", response);
+        };
+
+        let mut lines = Vec::new();
+        outline(&symbols, 0, &mut lines);
+        assert_eq!(
+            lines,
+            vec![
+                "other (Module)",
+                "LIMIT (Constant)",
+                "Point (Struct)",
+                "  x (Field)",
+                "  y (Field)",
+                "impl Point (Object)",
+                "  new (Method)",
+                "  sum (Method)",
+                "Describe (Interface)",
+                "  describe (Method)",
+                "impl Describe for Point (Object)",
+                "  describe (Method)",
+                "Alias (TypeParameter)",
+                "nested (Module)",
+                "  helper (Function)",
+                "main (Function)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,425 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::{codespan_files::Files, FileMap};
+use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind};
+use noirc_errors::Location;
+use noirc_frontend::{
+    ast::Visibility,
+    hir::def_map::{LocalModuleId, ModuleDefId, ModuleId},
+    hir_def::stmt::HirPattern,
+    node_interner::{DefinitionId, FuncId, GlobalId, ReferenceId, StructId, TraitId, TypeAliasId},
+    StructType, Type, TypeVariable,
+};
+
+use crate::{types::HoverResult, LspState};
+
+use super::{process_request, ProcessRequestCallbackArgs};
+
+pub(crate) fn on_hover_request(
+    state: &mut LspState,
+    params: HoverParams,
+) -> impl Future<Output = Result<HoverResult, ResponseError>> {
+    let result = process_request(state, params.text_document_position_params, |args| {
+        args.interner.find_referenced(args.location).map(|reference| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format_reference(reference, &args),
+            }),
+            range: None,
+        })
+    });
+
+    future::ready(result)
+}
+
+fn format_reference(reference: ReferenceId, args: &ProcessRequestCallbackArgs) -> String {
+    let (signature, module) = match reference {
+        ReferenceId::Struct(id) => format_struct(id, args),
+        ReferenceId::StructMember(id, field_index) => format_struct_member(id, field_index, args),
+        ReferenceId::Trait(id) => format_trait(id, args),
+        ReferenceId::Global(id) => format_global(id, args),
+        ReferenceId::Function(id) => format_function(id, args),
+        ReferenceId::Alias(id) => format_alias(id, args),
+        ReferenceId::Local(id) => (format_local(id, args), None),
+    };
+
+    let mut string = String::new();
+    if let Some(module_path) = module.and_then(|module| format_module_path(module, args)) {
+        string.push_str(&format!("```noir\n{module_path}\n```\n"));
+    }
+    string.push_str(&format!("```noir\n{signature}\n```"));
+
+    if !matches!(reference, ReferenceId::Local(_)) {
+        let location = args.interner.reference_location(reference);
+        if let Some(doc_comments) = doc_comments_before(location, args.files) {
+            string.push_str("\n\n---\n\n");
+            string.push_str(&doc_comments);
+        }
+    }
+
+    string
+}
+
+fn format_struct(id: StructId, args: &ProcessRequestCallbackArgs) -> (String, Option<ModuleId>) {
+    let struct_type = args.interner.get_struct(id);
+    let struct_type = struct_type.borrow();
+
+    let mut string =
+        format!("struct {}{} {{\n", struct_type.name, format_struct_generics(&struct_type));
+    for (name, typ) in struct_type.get_fields_as_written() {
+        string.push_str(&format!("    {name}: {typ},\n"));
+    }
+    string.push('}');
+
+    (string, parent_module(id.module_id(), args))
+}
+
+fn format_struct_member(
+    id: StructId,
+    field_index: usize,
+    args: &ProcessRequestCallbackArgs,
+) -> (String, Option<ModuleId>) {
+    let struct_type = args.interner.get_struct(id);
+    let struct_type = struct_type.borrow();
+    let (name, typ) = &struct_type.get_fields_as_written()[field_index];
+
+    let string =
+        format!("{}{}\n{name}: {typ}", struct_type.name, format_struct_generics(&struct_type));
+
+    (string, parent_module(id.module_id(), args))
+}
+
+fn format_trait(id: TraitId, args: &ProcessRequestCallbackArgs) -> (String, Option<ModuleId>) {
+    let trait_ = args.interner.get_trait(id);
+    (format!("trait {}", trait_.name), parent_module(id.0, args))
+}
+
+fn format_global(id: GlobalId, args: &ProcessRequestCallbackArgs) -> (String, Option<ModuleId>) {
+    let global_info = args.interner.get_global(id);
+    let typ = args.interner.definition_type(global_info.definition_id);
+
+    let string = format!("global {}: {typ}", global_info.ident);
+    (string, defining_module(ModuleDefId::GlobalId(id), args))
+}
+
+fn format_alias(id: TypeAliasId, args: &ProcessRequestCallbackArgs) -> (String, Option<ModuleId>) {
+    let type_alias = args.interner.get_type_alias(id);
+    let type_alias = type_alias.borrow();
+
+    let string = format!("type {} = {}", type_alias.name, type_alias.typ);
+    (string, defining_module(ModuleDefId::TypeAliasId(id), args))
+}
+
+fn format_function(id: FuncId, args: &ProcessRequestCallbackArgs) -> (String, Option<ModuleId>) {
+    let func_meta = args.interner.function_meta(&id);
+    let modifiers = args.interner.function_modifiers(&id);
+
+    let mut string = String::new();
+
+    let visibility = modifiers.visibility.to_string();
+    if !visibility.is_empty() {
+        string.push_str(&visibility);
+        string.push(' ');
+    }
+    if modifiers.is_comptime {
+        string.push_str("comptime ");
+    }
+    if modifiers.is_unconstrained {
+        string.push_str("unconstrained ");
+    }
+
+    string.push_str("fn ");
+    string.push_str(&modifiers.name);
+
+    if !func_meta.direct_generics.is_empty() {
+        let generics: Vec<_> =
+            func_meta.direct_generics.iter().map(|(name, _)| name.to_string()).collect();
+        string.push_str(&format!("<{}>", generics.join(", ")));
+    }
+
+    let parameters: Vec<_> = func_meta
+        .parameters
+        .iter()
+        .map(|(pattern, typ, visibility)| {
+            let pattern = format_pattern(pattern, args);
+            if pattern == "self" {
+                pattern
+            } else {
+                format!("{pattern}: {}{typ}", format_visibility(*visibility))
+            }
+        })
+        .collect();
+    string.push_str(&format!("({})", parameters.join(", ")));
+
+    let return_type = func_meta.return_type();
+    if *return_type != Type::Unit {
+        string.push_str(&format!(
+            " -> {}{return_type}",
+            format_visibility(func_meta.return_visibility)
+        ));
+    }
+
+    if !func_meta.trait_constraints.is_empty() {
+        string.push_str("\nwhere");
+        for constraint in &func_meta.trait_constraints {
+            let trait_ = args.interner.get_trait(constraint.trait_id);
+            string.push_str(&format!("\n    {}: {}", constraint.typ, trait_.name));
+            if !constraint.trait_generics.is_empty() {
+                let generics: Vec<_> =
+                    constraint.trait_generics.iter().map(ToString::to_string).collect();
+                string.push_str(&format!("<{}>", generics.join(", ")));
+            }
+            string.push(',');
+        }
+    }
+
+    (string, Some(args.interner.function_module(id)))
+}
+
+fn format_local(id: DefinitionId, args: &ProcessRequestCallbackArgs) -> String {
+    let definition_info = args.interner.definition(id);
+    let typ = args.interner.definition_type(id);
+
+    let mutable = if definition_info.mutable { "mut " } else { "" };
+    format!("let {mutable}{}: {typ}", definition_info.name)
+}
+
+fn format_pattern(pattern: &HirPattern, args: &ProcessRequestCallbackArgs) -> String {
+    match pattern {
+        HirPattern::Identifier(ident) => args.interner.definition_name(ident.id).to_string(),
+        HirPattern::Mutable(pattern, _) => format!("mut {}", format_pattern(pattern, args)),
+        HirPattern::Tuple(patterns, _) => {
+            let patterns: Vec<_> =
+                patterns.iter().map(|pattern| format_pattern(pattern, args)).collect();
+            format!("({})", patterns.join(", "))
+        }
+        HirPattern::Struct(typ, fields, _) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(name, pattern)| {
+                    let pattern = format_pattern(pattern, args);
+                    if pattern == name.0.contents {
+                        pattern
+                    } else {
+                        format!("{name}: {pattern}")
+                    }
+                })
+                .collect();
+            format!("{typ} {{ {} }}", fields.join(", "))
+        }
+        // Literal and variant patterns are only allowed in match arms, never in parameters
+        HirPattern::Literal(..) | HirPattern::Variant(..) => "_".to_string(),
+    }
+}
+
+fn format_visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "pub ",
+        Visibility::Private | Visibility::DataBus => "",
+    }
+}
+
+/// Struct generics don't keep their names around, so we recover them from
+/// the fields that use them. Generics that no field uses are shown as `_`.
+fn format_struct_generics(struct_type: &StructType) -> String {
+    if struct_type.generics.is_empty() {
+        return String::new();
+    }
+
+    let generics: Vec<_> = struct_type
+        .generics
+        .iter()
+        .map(|generic| {
+            struct_type
+                .get_fields_as_written()
+                .iter()
+                .find_map(|(_, typ)| find_generic_name(typ, generic))
+                .unwrap_or_else(|| "_".to_string())
+        })
+        .collect();
+
+    format!("<{}>", generics.join(", "))
+}
+
+fn find_generic_name(typ: &Type, generic: &TypeVariable) -> Option<String> {
+    match typ {
+        Type::NamedGeneric(type_variable, name) if type_variable.id() == generic.id() => {
+            Some(name.to_string())
+        }
+        Type::Array(length, element) => {
+            find_generic_name(length, generic).or_else(|| find_generic_name(element, generic))
+        }
+        Type::Slice(element) | Type::String(element) | Type::MutableReference(element) => {
+            find_generic_name(element, generic)
+        }
+        Type::FmtString(length, fields) => {
+            find_generic_name(length, generic).or_else(|| find_generic_name(fields, generic))
+        }
+        Type::Tuple(types) | Type::Struct(_, types) | Type::Alias(_, types) => {
+            types.iter().find_map(|typ| find_generic_name(typ, generic))
+        }
+        Type::Function(arguments, return_type, environment) => arguments
+            .iter()
+            .chain([return_type.as_ref(), environment.as_ref()])
+            .find_map(|typ| find_generic_name(typ, generic)),
+        _ => None,
+    }
+}
+
+fn parent_module(module: ModuleId, args: &ProcessRequestCallbackArgs) -> Option<ModuleId> {
+    let def_map = args.def_maps.get(&module.krate)?;
+    let parent = def_map.modules().get(module.local_id.0)?.parent?;
+    Some(ModuleId { krate: module.krate, local_id: parent })
+}
+
+/// Finds the module an item is declared in by looking for it in each module's definitions.
+fn defining_module(item: ModuleDefId, args: &ProcessRequestCallbackArgs) -> Option<ModuleId> {
+    args.def_maps.iter().find_map(|(krate, def_map)| {
+        def_map.modules().iter().find_map(|(index, module)| {
+            let mut definitions = module.type_definitions().chain(module.value_definitions());
+            definitions
+                .any(|definition| definition == item)
+                .then_some(ModuleId { krate: *krate, local_id: LocalModuleId(index) })
+        })
+    })
+}
+
+fn format_module_path(module: ModuleId, args: &ProcessRequestCallbackArgs) -> Option<String> {
+    let def_map = args.def_maps.get(&module.krate)?;
+    let module_data = def_map.modules().get(module.local_id.0)?;
+    let path = def_map.get_module_path_with_separator(module.local_id.0, module_data.parent, "::");
+    (!path.is_empty()).then_some(path)
+}
+
+/// Collects the `///` doc comments written right above the line of the given [Location],
+/// skipping over any attributes in between.
+fn doc_comments_before(location: Location, files: &FileMap) -> Option<String> {
+    let source = files.source(location.file).ok()?;
+    let start = location.span.start() as usize;
+    let line_start = source.get(..start)?.rfind('\n').map_or(0, |index| index + 1);
+
+    let mut lines = Vec::new();
+    for line in source[..line_start].lines().rev() {
+        let line = line.trim();
+        if let Some(doc) = line.strip_prefix("///") {
+            lines.push(doc.strip_prefix(' ').unwrap_or(doc));
+        } else if !line.starts_with("#[") {
+            break;
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod hover_tests {
+    use acvm::blackbox_solver::StubbedBlackBoxSolver;
+    use async_lsp::ClientSocket;
+    use lsp_types::{Position, TextDocumentIdentifier, TextDocumentPositionParams, Url};
+    use tokio::test;
+
+    use super::*;
+
+    async fn hover_text(file: &str, position: Position) -> String {
+        let client = ClientSocket::new_closed();
+        let mut state = LspState::new(&client, StubbedBlackBoxSolver);
+
+        let root_path = std::env::current_dir()
+            .unwrap()
+            .join("test_programs/hover")
+            .canonicalize()
+            .expect("Could not resolve root path");
+        let noir_text_document = Url::from_file_path(root_path.join(file).as_path())
+            .expect("Could not convert text document path to URI");
+        let root_uri = Some(
+            Url::from_file_path(root_path.as_path()).expect("Could not convert root path to URI"),
+        );
+
+        #[allow(deprecated)]
+        let initialize_params = lsp_types::InitializeParams {
+            process_id: Default::default(),
+            root_path: None,
+            root_uri,
+            initialization_options: None,
+            capabilities: Default::default(),
+            trace: Some(lsp_types::TraceValue::Verbose),
+            workspace_folders: None,
+            client_info: None,
+            locale: None,
+        };
+        let _initialize_response = crate::requests::on_initialize(&mut state, initialize_params)
+            .await
+            .expect("Could not initialize LSP server");
+
+        let params = HoverParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+        };
+
+        let hover = on_hover_request(&mut state, params)
+            .await
+            .expect("Could execute on_hover_request")
+            .expect("Expected a hover result");
+
+        let HoverContents::Markup(markup) = hover.contents else {
+            panic!("Expected markup hover contents");
+        };
+        markup.value
+    }
+
+    #[test]
+    async fn hover_on_function_call_shows_signature_and_docs() {
+        let text = hover_text("src/main.nr", Position { line: 10, character: 19 }).await;
+        assert!(text.contains("fn double<T>(value: T) -> T\nwhere\n    T: Add,"));
+        assert!(text.contains("Doubles the given value.\n\nWorks for any type"));
+    }
+
+    #[test]
+    async fn hover_on_main_shows_visibilities() {
+        let text = hover_text("src/main.nr", Position { line: 7, character: 4 }).await;
+        assert!(text.contains("fn main(x: Field, y: pub Field) -> pub Field"));
+    }
+
+    #[test]
+    async fn hover_on_local_shows_inferred_type() {
+        let text = hover_text("src/main.nr", Position { line: 9, character: 9 }).await;
+        assert!(text.contains("let sum: Field"));
+    }
+
+    #[test]
+    async fn hover_on_struct_shows_fields_and_module() {
+        let text = hover_text("src/main.nr", Position { line: 8, character: 18 }).await;
+        assert!(text.contains("```noir\ngeometry\n```"));
+        assert!(text.contains("struct Point {\n    x: Field,\n    y: Field,\n}"));
+        assert!(text.contains("A point in the plane."));
+    }
+
+    #[test]
+    async fn hover_on_member_access_shows_field_type() {
+        let text = hover_text("src/main.nr", Position { line: 12, character: 10 }).await;
+        assert!(text.contains("Point\nx: Field"));
+        assert!(text.contains("The horizontal coordinate."));
+    }
+
+    #[test]
+    async fn hover_on_global_shows_type_and_docs() {
+        let text = hover_text("src/main.nr", Position { line: 11, character: 24 }).await;
+        assert!(text.contains("global LIMIT: Field"));
+        assert!(text.contains("The largest coordinate we accept."));
+    }
+
+    #[test]
+    async fn hover_on_method_shows_self() {
+        let text = hover_text("src/geometry.nr", Position { line: 8, character: 12 }).await;
+        assert!(text.contains("pub fn sum(self) -> Field"));
+    }
+}
@@ -0,0 +1,416 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::{codespan_files::Files, FileId, FileMap};
+use lsp_types::{
+    InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams, Position, Range,
+    TextDocumentPositionParams,
+};
+use noirc_errors::Span;
+use noirc_frontend::{
+    hir_def::{
+        expr::{HirArrayLiteral, HirCallExpression, HirExpression, HirLiteral},
+        stmt::{HirPattern, HirStatement},
+    },
+    macros_api::NodeInterner,
+    node_interner::{DefinitionKind, ExprId, ReferenceId, StmtId},
+    token::Token,
+    Type,
+};
+
+use crate::{byte_span_to_range, types::InlayHintResult, LspState};
+
+use super::{completion::lex, process_request};
+
+pub(crate) fn on_inlay_hint_request(
+    state: &mut LspState,
+    params: InlayHintParams,
+) -> impl Future<Output = Result<InlayHintResult, ResponseError>> {
+    let text_document_position_params = TextDocumentPositionParams {
+        text_document: params.text_document,
+        position: params.range.start,
+    };
+
+    let result = process_request(state, text_document_position_params, |args| {
+        let file_id = args.location.file;
+        let source = args.files.source(file_id).ok()?;
+
+        let mut collector = InlayHintCollector {
+            interner: args.interner,
+            files: args.files,
+            file_id,
+            source,
+            tokens: lex(source),
+            range: params.range,
+            hints: Vec::new(),
+        };
+        collector.collect_in_file();
+
+        let mut hints = collector.hints;
+        hints.sort_by_key(|hint| hint.position);
+        Some(hints)
+    });
+    future::ready(result)
+}
+
+/// Walks the type-checked HIR of every function in a file, collecting a hint for each
+/// binding whose type was inferred and each argument passed to a named function parameter.
+struct InlayHintCollector<'a> {
+    interner: &'a NodeInterner,
+    files: &'a FileMap,
+    file_id: FileId,
+    source: &'a str,
+    tokens: Vec<(Token, Span)>,
+    range: Range,
+    hints: Vec<InlayHint>,
+}
+
+impl<'a> InlayHintCollector<'a> {
+    fn collect_in_file(&mut self) {
+        for (reference, _) in self.interner.declarations_in_file(self.file_id) {
+            match reference {
+                ReferenceId::Function(func_id) => {
+                    let body = self.interner.function(&func_id).as_expr();
+                    self.collect_in_expression(body);
+                }
+                ReferenceId::Global(global_id) => {
+                    let let_statement = self.interner.get_global(global_id).let_statement;
+                    self.collect_in_statement(let_statement);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn collect_in_statement(&mut self, statement: StmtId) {
+        match self.interner.statement(&statement) {
+            HirStatement::Let(let_statement) => {
+                if !self.is_annotated(&let_statement.pattern) {
+                    self.collect_type_hints(&let_statement.pattern);
+                }
+                self.collect_in_expression(let_statement.expression);
+            }
+            HirStatement::Constrain(constrain) => {
+                self.collect_in_expression(constrain.0);
+                if let Some(message) = constrain.2 {
+                    self.collect_in_expression(message);
+                }
+            }
+            HirStatement::Assign(assign) => self.collect_in_expression(assign.expression),
+            HirStatement::For(for_loop) => {
+                self.collect_in_expression(for_loop.start_range);
+                self.collect_in_expression(for_loop.end_range);
+                self.collect_in_expression(for_loop.block);
+            }
+            HirStatement::While(while_loop) => {
+                self.collect_in_expression(while_loop.condition);
+                self.collect_in_expression(while_loop.block);
+            }
+            HirStatement::Loop(block) => self.collect_in_expression(block),
+            HirStatement::Expression(expression) | HirStatement::Semi(expression) => {
+                self.collect_in_expression(expression);
+            }
+            HirStatement::Comptime(statement) => self.collect_in_statement(statement),
+            HirStatement::Break | HirStatement::Continue | HirStatement::Error => (),
+        }
+    }
+
+    fn collect_in_expression(&mut self, expression: ExprId) {
+        match self.interner.expression(&expression) {
+            HirExpression::Literal(literal) => match literal {
+                HirLiteral::Array(array) | HirLiteral::Slice(array) => match array {
+                    HirArrayLiteral::Standard(elements) => self.collect_in_expressions(&elements),
+                    HirArrayLiteral::Repeated { repeated_element, .. } => {
+                        self.collect_in_expression(repeated_element);
+                    }
+                },
+                HirLiteral::FmtStr(_, captures) => self.collect_in_expressions(&captures),
+                HirLiteral::Bool(_)
+                | HirLiteral::Integer(..)
+                | HirLiteral::Str(_)
+                | HirLiteral::Unit => (),
+            },
+            HirExpression::Block(block) | HirExpression::Comptime(block) => {
+                for statement in block.statements {
+                    self.collect_in_statement(statement);
+                }
+            }
+            HirExpression::Prefix(prefix) => self.collect_in_expression(prefix.rhs),
+            HirExpression::Infix(infix) => {
+                self.collect_in_expression(infix.lhs);
+                self.collect_in_expression(infix.rhs);
+            }
+            HirExpression::Index(index) => {
+                self.collect_in_expression(index.collection);
+                self.collect_in_expression(index.index);
+            }
+            HirExpression::Constructor(constructor) => {
+                for (_, field) in constructor.fields {
+                    self.collect_in_expression(field);
+                }
+            }
+            HirExpression::EnumConstructor(constructor) => {
+                self.collect_in_expressions(&constructor.arguments);
+            }
+            HirExpression::MemberAccess(member_access) => {
+                self.collect_in_expression(member_access.lhs);
+            }
+            HirExpression::Call(call) => {
+                self.collect_parameter_hints(&call);
+                self.collect_in_expression(call.func);
+                self.collect_in_expressions(&call.arguments);
+            }
+            HirExpression::MethodCall(method_call) => {
+                self.collect_in_expression(method_call.object);
+                self.collect_in_expressions(&method_call.arguments);
+            }
+            HirExpression::Cast(cast) => self.collect_in_expression(cast.lhs),
+            HirExpression::If(if_expression) => {
+                self.collect_in_expression(if_expression.condition);
+                self.collect_in_expression(if_expression.consequence);
+                if let Some(alternative) = if_expression.alternative {
+                    self.collect_in_expression(alternative);
+                }
+            }
+            HirExpression::Match(match_expression) => {
+                self.collect_in_expression(match_expression.expression);
+                for (pattern, branch) in &match_expression.rules {
+                    self.collect_type_hints(pattern);
+                    self.collect_in_expression(*branch);
+                }
+            }
+            HirExpression::Tuple(elements) => self.collect_in_expressions(&elements),
+            HirExpression::Lambda(lambda) => {
+                for (parameter, _) in &lambda.parameters {
+                    if !self.is_annotated(parameter) {
+                        self.collect_type_hints(parameter);
+                    }
+                }
+                self.collect_in_expression(lambda.body);
+            }
+            HirExpression::Ident(..)
+            | HirExpression::Quote(_)
+            | HirExpression::Unquote(_)
+            | HirExpression::Error => (),
+        }
+    }
+
+    fn collect_in_expressions(&mut self, expressions: &[ExprId]) {
+        for expression in expressions {
+            self.collect_in_expression(*expression);
+        }
+    }
+
+    /// Adds a `: Type` hint after every variable bound by the given pattern
+    fn collect_type_hints(&mut self, pattern: &HirPattern) {
+        match pattern {
+            HirPattern::Identifier(ident) => {
+                let name = self.interner.definition_name(ident.id);
+                if !self.is_written_in_source(ident.location.span, name) {
+                    return;
+                }
+
+                let typ = self.interner.definition_type(ident.id);
+                if matches!(typ, Type::Error) {
+                    return;
+                }
+
+                if let Some(range) = self.range(ident.location.span) {
+                    self.push_hint(range.end, format!(": {typ}"), InlayHintKind::TYPE);
+                }
+            }
+            HirPattern::Mutable(pattern, _) => self.collect_type_hints(pattern),
+            HirPattern::Tuple(patterns, _) => {
+                for pattern in patterns {
+                    self.collect_type_hints(pattern);
+                }
+            }
+            HirPattern::Struct(_, fields, _) => {
+                for (_, pattern) in fields {
+                    self.collect_type_hints(pattern);
+                }
+            }
+            HirPattern::Variant(_, _, fields, _) => {
+                for pattern in fields {
+                    self.collect_type_hints(pattern);
+                }
+            }
+            HirPattern::Literal(..) => (),
+        }
+    }
+
+    /// Adds a `name:` hint before every argument passed to a function whose parameter names
+    /// are known, i.e. when the call is to a named function rather than to a closure.
+    fn collect_parameter_hints(&mut self, call: &HirCallExpression) {
+        let HirExpression::Ident(ident, _) = self.interner.expression(&call.func) else {
+            return;
+        };
+        let DefinitionKind::Function(func_id) = self.interner.definition(ident.id).kind else {
+            return;
+        };
+        let Some(func_meta) = self.interner.try_function_meta(&func_id) else {
+            return;
+        };
+
+        let mut parameters = func_meta.parameters.0.iter();
+        let mut arguments = call.arguments.iter();
+
+        // A method call is desugared into a call whose first argument is the object the
+        // method was called on, and whose function spans the whole call.
+        if let Some(first_argument) = call.arguments.first() {
+            let func_span = self.interner.expr_span(&call.func);
+            if self.interner.expr_span(first_argument).start() <= func_span.start() {
+                parameters.next();
+                arguments.next();
+            }
+        }
+
+        for ((pattern, _, _), argument) in parameters.zip(arguments) {
+            let pattern = match pattern {
+                HirPattern::Mutable(pattern, _) => pattern.as_ref(),
+                other => other,
+            };
+            let HirPattern::Identifier(parameter) = pattern else {
+                continue;
+            };
+            let name = self.interner.definition_name(parameter.id);
+            if name.starts_with('_') || self.argument_name(*argument) == Some(name) {
+                continue;
+            }
+
+            let span = self.interner.expr_span(argument);
+            if span.start() >= span.end() || span.end() as usize > self.source.len() {
+                continue;
+            }
+            if let Some(range) = self.range(span) {
+                self.push_hint(range.start, format!("{name}:"), InlayHintKind::PARAMETER);
+            }
+        }
+    }
+
+    /// The name of the variable passed as an argument, if the argument is just a variable
+    fn argument_name(&self, argument: ExprId) -> Option<&str> {
+        match self.interner.expression(&argument) {
+            HirExpression::Ident(ident, _) => Some(self.interner.definition_name(ident.id)),
+            _ => None,
+        }
+    }
+
+    /// True if the pattern is followed by a type annotation, as in `let x: u8` or `|x: u8|`
+    fn is_annotated(&self, pattern: &HirPattern) -> bool {
+        let end = pattern.span().end();
+        let next_token = self.tokens.iter().find(|(_, span)| span.start() >= end);
+        matches!(next_token, Some((Token::Colon, _)))
+    }
+
+    /// Code generated by macros can point at spans that don't hold the generated names,
+    /// and hints there would be misplaced.
+    fn is_written_in_source(&self, span: Span, name: &str) -> bool {
+        let (start, end) = (span.start() as usize, span.end() as usize);
+        self.source.get(start..end) == Some(name)
+    }
+
+    fn range(&self, span: Span) -> Option<Range> {
+        byte_span_to_range(self.files, self.file_id, span.into())
+    }
+
+    fn push_hint(&mut self, position: Position, label: String, kind: InlayHintKind) {
+        if position < self.range.start || position > self.range.end {
+            return;
+        }
+
+        self.hints.push(InlayHint {
+            position,
+            label: InlayHintLabel::String(label),
+            kind: Some(kind),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(kind == InlayHintKind::PARAMETER),
+            data: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod inlay_hint_tests {
+    use lsp_types::{TextDocumentIdentifier, WorkDoneProgressParams};
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    async fn get_inlay_hints(range: Range) -> Vec<(u32, u32, String)> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("inlay_hints").await;
+
+        let params = InlayHintParams {
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            range,
+        };
+
+        let hints = on_inlay_hint_request(&mut state, params)
+            .await
+            .expect("Could not execute on_inlay_hint_request")
+            .expect("Expected inlay hints");
+        hints
+            .into_iter()
+            .map(|hint| {
+                let InlayHintLabel::String(label) = hint.label else {
+                    panic!("Expected a string label, got {:?}", hint.label);
+                };
+                (hint.position.line, hint.position.character, label)
+            })
+            .collect()
+    }
+
+    fn whole_file() -> Range {
+        Range {
+            start: Position { line: 0, character: 0 },
+            end: Position { line: u32::MAX, character: 0 },
+        }
+    }
+
+    #[test]
+    async fn test_inlay_hints() {
+        let hints = get_inlay_hints(whole_file()).await;
+
+        let expected = [
+            // let a = add(x, 2);
+            (16, 9, ": u8"),
+            (16, 16, "lhs:"),
+            (16, 19, "rhs:"),
+            // let lhs = a;
+            (17, 11, ": u8"),
+            // let (c, mut d) = (lhs, y);
+            (19, 10, ": u8"),
+            (19, 17, ": Field"),
+            // let double = |n| n * 2;
+            (20, 14, ": fn(u8) -> u8"),
+            (20, 19, ": u8"),
+            // let e = double(add(lhs, c));
+            (21, 9, ": u8"),
+            (21, 28, "rhs:"),
+            // let point = Point { x: y, y: d };
+            (22, 13, ": Point"),
+            // assert(point.offset(e as Field) != 0);
+            (23, 24, "amount:"),
+        ];
+        let expected: Vec<_> = expected
+            .into_iter()
+            .map(|(line, character, label)| (line, character, label.to_string()))
+            .collect();
+        assert_eq!(hints, expected);
+    }
+
+    #[test]
+    async fn test_inlay_hints_only_in_requested_range() {
+        let range = Range {
+            start: Position { line: 17, character: 0 },
+            end: Position { line: 18, character: 0 },
+        };
+        let hints = get_inlay_hints(range).await;
+        assert_eq!(hints, vec![(17, 11, ": u8".to_string())]);
+    }
+}
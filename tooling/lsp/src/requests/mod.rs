@@ -1,13 +1,26 @@
-use std::future::Future;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    path::Path,
+};
 
-use crate::types::{CodeLensOptions, InitializeParams};
+use crate::{
+    parse_diff, resolve_workspace_for_source_path,
+    types::{CodeLensOptions, InitializeParams},
+    workspace_file_manager,
+};
 use async_lsp::{ErrorCode, ResponseError};
-use fm::{codespan_files::Error, FileMap, PathString};
+use fm::{codespan_files::Error, FileManager, FileMap, PathString};
 use lsp_types::{
-    DeclarationCapability, Location, Position, TextDocumentSyncCapability, TextDocumentSyncKind,
+    CodeActionKind, CodeActionOptions, CodeActionProviderCapability, CompletionOptions,
+    DeclarationCapability, HoverProviderCapability, Location, OneOf, Position, RenameOptions,
+    SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensServerCapabilities,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind,
     TypeDefinitionProviderCapability, Url,
 };
+use nargo::{package::Package, workspace::Workspace};
 use nargo_fmt::Config;
+use noirc_frontend::{graph::CrateId, hir::def_map::CrateDefMap, node_interner::NodeInterner};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -25,18 +38,32 @@ use crate::{
 // They are not attached to the `NargoLspService` struct so they can be unit tested with only `LspState`
 // and params passed in.
 
+mod code_action;
 mod code_lens_request;
+mod completion;
+mod document_symbol;
 mod goto_declaration;
 mod goto_definition;
+mod hover;
+mod inlay_hint;
 mod profile_run;
+mod references;
+mod rename;
+mod semantic_tokens;
 mod test_run;
 mod tests;
+mod workspace_symbol;
 
 pub(crate) use {
-    code_lens_request::collect_lenses_for_package, code_lens_request::on_code_lens_request,
-    goto_declaration::on_goto_declaration_request, goto_definition::on_goto_definition_request,
-    goto_definition::on_goto_type_definition_request, profile_run::on_profile_run_request,
-    test_run::on_test_run_request, tests::on_tests_request,
+    code_action::on_code_action_request, code_lens_request::collect_lenses_for_package,
+    code_lens_request::on_code_lens_request, completion::on_completion_request,
+    document_symbol::on_document_symbol_request, goto_declaration::on_goto_declaration_request,
+    goto_definition::on_goto_definition_request, goto_definition::on_goto_type_definition_request,
+    hover::on_hover_request, inlay_hint::on_inlay_hint_request,
+    profile_run::on_profile_run_request, references::on_references_request,
+    rename::on_prepare_rename_request, rename::on_rename_request,
+    semantic_tokens::on_semantic_tokens_full_request, test_run::on_test_run_request,
+    tests::on_tests_request, workspace_symbol::on_workspace_symbol_request,
 };
 
 /// LSP client will send initialization request after the server has started.
@@ -103,9 +130,37 @@ pub(crate) fn on_initialize(
                 code_lens_provider: code_lens,
                 document_formatting_provider: true,
                 nargo: Some(nargo),
-                definition_provider: Some(lsp_types::OneOf::Left(true)),
+                definition_provider: Some(OneOf::Left(true)),
                 declaration_provider: Some(DeclarationCapability::Simple(true)),
                 type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string(), ":".to_string()]),
+                    ..Default::default()
+                }),
+                references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: semantic_tokens::semantic_tokens_legend(),
+                            full: Some(SemanticTokensFullOptions::Bool(true)),
+                            ..Default::default()
+                        },
+                    ),
+                ),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        ..Default::default()
+                    },
+                )),
             },
             server_info: None,
         })
@@ -236,6 +291,160 @@ where
     Some(Location { uri, range })
 }
 
+/// The information a request needs once the workspace for a document
+/// has been type-checked (or fetched from the cache).
+pub(crate) struct ProcessRequestCallbackArgs<'a> {
+    /// The location in the source program that the request is about
+    location: noirc_errors::Location,
+    files: &'a FileMap,
+    /// The interner of the package containing the document
+    interner: &'a NodeInterner,
+    /// The interners of every package in the workspace, keyed by package root directory
+    interners: &'a HashMap<String, NodeInterner>,
+    def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+}
+
+/// Resolves the workspace for the document in `text_document_position_params`,
+/// type-checks the package containing it unless that was already done since the workspace's
+/// documents last changed, and then invokes `callback` with the results.
+pub(crate) fn process_request<F, T>(
+    state: &mut LspState,
+    text_document_position_params: TextDocumentPositionParams,
+    callback: F,
+) -> Result<T, ResponseError>
+where
+    F: FnOnce(ProcessRequestCallbackArgs) -> T,
+{
+    process_request_with_packages(state, text_document_position_params, false, callback)
+}
+
+/// Like [process_request], but type-checks every package in the workspace which isn't cached.
+/// This is needed by requests which look for the uses of an item across the whole workspace.
+pub(crate) fn process_workspace_request<F, T>(
+    state: &mut LspState,
+    text_document_position_params: TextDocumentPositionParams,
+    callback: F,
+) -> Result<T, ResponseError>
+where
+    F: FnOnce(ProcessRequestCallbackArgs) -> T,
+{
+    process_request_with_packages(state, text_document_position_params, true, callback)
+}
+
+fn process_request_with_packages<F, T>(
+    state: &mut LspState,
+    text_document_position_params: TextDocumentPositionParams,
+    check_whole_workspace: bool,
+    callback: F,
+) -> Result<T, ResponseError>
+where
+    F: FnOnce(ProcessRequestCallbackArgs) -> T,
+{
+    let file_path =
+        text_document_position_params.text_document.uri.to_file_path().map_err(|_| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
+        })?;
+
+    let workspace = resolve_workspace_for_source_path(file_path.as_path())
+        .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?;
+    let package = package_for_file(&workspace, &file_path).ok_or_else(|| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "Workspace has no packages")
+    })?;
+
+    let workspace_file_manager = workspace_file_manager(&workspace, state);
+    let packages_to_check: Vec<&Package> =
+        if check_whole_workspace { workspace.members.iter().collect() } else { vec![package] };
+    check_uncached_packages(state, &workspace_file_manager, packages_to_check);
+
+    let package_root_path = package.root_dir.as_os_str().to_string_lossy().into_owned();
+    let interners = &state.cached_definitions;
+    let interner = &interners[&package_root_path];
+    let def_maps = &state.cached_def_maps[&package_root_path];
+
+    let files = workspace_file_manager.as_file_map();
+    let file_path = PathString::from(file_path);
+    let location =
+        position_to_location(files, &file_path, &text_document_position_params.position)?;
+
+    Ok(callback(ProcessRequestCallbackArgs { location, files, interner, interners, def_maps }))
+}
+
+/// Type-checks each of `packages` whose results aren't cached yet, and caches them.
+pub(crate) fn check_uncached_packages<'a>(
+    state: &mut LspState,
+    workspace_file_manager: &FileManager,
+    packages: impl IntoIterator<Item = &'a Package>,
+) {
+    let parsed_files = parse_diff(workspace_file_manager, state);
+
+    for package in packages {
+        let package_root_path = package.root_dir.as_os_str().to_string_lossy().into_owned();
+        if state.cached_definitions.contains_key(&package_root_path)
+            && state.cached_def_maps.contains_key(&package_root_path)
+        {
+            continue;
+        }
+
+        let (mut context, crate_id) =
+            nargo::prepare_package(workspace_file_manager, &parsed_files, package);
+
+        // We ignore the warnings and errors produced by compilation while processing the request
+        let _ = noirc_driver::check_crate(&mut context, crate_id, false, false, false);
+
+        state.cached_definitions.insert(package_root_path.clone(), context.def_interner);
+        state.cached_def_maps.insert(package_root_path, context.def_maps);
+    }
+}
+
+/// Returns the workspace member containing `file_path`, falling back to the first member
+/// for files outside of every package (e.g. a workspace-level file).
+pub(crate) fn package_for_file<'a>(
+    workspace: &'a Workspace,
+    file_path: &Path,
+) -> Option<&'a Package> {
+    workspace
+        .members
+        .iter()
+        .find(|package| file_path.starts_with(&package.root_dir))
+        .or_else(|| workspace.members.first())
+}
+
+/// Returns every place in the workspace where the item found at `location` is used,
+/// plus the place where it is declared if `include_declaration` is true.
+///
+/// Each package is type-checked separately, so an item defined in a library shows up in the
+/// interner of every package depending on it. Uses found in all of them are merged together.
+pub(crate) fn find_all_references_in_workspace(
+    location: noirc_errors::Location,
+    interner: &NodeInterner,
+    interners: &HashMap<String, NodeInterner>,
+    files: &FileMap,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    let referenced = interner.find_referenced(location)?;
+    let declaration = interner.reference_location(referenced);
+
+    let mut locations = interner.find_all_references(referenced, include_declaration);
+    for other_interner in interners.values() {
+        if std::ptr::eq(other_interner, interner) {
+            continue;
+        }
+        if let Some(referenced) = other_interner.find_definition_at(declaration) {
+            locations.extend(other_interner.find_all_references(referenced, include_declaration));
+        }
+    }
+
+    locations.sort_by_key(|location| (location.file, location.span));
+    locations.dedup();
+
+    Some(
+        locations
+            .into_iter()
+            .filter_map(|location| to_lsp_location(files, location.file, location.span))
+            .collect(),
+    )
+}
+
 pub(crate) fn on_shutdown(
     _state: &mut LspState,
     _params: (),
@@ -46,6 +46,7 @@ fn on_profile_run_request_inner(
         &toml_path,
         PackageSelection::DefaultOrAll,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        false,
     )
     .map_err(|err| {
         // If we found a manifest, but the workspace is invalid, we raise an error about it
@@ -0,0 +1,139 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use lsp_types::ReferenceParams;
+
+use crate::{types::ReferencesResult, LspState};
+
+use super::{find_all_references_in_workspace, process_workspace_request};
+
+pub(crate) fn on_references_request(
+    state: &mut LspState,
+    params: ReferenceParams,
+) -> impl Future<Output = Result<ReferencesResult, ResponseError>> {
+    let include_declaration = params.context.include_declaration;
+    let result = process_workspace_request(state, params.text_document_position, |args| {
+        find_all_references_in_workspace(
+            args.location,
+            args.interner,
+            args.interners,
+            args.files,
+            include_declaration,
+        )
+    });
+    future::ready(result)
+}
+
+#[cfg(test)]
+mod references_tests {
+    use lsp_types::{
+        PartialResultParams, Position, Range, ReferenceContext, TextDocumentIdentifier,
+        TextDocumentPositionParams, Url, WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    async fn check_references_succeeds(
+        directory: &str,
+        name: &str,
+        declaration_index: usize,
+        include_declaration: bool,
+    ) {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server(directory).await;
+
+        // First we find out all of the occurrences of `name` in the main.nr file.
+        // Note that this only works if that name doesn't show up in other places where we don't
+        // expect a rename, but we craft our tests to avoid that.
+        let ranges = test_utils::search_in_file(&noir_text_document, name);
+
+        // Test getting references works on any location, including the declaration.
+        for target_range in &ranges {
+            let target_position = target_range.start;
+
+            let params = ReferenceParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+                    position: target_position,
+                },
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+                partial_result_params: PartialResultParams { partial_result_token: None },
+                context: ReferenceContext { include_declaration },
+            };
+
+            let locations = on_references_request(&mut state, params)
+                .await
+                .expect("Could not execute on_references_request")
+                .unwrap();
+
+            let mut references_ranges: Vec<Range> =
+                locations.iter().map(|location| location.range).collect();
+            references_ranges.sort_by_key(|range| (range.start.line, range.start.character));
+
+            if include_declaration {
+                assert_eq!(ranges, references_ranges);
+            } else {
+                let mut ranges_without_declaration = ranges.clone();
+                ranges_without_declaration.remove(declaration_index);
+                assert_eq!(ranges_without_declaration, references_ranges);
+            }
+        }
+    }
+
+    #[test]
+    async fn test_on_references_request_including_declaration() {
+        check_references_succeeds("rename_function", "another_function", 0, true).await;
+    }
+
+    #[test]
+    async fn test_on_references_request_without_including_declaration() {
+        check_references_succeeds("rename_function", "another_function", 0, false).await;
+    }
+
+    #[test]
+    async fn test_on_references_request_for_struct_member() {
+        check_references_succeeds("rename_struct_member", "some_member", 0, true).await;
+    }
+
+    #[test]
+    async fn test_on_references_request_for_local_variable() {
+        check_references_succeeds("rename_local_variable", "some_var", 0, true).await;
+    }
+
+    #[test]
+    async fn test_on_references_request_for_trait_method() {
+        check_references_succeeds("rename_trait_method", "some_method", 0, true).await;
+    }
+
+    #[test]
+    async fn test_on_references_request_works_across_workspace_packages() {
+        let (mut state, _) = test_utils::init_lsp_server("workspace").await;
+
+        // The function `function_one` is declared once in `one/src/lib.nr`,
+        // and then used in `two/src/lib.nr` (the `use` and the call).
+        let root_path = test_utils::test_program_path("workspace");
+        let one_lib = Url::from_file_path(root_path.join("one/src/lib.nr")).unwrap();
+        let two_lib = Url::from_file_path(root_path.join("two/src/lib.nr")).unwrap();
+
+        let params = ReferenceParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: one_lib.clone() },
+                position: Position { line: 0, character: 7 },
+            },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+            context: ReferenceContext { include_declaration: true },
+        };
+
+        let locations = on_references_request(&mut state, params)
+            .await
+            .expect("Could not execute on_references_request")
+            .unwrap();
+
+        assert_eq!(locations.len(), 3);
+        assert_eq!(locations.iter().filter(|location| location.uri == one_lib).count(), 1);
+        assert_eq!(locations.iter().filter(|location| location.uri == two_lib).count(), 2);
+    }
+}
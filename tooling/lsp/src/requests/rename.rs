@@ -0,0 +1,192 @@
+use std::{
+    collections::HashMap,
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, ResponseError};
+use lsp_types::{
+    PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+};
+
+use noirc_frontend::token::Token;
+
+use crate::{
+    types::{PrepareRenameResult, RenameResult},
+    LspState,
+};
+
+use super::{
+    completion::lex, find_all_references_in_workspace, process_request,
+    process_workspace_request, to_lsp_location,
+};
+
+pub(crate) fn on_prepare_rename_request(
+    state: &mut LspState,
+    params: TextDocumentPositionParams,
+) -> impl Future<Output = Result<PrepareRenameResult, ResponseError>> {
+    let result = process_request(state, params, |args| {
+        let interner = args.interner;
+
+        // Only names can be renamed: either a use of an item or the place where it's declared
+        let rename_location = match interner.find_reference_at(args.location) {
+            Some((_, location)) => location,
+            None => {
+                let referenced = interner.find_definition_at(args.location)?;
+                interner.reference_location(referenced)
+            }
+        };
+
+        let location = to_lsp_location(args.files, rename_location.file, rename_location.span)?;
+        Some(PrepareRenameResponse::Range(location.range))
+    });
+    future::ready(result)
+}
+
+pub(crate) fn on_rename_request(
+    state: &mut LspState,
+    params: RenameParams,
+) -> impl Future<Output = Result<RenameResult, ResponseError>> {
+    if !is_identifier(&params.new_name) {
+        let message = format!("`{}` is not a valid identifier", params.new_name);
+        return future::ready(Err(ResponseError::new(ErrorCode::INVALID_PARAMS, message)));
+    }
+
+    let result = process_workspace_request(state, params.text_document_position, |args| {
+        let locations = find_all_references_in_workspace(
+            args.location,
+            args.interner,
+            args.interners,
+            args.files,
+            true,
+        )?;
+
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for location in locations {
+            let text_edit = TextEdit { range: location.range, new_text: params.new_name.clone() };
+            changes.entry(location.uri).or_default().push(text_edit);
+        }
+
+        Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        })
+    });
+    future::ready(result)
+}
+
+/// True if `name` is a single identifier, which excludes keywords
+fn is_identifier(name: &str) -> bool {
+    match lex(name).as_slice() {
+        [(Token::Ident(_), span)] => span.start() == 0 && span.end() as usize == name.len(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod rename_tests {
+    use lsp_types::{Position, Range, TextDocumentIdentifier, WorkDoneProgressParams};
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    async fn check_rename_succeeds(directory: &str, name: &str) {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server(directory).await;
+
+        // Every occurrence of `name` in main.nr is expected to be renamed, and nothing else.
+        let ranges = test_utils::search_in_file(&noir_text_document, name);
+
+        // Renaming works no matter which occurrence the cursor is on, including the declaration.
+        for target_range in &ranges {
+            let text_document_position = TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+                position: target_range.start,
+            };
+
+            let response = on_prepare_rename_request(&mut state, text_document_position.clone())
+                .await
+                .expect("Could not execute on_prepare_rename_request");
+            assert_eq!(response, Some(PrepareRenameResponse::Range(*target_range)));
+
+            let params = RenameParams {
+                text_document_position,
+                new_name: "renamed".to_string(),
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            };
+
+            let response = on_rename_request(&mut state, params)
+                .await
+                .expect("Could not execute on_rename_request")
+                .unwrap();
+
+            let changes = response.changes.expect("Expected to find rename changes");
+            let mut changes: Vec<Range> =
+                changes.values().flatten().map(|text_edit| text_edit.range).collect();
+            changes.sort_by_key(|range| (range.start.line, range.start.character));
+            assert_eq!(changes, ranges);
+        }
+    }
+
+    #[test]
+    async fn test_on_prepare_rename_request_cannot_be_applied_on_keywords() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("rename_function").await;
+
+        let params = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            position: Position { line: 0, character: 0 }, // This is at the "f" of an "fn" keyword
+        };
+
+        let response = on_prepare_rename_request(&mut state, params)
+            .await
+            .expect("Could not execute on_prepare_rename_request");
+
+        assert_eq!(response, None);
+    }
+
+    #[test]
+    async fn test_rename_rejects_invalid_identifiers() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("rename_function").await;
+        let position = test_utils::search_in_file(&noir_text_document, "another_function")[0].start;
+
+        for new_name in ["", "fn", "1abc", "two words", "a::b"] {
+            let params = RenameParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+                    position,
+                },
+                new_name: new_name.to_string(),
+                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            };
+
+            let response = on_rename_request(&mut state, params).await;
+            assert!(response.is_err(), "Expected renaming to `{new_name}` to fail");
+        }
+    }
+
+    #[test]
+    async fn test_rename_function() {
+        check_rename_succeeds("rename_function", "another_function").await;
+    }
+
+    #[test]
+    async fn test_rename_struct() {
+        check_rename_succeeds("rename_struct_member", "Foo").await;
+    }
+
+    #[test]
+    async fn test_rename_struct_member() {
+        check_rename_succeeds("rename_struct_member", "some_member").await;
+    }
+
+    #[test]
+    async fn test_rename_local_variable() {
+        check_rename_succeeds("rename_local_variable", "some_var").await;
+    }
+
+    #[test]
+    async fn test_rename_trait_method() {
+        check_rename_succeeds("rename_trait_method", "some_method").await;
+    }
+}
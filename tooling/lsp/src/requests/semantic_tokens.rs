@@ -0,0 +1,415 @@
+use std::{
+    collections::HashMap,
+    future::{self, Future},
+};
+
+use async_lsp::ResponseError;
+use fm::codespan_files::Files;
+use lsp_types::{
+    Position, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
+    SemanticTokensLegend, SemanticTokensParams, SemanticTokensResult, TextDocumentPositionParams,
+};
+use noirc_errors::Span;
+use noirc_frontend::{
+    lexer::Lexer,
+    macros_api::NodeInterner,
+    node_interner::ReferenceId,
+    token::{Keyword, SpannedToken, Token},
+};
+
+use crate::{types::SemanticTokensFullResult, LspState};
+
+use super::{completion::has_self_parameter, process_request};
+
+/// The token types we report, in the order their indices refer to
+const TOKEN_TYPES: [SemanticTokenType; 15] = [
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::INTERFACE,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::METHOD,
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::COMMENT,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::DECORATOR,
+    SemanticTokenType::MACRO,
+];
+
+/// The token modifiers we report, where the modifier at index `i` is the bit `1 << i`
+const TOKEN_MODIFIERS: [SemanticTokenModifier; 3] = [
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::READONLY,
+    SemanticTokenModifier::DOCUMENTATION,
+];
+
+pub(crate) fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+pub(crate) fn on_semantic_tokens_full_request(
+    state: &mut LspState,
+    params: SemanticTokensParams,
+) -> impl Future<Output = Result<SemanticTokensFullResult, ResponseError>> {
+    // The tokens don't depend on a position, but we still need one to process the request
+    let text_document_position_params = TextDocumentPositionParams {
+        text_document: params.text_document,
+        position: Position::default(),
+    };
+
+    let result = process_request(state, text_document_position_params, |args| {
+        let file_id = args.location.file;
+        let source = args.files.source(file_id).ok()?;
+
+        let tokens = lex(source);
+        let classifier = IdentClassifier::new(args.interner, file_id, &tokens);
+
+        let data = semantic_tokens(source, &tokens, |token, span, next_token| match token {
+            Token::Ident(name) => classifier.classify(name, span, next_token),
+            _ => classify_token(token),
+        });
+        Some(SemanticTokensResult::Tokens(SemanticTokens { result_id: None, data }))
+    });
+    future::ready(result)
+}
+
+/// Lexes `source`, keeping comments since they are highlighted too.
+/// Tokens that fail to lex are simply left out.
+fn lex(source: &str) -> Vec<SpannedToken> {
+    Lexer::new(source).skip_comments(false).filter_map(Result::ok).collect()
+}
+
+/// Encodes the `tokens` of `source` that `classify` assigns a token type and modifiers to,
+/// in the relative format of the LSP specification.
+fn semantic_tokens(
+    source: &str,
+    tokens: &[SpannedToken],
+    classify: impl Fn(&Token, Span, Option<&Token>) -> Option<(SemanticTokenType, u32)>,
+) -> Vec<SemanticToken> {
+    let lines = LineIndex::new(source);
+
+    let mut encoder = TokenEncoder::default();
+    for (index, token) in tokens.iter().enumerate() {
+        let span = token.to_span();
+        let next_token = tokens[index + 1..]
+            .iter()
+            .map(|token| token.token())
+            .find(|token| !matches!(token, Token::LineComment(..) | Token::BlockComment(..)));
+        let Some((token_type, modifiers)) = classify(token.token(), span, next_token) else {
+            continue;
+        };
+        let token_type =
+            TOKEN_TYPES.iter().position(|typ| *typ == token_type).expect("Unknown token type");
+
+        // Clients don't need to support tokens spanning multiple lines, so we split those up
+        let (start, end) = (span.start() as usize, (span.end() as usize).min(source.len()));
+        let mut line_start = start;
+        for line in source[start..end].split_inclusive('\n') {
+            let text = line.trim_end_matches(['\n', '\r']);
+            let position = lines.position(source, line_start);
+            let length = text.encode_utf16().count() as u32;
+            encoder.push(position, length, token_type as u32, modifiers);
+            line_start += line.len();
+        }
+    }
+    encoder.tokens
+}
+
+/// Classifies every token that isn't an identifier, just by looking at the token itself
+fn classify_token(token: &Token) -> Option<(SemanticTokenType, u32)> {
+    let token_type = match token {
+        Token::Keyword(keyword) => match keyword {
+            Keyword::Bool
+            | Keyword::Char
+            | Keyword::Expr
+            | Keyword::Field
+            | Keyword::FormatString
+            | Keyword::String
+            | Keyword::TopLevelItem
+            | Keyword::TypeDefinition
+            | Keyword::TypeType => SemanticTokenType::TYPE,
+            Keyword::Quote => SemanticTokenType::MACRO,
+            _ => SemanticTokenType::KEYWORD,
+        },
+        Token::IntType(_) => SemanticTokenType::TYPE,
+        Token::Bool(_) => SemanticTokenType::KEYWORD,
+        Token::Int(_) => SemanticTokenType::NUMBER,
+        Token::Str(_) | Token::RawStr(..) | Token::FmtStr(_) => SemanticTokenType::STRING,
+        Token::Attribute(_) => SemanticTokenType::DECORATOR,
+        Token::LineComment(_, doc_style) | Token::BlockComment(_, doc_style) => {
+            let modifiers = if doc_style.is_some() {
+                modifier(&SemanticTokenModifier::DOCUMENTATION)
+            } else {
+                0
+            };
+            return Some((SemanticTokenType::COMMENT, modifiers));
+        }
+        _ => return None,
+    };
+    Some((token_type, 0))
+}
+
+/// Classifies identifiers according to the item they refer to, as found by the elaborator
+struct IdentClassifier {
+    /// The token type and modifiers of every identifier the elaborator knows about, by span
+    kinds: HashMap<Span, (SemanticTokenType, u32)>,
+}
+
+impl IdentClassifier {
+    fn new(interner: &NodeInterner, file: fm::FileId, tokens: &[SpannedToken]) -> Self {
+        let mut kinds = HashMap::new();
+
+        let declaration = modifier(&SemanticTokenModifier::DECLARATION);
+        for (referenced, location) in interner.declarations_in_file(file) {
+            let (token_type, modifiers) = reference_token_type(interner, referenced);
+            kinds.insert(location.span, (token_type, modifiers | declaration));
+        }
+
+        // A use may be part of a larger one, e.g. `T` in a type `Foo<T>`, so the smallest
+        // uses are looked at first and win when two of them name the same identifier
+        let mut references = interner.references_in_file(file);
+        references.sort_by_key(|(_, location)| location.span.end() - location.span.start());
+        for (referenced, location) in references {
+            if let Some(span) = name_span(tokens, location.span) {
+                kinds.entry(span).or_insert_with(|| reference_token_type(interner, referenced));
+            }
+        }
+
+        IdentClassifier { kinds }
+    }
+
+    fn classify(
+        &self,
+        name: &str,
+        span: Span,
+        next_token: Option<&Token>,
+    ) -> Option<(SemanticTokenType, u32)> {
+        if let Some(kind) = self.kinds.get(&span) {
+            return Some(kind.clone());
+        }
+
+        // Names the elaborator doesn't record a use for, like path segments naming modules
+        match next_token {
+            _ if name == "Self" => Some((SemanticTokenType::TYPE, 0)),
+            Some(Token::DoubleColon) => Some((SemanticTokenType::NAMESPACE, 0)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the span of the identifier naming the item used at `span`, which is the last
+/// identifier before any generics, e.g. `Foo` in `a::Foo<T>`.
+fn name_span(tokens: &[SpannedToken], span: Span) -> Option<Span> {
+    let first = tokens.partition_point(|token| token.to_span().start() < span.start());
+    tokens[first..]
+        .iter()
+        .take_while(|token| token.to_span().end() <= span.end() && *token.token() != Token::Less)
+        .filter(|token| matches!(token.token(), Token::Ident(_)))
+        .last()
+        .map(|token| token.to_span())
+}
+
+fn reference_token_type(
+    interner: &NodeInterner,
+    referenced: ReferenceId,
+) -> (SemanticTokenType, u32) {
+    match referenced {
+        ReferenceId::Struct(_) => (SemanticTokenType::STRUCT, 0),
+        ReferenceId::StructMember(..) => (SemanticTokenType::PROPERTY, 0),
+        ReferenceId::Trait(_) => (SemanticTokenType::INTERFACE, 0),
+        ReferenceId::Alias(_) => (SemanticTokenType::TYPE, 0),
+        ReferenceId::Global(_) => {
+            (SemanticTokenType::VARIABLE, modifier(&SemanticTokenModifier::READONLY))
+        }
+        ReferenceId::Function(func_id) => {
+            let func_meta = interner.function_meta(&func_id);
+            if has_self_parameter(interner, func_meta) {
+                (SemanticTokenType::METHOD, 0)
+            } else {
+                (SemanticTokenType::FUNCTION, 0)
+            }
+        }
+        ReferenceId::Local(definition_id) => {
+            let name = interner.definition_name(definition_id);
+            if name == "self" {
+                (SemanticTokenType::PARAMETER, 0)
+            } else {
+                (SemanticTokenType::VARIABLE, 0)
+            }
+        }
+    }
+}
+
+fn modifier(modifier: &SemanticTokenModifier) -> u32 {
+    let index = TOKEN_MODIFIERS.iter().position(|other| other == modifier);
+    1 << index.expect("Unknown token modifier")
+}
+
+/// Converts byte offsets into LSP positions, where characters are counted in UTF-16 code units
+struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        let newlines = source.match_indices('\n').map(|(index, _)| index + 1);
+        LineIndex { line_starts: std::iter::once(0).chain(newlines).collect() }
+    }
+
+    fn position(&self, source: &str, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|line_start| *line_start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let character = source[line_start..offset].encode_utf16().count();
+        Position { line: line as u32, character: character as u32 }
+    }
+}
+
+/// Encodes each token relative to the previous one, as the LSP specification requires
+#[derive(Default)]
+struct TokenEncoder {
+    tokens: Vec<SemanticToken>,
+    previous: Position,
+}
+
+impl TokenEncoder {
+    fn push(&mut self, position: Position, length: u32, token_type: u32, modifiers: u32) {
+        if length == 0 {
+            return;
+        }
+
+        let delta_line = position.line - self.previous.line;
+        let delta_start = if delta_line == 0 {
+            position.character - self.previous.character
+        } else {
+            position.character
+        };
+        self.tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset: modifiers,
+        });
+        self.previous = position;
+    }
+}
+
+#[cfg(test)]
+mod semantic_tokens_tests {
+    use lsp_types::{PartialResultParams, TextDocumentIdentifier, WorkDoneProgressParams};
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    /// Decodes tokens into `(line, character, text, token type, modifiers)`
+    fn decode<'a>(
+        source: &'a str,
+        tokens: &[SemanticToken],
+    ) -> Vec<(u32, u32, &'a str, SemanticTokenType, u32)> {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut position = Position::default();
+        tokens
+            .iter()
+            .map(|token| {
+                if token.delta_line == 0 {
+                    position.character += token.delta_start;
+                } else {
+                    position.line += token.delta_line;
+                    position.character = token.delta_start;
+                }
+                let line = lines[position.line as usize];
+                let start = position.character as usize;
+                let text = &line[start..start + token.length as usize];
+                let token_type = TOKEN_TYPES[token.token_type as usize].clone();
+                (position.line, position.character, text, token_type, token.token_modifiers_bitset)
+            })
+            .collect()
+    }
+
+    #[test]
+    async fn test_lexical_tokens() {
+        let source = "/// Docs\n#[test]\ncomptime fn foo() -> u8 {\n    let _ = quote { 1 };\n    let s = \"a\nb\"; 3\n}";
+        let tokens = semantic_tokens(source, &lex(source), |token, _, _| classify_token(token));
+
+        let documentation = modifier(&SemanticTokenModifier::DOCUMENTATION);
+        assert_eq!(
+            decode(source, &tokens),
+            vec![
+                (0, 0, "/// Docs", SemanticTokenType::COMMENT, documentation),
+                (1, 0, "#[test]", SemanticTokenType::DECORATOR, 0),
+                (2, 0, "comptime", SemanticTokenType::KEYWORD, 0),
+                (2, 9, "fn", SemanticTokenType::KEYWORD, 0),
+                (2, 21, "u8", SemanticTokenType::TYPE, 0),
+                (3, 4, "let", SemanticTokenType::KEYWORD, 0),
+                (3, 12, "quote", SemanticTokenType::MACRO, 0),
+                (3, 20, "1", SemanticTokenType::NUMBER, 0),
+                (4, 4, "let", SemanticTokenType::KEYWORD, 0),
+                (4, 12, "\"a", SemanticTokenType::STRING, 0),
+                (5, 0, "b\"", SemanticTokenType::STRING, 0),
+                (5, 4, "3", SemanticTokenType::NUMBER, 0),
+            ]
+        );
+    }
+
+    #[test]
+    async fn test_on_semantic_tokens_full_request() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+
+        let params = SemanticTokensParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+        let response = on_semantic_tokens_full_request(&mut state, params)
+            .await
+            .expect("Could not execute on_semantic_tokens_full_request");
+        let Some(SemanticTokensResult::Tokens(tokens)) = response else {
+            panic!("Expected semantic tokens, got {:?}", response);
+        };
+
+        let path = noir_text_document.to_file_path().unwrap();
+        let source = std::fs::read_to_string(path).unwrap();
+        let tokens = decode(&source, &tokens.data);
+
+        let declaration = modifier(&SemanticTokenModifier::DECLARATION);
+        let readonly = modifier(&SemanticTokenModifier::READONLY);
+        let find = |line: u32, text: &str| {
+            let token = tokens.iter().find(|token| token.0 == line && token.2 == text);
+            token.map(|(_, _, _, token_type, modifiers)| (token_type.clone(), *modifiers))
+        };
+
+        // global LIMIT: Field = 10;
+        assert_eq!(find(2, "LIMIT"), Some((SemanticTokenType::VARIABLE, readonly | declaration)));
+        assert_eq!(find(2, "Field"), Some((SemanticTokenType::TYPE, 0)));
+        // struct Point {
+        assert_eq!(find(4, "struct"), Some((SemanticTokenType::KEYWORD, 0)));
+        assert_eq!(find(4, "Point"), Some((SemanticTokenType::STRUCT, declaration)));
+        // x: Field,
+        assert_eq!(find(5, "x"), Some((SemanticTokenType::PROPERTY, declaration)));
+        // fn sum(self) -> Field {
+        assert_eq!(find(14, "sum"), Some((SemanticTokenType::METHOD, declaration)));
+        // self.x + self.y
+        assert_eq!(find(15, "self"), Some((SemanticTokenType::PARAMETER, 0)));
+        assert_eq!(find(15, "x"), Some((SemanticTokenType::PROPERTY, 0)));
+        // trait Describe {
+        assert_eq!(find(19, "Describe"), Some((SemanticTokenType::INTERFACE, declaration)));
+        // let point: Alias = Point::new(x, other::double(x));
+        assert_eq!(find(38, "point"), Some((SemanticTokenType::VARIABLE, declaration)));
+        assert_eq!(find(38, "Alias"), Some((SemanticTokenType::TYPE, 0)));
+        assert_eq!(find(38, "new"), Some((SemanticTokenType::FUNCTION, 0)));
+        assert_eq!(find(38, "other"), Some((SemanticTokenType::NAMESPACE, 0)));
+        assert_eq!(find(38, "double"), Some((SemanticTokenType::FUNCTION, 0)));
+        // assert(point.describe() != LIMIT + nested::helper());
+        assert_eq!(find(39, "describe"), Some((SemanticTokenType::METHOD, 0)));
+        assert_eq!(find(39, "LIMIT"), Some((SemanticTokenType::VARIABLE, readonly)));
+    }
+}
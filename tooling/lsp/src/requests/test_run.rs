@@ -3,7 +3,7 @@ use std::future::{self, Future};
 use async_lsp::{ErrorCode, ResponseError};
 use nargo::{
     insert_all_files_for_workspace_into_file_manager,
-    ops::{run_test, TestStatus},
+    ops::{run_test, ExecutionLimits, TestStatus},
     prepare_package,
 };
 use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
@@ -45,6 +45,7 @@ fn on_test_run_request_inner(
         &toml_path,
         PackageSelection::Selected(crate_name.clone()),
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        false,
     )
     .map_err(|err| {
         // If we found a manifest, but the workspace is invalid, we raise an error about it
@@ -88,6 +89,8 @@ fn on_test_run_request_inner(
                 false,
                 None,
                 &CompileOptions::default(),
+                ExecutionLimits::default(),
+                None,
             );
             let result = match test_result {
                 TestStatus::Pass => NargoTestRunResult {
@@ -105,6 +108,11 @@ fn on_test_run_request_inner(
                     result: "error".to_string(),
                     message: Some(diag.diagnostic.message),
                 },
+                TestStatus::LimitExceeded(limit) => NargoTestRunResult {
+                    id: params.id.clone(),
+                    result: "fail".to_string(),
+                    message: Some(limit.to_string()),
+                },
             };
             Ok(result)
         }
@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeSet,
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, LanguageClient, ResponseError};
+use fm::{codespan_files::Files, FileId};
+use lsp_types::{
+    DocumentSymbol, Location, LogMessageParams, MessageType, SymbolInformation, Url,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
+use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+
+use crate::{types::WorkspaceSymbolResult, workspace_file_manager, LspState};
+
+use super::{
+    check_uncached_packages,
+    document_symbol::{modules_in_file, DocumentSymbolBuilder},
+};
+
+pub(crate) fn on_workspace_symbol_request(
+    state: &mut LspState,
+    params: WorkspaceSymbolParams,
+) -> impl Future<Output = Result<WorkspaceSymbolResult, ResponseError>> {
+    future::ready(on_workspace_symbol_request_inner(state, params))
+}
+
+fn on_workspace_symbol_request_inner(
+    state: &mut LspState,
+    params: WorkspaceSymbolParams,
+) -> Result<WorkspaceSymbolResult, ResponseError> {
+    let root_path = state.root_path.as_deref().ok_or_else(|| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "Could not find project root")
+    })?;
+
+    let toml_path = match find_package_manifest(root_path, root_path) {
+        Ok(toml_path) => toml_path,
+        Err(err) => {
+            // Without a manifest there is no workspace to look for symbols in
+            let _ = state.client.log_message(LogMessageParams {
+                typ: MessageType::WARNING,
+                message: err.to_string(),
+            });
+            return Ok(None);
+        }
+    };
+
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        false,
+    )
+    .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err))?;
+
+    // Symbols are still worth listing when a package doesn't compile
+    let workspace_file_manager = workspace_file_manager(&workspace, state);
+    check_uncached_packages(state, &workspace_file_manager, &workspace);
+    let files = workspace_file_manager.as_file_map();
+
+    let mut symbols = Vec::new();
+    for package in &workspace {
+        let package_root_path = package.root_dir.as_os_str().to_string_lossy().into_owned();
+        let interner = &state.cached_definitions[&package_root_path];
+        let def_maps = &state.cached_def_maps[&package_root_path];
+
+        // Only the package's own crate is searched, so that a dependency which is also a
+        // workspace member doesn't get its symbols listed twice
+        let Some(def_map) =
+            def_maps.iter().find(|(crate_id, _)| crate_id.is_root()).map(|(_, def_map)| def_map)
+        else {
+            continue;
+        };
+        let file_ids: BTreeSet<FileId> =
+            def_map.modules().iter().map(|(_, module)| module.location.file).collect();
+        for file_id in file_ids {
+            let Some(uri) = files
+                .name(file_id)
+                .ok()
+                .and_then(|name| Url::from_file_path(name.to_string()).ok())
+            else {
+                continue;
+            };
+
+            for module_id in modules_in_file(def_map, file_id) {
+                let builder = DocumentSymbolBuilder {
+                    interner,
+                    def_map,
+                    files,
+                    file_id,
+                };
+                let document_symbols = builder.module_contents(module_id);
+                flatten_symbols(document_symbols, None, &mut |symbol, container_name| {
+                    if !matches_query(&symbol.name, &params.query) {
+                        return;
+                    }
+                    let location = Location { uri: uri.clone(), range: symbol.selection_range };
+
+                    #[allow(deprecated)]
+                    symbols.push(SymbolInformation {
+                        name: symbol.name.clone(),
+                        kind: symbol.kind,
+                        tags: None,
+                        deprecated: None,
+                        location,
+                        container_name: container_name.map(|name| name.to_string()),
+                    });
+                });
+            }
+        }
+    }
+
+    symbols.sort_by(|symbol1, symbol2| symbol1.name.cmp(&symbol2.name));
+    symbols.dedup_by(|symbol1, symbol2| {
+        symbol1.name == symbol2.name && symbol1.location == symbol2.location
+    });
+    Ok(Some(WorkspaceSymbolResponse::Flat(symbols)))
+}
+
+/// Calls `f` with every symbol in `symbols` and their children, along with the name of the
+/// symbol containing each of them, if any.
+fn flatten_symbols(
+    symbols: Vec<DocumentSymbol>,
+    container_name: Option<&str>,
+    f: &mut impl FnMut(&DocumentSymbol, Option<&str>),
+) {
+    for mut symbol in symbols {
+        f(&symbol, container_name);
+        if let Some(children) = symbol.children.take() {
+            flatten_symbols(children, Some(&symbol.name), f);
+        }
+    }
+}
+
+/// A name matches a query if it contains every character of the query in order,
+/// ignoring case. This is the same kind of fuzzy matching editors do on the results.
+fn matches_query(name: &str, query: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char))
+}
+
+#[cfg(test)]
+mod workspace_symbol_tests {
+    use lsp_types::{PartialResultParams, WorkDoneProgressParams};
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    async fn workspace_symbols(directory: &str, query: &str) -> Vec<SymbolInformation> {
+        let (mut state, _) = test_utils::init_lsp_server(directory).await;
+
+        let params = WorkspaceSymbolParams {
+            query: query.to_string(),
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+        };
+
+        let response = on_workspace_symbol_request(&mut state, params)
+            .await
+            .expect("Could not execute on_workspace_symbol_request");
+        match response {
+            Some(WorkspaceSymbolResponse::Flat(symbols)) => symbols,
+            other => panic!("Expected a flat list of symbols, got {:?}", other),
+        }
+    }
+
+    #[test]
+    async fn test_workspace_symbols_across_packages() {
+        let symbols = workspace_symbols("workspace", "func").await;
+
+        let root_path = test_utils::test_program_path("workspace");
+        let locations: Vec<_> = symbols
+            .iter()
+            .map(|symbol| {
+                let path = symbol.location.uri.to_file_path().unwrap();
+                (symbol.name.as_str(), path.strip_prefix(&root_path).unwrap().to_path_buf())
+            })
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                ("function_one", "one/src/lib.nr".into()),
+                ("function_two", "two/src/lib.nr".into()),
+            ]
+        );
+    }
+
+    #[test]
+    async fn test_workspace_symbols_include_container_names() {
+        let symbols = workspace_symbols("document_symbol", "dscb").await;
+
+        let names: Vec<_> = symbols
+            .iter()
+            .map(|symbol| (symbol.name.as_str(), symbol.container_name.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Describe", None),
+                ("describe", Some("Describe")),
+                ("describe", Some("impl Describe for Point")),
+                ("impl Describe for Point", None),
+            ]
+        );
+    }
+
+    #[test]
+    async fn test_matches_query() {
+        assert!(matches_query("function_one", ""));
+        assert!(matches_query("function_one", "FnOne"));
+        assert!(!matches_query("function_one", "one_function"));
+    }
+}
@@ -0,0 +1,64 @@
+use acvm::blackbox_solver::StubbedBlackBoxSolver;
+use async_lsp::ClientSocket;
+use lsp_types::{Position, Range, Url};
+
+use crate::LspState;
+
+/// Starts an LSP server for the test program in `test_programs/{directory}` and returns it,
+/// together with the URI of that program's `src/main.nr` file.
+pub(crate) async fn init_lsp_server(directory: &str) -> (LspState, Url) {
+    let client = ClientSocket::new_closed();
+    let mut state = LspState::new(&client, StubbedBlackBoxSolver);
+
+    let root_path = test_program_path(directory);
+    let noir_text_document = Url::from_file_path(root_path.join("src/main.nr").as_path())
+        .expect("Could not convert text document path to URI");
+    let root_uri =
+        Some(Url::from_file_path(root_path.as_path()).expect("Could not convert root path to URI"));
+
+    #[allow(deprecated)]
+    let initialize_params = lsp_types::InitializeParams {
+        process_id: Default::default(),
+        root_path: None,
+        root_uri,
+        initialization_options: None,
+        capabilities: Default::default(),
+        trace: Some(lsp_types::TraceValue::Verbose),
+        workspace_folders: None,
+        client_info: None,
+        locale: None,
+    };
+    let _initialize_response = crate::requests::on_initialize(&mut state, initialize_params)
+        .await
+        .expect("Could not initialize LSP server");
+
+    (state, noir_text_document)
+}
+
+/// Returns the canonical path of `test_programs/{directory}`
+pub(crate) fn test_program_path(directory: &str) -> std::path::PathBuf {
+    std::env::current_dir()
+        .unwrap()
+        .join("test_programs")
+        .join(directory)
+        .canonicalize()
+        .expect("Could not resolve root path")
+}
+
+/// Returns the ranges of every occurrence of `search_string` in the given file,
+/// sorted by position.
+pub(crate) fn search_in_file(uri: &Url, search_string: &str) -> Vec<Range> {
+    let file_path = uri.to_file_path().expect("Could not convert URI to file path");
+    let contents = std::fs::read_to_string(file_path).expect("Could not read file");
+
+    let mut ranges = Vec::new();
+    for (line, line_contents) in contents.lines().enumerate() {
+        for (character, _) in line_contents.match_indices(search_string) {
+            let start = Position { line: line as u32, character: character as u32 };
+            let end =
+                Position { line: line as u32, character: (character + search_string.len()) as u32 };
+            ranges.push(Range { start, end });
+        }
+    }
+    ranges
+}
@@ -1,6 +1,9 @@
 use fm::FileId;
 use lsp_types::{
-    DeclarationCapability, DefinitionOptions, OneOf, TypeDefinitionProviderCapability,
+    CodeActionProviderCapability, CompletionOptions, DeclarationCapability, DefinitionOptions,
+    DocumentSymbolOptions, HoverProviderCapability, InlayHintServerCapabilities, OneOf,
+    ReferencesOptions, RenameOptions, SemanticTokensServerCapabilities,
+    TypeDefinitionProviderCapability, WorkspaceSymbolOptions,
 };
 use noirc_driver::DebugFile;
 use noirc_errors::{debug_info::OpCodesCount, Location};
@@ -27,8 +30,10 @@ pub(crate) mod request {
 
     // Re-providing lsp_types that we don't need to override
     pub(crate) use lsp_types::request::{
-        CodeLensRequest as CodeLens, Formatting, GotoDeclaration, GotoDefinition,
-        GotoTypeDefinition, Shutdown,
+        CodeActionRequest, CodeLensRequest as CodeLens, Completion, DocumentSymbolRequest,
+        Formatting, GotoDeclaration, GotoDefinition, GotoTypeDefinition, HoverRequest,
+        InlayHintRequest, PrepareRenameRequest, References, Rename, SemanticTokensFullRequest,
+        Shutdown, WorkspaceSymbolRequest,
     };
 
     #[derive(Debug)]
@@ -125,6 +130,42 @@ pub(crate) struct ServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) type_definition_provider: Option<TypeDefinitionProviderCapability>,
 
+    /// The server provides hover support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hover_provider: Option<HoverProviderCapability>,
+
+    /// The server provides completion support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) completion_provider: Option<CompletionOptions>,
+
+    /// The server provides find references support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) references_provider: Option<OneOf<bool, ReferencesOptions>>,
+
+    /// The server provides rename support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) rename_provider: Option<OneOf<bool, RenameOptions>>,
+
+    /// The server provides document symbol support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_symbol_provider: Option<OneOf<bool, DocumentSymbolOptions>>,
+
+    /// The server provides workspace symbol support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) workspace_symbol_provider: Option<OneOf<bool, WorkspaceSymbolOptions>>,
+
+    /// The server provides semantic tokens support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) semantic_tokens_provider: Option<SemanticTokensServerCapabilities>,
+
+    /// The server provides inlay hints support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) inlay_hint_provider: Option<OneOf<bool, InlayHintServerCapabilities>>,
+
+    /// The server provides code actions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) code_action_provider: Option<CodeActionProviderCapability>,
+
     /// The server provides code lens.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) code_lens_provider: Option<CodeLensOptions>,
@@ -234,3 +275,13 @@ pub(crate) struct NargoProfileRunResult {
 pub(crate) type CodeLensResult = Option<Vec<CodeLens>>;
 pub(crate) type GotoDefinitionResult = Option<lsp_types::GotoDefinitionResponse>;
 pub(crate) type GotoDeclarationResult = Option<lsp_types::request::GotoDeclarationResponse>;
+pub(crate) type HoverResult = Option<lsp_types::Hover>;
+pub(crate) type ReferencesResult = Option<Vec<lsp_types::Location>>;
+pub(crate) type CompletionResult = Option<lsp_types::CompletionResponse>;
+pub(crate) type PrepareRenameResult = Option<lsp_types::PrepareRenameResponse>;
+pub(crate) type RenameResult = Option<lsp_types::WorkspaceEdit>;
+pub(crate) type DocumentSymbolResult = Option<lsp_types::DocumentSymbolResponse>;
+pub(crate) type WorkspaceSymbolResult = Option<lsp_types::WorkspaceSymbolResponse>;
+pub(crate) type SemanticTokensFullResult = Option<lsp_types::SemanticTokensResult>;
+pub(crate) type InlayHintResult = Option<Vec<lsp_types::InlayHint>>;
+pub(crate) type CodeActionResult = Option<lsp_types::CodeActionResponse>;
@@ -9,12 +9,16 @@ pub const SRC_DIR: &str = "src";
 pub const TARGET_DIR: &str = "target";
 /// The directory to store serialized ACIR representations of exported library functions.
 pub const EXPORT_DIR: &str = "export";
+/// The directory `nargo vendor` copies a workspace's git dependencies into.
+pub const VENDOR_DIR: &str = "vendor";
 
 // Files
 /// The file from which Nargo pulls prover inputs
 pub const PROVER_INPUT_FILE: &str = "Prover";
 /// The package definition file for a Noir project.
 pub const PKG_FILE: &str = "Nargo.toml";
+/// The file which pins the exact revision of each git dependency used by a workspace.
+pub const LOCK_FILE: &str = "Nargo.lock";
 
 // Extensions
 /// The extension for files containing circuit proofs.
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, time::Duration};
 
 use acvm::{
     acir::circuit::{
@@ -52,6 +52,10 @@ pub enum NargoError<F: AcirField> {
     /// Oracle handling error
     #[error(transparent)]
     ForeignCallError(#[from] ForeignCallError),
+
+    /// Execution was abandoned for running for too long
+    #[error(transparent)]
+    ExecutionLimitExceeded(#[from] ExecutionLimit),
 }
 
 impl<F: AcirField> NargoError<F> {
@@ -93,6 +97,16 @@ impl<F: AcirField> NargoError<F> {
     }
 }
 
+/// A bound set by [`ExecutionLimits`][crate::ops::ExecutionLimits] which a program exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ExecutionLimit {
+    #[error("Execution timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("Execution exceeded the limit of {0} steps")]
+    Steps(u64),
+}
+
 #[derive(Debug, Error)]
 pub enum ExecutionError<F: AcirField> {
     #[error("Failed assertion")]
@@ -13,7 +13,10 @@ pub mod ops;
 pub mod package;
 pub mod workspace;
 
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 
 use fm::{FileManager, FILE_EXTENSION};
 use noirc_driver::{add_dep, prepare_crate, prepare_dependency};
@@ -33,7 +36,7 @@ pub fn prepare_dependencies(
 ) {
     for (dep_name, dep) in dependencies.iter() {
         match dep {
-            Dependency::Remote { package } | Dependency::Local { package } => {
+            Dependency::Remote { package, .. } | Dependency::Local { package } => {
                 let crate_id = prepare_dependency(context, &package.entry_path);
                 add_dep(context, parent_crate, crate_id, dep_name.clone());
                 prepare_dependencies(context, crate_id, &package.dependencies);
@@ -45,9 +48,24 @@ pub fn prepare_dependencies(
 pub fn insert_all_files_for_workspace_into_file_manager(
     workspace: &workspace::Workspace,
     file_manager: &mut FileManager,
+) {
+    insert_all_files_for_workspace_into_file_manager_with_overrides(
+        workspace,
+        file_manager,
+        &HashMap::new(),
+    );
+}
+
+/// Like [insert_all_files_for_workspace_into_file_manager], but the files in `overrides` get
+/// the given source instead of what's on disk. Files are added in the same order either way,
+/// so they get the same [fm::FileId]s.
+pub fn insert_all_files_for_workspace_into_file_manager_with_overrides(
+    workspace: &workspace::Workspace,
+    file_manager: &mut FileManager,
+    overrides: &HashMap<&Path, &str>,
 ) {
     for package in workspace.clone().into_iter() {
-        insert_all_files_for_package_into_file_manager(package, file_manager);
+        insert_all_files_for_package_into_file_manager(package, file_manager, overrides);
     }
 }
 // We will pre-populate the file manager with all the files in the package
@@ -59,6 +77,7 @@ pub fn insert_all_files_for_workspace_into_file_manager(
 fn insert_all_files_for_package_into_file_manager(
     package: &Package,
     file_manager: &mut FileManager,
+    overrides: &HashMap<&Path, &str>,
 ) {
     // Start off at the entry path and read all files in the parent directory.
     let entry_path_parent = package
@@ -70,12 +89,15 @@ fn insert_all_files_for_package_into_file_manager(
     let paths = get_all_noir_source_in_dir(entry_path_parent)
         .expect("could not get all paths in the package");
     for path in paths {
-        let source = std::fs::read_to_string(path.as_path())
-            .unwrap_or_else(|_| panic!("could not read file {:?} into string", path));
+        let source = match overrides.get(path.as_path()) {
+            Some(source) => source.to_string(),
+            None => std::fs::read_to_string(path.as_path())
+                .unwrap_or_else(|_| panic!("could not read file {:?} into string", path)),
+        };
         file_manager.add_file_with_source(path.as_path(), source);
     }
 
-    insert_all_files_for_packages_dependencies_into_file_manager(package, file_manager);
+    insert_all_files_for_packages_dependencies_into_file_manager(package, file_manager, overrides);
 }
 
 // Inserts all files for the dependencies of the package into the file manager
@@ -83,12 +105,17 @@ fn insert_all_files_for_package_into_file_manager(
 fn insert_all_files_for_packages_dependencies_into_file_manager(
     package: &Package,
     file_manager: &mut FileManager,
+    overrides: &HashMap<&Path, &str>,
 ) {
     for (_, dep) in package.dependencies.iter() {
         match dep {
-            Dependency::Local { package } | Dependency::Remote { package } => {
-                insert_all_files_for_package_into_file_manager(package, file_manager);
-                insert_all_files_for_packages_dependencies_into_file_manager(package, file_manager);
+            Dependency::Local { package } | Dependency::Remote { package, .. } => {
+                insert_all_files_for_package_into_file_manager(package, file_manager, overrides);
+                insert_all_files_for_packages_dependencies_into_file_manager(
+                    package,
+                    file_manager,
+                    overrides,
+                );
             }
         }
     }
@@ -0,0 +1,75 @@
+use std::collections::{BTreeMap, HashMap};
+
+use acvm::acir::circuit::OpcodeLocation;
+use noirc_errors::{debug_info::DebugInfo, Location};
+
+/// How many times each ACIR opcode and Brillig instruction of a program was executed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OpcodeCoverage {
+    /// The hit counts of the opcodes of each ACIR function, indexed by the function's position in the program
+    functions: Vec<BTreeMap<OpcodeLocation, u64>>,
+}
+
+impl OpcodeCoverage {
+    pub(crate) fn record(&mut self, function_index: usize, opcode_location: OpcodeLocation) {
+        *self.function_hits(function_index).entry(opcode_location).or_default() += 1;
+    }
+
+    fn function_hits(&mut self, function_index: usize) -> &mut BTreeMap<OpcodeLocation, u64> {
+        if self.functions.len() <= function_index {
+            self.functions.resize_with(function_index + 1, BTreeMap::new);
+        }
+        &mut self.functions[function_index]
+    }
+
+    /// The number of times the opcode at `opcode_location` in the given ACIR function was executed
+    pub fn hits(&self, function_index: usize, opcode_location: &OpcodeLocation) -> u64 {
+        self.functions
+            .get(function_index)
+            .and_then(|hits| hits.get(opcode_location))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Adds the hit counts from another execution of the same program to these.
+    pub fn merge(&mut self, other: &OpcodeCoverage) {
+        for (function_index, hits) in other.functions.iter().enumerate() {
+            let function_hits = self.function_hits(function_index);
+            for (opcode_location, count) in hits {
+                *function_hits.entry(*opcode_location).or_default() += count;
+            }
+        }
+    }
+
+    /// Maps the hit counts back to the source code the opcodes were generated from.
+    ///
+    /// Every location in `debug` is included, with a count of zero if none of its opcodes were executed.
+    pub fn source_coverage(&self, debug: &[DebugInfo]) -> SourceCoverage {
+        let mut coverage = SourceCoverage::default();
+        for (function_index, debug_info) in debug.iter().enumerate() {
+            for (opcode_location, locations) in &debug_info.locations {
+                let count = self.hits(function_index, opcode_location);
+                // An inlined opcode counts towards each call site it was inlined through.
+                for location in locations {
+                    *coverage.hits.entry(*location).or_default() += count;
+                }
+            }
+        }
+        coverage
+    }
+}
+
+/// How many times the opcodes generated from each location in the source code were executed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceCoverage {
+    pub hits: HashMap<Location, u64>,
+}
+
+impl SourceCoverage {
+    /// Adds the hit counts of `other`, which may come from a different program, to these.
+    pub fn merge(&mut self, other: SourceCoverage) {
+        for (location, count) in other.hits {
+            *self.hits.entry(location).or_default() += count;
+        }
+    }
+}
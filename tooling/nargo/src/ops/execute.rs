@@ -1,16 +1,43 @@
+use acvm::acir::brillig::Opcode as BrilligOpcode;
 use acvm::acir::circuit::brillig::BrilligBytecode;
 use acvm::acir::circuit::{
-    OpcodeLocation, Program, ResolvedAssertionPayload, ResolvedOpcodeLocation,
+    Opcode, OpcodeLocation, Program, ResolvedAssertionPayload, ResolvedOpcodeLocation,
 };
+use std::time::{Duration, Instant};
+
 use acvm::acir::native_types::WitnessStack;
-use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError, ACVM};
+use acvm::pwg::{
+    get_value, ACVMStatus, BrilligSolver, BrilligSolverStatus, ErrorLocation, OpcodeNotSolvable,
+    OpcodeResolutionError, StepResult, ACVM,
+};
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 use acvm::{AcirField, BlackBoxFunctionSolver};
 
-use crate::errors::ExecutionError;
+use crate::errors::{ExecutionError, ExecutionLimit};
 use crate::NargoError;
 
+use super::coverage::OpcodeCoverage;
 use super::foreign_calls::ForeignCallExecutor;
+use super::profile::{BrilligProfile, ProfileRecorder};
+
+/// Bounds on how much work executing a program may do before it is abandoned, so that a program
+/// which never terminates results in an error rather than hanging.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    /// The wall-clock time execution may take
+    pub timeout: Option<Duration>,
+    /// The number of ACIR opcodes and Brillig instructions which may be executed, across all of the program's functions
+    pub max_steps: Option<u64>,
+}
+
+impl ExecutionLimits {
+    fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.max_steps.is_none()
+    }
+}
+
+/// Reading the clock is slow compared to executing a Brillig instruction, so the deadline is only checked once every this many steps.
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
 
 struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>> {
     functions: &'a [Circuit<F>],
@@ -32,6 +59,20 @@ struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecut
     // This is used to fetch the function we want to execute
     // and to resolve call stack locations across many function calls.
     current_function_index: usize,
+
+    limits: ExecutionLimits,
+
+    // The point in time at which execution exceeds `limits.timeout`
+    deadline: Option<Instant>,
+
+    // The number of ACIR opcodes and Brillig instructions executed so far
+    steps: u64,
+
+    // Records every ACIR opcode and Brillig instruction as it's executed, if coverage is being collected
+    coverage: Option<&'a mut OpcodeCoverage>,
+
+    // Records the call stack of every Brillig instruction as it's executed, if the program is being profiled
+    profile: Option<ProfileRecorder<'a>>,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
@@ -42,6 +83,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         unconstrained_functions: &'a [BrilligBytecode<F>],
         blackbox_solver: &'a B,
         foreign_call_executor: &'a mut E,
+        limits: ExecutionLimits,
+        coverage: Option<&'a mut OpcodeCoverage>,
+        profile: Option<&'a mut BrilligProfile>,
     ) -> Self {
         ProgramExecutor {
             functions,
@@ -51,6 +95,11 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             foreign_call_executor,
             call_stack: Vec::default(),
             current_function_index: 0,
+            limits,
+            deadline: limits.timeout.and_then(|timeout| Instant::now().checked_add(timeout)),
+            steps: 0,
+            coverage,
+            profile: profile.map(ProfileRecorder::new),
         }
     }
 
@@ -58,6 +107,14 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         self.witness_stack
     }
 
+    /// Adds everything recorded while executing to the profile, if the program is being profiled.
+    /// This is done even when execution fails, so that the work done up to the failure shows up.
+    fn finish_profile(&mut self) {
+        if let Some(profile) = self.profile.take() {
+            profile.finish();
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     fn execute_circuit(
         &mut self,
@@ -72,8 +129,10 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             &circuit.assert_messages,
         );
 
+        // Whether the ACVM is resuming from an ACIR call, in which case it visits the call opcode again
+        let mut resuming_call = false;
         loop {
-            let solver_status = acvm.solve();
+            let solver_status = self.solve(&mut acvm, std::mem::take(&mut resuming_call))?;
 
             match solver_status {
                 ACVMStatus::Solved => break,
@@ -164,12 +223,158 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                     }
                     acvm.resolve_pending_acir_call(call_resolved_outputs);
                     self.witness_stack.push(call_info.id, call_solved_witness);
+                    resuming_call = true;
                 }
             }
         }
 
         Ok(acvm.finalize())
     }
+
+    /// Executes the circuit until execution halts, as [`ACVM::solve`] does, but one opcode or Brillig
+    /// instruction at a time so that the execution limits can be enforced and coverage or profiles recorded.
+    ///
+    /// Foreign calls made from Brillig are resolved here rather than being returned.
+    ///
+    /// When `resuming_call` is set the ACVM is on a call opcode which has already been counted and recorded,
+    /// and which it only visits again to write the call's outputs.
+    fn solve(
+        &mut self,
+        acvm: &mut ACVM<'a, F, B>,
+        mut resuming_call: bool,
+    ) -> Result<ACVMStatus<F>, NargoError<F>> {
+        if self.limits.is_unlimited() && self.coverage.is_none() && self.profile.is_none() {
+            return Ok(acvm.solve());
+        }
+
+        while *acvm.get_status() == ACVMStatus::InProgress {
+            if !std::mem::take(&mut resuming_call) {
+                self.step()?;
+                self.record_acir_opcode(acvm);
+            }
+            let acir_index = acvm.instruction_pointer();
+            if let StepResult::IntoBrillig(solver) = acvm.step_into_brillig() {
+                let solver = self.solve_brillig(solver, acir_index)?;
+                acvm.finish_brillig_with_solver(solver);
+            }
+        }
+        Ok(acvm.get_status().clone())
+    }
+
+    /// Steps through a Brillig call until it finishes or fails, after which it's handed back to the ACVM
+    /// to write its outputs or to report its failure.
+    fn solve_brillig(
+        &mut self,
+        mut solver: BrilligSolver<'a, F, B>,
+        acir_index: usize,
+    ) -> Result<BrilligSolver<'a, F, B>, NargoError<F>> {
+        let bytecode = self.enter_brillig_call(acir_index);
+        // The VM stays on a foreign call until its result has been resolved, so it's stepped again to write it
+        let mut resuming_foreign_call = false;
+        loop {
+            if !std::mem::take(&mut resuming_foreign_call) {
+                self.step()?;
+                if let Some(coverage) = self.coverage.as_deref_mut() {
+                    let brillig_index = solver.program_counter();
+                    coverage.record(
+                        self.current_function_index,
+                        OpcodeLocation::Brillig { acir_index, brillig_index },
+                    );
+                }
+                self.record_brillig_instruction(&solver, acir_index, bytecode);
+            }
+            match solver.step() {
+                Ok(BrilligSolverStatus::InProgress) => {}
+                Ok(BrilligSolverStatus::ForeignCallWait(foreign_call)) => {
+                    if let Some(profile) = self.profile.as_mut() {
+                        profile.record_foreign_call(&foreign_call.function);
+                    }
+                    let foreign_call_result = self.foreign_call_executor.execute(&foreign_call)?;
+                    solver.resolve_pending_foreign_call(foreign_call_result);
+                    resuming_foreign_call = true;
+                }
+                // The VM stays on the instruction which halted it, so the ACVM will run into the same result.
+                Ok(BrilligSolverStatus::Finished) | Err(_) => return Ok(solver),
+            }
+        }
+    }
+
+    /// Starts recording the Brillig call at `acir_index` in the profile, with the call stack from the
+    /// program's entry point through the ACIR calls, and returns the bytecode being called.
+    fn enter_brillig_call(&mut self, acir_index: usize) -> &'a [BrilligOpcode<F>] {
+        let Some(profile) = self.profile.as_mut() else {
+            return &[];
+        };
+        let function_index = self.current_function_index;
+        let acir_calls = self
+            .call_stack
+            .iter()
+            .map(|location| (location.acir_function_index, location.opcode_location));
+        let brillig_call = std::iter::once((function_index, OpcodeLocation::Acir(acir_index)));
+        profile.enter_brillig_call(acir_calls.chain(brillig_call));
+
+        let (functions, unconstrained_functions) = (self.functions, self.unconstrained_functions);
+        match &functions[function_index].opcodes[acir_index] {
+            Opcode::BrilligCall { id, .. } => &unconstrained_functions[*id as usize].bytecode,
+            _ => &[],
+        }
+    }
+
+    /// Records the Brillig instruction about to be executed in the profile, following the calls made
+    /// within Brillig.
+    fn record_brillig_instruction(
+        &mut self,
+        solver: &BrilligSolver<'a, F, B>,
+        acir_index: usize,
+        bytecode: &[BrilligOpcode<F>],
+    ) {
+        let Some(profile) = self.profile.as_mut() else {
+            return;
+        };
+        let brillig_index = solver.program_counter();
+        profile.record_instruction(
+            self.current_function_index,
+            OpcodeLocation::Brillig { acir_index, brillig_index },
+            bytecode.get(brillig_index),
+            solver.get_memory().len(),
+        );
+    }
+
+    /// Records the ACIR opcode about to be executed in the coverage, unless it's a call which will be skipped.
+    fn record_acir_opcode(&mut self, acvm: &ACVM<'a, F, B>) {
+        let Some(coverage) = self.coverage.as_deref_mut() else {
+            return;
+        };
+        let opcode_index = acvm.instruction_pointer();
+        let predicate = match &acvm.opcodes()[opcode_index] {
+            Opcode::BrilligCall { predicate, .. } | Opcode::Call { predicate, .. } => {
+                predicate.as_ref()
+            }
+            _ => None,
+        };
+        let skipped = predicate.is_some_and(|predicate| {
+            get_value(predicate, acvm.witness_map()).is_ok_and(|value| value.is_zero())
+        });
+        if !skipped {
+            coverage.record(self.current_function_index, OpcodeLocation::Acir(opcode_index));
+        }
+    }
+
+    /// Counts an ACIR opcode or Brillig instruction as executed, failing if this exceeds the execution limits.
+    fn step(&mut self) -> Result<(), ExecutionLimit> {
+        self.steps += 1;
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.steps > max_steps {
+                return Err(ExecutionLimit::Steps(max_steps));
+            }
+        }
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.limits.timeout) {
+            if self.steps % DEADLINE_CHECK_INTERVAL == 0 && Instant::now() >= deadline {
+                return Err(ExecutionLimit::Timeout(timeout));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[tracing::instrument(level = "trace", skip_all)]
@@ -178,15 +383,365 @@ pub fn execute_program<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCal
     initial_witness: WitnessMap<F>,
     blackbox_solver: &B,
     foreign_call_executor: &mut E,
+) -> Result<WitnessStack<F>, NargoError<F>> {
+    execute_program_with_limits(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        ExecutionLimits::default(),
+    )
+}
+
+/// Executes `program` as [`execute_program`] does, returning a [`NargoError::ExecutionLimitExceeded`]
+/// error if execution exceeds the given `limits`.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_limits<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    limits: ExecutionLimits,
+) -> Result<WitnessStack<F>, NargoError<F>> {
+    run_program(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        limits,
+        None,
+        None,
+    )
+}
+
+/// Executes `program` as [`execute_program_with_limits`] does, adding the number of times each ACIR
+/// opcode and Brillig instruction was executed to `coverage`.
+///
+/// The opcodes executed before any error are recorded as well.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_coverage<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    limits: ExecutionLimits,
+    coverage: &mut OpcodeCoverage,
+) -> Result<WitnessStack<F>, NargoError<F>> {
+    run_program(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        limits,
+        Some(coverage),
+        None,
+    )
+}
+
+/// Executes `program` as [`execute_program`] does, recording the call stack of every Brillig
+/// instruction executed, the peak Brillig memory usage and the foreign calls made in `profile`.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_profile<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    profile: &mut BrilligProfile,
+) -> Result<WitnessStack<F>, NargoError<F>> {
+    run_program(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        ExecutionLimits::default(),
+        None,
+        Some(profile),
+    )
+}
+
+fn run_program<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    limits: ExecutionLimits,
+    coverage: Option<&mut OpcodeCoverage>,
+    profile: Option<&mut BrilligProfile>,
 ) -> Result<WitnessStack<F>, NargoError<F>> {
     let mut executor = ProgramExecutor::new(
         &program.functions,
         &program.unconstrained_functions,
         blackbox_solver,
         foreign_call_executor,
+        limits,
+        coverage,
+        profile,
     );
-    let main_witness = executor.execute_circuit(initial_witness)?;
-    executor.witness_stack.push(0, main_witness);
+    let result = executor.execute_circuit(initial_witness);
+    executor.finish_profile();
+    executor.witness_stack.push(0, result?);
 
     Ok(executor.finalize())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use acvm::{
+        acir::{
+            brillig::{MemoryAddress, Opcode as BrilligOpcode},
+            circuit::{
+                brillig::BrilligBytecode, opcodes::Opcode, Circuit, OpcodeLocation, Program,
+            },
+            native_types::{Expression, WitnessMap},
+        },
+        blackbox_solver::StubbedBlackBoxSolver,
+        FieldElement,
+    };
+
+    use crate::{
+        errors::ExecutionLimit,
+        ops::{BrilligProfile, DefaultForeignCallExecutor, OpcodeCoverage},
+        NargoError,
+    };
+
+    use super::{
+        execute_program_with_coverage, execute_program_with_limits, execute_program_with_profile,
+        ExecutionLimits,
+    };
+
+    /// A program which calls into an unconstrained function that never returns
+    fn infinite_loop() -> Program<FieldElement> {
+        let main = Circuit {
+            opcodes: vec![Opcode::BrilligCall {
+                id: 0,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                predicate: None,
+            }],
+            ..Circuit::default()
+        };
+        let spin = BrilligBytecode { bytecode: vec![BrilligOpcode::Jump { location: 0 }] };
+        Program { functions: vec![main], unconstrained_functions: vec![spin] }
+    }
+
+    fn execute(limits: ExecutionLimits) -> Option<ExecutionLimit> {
+        let result = execute_program_with_limits(
+            &infinite_loop(),
+            WitnessMap::new(),
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            limits,
+        );
+        match result {
+            Err(NargoError::ExecutionLimitExceeded(limit)) => Some(limit),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn stops_after_max_steps() {
+        let limits = ExecutionLimits { max_steps: Some(1000), ..ExecutionLimits::default() };
+        assert_eq!(execute(limits), Some(ExecutionLimit::Steps(1000)));
+    }
+
+    #[test]
+    fn stops_after_timeout() {
+        let timeout = Duration::from_millis(50);
+        let limits = ExecutionLimits { timeout: Some(timeout), ..ExecutionLimits::default() };
+        assert_eq!(execute(limits), Some(ExecutionLimit::Timeout(timeout)));
+    }
+
+    /// A program which makes an ACIR call into a function which calls an unconstrained function making
+    /// a foreign call, so that execution resumes once from each kind of call
+    fn resumed_calls() -> Program<FieldElement> {
+        let main = Circuit {
+            opcodes: vec![Opcode::Call {
+                id: 1,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                predicate: None,
+            }],
+            ..Circuit::default()
+        };
+        let callee = Circuit {
+            opcodes: vec![Opcode::BrilligCall {
+                id: 0,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                predicate: None,
+            }],
+            ..Circuit::default()
+        };
+        let log = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::ForeignCall {
+                    function: "log".to_string(),
+                    destinations: Vec::new(),
+                    destination_value_types: Vec::new(),
+                    inputs: Vec::new(),
+                    input_value_types: Vec::new(),
+                },
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            ],
+        };
+        Program { functions: vec![main, callee], unconstrained_functions: vec![log] }
+    }
+
+    #[test]
+    fn resumed_calls_count_as_one_step() {
+        // The ACIR call, the Brillig call, the foreign call and stopping
+        let limits = ExecutionLimits { max_steps: Some(4), ..ExecutionLimits::default() };
+        let result = execute_program_with_limits(
+            &resumed_calls(),
+            WitnessMap::new(),
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            limits,
+        );
+        assert!(result.is_ok(), "Expected execution to stay within its limits: {result:?}");
+    }
+
+    #[test]
+    fn records_coverage_of_executed_opcodes() {
+        let brillig_call = |predicate: Option<Expression<FieldElement>>| Opcode::BrilligCall {
+            id: 0,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            predicate,
+        };
+        let main = Circuit {
+            opcodes: vec![brillig_call(Some(Expression::zero())), brillig_call(None)],
+            ..Circuit::default()
+        };
+        let stop = BrilligBytecode {
+            bytecode: vec![BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 }],
+        };
+        let program = Program { functions: vec![main], unconstrained_functions: vec![stop] };
+
+        let mut coverage = OpcodeCoverage::default();
+        execute_program_with_coverage(
+            &program,
+            WitnessMap::new(),
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            ExecutionLimits::default(),
+            &mut coverage,
+        )
+        .unwrap();
+
+        // The first call's predicate is false, so it's skipped without running the function
+        assert_eq!(coverage.hits(0, &OpcodeLocation::Acir(0)), 0);
+        assert_eq!(
+            coverage.hits(0, &OpcodeLocation::Brillig { acir_index: 0, brillig_index: 0 }),
+            0
+        );
+        assert_eq!(coverage.hits(0, &OpcodeLocation::Acir(1)), 1);
+        assert_eq!(
+            coverage.hits(0, &OpcodeLocation::Brillig { acir_index: 1, brillig_index: 0 }),
+            1
+        );
+    }
+
+    #[test]
+    fn records_resumed_calls_once() {
+        let mut coverage = OpcodeCoverage::default();
+        execute_program_with_coverage(
+            &resumed_calls(),
+            WitnessMap::new(),
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            ExecutionLimits::default(),
+            &mut coverage,
+        )
+        .unwrap();
+
+        assert_eq!(coverage.hits(0, &OpcodeLocation::Acir(0)), 1);
+        assert_eq!(coverage.hits(1, &OpcodeLocation::Acir(0)), 1);
+        let brillig = |brillig_index| OpcodeLocation::Brillig { acir_index: 0, brillig_index };
+        assert_eq!(coverage.hits(1, &brillig(0)), 1);
+        assert_eq!(coverage.hits(1, &brillig(1)), 1);
+    }
+
+    #[test]
+    fn profiles_brillig_call_stacks() {
+        let main = Circuit {
+            opcodes: vec![Opcode::BrilligCall {
+                id: 0,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                predicate: None,
+            }],
+            ..Circuit::default()
+        };
+        // Calls a function which writes to the sixth memory slot
+        let bytecode = vec![
+            BrilligOpcode::Call { location: 2 },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            BrilligOpcode::Const {
+                destination: MemoryAddress(5),
+                bit_size: 32,
+                value: FieldElement::from(1u128),
+            },
+            BrilligOpcode::Return,
+        ];
+        let program = Program {
+            functions: vec![main],
+            unconstrained_functions: vec![BrilligBytecode { bytecode }],
+        };
+
+        let mut profile = BrilligProfile::default();
+        execute_program_with_profile(
+            &program,
+            WitnessMap::new(),
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            &mut profile,
+        )
+        .unwrap();
+
+        let brillig = |brillig_index| (0, OpcodeLocation::Brillig { acir_index: 0, brillig_index });
+        let call_site = (0, OpcodeLocation::Acir(0));
+        assert_eq!(profile.total_instructions(), 4);
+        assert_eq!(profile.samples[&vec![call_site, brillig(0)]], 1);
+        assert_eq!(profile.samples[&vec![call_site, brillig(0), brillig(2)]], 1);
+        assert_eq!(profile.samples[&vec![call_site, brillig(0), brillig(3)]], 1);
+        assert_eq!(profile.samples[&vec![call_site, brillig(1)]], 1);
+        assert_eq!(profile.peak_memory, 6);
+    }
+
+    #[test]
+    fn profiles_resumed_foreign_calls_once() {
+        let mut profile = BrilligProfile::default();
+        execute_program_with_profile(
+            &resumed_calls(),
+            WitnessMap::new(),
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            &mut profile,
+        )
+        .unwrap();
+
+        let brillig = |brillig_index| (1, OpcodeLocation::Brillig { acir_index: 0, brillig_index });
+        let call_stack = |brillig_index| {
+            vec![(0, OpcodeLocation::Acir(0)), (1, OpcodeLocation::Acir(0)), brillig(brillig_index)]
+        };
+        assert_eq!(profile.total_instructions(), 2);
+        assert_eq!(profile.samples[&call_stack(0)], 1);
+        assert_eq!(profile.samples[&call_stack(1)], 1);
+        assert_eq!(profile.foreign_calls["log"], 1);
+    }
+}
@@ -2,16 +2,25 @@ pub use self::compile::{
     collect_errors, compile_contract, compile_program, compile_program_with_debug_instrumenter,
     compile_workspace, report_errors,
 };
-pub use self::execute::execute_program;
+pub use self::coverage::{OpcodeCoverage, SourceCoverage};
+pub use self::execute::{
+    execute_program, execute_program_with_coverage, execute_program_with_limits,
+    execute_program_with_profile, ExecutionLimits,
+};
 pub use self::foreign_calls::{DefaultForeignCallExecutor, ForeignCall, ForeignCallExecutor};
 pub use self::optimize::{optimize_contract, optimize_program};
+pub use self::profile::{BrilligProfile, ProfileCallStack};
 pub use self::transform::{transform_contract, transform_program};
 
-pub use self::test::{run_test, TestStatus};
+pub use self::test::{
+    run_test, test_status_program_compile_fail, test_status_program_compile_pass, TestStatus,
+};
 
 mod compile;
+mod coverage;
 mod execute;
 mod foreign_calls;
 mod optimize;
+mod profile;
 mod test;
 mod transform;
@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap};
+
+use acvm::acir::{brillig::Opcode as BrilligOpcode, circuit::OpcodeLocation};
+
+/// A call stack through a program, from its entry point to a Brillig instruction.
+///
+/// Each frame is the index of the ACIR function it's in, along with the location of the call
+/// or, for the last frame, the instruction being executed.
+pub type ProfileCallStack = Vec<(usize, OpcodeLocation)>;
+
+/// Statistics gathered while executing the unconstrained functions of a program.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BrilligProfile {
+    /// How many Brillig instructions were executed with each call stack
+    pub samples: HashMap<ProfileCallStack, u64>,
+    /// The most memory slots used by any single Brillig call
+    pub peak_memory: usize,
+    /// How many times each oracle was called
+    pub foreign_calls: BTreeMap<String, u64>,
+}
+
+impl BrilligProfile {
+    /// The number of Brillig instructions executed in total
+    pub fn total_instructions(&self) -> u64 {
+        self.samples.values().sum()
+    }
+}
+
+/// Records a [BrilligProfile] while a program executes.
+///
+/// Building the call stack of every instruction executed would be far too slow, so call stacks
+/// are interned as calls are made and instructions are counted by the index of the call stack
+/// they're executed with. Each call stack is only resolved once, when recording finishes.
+pub(crate) struct ProfileRecorder<'a> {
+    profile: &'a mut BrilligProfile,
+    /// Every call stack entered so far, as the index of its caller's stack and the call's frame
+    stacks: Vec<(Option<usize>, (usize, OpcodeLocation))>,
+    stack_indices: HashMap<(Option<usize>, (usize, OpcodeLocation)), usize>,
+    /// The stacks of the Brillig functions being executed, with the innermost one last
+    brillig_stacks: Vec<usize>,
+    /// How many times each instruction was executed, by the index of the call stack it ran with
+    counts: HashMap<(usize, OpcodeLocation), u64>,
+}
+
+impl<'a> ProfileRecorder<'a> {
+    pub(crate) fn new(profile: &'a mut BrilligProfile) -> Self {
+        ProfileRecorder {
+            profile,
+            stacks: Vec::new(),
+            stack_indices: HashMap::new(),
+            brillig_stacks: Vec::new(),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Starts recording a Brillig call, where `call_stack` holds the frames of the ACIR calls
+    /// leading up to it followed by the Brillig call itself.
+    pub(crate) fn enter_brillig_call(
+        &mut self,
+        call_stack: impl IntoIterator<Item = (usize, OpcodeLocation)>,
+    ) {
+        let mut stack = None;
+        for frame in call_stack {
+            stack = Some(self.intern(stack, frame));
+        }
+        self.brillig_stacks.clear();
+        self.brillig_stacks.extend(stack);
+    }
+
+    /// Counts the Brillig instruction `opcode` at `location` in the ACIR function at
+    /// `function_index` as executed, keeping track of the calls and returns it makes.
+    pub(crate) fn record_instruction<F>(
+        &mut self,
+        function_index: usize,
+        location: OpcodeLocation,
+        opcode: Option<&BrilligOpcode<F>>,
+        memory_size: usize,
+    ) {
+        let Some(&stack) = self.brillig_stacks.last() else {
+            return;
+        };
+        *self.counts.entry((stack, location)).or_default() += 1;
+        self.profile.peak_memory = self.profile.peak_memory.max(memory_size);
+
+        match opcode {
+            Some(BrilligOpcode::Call { .. }) => {
+                let callee_stack = self.intern(Some(stack), (function_index, location));
+                self.brillig_stacks.push(callee_stack);
+            }
+            // The outermost function stops rather than returning, but the stack is kept either way
+            Some(BrilligOpcode::Return) if self.brillig_stacks.len() > 1 => {
+                self.brillig_stacks.pop();
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn record_foreign_call(&mut self, function: &str) {
+        *self.profile.foreign_calls.entry(function.to_string()).or_default() += 1;
+    }
+
+    /// Adds the instructions counted to the profile, along with their resolved call stacks.
+    pub(crate) fn finish(self) {
+        // A stack is always interned after its caller's, so each caller is resolved first
+        let mut resolved: Vec<ProfileCallStack> = Vec::with_capacity(self.stacks.len());
+        for (caller, frame) in &self.stacks {
+            let mut call_stack = caller.map(|caller| resolved[caller].clone()).unwrap_or_default();
+            call_stack.push(*frame);
+            resolved.push(call_stack);
+        }
+
+        for ((stack, location), count) in self.counts {
+            let mut call_stack = resolved[stack].clone();
+            let (function_index, _) = call_stack[call_stack.len() - 1];
+            call_stack.push((function_index, location));
+            *self.profile.samples.entry(call_stack).or_default() += count;
+        }
+    }
+
+    fn intern(&mut self, caller: Option<usize>, frame: (usize, OpcodeLocation)) -> usize {
+        *self.stack_indices.entry((caller, frame)).or_insert_with(|| {
+            self.stacks.push((caller, frame));
+            self.stacks.len() - 1
+        })
+    }
+}
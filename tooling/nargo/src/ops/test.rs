@@ -7,14 +7,25 @@ use noirc_driver::{compile_no_check, CompileError, CompileOptions};
 use noirc_errors::{debug_info::DebugInfo, FileDiagnostic};
 use noirc_frontend::hir::{def_map::TestFunction, Context};
 
-use crate::{errors::try_to_diagnose_runtime_error, NargoError};
+use crate::{
+    errors::{try_to_diagnose_runtime_error, ExecutionLimit},
+    NargoError,
+};
 
-use super::{execute_program, DefaultForeignCallExecutor};
+use super::{
+    execute_program_with_coverage, execute_program_with_limits, DefaultForeignCallExecutor,
+    ExecutionLimits, OpcodeCoverage, SourceCoverage,
+};
 
 pub enum TestStatus {
     Pass,
-    Fail { message: String, error_diagnostic: Option<FileDiagnostic> },
+    Fail {
+        message: String,
+        error_diagnostic: Option<FileDiagnostic>,
+    },
     CompileError(FileDiagnostic),
+    /// The test was abandoned for running for too long, regardless of whether it should fail
+    LimitExceeded(ExecutionLimit),
 }
 
 impl TestStatus {
@@ -23,6 +34,7 @@ impl TestStatus {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
     blackbox_solver: &B,
     context: &mut Context,
@@ -30,22 +42,42 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
     show_output: bool,
     foreign_call_resolver_url: Option<&str>,
     config: &CompileOptions,
+    limits: ExecutionLimits,
+    coverage: Option<&mut SourceCoverage>,
 ) -> TestStatus {
     let compiled_program = compile_no_check(context, config, test_function.get_id(), None, false);
     match compiled_program {
         Ok(compiled_program) => {
             // Run the backend to ensure the PWG evaluates functions like std::hash::pedersen,
             // otherwise constraints involving these expressions will not error.
-            let circuit_execution = execute_program(
-                &compiled_program.program,
-                WitnessMap::new(),
-                blackbox_solver,
-                &mut DefaultForeignCallExecutor::new(show_output, foreign_call_resolver_url),
-            );
+            let mut foreign_call_executor =
+                DefaultForeignCallExecutor::new(show_output, foreign_call_resolver_url);
+            let circuit_execution = match coverage {
+                Some(coverage) => {
+                    let mut opcode_coverage = OpcodeCoverage::default();
+                    let circuit_execution = execute_program_with_coverage(
+                        &compiled_program.program,
+                        WitnessMap::new(),
+                        blackbox_solver,
+                        &mut foreign_call_executor,
+                        limits,
+                        &mut opcode_coverage,
+                    );
+                    coverage.merge(opcode_coverage.source_coverage(&compiled_program.debug));
+                    circuit_execution
+                }
+                None => execute_program_with_limits(
+                    &compiled_program.program,
+                    WitnessMap::new(),
+                    blackbox_solver,
+                    &mut foreign_call_executor,
+                    limits,
+                ),
+            };
             test_status_program_compile_pass(
                 test_function,
-                compiled_program.abi,
-                compiled_program.debug,
+                &compiled_program.abi,
+                &compiled_program.debug,
                 circuit_execution,
             )
         }
@@ -59,7 +91,10 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
 /// that a constraint was never satisfiable.
 /// An example of this is the program `assert(false)`
 /// In that case, we check if the test function should fail, and if so, we return `TestStatus::Pass`.
-fn test_status_program_compile_fail(err: CompileError, test_function: &TestFunction) -> TestStatus {
+pub fn test_status_program_compile_fail(
+    err: CompileError,
+    test_function: &TestFunction,
+) -> TestStatus {
     // The test has failed compilation, but it should never fail. Report error.
     if !test_function.should_fail() {
         return TestStatus::CompileError(err.into());
@@ -72,10 +107,10 @@ fn test_status_program_compile_fail(err: CompileError, test_function: &TestFunct
 ///
 /// We now check whether execution passed/failed and whether it should have
 /// passed/failed to determine the test status.
-fn test_status_program_compile_pass(
+pub fn test_status_program_compile_pass(
     test_function: &TestFunction,
-    abi: Abi,
-    debug: Vec<DebugInfo>,
+    abi: &Abi,
+    debug: &[DebugInfo],
     circuit_execution: Result<WitnessStack<FieldElement>, NargoError<FieldElement>>,
 ) -> TestStatus {
     let circuit_execution_err = match circuit_execution {
@@ -90,13 +125,15 @@ fn test_status_program_compile_pass(
             }
             return TestStatus::Pass;
         }
+        // Running out of time or steps doesn't count as the failure a `should_fail` test expects.
+        Err(NargoError::ExecutionLimitExceeded(limit)) => return TestStatus::LimitExceeded(limit),
         Err(err) => err,
     };
 
     // If we reach here, then the circuit execution failed.
     //
     // Check if the function should have passed
-    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, &abi, &debug);
+    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, abi, debug);
     let test_should_have_passed = !test_function.should_fail();
     if test_should_have_passed {
         return TestStatus::Fail {
@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf, time::Duration};
 
 use noirc_frontend::graph::CrateName;
 
@@ -23,24 +23,43 @@ impl Display for PackageType {
 
 #[derive(Clone)]
 pub enum Dependency {
-    Local { package: Package },
-    Remote { package: Package },
+    Local {
+        package: Package,
+    },
+    Remote {
+        package: Package,
+        /// Where the package was fetched from, e.g. "https://github.com/noir-lang/ec at tag `v0.1.0`"
+        source: String,
+    },
 }
 
 impl Dependency {
     pub fn is_binary(&self) -> bool {
-        match self {
-            Self::Local { package } | Self::Remote { package } => package.is_binary(),
-        }
+        self.package().is_binary()
     }
 
     pub fn package_name(&self) -> &CrateName {
+        &self.package().name
+    }
+
+    pub fn package(&self) -> &Package {
         match self {
-            Self::Local { package } | Self::Remote { package } => &package.name,
+            Self::Local { package } | Self::Remote { package, .. } => package,
         }
     }
 }
 
+/// Settings from the `[test]` table of a `Nargo.toml` which control how `nargo test` runs this package's tests.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestConfig {
+    /// The number of inputs each `#[test]` function which takes arguments is fuzzed with
+    pub fuzz_runs: Option<u32>,
+    /// How long each test may run for before it's abandoned
+    pub timeout: Option<Duration>,
+    /// How many ACIR opcodes and Brillig instructions each test may execute before it's abandoned
+    pub max_steps: Option<u64>,
+}
+
 #[derive(Clone)]
 pub struct Package {
     pub version: Option<String>,
@@ -51,6 +70,7 @@ pub struct Package {
     pub entry_path: PathBuf,
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
+    pub test_config: TestConfig,
 }
 
 impl Package {
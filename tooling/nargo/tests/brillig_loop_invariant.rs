@@ -0,0 +1,87 @@
+//! Checks that loop-invariant code motion makes the `brillig_loop_invariant` test program execute
+//! fewer Brillig opcodes, by compiling it with and without the pass.
+use std::path::Path;
+
+use acvm::blackbox_solver::StubbedBlackBoxSolver;
+use nargo::ops::{execute_program_with_profile, BrilligProfile, DefaultForeignCallExecutor};
+use noirc_abi::input_parser::Format;
+use noirc_driver::{
+    compile_main, file_manager_with_stdlib, prepare_crate, CompilationResult, CompileOptions,
+    CompiledProgram,
+};
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+const SOURCE: &str =
+    include_str!("../../../test_programs/execution_success/brillig_loop_invariant/src/main.nr");
+const PROVER_TOML: &str =
+    include_str!("../../../test_programs/execution_success/brillig_loop_invariant/Prover.toml");
+
+fn compile(source: &str, options: &CompileOptions) -> CompilationResult<CompiledProgram> {
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+
+    compile_main(&mut context, root_crate_id, options, None)
+}
+
+/// Executes `program` with the inputs of the test program's `Prover.toml`, returning the number
+/// of Brillig opcodes executed.
+fn brillig_opcodes_executed(program: &CompiledProgram) -> u64 {
+    let inputs = Format::Toml.parse(PROVER_TOML, &program.abi).expect("Prover.toml should parse");
+    let witness_map = program.abi.encode(&inputs, None).unwrap();
+
+    let mut profile = BrilligProfile::default();
+    execute_program_with_profile(
+        &program.program,
+        witness_map,
+        &StubbedBlackBoxSolver,
+        &mut DefaultForeignCallExecutor::new(false, None),
+        &mut profile,
+    )
+    .expect("program should execute");
+    profile.total_instructions()
+}
+
+#[test]
+fn loop_invariant_code_motion_reduces_brillig_opcodes_executed() {
+    let optimized = compile(SOURCE, &CompileOptions::default()).expect("program should compile").0;
+    let unoptimized = compile(
+        SOURCE,
+        &CompileOptions {
+            skip_ssa_pass: vec!["loop_invariant_code_motion".to_string()],
+            ..CompileOptions::default()
+        },
+    )
+    .expect("program should compile")
+    .0;
+
+    let optimized_executed = brillig_opcodes_executed(&optimized);
+    let unoptimized_executed = brillig_opcodes_executed(&unoptimized);
+    assert!(
+        optimized_executed < unoptimized_executed,
+        "Expected fewer than {unoptimized_executed} opcodes but {optimized_executed} were executed"
+    );
+}
+
+#[test]
+fn skipping_an_unknown_ssa_pass_is_an_error() {
+    let options = CompileOptions {
+        skip_ssa_pass: vec!["loop_invariant_code_emotion".to_string()],
+        ..CompileOptions::default()
+    };
+    let errors = compile(SOURCE, &options).expect_err("unknown passes should be rejected");
+    assert_eq!(errors.len(), 1);
+    let message = &errors[0].diagnostic.message;
+    assert_eq!(message, "Cannot skip unknown SSA pass 'loop_invariant_code_emotion'");
+}
@@ -0,0 +1,100 @@
+//! An enum input is only checked to have a valid tag when it's decoded from its ABI encoding,
+//! so these tests execute programs with witnesses which have been tampered with directly.
+use std::{collections::BTreeMap, path::Path};
+
+use acvm::{blackbox_solver::StubbedBlackBoxSolver, FieldElement};
+use nargo::ops::{execute_program, DefaultForeignCallExecutor};
+use noirc_abi::input_parser::InputValue;
+use noirc_driver::{
+    compile_main, file_manager_with_stdlib, prepare_crate, CompileOptions, CompiledProgram,
+};
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+fn compile(source: &str) -> CompiledProgram {
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+
+    let (program, _) = compile_main(&mut context, root_crate_id, &CompileOptions::default(), None)
+        .expect("program should compile");
+    program
+}
+
+/// Executes `program` with `shape` as its `Empty` variant, but with `tag` in place of its tag.
+fn execute_with_tag(program: &CompiledProgram, tag: u128) -> bool {
+    let empty =
+        InputValue::Struct(BTreeMap::from([("Empty".to_string(), InputValue::Vec(vec![]))]));
+    let inputs = BTreeMap::from([("shape".to_string(), empty)]);
+    let mut witness_map = program.abi.encode(&inputs, None).unwrap();
+
+    // `shape` is the only parameter, so its tag is the first witness
+    let (tag_witness, _) = witness_map.clone().into_iter().next().unwrap();
+    witness_map.insert(tag_witness, FieldElement::from(tag));
+
+    execute_program(
+        &program.program,
+        witness_map,
+        &StubbedBlackBoxSolver,
+        &mut DefaultForeignCallExecutor::new(false, None),
+    )
+    .is_ok()
+}
+
+const SHAPE: &str = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+"#;
+
+#[test]
+fn constrained_match_rejects_invalid_tags() {
+    let source = format!(
+        "{SHAPE}
+        fn main(shape: Shape) -> pub Field {{
+            match shape {{
+                Shape::Circle(radius) => 3 * radius * radius,
+                Shape::Rectangle(width, height) => width * height,
+                Shape::Empty => 0,
+            }}
+        }}"
+    );
+    let program = compile(&source);
+
+    assert!(execute_with_tag(&program, 2));
+    assert!(!execute_with_tag(&program, 3));
+}
+
+#[test]
+fn unconstrained_match_rejects_invalid_tags() {
+    let source = format!(
+        "{SHAPE}
+        fn main(shape: Shape) -> pub Field {{
+            area(shape)
+        }}
+
+        unconstrained fn area(shape: Shape) -> Field {{
+            match shape {{
+                Shape::Circle(radius) => 3 * radius * radius,
+                Shape::Rectangle(width, height) => width * height,
+                Shape::Empty => 0,
+            }}
+        }}"
+    );
+    let program = compile(&source);
+
+    assert!(execute_with_tag(&program, 2));
+    assert!(!execute_with_tag(&program, 3));
+}
@@ -50,6 +50,7 @@ pub(crate) fn run(args: CheckCommand, config: NargoConfig) -> Result<(), CliErro
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.offline,
     )?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
@@ -138,6 +139,15 @@ fn create_input_toml_template(
                 );
                 toml::Value::Table(default_value_map)
             }
+            AbiType::Enum { variants, .. } => {
+                // Enum values are written as a table holding a single variant, with its fields
+                // in an array, so the first variant is used as a placeholder
+                let default_value_map =
+                    toml::map::Map::from_iter(variants.into_iter().take(1).map(|(name, fields)| {
+                        (name, toml::Value::Array(fields.into_iter().map(default_value).collect()))
+                    }));
+                toml::Value::Table(default_value_map)
+            }
             _ => toml::Value::String("".to_owned()),
         }
     }
@@ -197,6 +207,16 @@ mod tests {
                 },
             ),
             typed_param("e", AbiType::Boolean),
+            typed_param(
+                "f",
+                AbiType::Enum {
+                    path: String::from("MyEnum"),
+                    variants: vec![
+                        (String::from("Pair"), vec![AbiType::Field, AbiType::Boolean]),
+                        (String::from("Empty"), vec![]),
+                    ],
+                },
+            ),
         ];
 
         let toml_str = create_input_toml_template(parameters, None);
@@ -209,6 +229,9 @@ e = ""
 [d]
 d1 = ""
 d2 = ["", "", ""]
+
+[f]
+Pair = ["", ""]
 "#;
         assert_eq!(toml_str, expected_toml_str);
     }
@@ -76,6 +76,7 @@ fn find_workspace(project_folder: &str, package: Option<&str>) -> Option<Workspa
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        false,
     ) {
         Ok(workspace) => Some(workspace),
         Err(err) => {
@@ -5,7 +5,7 @@ use clap::Args;
 
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
-use nargo::ops::DefaultForeignCallExecutor;
+use nargo::ops::{BrilligProfile, DefaultForeignCallExecutor};
 use nargo::package::Package;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
@@ -15,11 +15,13 @@ use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING
 use noirc_frontend::graph::CrateName;
 
 use super::compile_cmd::compile_workspace_full;
-use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
+use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir, write_to_file};
 use super::NargoConfig;
 use crate::cli::fs::program::read_program_from_file;
 use crate::errors::CliError;
 
+mod profile;
+
 /// Executes a circuit to calculate its return value
 #[derive(Debug, Clone, Args)]
 #[clap(visible_alias = "e")]
@@ -45,6 +47,11 @@ pub(crate) struct ExecuteCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// Report how many Brillig instructions were executed per function and source location, and
+    /// write their call stacks to `target/<package>-brillig.folded` for use with flamegraph tools
+    #[clap(long)]
+    profile_brillig: bool,
 }
 
 pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -56,6 +63,7 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.offline,
     )?;
     let target_dir = &workspace.target_directory_path();
 
@@ -67,11 +75,13 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
         let program_artifact_path = workspace.package_build_path(package);
         let program: CompiledProgram = read_program_from_file(program_artifact_path)?.into();
 
+        let mut brillig_profile = args.profile_brillig.then(BrilligProfile::default);
         let (return_value, witness_stack) = execute_program_and_decode(
-            program,
+            &program,
             package,
             &args.prover_name,
             args.oracle_resolver.as_deref(),
+            brillig_profile.as_mut(),
         )?;
 
         println!("[{}] Circuit witness successfully solved", package.name);
@@ -83,20 +93,38 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
 
             println!("[{}] Witness saved to {}", package.name, witness_path.display());
         }
+        if let Some(brillig_profile) = &brillig_profile {
+            let package_name = package.name.to_string();
+            let debug_artifact = DebugArtifact {
+                debug_symbols: program.debug.clone(),
+                file_map: program.file_map.clone(),
+            };
+            let summary =
+                profile::summarize(&package_name, &program.names, &debug_artifact, brillig_profile);
+            profile::print_report(&package_name, brillig_profile, &summary);
+
+            let folded: String =
+                summary.folded.iter().map(|(stack, count)| format!("{stack} {count}\n")).collect();
+            let folded_path = target_dir.join(format!("{package_name}-brillig.folded"));
+            let folded_path = write_to_file(folded.as_bytes(), &folded_path);
+            println!("[{package_name}] Brillig call stacks saved to {folded_path}");
+        }
     }
     Ok(())
 }
 
 fn execute_program_and_decode(
-    program: CompiledProgram,
+    program: &CompiledProgram,
     package: &Package,
     prover_name: &str,
     foreign_call_resolver_url: Option<&str>,
+    brillig_profile: Option<&mut BrilligProfile>,
 ) -> Result<(Option<InputValue>, WitnessStack<FieldElement>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let witness_stack = execute_program(&program, &inputs_map, foreign_call_resolver_url)?;
+    let witness_stack =
+        execute_program(program, &inputs_map, foreign_call_resolver_url, brillig_profile)?;
     // Get the entry point witness for the ABI
     let main_witness =
         &witness_stack.peek().expect("Should have at least one witness on the stack").witness;
@@ -109,15 +137,27 @@ pub(crate) fn execute_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
     foreign_call_resolver_url: Option<&str>,
+    brillig_profile: Option<&mut BrilligProfile>,
 ) -> Result<WitnessStack<FieldElement>, CliError> {
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    let solved_witness_stack_err = nargo::ops::execute_program(
-        &compiled_program.program,
-        initial_witness,
-        &Bn254BlackBoxSolver,
-        &mut DefaultForeignCallExecutor::new(true, foreign_call_resolver_url),
-    );
+    let mut foreign_call_executor =
+        DefaultForeignCallExecutor::new(true, foreign_call_resolver_url);
+    let solved_witness_stack_err = match brillig_profile {
+        Some(brillig_profile) => nargo::ops::execute_program_with_profile(
+            &compiled_program.program,
+            initial_witness,
+            &Bn254BlackBoxSolver,
+            &mut foreign_call_executor,
+            brillig_profile,
+        ),
+        None => nargo::ops::execute_program(
+            &compiled_program.program,
+            initial_witness,
+            &Bn254BlackBoxSolver,
+            &mut foreign_call_executor,
+        ),
+    };
     match solved_witness_stack_err {
         Ok(solved_witness_stack) => Ok(solved_witness_stack),
         Err(err) => {
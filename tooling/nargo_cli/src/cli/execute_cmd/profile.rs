@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, HashMap};
+
+use nargo::ops::{BrilligProfile, ProfileCallStack};
+use noirc_artifacts::debug::DebugArtifact;
+use prettytable::{row, table};
+
+use crate::cli::flamegraph_common::frame_label;
+
+/// The executed Brillig instructions of a program, attributed to the source code they came from
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct ProfileSummary {
+    /// Instructions executed within each function, including those of the functions it calls,
+    /// added up over every call made to it
+    pub(super) functions: Vec<(String, u64)>,
+    /// Instructions generated from each source location, excluding those of any functions it calls
+    pub(super) locations: Vec<(String, u64)>,
+    /// Instructions executed with each call stack, in the folded format used by flamegraph tools
+    pub(super) folded: BTreeMap<String, u64>,
+}
+
+/// Maps each of the profile's call stacks back to the source code, adding up the instructions
+/// executed per function and per source location, sorted with the most expensive first.
+///
+/// `function_names` holds the name of each of the program's ACIR functions.
+pub(super) fn summarize(
+    package_name: &str,
+    function_names: &[String],
+    debug_artifact: &DebugArtifact,
+    profile: &BrilligProfile,
+) -> ProfileSummary {
+    let mut functions: HashMap<String, u64> = HashMap::new();
+    let mut locations: HashMap<String, u64> = HashMap::new();
+    let mut folded: BTreeMap<String, u64> = BTreeMap::new();
+    for (call_stack, count) in &profile.samples {
+        let frames = resolve_call_stack(package_name, function_names, call_stack, debug_artifact);
+
+        // A recursive call only counts once towards the instructions spent in the function.
+        let mut callees: Vec<&String> =
+            frames.iter().filter_map(|frame| frame.callee.as_ref()).collect();
+        callees.sort();
+        callees.dedup();
+        for callee in callees {
+            *functions.entry(callee.clone()).or_default() += count;
+        }
+
+        if let Some(location) = frames.last().and_then(|frame| frame.labels.last()) {
+            *locations.entry(location.clone()).or_default() += count;
+        }
+
+        let stack: Vec<&str> =
+            frames.iter().flat_map(|frame| frame.labels.iter().map(String::as_str)).collect();
+        *folded.entry(stack.join(";")).or_default() += count;
+    }
+
+    ProfileSummary {
+        functions: sort_by_count(functions),
+        locations: sort_by_count(locations),
+        folded,
+    }
+}
+
+/// A frame of a profiled call stack, along with the source code it was generated from
+struct ResolvedFrame {
+    /// The names of the function and the chain of inlined call sites leading to the frame
+    labels: Vec<String>,
+    /// The name of the function this frame calls into, unless it's the innermost frame
+    callee: Option<String>,
+}
+
+fn resolve_call_stack(
+    package_name: &str,
+    function_names: &[String],
+    call_stack: &ProfileCallStack,
+    debug_artifact: &DebugArtifact,
+) -> Vec<ResolvedFrame> {
+    let mut frames = Vec::with_capacity(call_stack.len());
+    let mut current_function = None;
+    for (index, (function_index, opcode_location)) in call_stack.iter().enumerate() {
+        let mut labels = Vec::new();
+        if current_function != Some(*function_index) {
+            let name = function_names.get(*function_index).map_or("<unknown>", String::as_str);
+            labels.push(format!("{package_name}::{name}"));
+            current_function = Some(*function_index);
+        }
+        let debug_info = debug_artifact.debug_symbols.get(*function_index);
+        let locations = debug_info
+            .and_then(|debug_info| debug_info.opcode_location(opcode_location))
+            .unwrap_or_default();
+        labels.extend(locations.into_iter().map(|location| frame_label(location, debug_artifact)));
+
+        // Calls between ACIR functions land in another function, whose name is known. Otherwise
+        // it's a call within Brillig, whose callee is recorded in the debug info.
+        let callee = call_stack.get(index + 1).and_then(|(callee_index, _)| {
+            if callee_index != function_index {
+                function_names.get(*callee_index).cloned()
+            } else {
+                debug_info?.brillig_function_calls.get(opcode_location).cloned()
+            }
+        });
+        frames.push(ResolvedFrame { labels, callee });
+    }
+    frames
+}
+
+fn sort_by_count(counts: HashMap<String, u64>) -> Vec<(String, u64)> {
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a_label, a_count), (b_label, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_label.cmp(b_label))
+    });
+    counts
+}
+
+/// Prints the totals of the profile followed by the instructions spent in each function and at
+/// each source location.
+pub(super) fn print_report(package_name: &str, profile: &BrilligProfile, summary: &ProfileSummary) {
+    let total = profile.total_instructions();
+    println!("[{package_name}] Brillig instructions executed: {total}");
+    println!("[{package_name}] Peak Brillig memory usage: {} slots", profile.peak_memory);
+    for (function, calls) in &profile.foreign_calls {
+        println!("[{package_name}] Foreign calls to {function}: {calls}");
+    }
+    if total == 0 {
+        return;
+    }
+
+    let percentage = |count: u64| format!("{:.1}%", count as f64 * 100.0 / total as f64);
+
+    let mut functions_table = table!([Fm->"Function", Fm->"Instructions", Fm->"Share"]);
+    for (function, count) in &summary.functions {
+        functions_table.add_row(row![Fc->function, count, percentage(*count)]);
+    }
+    functions_table.printstd();
+
+    let mut locations_table = table!([Fm->"Source location", Fm->"Instructions", Fm->"Share"]);
+    for (location, count) in &summary.locations {
+        locations_table.add_row(row![Fc->location, count, percentage(*count)]);
+    }
+    locations_table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        path::PathBuf,
+    };
+
+    use acvm::acir::circuit::OpcodeLocation;
+    use fm::FileId;
+    use nargo::ops::BrilligProfile;
+    use noirc_artifacts::debug::DebugArtifact;
+    use noirc_driver::DebugFile;
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::summarize;
+
+    #[test]
+    fn attributes_instructions_to_calls_and_locations() {
+        let source = "fn main(x: u32) {\n    let y = foo(x);\n}\nunconstrained fn foo(x: u32) -> u32 {\n    bar(x) + 1\n}\nunconstrained fn bar(x: u32) -> u32 {\n    x * 2\n}\n";
+        let file = FileId::dummy();
+        let location = |start: u32, end: u32| Location::new(Span::from(start..end), file);
+        let call_foo = location(30, 36);
+        let call_bar = location(82, 88);
+        let increment = location(82, 92);
+        let double = location(137, 142);
+
+        // The entry point of the Brillig call at 0 calls `foo` at 1, which calls `bar` at 5
+        let opcode = |brillig_index| OpcodeLocation::Brillig { acir_index: 0, brillig_index };
+        let brillig = |brillig_index| (0, opcode(brillig_index));
+        let call_site = (0, OpcodeLocation::Acir(0));
+        let profile = BrilligProfile {
+            samples: HashMap::from([
+                (vec![call_site, brillig(0), brillig(1)], 3),
+                (vec![call_site, brillig(0), brillig(2)], 2),
+                (vec![call_site, brillig(0), brillig(1), brillig(5)], 10),
+            ]),
+            ..BrilligProfile::default()
+        };
+        let debug_artifact = DebugArtifact {
+            debug_symbols: vec![DebugInfo {
+                locations: BTreeMap::from([
+                    (OpcodeLocation::Acir(0), vec![call_foo]),
+                    (opcode(1), vec![call_bar]),
+                    (opcode(2), vec![increment]),
+                    (opcode(5), vec![double]),
+                ]),
+                brillig_function_calls: BTreeMap::from([
+                    (opcode(0), "foo".to_string()),
+                    (opcode(1), "bar".to_string()),
+                ]),
+                ..DebugInfo::default()
+            }],
+            file_map: BTreeMap::from([(
+                file,
+                DebugFile { source: source.to_string(), path: PathBuf::from("src/main.nr") },
+            )]),
+        };
+
+        let summary = summarize("foo", &["main".to_string()], &debug_artifact, &profile);
+
+        let label = |label: &str| label.to_string();
+        assert_eq!(summary.functions, vec![(label("foo"), 15), (label("bar"), 10)]);
+        assert_eq!(
+            summary.locations,
+            vec![
+                (label("x * 2 main.nr:8"), 10),
+                (label("bar(x) main.nr:5"), 3),
+                (label("bar(x) + 1 main.nr:5"), 2),
+            ]
+        );
+        assert_eq!(
+            summary.folded,
+            BTreeMap::from([
+                (label("foo::main;foo(x) main.nr:2;bar(x) + 1 main.nr:5"), 2),
+                (label("foo::main;foo(x) main.nr:2;bar(x) main.nr:5"), 3),
+                (label("foo::main;foo(x) main.nr:2;bar(x) main.nr:5;x * 2 main.nr:8"), 10),
+            ])
+        );
+    }
+}
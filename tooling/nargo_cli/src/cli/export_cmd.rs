@@ -52,6 +52,7 @@ pub(crate) fn run(args: ExportCommand, config: NargoConfig) -> Result<(), CliErr
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+        config.offline,
     )?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
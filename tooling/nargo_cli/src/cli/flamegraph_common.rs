@@ -0,0 +1,36 @@
+use noirc_artifacts::debug::DebugArtifact;
+use noirc_errors::Location;
+
+/// Source snippets longer than this are cut short so that frames stay readable
+const MAX_SNIPPET_LENGTH: usize = 40;
+
+/// Labels a call site with the start of its source code along with its file and line
+pub(super) fn frame_label(location: Location, debug_artifact: &DebugArtifact) -> String {
+    let Some(file) = debug_artifact.file_map.get(&location.file) else {
+        return "<unknown>".to_string();
+    };
+    let snippet = source_code(location, debug_artifact)
+        .and_then(|source| source.lines().next())
+        .unwrap_or("");
+    let snippet = snippet.trim();
+    let snippet = if snippet.chars().count() > MAX_SNIPPET_LENGTH {
+        format!("{}...", snippet.chars().take(MAX_SNIPPET_LENGTH).collect::<String>())
+    } else {
+        snippet.to_string()
+    };
+    let file_name = file.path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let line = debug_artifact.location_line_number(location).unwrap_or_default();
+    sanitize(&format!("{snippet} {file_name}:{line}"))
+}
+
+/// Returns the source code at `location`, if its file is part of the artifact
+fn source_code(location: Location, debug_artifact: &DebugArtifact) -> Option<&str> {
+    let file = debug_artifact.file_map.get(&location.file)?;
+    file.source.get(location.span.start() as usize..location.span.end() as usize)
+}
+
+/// Frames are separated by `;` and followed by the weight after a space, so neither can be
+/// allowed to confuse the folded format.
+pub(super) fn sanitize(frame: &str) -> String {
+    frame.replace(';', ",").replace(char::is_whitespace, " ")
+}
@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+};
 
-use acvm::acir::circuit::ExpressionWidth;
+use acvm::acir::circuit::{ExpressionWidth, Opcode};
 use clap::Args;
 use iter_extended::vecmap;
 use nargo::package::Package;
@@ -11,19 +14,24 @@ use noirc_errors::{debug_info::OpCodesCount, Location};
 use noirc_frontend::graph::CrateName;
 use prettytable::{row, table, Row};
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::CliError;
 
 use super::{
-    compile_cmd::compile_workspace_full, fs::program::read_program_from_file, NargoConfig,
+    compile_cmd::compile_workspace_full,
+    fs::{program::read_program_from_file, write_to_file},
+    NargoConfig,
 };
 
+mod baseline;
+mod flamegraph;
+
 /// Provides detailed information on each of a program's function (represented by a single circuit)
 ///
 /// Current information provided per circuit:
 /// 1. The number of ACIR opcodes
-/// 2. Counts the final number gates in the circuit used by a backend
+/// 2. The number of Brillig opcodes in the unconstrained functions called from the circuit
 #[derive(Debug, Clone, Args)]
 #[clap(visible_alias = "i")]
 pub(crate) struct InfoCommand {
@@ -42,6 +50,24 @@ pub(crate) struct InfoCommand {
     #[clap(long, hide = true)]
     profile_info: bool,
 
+    /// Write an SVG flamegraph of where each program's ACIR opcodes come from to the given file
+    #[clap(long, value_name = "SVG")]
+    flamegraph: Option<PathBuf>,
+
+    /// Compare the size of each function against a baseline saved with `--save-baseline`,
+    /// failing if any has grown by more than `--threshold`
+    #[clap(long, value_name = "BASELINE", conflicts_with = "save_baseline")]
+    check: Option<PathBuf>,
+
+    /// Save the size of each function to a file, to compare later builds against with `--check`
+    #[clap(long, value_name = "BASELINE")]
+    save_baseline: Option<PathBuf>,
+
+    /// The percentage by which a function's ACIR opcode count or Brillig bytecode size may grow
+    /// before `--check` fails
+    #[clap(long, value_name = "PERCENT", default_value_t = 0.0, requires = "check")]
+    threshold: f64,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -55,6 +81,7 @@ pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.offline,
     )?;
 
     // Compile the full workspace in order to generate any build artifacts.
@@ -80,6 +107,11 @@ pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError
         }
     }
 
+    if let Some(flamegraph_path) = &args.flamegraph {
+        flamegraph::write_flamegraph(&binary_packages, flamegraph_path)?;
+        println!("Wrote flamegraph to {}", flamegraph_path.display());
+    }
+
     let program_info = binary_packages
         .into_iter()
         .par_bridge()
@@ -94,14 +126,41 @@ pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError
 
     let info_report = InfoReport { programs: program_info };
 
+    if let Some(baseline_path) = &args.save_baseline {
+        let baseline = serde_json::to_string_pretty(&info_report).unwrap();
+        let path = write_to_file(baseline.as_bytes(), baseline_path);
+        println!("Saved baseline to {path}");
+        return Ok(());
+    }
+
+    if let Some(baseline_path) = &args.check {
+        let baseline = baseline::read_baseline(baseline_path)?;
+        let diffs = baseline::compare(&baseline, &info_report, args.threshold);
+        if diffs.is_empty() {
+            println!("No functions have changed size since the baseline");
+            return Ok(());
+        }
+        baseline::diff_table(&diffs).printstd();
+
+        let regressions = diffs.iter().filter(|diff| diff.regressed).count();
+        return if regressions == 0 {
+            Ok(())
+        } else {
+            let plural = if regressions == 1 { "" } else { "s" };
+            Err(CliError::Generic(format!(
+                "{regressions} function{plural} grew by more than {}% since the baseline",
+                args.threshold
+            )))
+        };
+    }
+
     if args.json {
         // Expose machine-readable JSON data.
         println!("{}", serde_json::to_string(&info_report).unwrap());
     } else {
         // Otherwise print human-readable table.
         if !info_report.programs.is_empty() {
-            let mut program_table =
-                table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes"]);
+            let mut program_table = table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Brillig Opcodes"]);
 
             for program_info in info_report.programs {
                 let program_rows: Vec<Row> = program_info.into();
@@ -165,12 +224,12 @@ fn byte_index(string: &str, index: u32) -> usize {
     byte_index
 }
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct InfoReport {
     programs: Vec<ProgramInfo>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct ProgramInfo {
     package_name: String,
     #[serde(skip)]
@@ -186,6 +245,7 @@ impl From<ProgramInfo> for Vec<Row> {
                 Fc->format!("{}", function.name),
                 format!("{:?}", program_info.expression_width),
                 Fc->format!("{}", function.acir_opcodes),
+                Fc->format!("{}", function.brillig_opcodes),
             ]
         })
     }
@@ -200,10 +260,13 @@ struct ContractInfo {
     functions: Vec<FunctionInfo>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct FunctionInfo {
     name: String,
     acir_opcodes: usize,
+    /// The total bytecode size of the unconstrained functions this function calls directly
+    #[serde(default)]
+    brillig_opcodes: usize,
 }
 
 impl From<ContractInfo> for Vec<Row> {
@@ -214,6 +277,7 @@ impl From<ContractInfo> for Vec<Row> {
                 Fc->format!("{}", function.name),
                 format!("{:?}", contract_info.expression_width),
                 Fc->format!("{}", function.acir_opcodes),
+                Fc->format!("{}", function.brillig_opcodes),
             ]
         })
     }
@@ -224,14 +288,29 @@ fn count_opcodes_and_gates_in_program(
     package: &Package,
     expression_width: ExpressionWidth,
 ) -> ProgramInfo {
+    let unconstrained_functions = &compiled_program.bytecode.unconstrained_functions;
     let functions = compiled_program
         .bytecode
         .functions
-        .into_par_iter()
+        .par_iter()
         .enumerate()
-        .map(|(i, function)| FunctionInfo {
-            name: compiled_program.names[i].clone(),
-            acir_opcodes: function.opcodes.len(),
+        .map(|(i, function)| {
+            let brillig_calls: BTreeSet<_> = function
+                .opcodes
+                .iter()
+                .filter_map(|opcode| match opcode {
+                    Opcode::BrilligCall { id, .. } => Some(*id as usize),
+                    _ => None,
+                })
+                .collect();
+            FunctionInfo {
+                name: compiled_program.names[i].clone(),
+                acir_opcodes: function.opcodes.len(),
+                brillig_opcodes: brillig_calls
+                    .into_iter()
+                    .map(|id| unconstrained_functions[id].bytecode.len())
+                    .sum(),
+            }
         })
         .collect();
 
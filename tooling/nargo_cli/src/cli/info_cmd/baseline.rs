@@ -0,0 +1,201 @@
+use std::{collections::BTreeMap, path::Path};
+
+use prettytable::{row, table, Table};
+
+use crate::errors::CliError;
+
+use super::{FunctionInfo, InfoReport};
+
+/// Reads a baseline saved by `nargo info --save-baseline`
+pub(super) fn read_baseline(path: &Path) -> Result<InfoReport, CliError> {
+    let contents = std::fs::read_to_string(path).map_err(|err| {
+        CliError::Generic(format!("Could not read baseline {}: {err}", path.display()))
+    })?;
+    serde_json::from_str(&contents).map_err(|err| {
+        CliError::Generic(format!("Could not parse baseline {}: {err}", path.display()))
+    })
+}
+
+/// The sizes of a single function in the baseline and in the current build, where they differ
+#[derive(Debug, PartialEq)]
+pub(super) struct FunctionDiff {
+    package_name: String,
+    function_name: String,
+    /// `None` if the function has been added since the baseline was saved
+    baseline: Option<(usize, usize)>,
+    /// `None` if the function has been removed since the baseline was saved
+    current: Option<(usize, usize)>,
+    /// Whether either size grew by more than the allowed threshold
+    pub(super) regressed: bool,
+}
+
+/// Compares the ACIR opcode and Brillig bytecode counts of every function against the baseline,
+/// returning those which changed. A function regresses if either count grows by more than
+/// `threshold` percent of its baseline value.
+pub(super) fn compare(
+    baseline: &InfoReport,
+    current: &InfoReport,
+    threshold: f64,
+) -> Vec<FunctionDiff> {
+    let sizes = |report: &InfoReport| -> BTreeMap<(String, String), (usize, usize)> {
+        report
+            .programs
+            .iter()
+            .flat_map(|program| {
+                program.functions.iter().map(|function| {
+                    let key = (program.package_name.clone(), function.name.clone());
+                    (key, sizes_of(function))
+                })
+            })
+            .collect()
+    };
+    let mut baseline = sizes(baseline);
+    let current = sizes(current);
+
+    let grew = |old: usize, new: usize| new as f64 > old as f64 * (1.0 + threshold / 100.0);
+
+    let mut diffs = Vec::new();
+    for ((package_name, function_name), current) in current {
+        let baseline = baseline.remove(&(package_name.clone(), function_name.clone()));
+        if baseline == Some(current) {
+            continue;
+        }
+        let regressed = baseline
+            .is_some_and(|(acir, brillig)| grew(acir, current.0) || grew(brillig, current.1));
+        diffs.push(FunctionDiff {
+            package_name,
+            function_name,
+            baseline,
+            current: Some(current),
+            regressed,
+        });
+    }
+    // Functions left in the baseline no longer exist, they may have been renamed or inlined.
+    diffs.extend(baseline.into_iter().map(|((package_name, function_name), baseline)| {
+        FunctionDiff {
+            package_name,
+            function_name,
+            baseline: Some(baseline),
+            current: None,
+            regressed: false,
+        }
+    }));
+    diffs
+}
+
+fn sizes_of(function: &FunctionInfo) -> (usize, usize) {
+    (function.acir_opcodes, function.brillig_opcodes)
+}
+
+/// Builds a table of the changed functions, with regressions highlighted in red and improvements in green
+pub(super) fn diff_table(diffs: &[FunctionDiff]) -> Table {
+    let mut table =
+        table!([Fm->"Package", Fm->"Function", Fm->"ACIR Opcodes", Fm->"Brillig Opcodes"]);
+    for diff in diffs {
+        let acir =
+            format_change(diff.baseline.map(|sizes| sizes.0), diff.current.map(|sizes| sizes.0));
+        let brillig =
+            format_change(diff.baseline.map(|sizes| sizes.1), diff.current.map(|sizes| sizes.1));
+        let shrank = match (diff.baseline, diff.current) {
+            (Some(old), Some(new)) => new.0 <= old.0 && new.1 <= old.1,
+            _ => false,
+        };
+        let row = if diff.regressed {
+            row![Fm->diff.package_name, Fr->diff.function_name, Fr->acir, Fr->brillig]
+        } else if shrank {
+            row![Fm->diff.package_name, Fg->diff.function_name, Fg->acir, Fg->brillig]
+        } else {
+            row![Fm->diff.package_name, Fc->diff.function_name, acir, brillig]
+        };
+        table.add_row(row);
+    }
+    table
+}
+
+fn format_change(baseline: Option<usize>, current: Option<usize>) -> String {
+    match (baseline, current) {
+        (Some(old), Some(new)) if old == new => new.to_string(),
+        (Some(old), Some(new)) => {
+            let change = if old == 0 {
+                String::new()
+            } else {
+                format!(" ({:+.1}%)", (new as f64 - old as f64) * 100.0 / old as f64)
+            };
+            format!("{old} -> {new}{change}")
+        }
+        (None, Some(new)) => format!("{new} (new)"),
+        (Some(old), None) => format!("{old} (removed)"),
+        (None, None) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::circuit::ExpressionWidth;
+
+    use super::{
+        super::ProgramInfo, compare, format_change, FunctionDiff, FunctionInfo, InfoReport,
+    };
+
+    fn report(functions: &[(&str, usize, usize)]) -> InfoReport {
+        let functions = functions
+            .iter()
+            .map(|(name, acir_opcodes, brillig_opcodes)| FunctionInfo {
+                name: name.to_string(),
+                acir_opcodes: *acir_opcodes,
+                brillig_opcodes: *brillig_opcodes,
+            })
+            .collect();
+        InfoReport {
+            programs: vec![ProgramInfo {
+                package_name: "foo".to_string(),
+                expression_width: ExpressionWidth::default(),
+                functions,
+            }],
+        }
+    }
+
+    fn diff(
+        name: &str,
+        baseline: Option<(usize, usize)>,
+        current: Option<(usize, usize)>,
+        regressed: bool,
+    ) -> FunctionDiff {
+        FunctionDiff {
+            package_name: "foo".to_string(),
+            function_name: name.to_string(),
+            baseline,
+            current,
+            regressed,
+        }
+    }
+
+    #[test]
+    fn flags_functions_which_grew_beyond_threshold() {
+        let baseline =
+            report(&[("main", 100, 0), ("helper", 10, 200), ("same", 5, 5), ("old", 1, 1)]);
+        let current =
+            report(&[("main", 105, 0), ("helper", 10, 230), ("same", 5, 5), ("new", 3, 0)]);
+
+        assert_eq!(
+            compare(&baseline, &current, 10.0),
+            vec![
+                diff("helper", Some((10, 200)), Some((10, 230)), true),
+                diff("main", Some((100, 0)), Some((105, 0)), false),
+                diff("new", None, Some((3, 0)), false),
+                diff("old", Some((1, 1)), None, false),
+            ]
+        );
+        assert!(compare(&baseline, &current, 0.0)
+            .iter()
+            .any(|diff| diff.function_name == "main" && diff.regressed));
+    }
+
+    #[test]
+    fn formats_size_changes() {
+        assert_eq!(format_change(Some(200), Some(230)), "200 -> 230 (+15.0%)");
+        assert_eq!(format_change(Some(0), Some(4)), "0 -> 4");
+        assert_eq!(format_change(Some(7), Some(7)), "7");
+        assert_eq!(format_change(None, Some(3)), "3 (new)");
+    }
+}
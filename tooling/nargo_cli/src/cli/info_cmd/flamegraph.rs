@@ -0,0 +1,281 @@
+use std::{collections::BTreeMap, path::Path};
+
+use acvm::{
+    acir::{
+        circuit::{Circuit, Opcode, OpcodeLocation},
+        BlackBoxFunc,
+    },
+    FieldElement,
+};
+use inferno::flamegraph::{from_lines, Options};
+use nargo::package::Package;
+use noirc_artifacts::{debug::DebugArtifact, program::ProgramArtifact};
+use noirc_errors::debug_info::DebugInfo;
+
+use crate::{
+    cli::flamegraph_common::{frame_label, sanitize},
+    errors::CliError,
+};
+
+/// Renders an SVG flamegraph of the ACIR opcodes of every program, attributing each opcode to the
+/// chain of calls it was generated from, and writes it to `path`.
+pub(super) fn write_flamegraph(
+    programs: &[(Package, ProgramArtifact)],
+    path: &Path,
+) -> Result<(), CliError> {
+    let mut stacks = BTreeMap::new();
+    for (package, program) in programs {
+        let debug_artifact = DebugArtifact::from(program.clone());
+        let mut folder = OpcodeFolder {
+            package_name: package.name.to_string(),
+            circuits: &program.bytecode.functions,
+            debug_infos: &program.debug_symbols.debug_infos,
+            names: &program.names,
+            debug_artifact: &debug_artifact,
+            stacks: &mut stacks,
+            active_functions: Vec::new(),
+        };
+        // Every other function is reached through calls from `main`, which is the first one
+        folder.fold_function(0, &mut Vec::new());
+    }
+    let lines: Vec<String> =
+        stacks.into_iter().map(|(stack, weight)| format!("{stack} {weight}")).collect();
+
+    let mut options = Options::default();
+    options.title = "ACIR opcodes".to_string();
+    options.count_name = "weight".to_string();
+
+    let mut svg = Vec::new();
+    from_lines(&mut options, lines.iter().map(String::as_str), &mut svg)
+        .map_err(|err| CliError::Generic(format!("Could not render flamegraph: {err}")))?;
+    std::fs::write(path, svg).map_err(|err| {
+        CliError::Generic(format!("Could not write flamegraph to {}: {err}", path.display()))
+    })
+}
+
+/// Adds up the weight of a program's opcodes by their call stack.
+struct OpcodeFolder<'a> {
+    package_name: String,
+    circuits: &'a [Circuit<FieldElement>],
+    debug_infos: &'a [DebugInfo],
+    /// The name of each of the program's functions
+    names: &'a [String],
+    debug_artifact: &'a DebugArtifact,
+    /// The total weight of the opcodes with each call stack, in the folded format,
+    /// e.g. `pkg::main;foo(x) main.nr:3;sha256(x) lib.nr:10;sha256`
+    stacks: &'a mut BTreeMap<String, usize>,
+    /// The functions currently being folded, so that recursive calls aren't followed forever
+    active_functions: Vec<usize>,
+}
+
+impl OpcodeFolder<'_> {
+    /// Adds the weight of each of the opcodes of the function at `function_index` to `stacks`,
+    /// keyed by `frames` followed by the opcode's own call stack. The opcodes of the functions it
+    /// calls are added below the call, once per call as each of them needs to be proven.
+    fn fold_function(&mut self, function_index: usize, frames: &mut Vec<String>) {
+        let (circuits, debug_infos) = (self.circuits, self.debug_infos);
+        let Some(circuit) = circuits.get(function_index) else {
+            return;
+        };
+        let name = self.names.get(function_index).map_or("<unknown>", String::as_str);
+        frames.push(sanitize(&format!("{}::{name}", self.package_name)));
+        self.active_functions.push(function_index);
+
+        let debug_info = debug_infos.get(function_index);
+        for (index, opcode) in circuit.opcodes.iter().enumerate() {
+            let caller_depth = frames.len();
+            let call_stack = debug_info
+                .and_then(|debug_info| debug_info.opcode_location(&OpcodeLocation::Acir(index)))
+                .unwrap_or_default();
+            frames.extend(
+                call_stack.into_iter().map(|location| frame_label(location, self.debug_artifact)),
+            );
+
+            frames.push(opcode_label(opcode));
+            *self.stacks.entry(frames.join(";")).or_default() += opcode_weight(opcode);
+            frames.pop();
+
+            if let Opcode::Call { id, .. } = opcode {
+                let callee = *id as usize;
+                if !self.active_functions.contains(&callee) {
+                    self.fold_function(callee, frames);
+                }
+            }
+            frames.truncate(caller_depth);
+        }
+
+        self.active_functions.pop();
+        frames.pop();
+    }
+}
+
+fn opcode_label<F>(opcode: &Opcode<F>) -> String {
+    match opcode {
+        Opcode::AssertZero(_) => "arithmetic".to_string(),
+        Opcode::BlackBoxFuncCall(call) => call.name().to_string(),
+        Opcode::Directive(_) => "directive".to_string(),
+        Opcode::MemoryOp { .. } => "memory_op".to_string(),
+        Opcode::MemoryInit { .. } => "memory_init".to_string(),
+        Opcode::BrilligCall { .. } => "brillig_call".to_string(),
+        Opcode::Call { .. } => "call".to_string(),
+    }
+}
+
+/// A backend-independent estimate of an opcode's cost. Most opcodes count once, while black box
+/// functions are weighted by a rough number of arithmetic gates they take, as their cost depends
+/// far more on which function they are.
+fn opcode_weight<F>(opcode: &Opcode<F>) -> usize {
+    match opcode {
+        Opcode::BlackBoxFuncCall(call) => black_box_weight(call.get_black_box_func()),
+        _ => 1,
+    }
+}
+
+/// The approximate number of arithmetic gates each black box function is worth. These are only
+/// meant to rank the functions against each other and against other opcodes, as the actual cost
+/// depends on the backend.
+fn black_box_weight(func: BlackBoxFunc) -> usize {
+    match func {
+        BlackBoxFunc::RANGE => 1,
+        BlackBoxFunc::AND | BlackBoxFunc::XOR => 4,
+        BlackBoxFunc::BigIntAdd
+        | BlackBoxFunc::BigIntSub
+        | BlackBoxFunc::BigIntMul
+        | BlackBoxFunc::BigIntDiv
+        | BlackBoxFunc::BigIntFromLeBytes
+        | BlackBoxFunc::BigIntToLeBytes => 16,
+        BlackBoxFunc::EmbeddedCurveAdd => 16,
+        BlackBoxFunc::Poseidon2Permutation => 80,
+        BlackBoxFunc::PedersenCommitment | BlackBoxFunc::PedersenHash => 3_000,
+        BlackBoxFunc::MultiScalarMul => 3_500,
+        BlackBoxFunc::SchnorrVerify => 5_000,
+        BlackBoxFunc::Sha256Compression => 7_000,
+        BlackBoxFunc::AES128Encrypt => 8_000,
+        BlackBoxFunc::Keccakf1600 => 20_000,
+        BlackBoxFunc::Blake2s | BlackBoxFunc::Blake3 => 25_000,
+        BlackBoxFunc::SHA256 => 30_000,
+        BlackBoxFunc::EcdsaSecp256k1 => 35_000,
+        BlackBoxFunc::Keccak256 => 55_000,
+        BlackBoxFunc::EcdsaSecp256r1 => 70_000,
+        BlackBoxFunc::RecursiveAggregation => 200_000,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use acvm::{
+        acir::{
+            circuit::{
+                opcodes::{BlackBoxFuncCall, FunctionInput},
+                Circuit, Opcode, OpcodeLocation,
+            },
+            native_types::{Expression, Witness},
+        },
+        FieldElement,
+    };
+    use fm::FileId;
+    use noirc_artifacts::debug::DebugArtifact;
+    use noirc_driver::DebugFile;
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::OpcodeFolder;
+
+    fn fold_program(
+        circuits: &[Circuit<FieldElement>],
+        debug_infos: &[DebugInfo],
+        names: &[String],
+        debug_artifact: &DebugArtifact,
+    ) -> BTreeMap<String, usize> {
+        let mut stacks = BTreeMap::new();
+        let mut folder = OpcodeFolder {
+            package_name: "foo".to_string(),
+            circuits,
+            debug_infos,
+            names,
+            debug_artifact,
+            stacks: &mut stacks,
+            active_functions: Vec::new(),
+        };
+        folder.fold_function(0, &mut Vec::new());
+        stacks
+    }
+
+    #[test]
+    fn folds_opcodes_along_their_call_stacks() {
+        let source = "fn main(x: Field) {\n    foo(x);\n}\nfn foo(x: Field) {\n    assert(x as u8 == 0);\n}\n";
+        let file = FileId::dummy();
+        let location = |start: u32, end: u32| Location::new(Span::from(start..end), file);
+        let call_foo = location(24, 30);
+        let range_check = location(64, 71);
+        let assertion = location(57, 77);
+
+        let circuit: Circuit<FieldElement> = Circuit {
+            opcodes: vec![
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                    input: FunctionInput { witness: Witness(0), num_bits: 8 },
+                }),
+                Opcode::AssertZero(Expression::from(Witness(0))),
+                Opcode::AssertZero(Expression::from(Witness(1))),
+            ],
+            ..Circuit::default()
+        };
+        let debug_info = DebugInfo {
+            locations: BTreeMap::from([
+                (OpcodeLocation::Acir(0), vec![call_foo, range_check]),
+                (OpcodeLocation::Acir(1), vec![call_foo, assertion]),
+                (OpcodeLocation::Acir(2), vec![call_foo, assertion]),
+            ]),
+            ..DebugInfo::default()
+        };
+        let debug_artifact = DebugArtifact {
+            debug_symbols: vec![debug_info.clone()],
+            file_map: BTreeMap::from([(
+                file,
+                DebugFile { source: source.to_string(), path: PathBuf::from("src/main.nr") },
+            )]),
+        };
+
+        let stacks =
+            fold_program(&[circuit], &[debug_info], &["main".to_string()], &debug_artifact);
+
+        assert_eq!(
+            stacks,
+            BTreeMap::from([
+                (
+                    "foo::main;foo(x) main.nr:2;assert(x as u8 == 0) main.nr:5;arithmetic"
+                        .to_string(),
+                    2
+                ),
+                ("foo::main;foo(x) main.nr:2;x as u8 main.nr:5;range".to_string(), 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn follows_calls_into_other_functions() {
+        let call = |id| Opcode::Call { id, inputs: vec![], outputs: vec![], predicate: None };
+        let assertion = Opcode::AssertZero(Expression::from(Witness(0)));
+        let circuits: Vec<Circuit<FieldElement>> = vec![
+            Circuit { opcodes: vec![call(1), call(1)], ..Circuit::default() },
+            Circuit { opcodes: vec![assertion, call(1)], ..Circuit::default() },
+        ];
+        let debug_infos = vec![DebugInfo::default(), DebugInfo::default()];
+        let names = vec!["main".to_string(), "bar".to_string()];
+        let debug_artifact =
+            DebugArtifact { debug_symbols: debug_infos.clone(), file_map: BTreeMap::new() };
+
+        let stacks = fold_program(&circuits, &debug_infos, &names, &debug_artifact);
+
+        // `bar` is counted once per call, without following its recursive call
+        assert_eq!(
+            stacks,
+            BTreeMap::from([
+                ("foo::main;call".to_string(), 2),
+                ("foo::main;foo::bar;arithmetic".to_string(), 2),
+                ("foo::main;foo::bar;call".to_string(), 2),
+            ])
+        );
+    }
+}
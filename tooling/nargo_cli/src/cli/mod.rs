@@ -14,12 +14,17 @@ mod dap_cmd;
 mod debug_cmd;
 mod execute_cmd;
 mod export_cmd;
+mod flamegraph_common;
 mod fmt_cmd;
 mod info_cmd;
 mod init_cmd;
 mod lsp_cmd;
 mod new_cmd;
+mod opt_ssa_cmd;
 mod test_cmd;
+mod tree_cmd;
+mod update_cmd;
+mod vendor_cmd;
 
 const GIT_HASH: &str = env!("GIT_COMMIT");
 const IS_DIRTY: &str = env!("GIT_DIRTY");
@@ -49,6 +54,10 @@ pub(crate) struct NargoConfig {
     // REMINDER: Also change this flag in the LSP test lens if renamed
     #[arg(long, hide = true, global = true, default_value = "./")]
     program_dir: PathBuf,
+
+    /// Fail rather than access the network, git dependencies must be vendored or already cached
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
 #[non_exhaustive]
@@ -66,10 +75,16 @@ enum NargoCommand {
     #[command(hide = true)] // Hidden while the feature is being built out
     Debug(debug_cmd::DebugCommand),
     Test(test_cmd::TestCommand),
+    Tree(tree_cmd::TreeCommand),
     Info(info_cmd::InfoCommand),
     Lsp(lsp_cmd::LspCommand),
+    Update(update_cmd::UpdateCommand),
+    Vendor(vendor_cmd::VendorCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
+    // For compiler contributors to isolate the effect of individual SSA passes
+    #[command(hide = true)]
+    OptSsa(opt_ssa_cmd::OptSsaCommand),
 }
 
 #[cfg(not(feature = "codegen-docs"))]
@@ -84,7 +99,11 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     // Search through parent directories to find package root if necessary.
     if !matches!(
         command,
-        NargoCommand::New(_) | NargoCommand::Init(_) | NargoCommand::Lsp(_) | NargoCommand::Dap(_)
+        NargoCommand::New(_)
+            | NargoCommand::Init(_)
+            | NargoCommand::Lsp(_)
+            | NargoCommand::Dap(_)
+            | NargoCommand::OptSsa(_)
     ) {
         config.program_dir = find_package_root(&config.program_dir)?;
     }
@@ -98,10 +117,14 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Execute(args) => execute_cmd::run(args, config),
         NargoCommand::Export(args) => export_cmd::run(args, config),
         NargoCommand::Test(args) => test_cmd::run(args, config),
+        NargoCommand::Tree(args) => tree_cmd::run(args, config),
         NargoCommand::Info(args) => info_cmd::run(args, config),
         NargoCommand::Lsp(args) => lsp_cmd::run(args, config),
+        NargoCommand::Update(args) => update_cmd::run(args, config),
+        NargoCommand::Vendor(args) => vendor_cmd::run(args, config),
         NargoCommand::Dap(args) => dap_cmd::run(args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
+        NargoCommand::OptSsa(args) => opt_ssa_cmd::run(args, config),
     }?;
 
     Ok(())
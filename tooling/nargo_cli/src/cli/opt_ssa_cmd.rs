@@ -0,0 +1,76 @@
+use std::{io::Read, path::PathBuf};
+
+use clap::Args;
+use noirc_evaluator::ssa::pipeline::{
+    default_pipeline, parse_pipeline, run_pipeline, PipelineOptions, SsaPass,
+};
+
+use crate::errors::CliError;
+
+use super::NargoConfig;
+
+/// Run a list of SSA passes over SSA printed with `--show-ssa`, without compiling a program
+#[derive(Debug, Clone, Args)]
+pub(crate) struct OptSsaCommand {
+    /// The file containing the SSA to optimize, which is read from stdin if not given
+    file: Option<PathBuf>,
+
+    /// A comma-separated list of the passes to run, e.g. `mem2reg,unroll,flatten_cfg`.
+    /// `default` stands for the passes run when compiling a program, which are run if this isn't given.
+    #[arg(long)]
+    passes: Option<String>,
+
+    /// Print the SSA after each pass rather than only at the end
+    #[arg(long)]
+    show_ssa: bool,
+
+    /// Check that the SSA produced by each pass is well-formed
+    #[arg(long)]
+    validate: bool,
+
+    /// List the passes which can be run and exit
+    #[arg(long, conflicts_with_all = ["file", "passes"])]
+    list_passes: bool,
+}
+
+pub(crate) fn run(args: OptSsaCommand, _config: NargoConfig) -> Result<(), CliError> {
+    if args.list_passes {
+        for pass in SsaPass::all() {
+            println!("{}", pass.name());
+        }
+        return Ok(());
+    }
+
+    let passes = match &args.passes {
+        Some(passes) => {
+            parse_pipeline(passes).map_err(|error| CliError::Generic(error.to_string()))?
+        }
+        None => default_pipeline(),
+    };
+
+    let src = match &args.file {
+        Some(file) => std::fs::read_to_string(file).map_err(|error| {
+            CliError::Generic(format!("Could not read {}: {error}", file.display()))
+        })?,
+        None => {
+            let mut src = String::new();
+            std::io::stdin()
+                .read_to_string(&mut src)
+                .map_err(|error| CliError::Generic(format!("Could not read stdin: {error}")))?;
+            src
+        }
+    };
+
+    let options = PipelineOptions { validate: args.validate };
+    let ssa = run_pipeline(&src, &passes, options, |pass, ssa| {
+        if args.show_ssa {
+            println!("After {}:\n{ssa}", pass.name());
+        }
+    })
+    .map_err(|error| CliError::Generic(error.to_string()))?;
+
+    if !args.show_ssa {
+        println!("{ssa}");
+    }
+    Ok(())
+}
@@ -1,12 +1,21 @@
-use std::io::Write;
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use acvm::{BlackBoxFunctionSolver, FieldElement};
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 use fm::FileManager;
 use nargo::{
-    insert_all_files_for_workspace_into_file_manager, ops::TestStatus, package::Package, parse_all,
-    prepare_package,
+    insert_all_files_for_workspace_into_file_manager,
+    ops::{
+        test_status_program_compile_fail, test_status_program_compile_pass, ExecutionLimits,
+        SourceCoverage, TestStatus,
+    },
+    package::Package,
+    parse_all, prepare_package,
 };
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{
@@ -18,11 +27,19 @@ use noirc_frontend::{
     hir::{FunctionNameMatch, ParsedFiles},
 };
 use rayon::prelude::{IntoParallelIterator, ParallelBridge, ParallelIterator};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
-use super::NargoConfig;
+use super::{
+    fs::{create_named_dir, write_to_file},
+    NargoConfig,
+};
+
+mod coverage;
+mod formatters;
+
+use coverage::FileCoverage;
+use formatters::{Format, Formatter, TestResult};
 
 /// Run the tests for this program
 #[derive(Debug, Clone, Args)]
@@ -31,7 +48,7 @@ pub(crate) struct TestCommand {
     /// If given, only tests with names containing this string will be run
     test_name: Option<String>,
 
-    /// Display output of `println` statements
+    /// Display output of `println` statements. Only available with `--format pretty`
     #[arg(long)]
     show_output: bool,
 
@@ -53,9 +70,40 @@ pub(crate) struct TestCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// The number of inputs to fuzz each test which takes arguments with.
+    /// Overrides the `fuzz-runs` setting in the `[test]` table of `Nargo.toml`
+    #[clap(long)]
+    fuzz_runs: Option<u32>,
+
+    /// The number of seconds each test may run for before it fails.
+    /// Overrides the `timeout` setting in the `[test]` table of `Nargo.toml`
+    #[clap(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// The number of ACIR opcodes and Brillig instructions each test may execute before it fails.
+    /// Overrides the `max-steps` setting in the `[test]` table of `Nargo.toml`
+    #[clap(long)]
+    max_steps: Option<u64>,
+
+    /// Write the number of times each line was executed by the tests to `target/lcov.info`
+    /// and list the functions which no test executed
+    #[clap(long)]
+    coverage: bool,
+
+    /// How to report test results
+    #[clap(long, value_enum, default_value_t = Format::Pretty)]
+    format: Format,
 }
 
 pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError> {
+    // The output of `println` goes to stdout, where it would corrupt a JSON or JUnit report.
+    if args.show_output && args.format != Format::Pretty {
+        return Err(CliError::Generic(
+            "`--show-output` can only be used with `--format pretty`".to_string(),
+        ));
+    }
+
     let toml_path = get_package_manifest(&config.program_dir)?;
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
@@ -64,6 +112,7 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.offline,
     )?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
@@ -81,11 +130,23 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
         None => FunctionNameMatch::Anything,
     };
 
-    let test_reports: Vec<Vec<(String, TestStatus)>> = workspace
+    let regressions_dir = workspace.target_directory_path().join(FUZZ_REGRESSIONS_DIR);
+    let formatter = args.format.formatter();
+
+    let test_reports: Vec<(Vec<TestResult>, Vec<FileCoverage>)> = workspace
         .into_iter()
         .par_bridge()
         .map(|package| {
-            run_tests::<Bn254BlackBoxSolver>(
+            let fuzz_options = FuzzOptions {
+                runs: args.fuzz_runs.or(package.test_config.fuzz_runs),
+                regressions_dir: &regressions_dir.join(String::from(package.name.clone())),
+            };
+            let limits = ExecutionLimits {
+                timeout: args.timeout.map(Duration::from_secs).or(package.test_config.timeout),
+                max_steps: args.max_steps.or(package.test_config.max_steps),
+            };
+            let coverage = args.coverage.then(|| Mutex::new(SourceCoverage::default()));
+            let test_report = run_tests::<Bn254BlackBoxSolver>(
                 &workspace_file_manager,
                 &parsed_files,
                 package,
@@ -93,10 +154,36 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
                 args.show_output,
                 args.oracle_resolver.as_deref(),
                 &args.compile_options,
-            )
+                &fuzz_options,
+                limits,
+                coverage.as_ref(),
+                formatter.as_ref(),
+            )?;
+            let file_coverage = match coverage {
+                Some(coverage) => coverage::package_coverage(
+                    &workspace_file_manager,
+                    &parsed_files,
+                    package,
+                    &coverage.into_inner().expect("Coverage lock should not be poisoned"),
+                    &args.compile_options,
+                ),
+                None => Vec::new(),
+            };
+            Ok::<_, CliError>((test_report, file_coverage))
         })
         .collect::<Result<_, _>>()?;
-    let test_report: Vec<(String, TestStatus)> = test_reports.into_iter().flatten().collect();
+    formatter.finish();
+    let (test_reports, file_coverage): (Vec<_>, Vec<_>) = test_reports.into_iter().unzip();
+    let test_report: Vec<TestResult> = test_reports.into_iter().flatten().collect();
+
+    if args.coverage {
+        let file_coverage: Vec<FileCoverage> = file_coverage.into_iter().flatten().collect();
+        let target_dir = create_named_dir(&workspace.target_directory_path(), "target");
+        let report = coverage::render_lcov(&file_coverage);
+        let report_path = write_to_file(report.as_bytes(), &target_dir.join(COVERAGE_REPORT_FILE));
+        eprintln!("Wrote coverage report to {report_path}");
+        eprint!("{}", coverage::render_summary(&file_coverage));
+    }
 
     if test_report.is_empty() {
         match &pattern {
@@ -113,13 +200,28 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
         };
     }
 
-    if test_report.iter().any(|(_, status)| status.failed()) {
+    if test_report.iter().any(|result| result.status.failed()) {
         Err(CliError::Generic(String::new()))
     } else {
         Ok(())
     }
 }
 
+/// Directory inside the workspace's target directory in which the seeds of failing fuzz runs are kept
+const FUZZ_REGRESSIONS_DIR: &str = "fuzz";
+
+/// File inside the workspace's target directory which `--coverage` writes its lcov report to
+const COVERAGE_REPORT_FILE: &str = "lcov.info";
+
+/// Settings for fuzzing test functions which take arguments
+struct FuzzOptions<'a> {
+    /// How many inputs to try for each test, if `None` then proptest's default is used
+    runs: Option<u32>,
+    /// Directory holding one file of failing seeds per test, these are replayed before any new inputs
+    regressions_dir: &'a Path,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
@@ -128,18 +230,23 @@ fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     show_output: bool,
     foreign_call_resolver_url: Option<&str>,
     compile_options: &CompileOptions,
-) -> Result<Vec<(String, TestStatus)>, CliError> {
+    fuzz_options: &FuzzOptions,
+    limits: ExecutionLimits,
+    coverage: Option<&Mutex<SourceCoverage>>,
+    formatter: &dyn Formatter,
+) -> Result<Vec<TestResult>, CliError> {
     let test_functions =
         get_tests_in_package(file_manager, parsed_files, package, fn_name, compile_options)?;
 
-    let count_all = test_functions.len();
+    let start = Instant::now();
+    formatter.package_start(package, test_functions.len());
 
-    let plural = if count_all == 1 { "" } else { "s" };
-    println!("[{}] Running {count_all} test function{plural}", package.name);
-
-    let test_report: Vec<(String, TestStatus)> = test_functions
+    let test_report: Vec<TestResult> = test_functions
         .into_par_iter()
         .map(|test_name| {
+            formatter.test_start(package, &test_name);
+            let test_start = Instant::now();
+            let mut test_coverage = coverage.map(|_| SourceCoverage::default());
             let status = run_test::<S>(
                 file_manager,
                 parsed_files,
@@ -148,16 +255,25 @@ fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
                 show_output,
                 foreign_call_resolver_url,
                 compile_options,
+                fuzz_options,
+                limits,
+                test_coverage.as_mut(),
             );
+            if let (Some(coverage), Some(test_coverage)) = (coverage, test_coverage) {
+                coverage.lock().expect("Coverage lock should not be poisoned").merge(test_coverage);
+            }
 
-            (test_name, status)
+            let result = TestResult { name: test_name, status, time: test_start.elapsed() };
+            formatter.test_end(package, &result, file_manager);
+            result
         })
         .collect();
 
-    display_test_report(file_manager, package, compile_options, &test_report)?;
+    formatter.package_end(package, &test_report, start.elapsed(), file_manager, compile_options);
     Ok(test_report)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
@@ -166,6 +282,9 @@ fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     show_output: bool,
     foreign_call_resolver_url: Option<&str>,
     compile_options: &CompileOptions,
+    fuzz_options: &FuzzOptions,
+    limits: ExecutionLimits,
+    coverage: Option<&mut SourceCoverage>,
 ) -> TestStatus {
     // This is really hacky but we can't share `Context` or `S` across threads.
     // We then need to construct a separate copy for each test.
@@ -201,30 +320,73 @@ fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
             show_output,
             foreign_call_resolver_url,
             compile_options,
+            limits,
+            coverage,
         )
     } else {
-        use noir_fuzzer::FuzzedExecutor;
-        use proptest::test_runner::TestRunner;
+        use noir_fuzzer::{format_arguments, FileRegressionPersistence, FuzzedExecutor};
+        use proptest::test_runner::{Config, TestRunner};
 
         let compiled_program =
             compile_no_check(&mut context, compile_options, test_function.get_id(), None, false);
         match compiled_program {
             Ok(compiled_program) => {
-                let runner = TestRunner::default();
-
-                let fuzzer = FuzzedExecutor::new(compiled_program.into(), runner);
+                // `::` can't appear in file names on all platforms.
+                let regressions_file = fuzz_options
+                    .regressions_dir
+                    .join(fn_name.replace("::", "-"))
+                    .with_extension("txt");
+                let default_config = Config::default();
+                let config = Config {
+                    cases: fuzz_options.runs.unwrap_or(default_config.cases),
+                    failure_persistence: Some(Box::new(FileRegressionPersistence::new(
+                        regressions_file,
+                    ))),
+                    ..default_config
+                };
+                let runner = TestRunner::new(config);
+
+                let abi = compiled_program.abi.clone();
+                let debug = compiled_program.debug.clone();
+                let mut fuzzer =
+                    FuzzedExecutor::new(compiled_program.into(), runner, blackbox_solver)
+                        .with_foreign_calls(show_output, foreign_call_resolver_url)
+                        .with_execution_limits(limits);
+                if coverage.is_some() {
+                    fuzzer = fuzzer.with_coverage();
+                }
 
-                let result = fuzzer.fuzz();
-                if result.success {
+                // Every input has to pass the same checks as a test without arguments, so that
+                // e.g. a `should_fail` test must fail whatever its inputs
+                let result = fuzzer.fuzz(|execution| {
+                    match test_status_program_compile_pass(test_function, &abi, &debug, execution)
+                    {
+                        TestStatus::Pass => Ok(()),
+                        TestStatus::Fail { message, .. } => Err(message),
+                        TestStatus::LimitExceeded(limit) => Err(limit.to_string()),
+                        TestStatus::CompileError(_) => {
+                            unreachable!("The program has already been compiled")
+                        }
+                    }
+                });
+                if let (Some(coverage), Some(opcode_coverage)) = (coverage, &result.coverage) {
+                    coverage.merge(opcode_coverage.source_coverage(&debug));
+                }
+                if let Some(limit) = result.exceeded_limit {
+                    TestStatus::LimitExceeded(limit)
+                } else if result.success {
                     TestStatus::Pass
                 } else {
-                    TestStatus::Fail {
-                        message: result.reason.unwrap_or_default(),
-                        error_diagnostic: None,
+                    let mut message = result.reason.unwrap_or_default();
+                    if let Some(counterexample) = result.counterexample {
+                        let arguments = format_arguments(&abi, &counterexample);
+                        let test_name = fn_name.rsplit("::").next().unwrap_or(fn_name);
+                        message.push_str(&format!("\nCounterexample: {test_name}({arguments})"));
                     }
+                    TestStatus::Fail { message, error_diagnostic: None }
                 }
             }
-            Err(err) => TestStatus::CompileError(err.into()),
+            Err(err) => test_status_program_compile_fail(err, test_function),
         }
     }
 }
@@ -252,82 +414,3 @@ fn get_tests_in_package(
         .map(|(test_name, _)| test_name)
         .collect())
 }
-
-fn display_test_report(
-    file_manager: &FileManager,
-    package: &Package,
-    compile_options: &CompileOptions,
-    test_report: &[(String, TestStatus)],
-) -> Result<(), CliError> {
-    let writer = StandardStream::stderr(ColorChoice::Always);
-    let mut writer = writer.lock();
-
-    for (test_name, test_status) in test_report {
-        write!(writer, "[{}] Testing {test_name}... ", package.name)
-            .expect("Failed to write to stderr");
-        writer.flush().expect("Failed to flush writer");
-
-        match &test_status {
-            TestStatus::Pass { .. } => {
-                writer
-                    .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
-                    .expect("Failed to set color");
-                writeln!(writer, "ok").expect("Failed to write to stderr");
-            }
-            TestStatus::Fail { message, error_diagnostic } => {
-                writer
-                    .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
-                    .expect("Failed to set color");
-                writeln!(writer, "FAIL\n{message}\n").expect("Failed to write to stderr");
-                if let Some(diag) = error_diagnostic {
-                    noirc_errors::reporter::report_all(
-                        file_manager.as_file_map(),
-                        &[diag.clone()],
-                        compile_options.deny_warnings,
-                        compile_options.silence_warnings,
-                    );
-                }
-            }
-            TestStatus::CompileError(err) => {
-                noirc_errors::reporter::report_all(
-                    file_manager.as_file_map(),
-                    &[err.clone()],
-                    compile_options.deny_warnings,
-                    compile_options.silence_warnings,
-                );
-            }
-        }
-        writer.reset().expect("Failed to reset writer");
-    }
-
-    write!(writer, "[{}] ", package.name).expect("Failed to write to stderr");
-
-    let count_all = test_report.len();
-    let count_failed = test_report.iter().filter(|(_, status)| status.failed()).count();
-    let plural = if count_all == 1 { "" } else { "s" };
-    if count_failed == 0 {
-        writer.set_color(ColorSpec::new().set_fg(Some(Color::Green))).expect("Failed to set color");
-        write!(writer, "{count_all} test{plural} passed").expect("Failed to write to stderr");
-        writer.reset().expect("Failed to reset writer");
-        writeln!(writer).expect("Failed to write to stderr");
-    } else {
-        let count_passed = count_all - count_failed;
-        let plural_failed = if count_failed == 1 { "" } else { "s" };
-        let plural_passed = if count_passed == 1 { "" } else { "s" };
-
-        if count_passed != 0 {
-            writer
-                .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
-                .expect("Failed to set color");
-            write!(writer, "{count_passed} test{plural_passed} passed, ",)
-                .expect("Failed to write to stderr");
-        }
-
-        writer.set_color(ColorSpec::new().set_fg(Some(Color::Red))).expect("Failed to set color");
-        writeln!(writer, "{count_failed} test{plural_failed} failed")
-            .expect("Failed to write to stderr");
-        writer.reset().expect("Failed to reset writer");
-    }
-
-    Ok(())
-}
@@ -0,0 +1,206 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::PathBuf,
+};
+
+use fm::{codespan_files::Files, FileManager};
+use nargo::{ops::SourceCoverage, package::Package, prepare_package};
+use noirc_driver::{check_crate, CompileOptions};
+use noirc_frontend::hir::ParsedFiles;
+
+/// Line and function hit counts for one of a package's source files
+pub(super) struct FileCoverage {
+    pub(super) path: PathBuf,
+    /// Hit counts keyed by one-based line number, for each line which opcodes were generated from
+    pub(super) lines: BTreeMap<usize, u64>,
+    pub(super) functions: Vec<FunctionCoverage>,
+}
+
+pub(super) struct FunctionCoverage {
+    pub(super) name: String,
+    /// One-based line number of the function's name
+    pub(super) line: usize,
+    /// The hit count of the most executed line in the function's body
+    pub(super) hits: u64,
+}
+
+/// Maps the source locations executed by a package's tests onto the lines and functions of the
+/// package's own source files. Dependencies, including the standard library, are left out.
+pub(super) fn package_coverage(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    package: &Package,
+    coverage: &SourceCoverage,
+    compile_options: &CompileOptions,
+) -> Vec<FileCoverage> {
+    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    check_crate(
+        &mut context,
+        crate_id,
+        compile_options.deny_warnings,
+        compile_options.disable_macros,
+        compile_options.use_legacy,
+    )
+    .expect("Any errors should have occurred when collecting test functions");
+
+    let def_map = context.def_map(&crate_id).expect("The local crate should be analyzed already");
+    let file_ids: BTreeSet<_> =
+        def_map.modules().iter().map(|(_, module)| module.location.file).collect();
+
+    let file_map = file_manager.as_file_map();
+    let mut files: BTreeMap<_, _> = file_ids
+        .into_iter()
+        .filter_map(|file_id| {
+            let path = file_manager.path(file_id)?.to_path_buf();
+            Some((file_id, FileCoverage { path, lines: BTreeMap::new(), functions: Vec::new() }))
+        })
+        .collect();
+
+    // Several locations, e.g. the operands of an expression, can start on the same line so the
+    // line is attributed the count of its most executed one.
+    for (location, hits) in &coverage.hits {
+        let Some(file) = files.get_mut(&location.file) else { continue };
+        let Ok(line_index) = file_map.line_index(location.file, location.span.start() as usize)
+        else {
+            continue;
+        };
+        let line_hits = file.lines.entry(line_index + 1).or_default();
+        *line_hits = (*line_hits).max(*hits);
+    }
+
+    for func_id in context.def_interner.functions_in_crate(crate_id) {
+        let name_location = context.def_interner.function_meta(&func_id).name.location;
+        let Some(file) = files.get_mut(&name_location.file) else { continue };
+
+        let body = context.def_interner.function(&func_id).as_expr();
+        let body_span = context.def_interner.expr_span(&body);
+        let line_of = |byte_index: u32| {
+            file_map.line_index(name_location.file, byte_index as usize).map(|index| index + 1)
+        };
+        let (Ok(line), Ok(body_start), Ok(body_end)) = (
+            line_of(name_location.span.start()),
+            line_of(body_span.start()),
+            line_of(body_span.end()),
+        ) else {
+            continue;
+        };
+
+        let hits = file.lines.range(body_start..=body_end).map(|(_, hits)| *hits).max();
+        file.functions.push(FunctionCoverage {
+            name: context.fully_qualified_function_name(&crate_id, &func_id),
+            line,
+            hits: hits.unwrap_or_default(),
+        });
+    }
+
+    files
+        .into_values()
+        .map(|mut file| {
+            file.functions.sort_by_key(|function| function.line);
+            file
+        })
+        .collect()
+}
+
+/// Renders the coverage of each file as a tracefile in the format read by `genhtml` and most
+/// coverage tooling. See `man geninfo` for the details.
+pub(super) fn render_lcov(files: &[FileCoverage]) -> String {
+    let mut output = String::new();
+    for file in files {
+        writeln!(output, "TN:").unwrap();
+        writeln!(output, "SF:{}", file.path.display()).unwrap();
+        for function in &file.functions {
+            writeln!(output, "FN:{},{}", function.line, function.name).unwrap();
+        }
+        for function in &file.functions {
+            writeln!(output, "FNDA:{},{}", function.hits, function.name).unwrap();
+        }
+        let functions_hit = file.functions.iter().filter(|function| function.hits > 0).count();
+        writeln!(output, "FNF:{}", file.functions.len()).unwrap();
+        writeln!(output, "FNH:{functions_hit}").unwrap();
+        for (line, hits) in &file.lines {
+            writeln!(output, "DA:{line},{hits}").unwrap();
+        }
+        let lines_hit = file.lines.values().filter(|hits| **hits > 0).count();
+        writeln!(output, "LF:{}", file.lines.len()).unwrap();
+        writeln!(output, "LH:{lines_hit}").unwrap();
+        writeln!(output, "end_of_record").unwrap();
+    }
+    output
+}
+
+/// Summarises the line coverage of `files` and lists every function which was never executed.
+pub(super) fn render_summary(files: &[FileCoverage]) -> String {
+    let lines_found: usize = files.iter().map(|file| file.lines.len()).sum();
+    let lines_hit: usize =
+        files.iter().map(|file| file.lines.values().filter(|hits| **hits > 0).count()).sum();
+    let percentage =
+        if lines_found == 0 { 100.0 } else { lines_hit as f64 * 100.0 / lines_found as f64 };
+
+    let mut output = format!("Line coverage: {lines_hit}/{lines_found} ({percentage:.1}%)\n");
+    let uncovered: Vec<_> = files
+        .iter()
+        .flat_map(|file| {
+            file.functions
+                .iter()
+                .filter(|function| function.hits == 0)
+                .map(move |function| (file, function))
+        })
+        .collect();
+    if !uncovered.is_empty() {
+        output.push_str("Functions not executed by any test:\n");
+        for (file, function) in uncovered {
+            writeln!(output, "  {} ({}:{})", function.name, file.path.display(), function.line)
+                .unwrap();
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use super::{render_lcov, render_summary, FileCoverage, FunctionCoverage};
+
+    fn file_coverage() -> FileCoverage {
+        FileCoverage {
+            path: PathBuf::from("/work/foo/src/main.nr"),
+            lines: BTreeMap::from([(2, 3), (3, 1), (7, 0)]),
+            functions: vec![
+                FunctionCoverage { name: "main".to_string(), line: 1, hits: 3 },
+                FunctionCoverage { name: "helper".to_string(), line: 6, hits: 0 },
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_lcov_tracefile() {
+        let expected = "TN:
+SF:/work/foo/src/main.nr
+FN:1,main
+FN:6,helper
+FNDA:3,main
+FNDA:0,helper
+FNF:2
+FNH:1
+DA:2,3
+DA:3,1
+DA:7,0
+LF:3
+LH:2
+end_of_record
+";
+        assert_eq!(render_lcov(&[file_coverage()]), expected);
+    }
+
+    #[test]
+    fn summarises_uncovered_functions() {
+        let expected = "Line coverage: 2/3 (66.7%)
+Functions not executed by any test:
+  helper (/work/foo/src/main.nr:6)
+";
+        assert_eq!(render_summary(&[file_coverage()]), expected);
+    }
+}
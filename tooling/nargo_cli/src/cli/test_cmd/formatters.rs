@@ -0,0 +1,438 @@
+use std::{io::Write, sync::Mutex, time::Duration};
+
+use clap::ValueEnum;
+use fm::{codespan_files::Files, FileManager};
+use nargo::{ops::TestStatus, package::Package};
+use noirc_driver::CompileOptions;
+use noirc_errors::FileDiagnostic;
+use serde_json::json;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+/// How `nargo test` reports its results
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    /// Human readable, coloured output
+    #[default]
+    Pretty,
+    /// A stream of JSON objects on stdout, one per line for each event
+    Json,
+    /// A JUnit XML report on stdout once all tests have run
+    Junit,
+}
+
+impl Format {
+    pub(super) fn formatter(self) -> Box<dyn Formatter> {
+        match self {
+            Format::Pretty => Box::new(PrettyFormatter),
+            Format::Json => Box::new(JsonFormatter),
+            Format::Junit => Box::new(JunitFormatter::default()),
+        }
+    }
+}
+
+/// The outcome of running a single test function
+pub(super) struct TestResult {
+    pub(super) name: String,
+    pub(super) status: TestStatus,
+    pub(super) time: Duration,
+}
+
+/// Reports the progress of a test run.
+///
+/// Tests within a package, as well as packages themselves, are run in parallel so
+/// events for different tests may be interleaved.
+pub(super) trait Formatter: Send + Sync {
+    fn package_start(&self, package: &Package, test_count: usize);
+
+    fn test_start(&self, package: &Package, test_name: &str);
+
+    fn test_end(&self, package: &Package, result: &TestResult, file_manager: &FileManager);
+
+    fn package_end(
+        &self,
+        package: &Package,
+        results: &[TestResult],
+        time: Duration,
+        file_manager: &FileManager,
+        compile_options: &CompileOptions,
+    );
+
+    /// Called once every package has been tested
+    fn finish(&self) {}
+}
+
+struct PrettyFormatter;
+
+impl Formatter for PrettyFormatter {
+    fn package_start(&self, package: &Package, test_count: usize) {
+        let plural = if test_count == 1 { "" } else { "s" };
+        println!("[{}] Running {test_count} test function{plural}", package.name);
+    }
+
+    fn test_start(&self, _package: &Package, _test_name: &str) {}
+
+    fn test_end(&self, _package: &Package, _result: &TestResult, _file_manager: &FileManager) {}
+
+    fn package_end(
+        &self,
+        package: &Package,
+        results: &[TestResult],
+        _time: Duration,
+        file_manager: &FileManager,
+        compile_options: &CompileOptions,
+    ) {
+        let writer = StandardStream::stderr(ColorChoice::Always);
+        let mut writer = writer.lock();
+
+        for TestResult { name: test_name, status: test_status, .. } in results {
+            write!(writer, "[{}] Testing {test_name}... ", package.name)
+                .expect("Failed to write to stderr");
+            writer.flush().expect("Failed to flush writer");
+
+            match &test_status {
+                TestStatus::Pass => {
+                    writer
+                        .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
+                        .expect("Failed to set color");
+                    writeln!(writer, "ok").expect("Failed to write to stderr");
+                }
+                TestStatus::Fail { message, error_diagnostic } => {
+                    writer
+                        .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
+                        .expect("Failed to set color");
+                    writeln!(writer, "FAIL\n{message}\n").expect("Failed to write to stderr");
+                    if let Some(diag) = error_diagnostic {
+                        noirc_errors::reporter::report_all(
+                            file_manager.as_file_map(),
+                            std::slice::from_ref(diag),
+                            compile_options.deny_warnings,
+                            compile_options.silence_warnings,
+                        );
+                    }
+                }
+                TestStatus::CompileError(err) => {
+                    noirc_errors::reporter::report_all(
+                        file_manager.as_file_map(),
+                        std::slice::from_ref(err),
+                        compile_options.deny_warnings,
+                        compile_options.silence_warnings,
+                    );
+                }
+                TestStatus::LimitExceeded(limit) => {
+                    writer
+                        .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
+                        .expect("Failed to set color");
+                    writeln!(writer, "FAIL\n{limit}\n").expect("Failed to write to stderr");
+                }
+            }
+            writer.reset().expect("Failed to reset writer");
+        }
+
+        write!(writer, "[{}] ", package.name).expect("Failed to write to stderr");
+
+        let count_all = results.len();
+        let count_failed = results.iter().filter(|result| result.status.failed()).count();
+        let plural = if count_all == 1 { "" } else { "s" };
+        if count_failed == 0 {
+            writer
+                .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
+                .expect("Failed to set color");
+            write!(writer, "{count_all} test{plural} passed").expect("Failed to write to stderr");
+            writer.reset().expect("Failed to reset writer");
+            writeln!(writer).expect("Failed to write to stderr");
+        } else {
+            let count_passed = count_all - count_failed;
+            let plural_failed = if count_failed == 1 { "" } else { "s" };
+            let plural_passed = if count_passed == 1 { "" } else { "s" };
+
+            if count_passed != 0 {
+                writer
+                    .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
+                    .expect("Failed to set color");
+                write!(writer, "{count_passed} test{plural_passed} passed, ",)
+                    .expect("Failed to write to stderr");
+            }
+
+            writer
+                .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
+                .expect("Failed to set color");
+            writeln!(writer, "{count_failed} test{plural_failed} failed")
+                .expect("Failed to write to stderr");
+            writer.reset().expect("Failed to reset writer");
+        }
+    }
+}
+
+/// Emits events in the same shape as libtest's `--format json`, extended with the package
+/// each event belongs to and the source location of failures.
+struct JsonFormatter;
+
+impl JsonFormatter {
+    fn emit(&self, event: serde_json::Value) {
+        // Each event is written with a single call so that events from parallel tests don't interleave.
+        let line = format!("{event}\n");
+        std::io::stdout().lock().write_all(line.as_bytes()).expect("Failed to write to stdout");
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn package_start(&self, package: &Package, test_count: usize) {
+        self.emit(json!({
+            "type": "suite",
+            "event": "started",
+            "package": package.name.to_string(),
+            "test_count": test_count,
+        }));
+    }
+
+    fn test_start(&self, package: &Package, test_name: &str) {
+        self.emit(json!({
+            "type": "test",
+            "event": "started",
+            "package": package.name.to_string(),
+            "name": test_name,
+        }));
+    }
+
+    fn test_end(&self, package: &Package, result: &TestResult, file_manager: &FileManager) {
+        let mut event = json!({
+            "type": "test",
+            "event": if result.status.failed() { "failed" } else { "ok" },
+            "package": package.name.to_string(),
+            "name": result.name,
+            "exec_time": result.time.as_secs_f64(),
+        });
+        if let Some((message, diagnostic)) = failure(&result.status) {
+            event["message"] = message.into();
+            if let Some(location) = diagnostic.and_then(|diag| location(diag, file_manager)) {
+                event["location"] = json!({
+                    "file": location.file,
+                    "line": location.line,
+                    "column": location.column,
+                });
+            }
+        }
+        self.emit(event);
+    }
+
+    fn package_end(
+        &self,
+        package: &Package,
+        results: &[TestResult],
+        time: Duration,
+        _file_manager: &FileManager,
+        _compile_options: &CompileOptions,
+    ) {
+        let failed = results.iter().filter(|result| result.status.failed()).count();
+        self.emit(json!({
+            "type": "suite",
+            "event": if failed == 0 { "ok" } else { "failed" },
+            "package": package.name.to_string(),
+            "passed": results.len() - failed,
+            "failed": failed,
+            "exec_time": time.as_secs_f64(),
+        }));
+    }
+}
+
+/// The parts of a package's test results which make it into a JUnit report
+struct JunitSuite {
+    name: String,
+    time: Duration,
+    /// The XML of each `<testcase>`
+    test_cases: Vec<String>,
+    failures: usize,
+    errors: usize,
+}
+
+/// Collects the results of every package and writes them out as a single JUnit XML report.
+#[derive(Default)]
+struct JunitFormatter {
+    suites: Mutex<Vec<JunitSuite>>,
+}
+
+impl Formatter for JunitFormatter {
+    fn package_start(&self, _package: &Package, _test_count: usize) {}
+
+    fn test_start(&self, _package: &Package, _test_name: &str) {}
+
+    fn test_end(&self, _package: &Package, _result: &TestResult, _file_manager: &FileManager) {}
+
+    fn package_end(
+        &self,
+        package: &Package,
+        results: &[TestResult],
+        time: Duration,
+        file_manager: &FileManager,
+        _compile_options: &CompileOptions,
+    ) {
+        let package_name = package.name.to_string();
+        let mut suite = JunitSuite {
+            name: package_name.clone(),
+            time,
+            test_cases: Vec::new(),
+            failures: 0,
+            errors: 0,
+        };
+
+        for result in results {
+            let attributes = format!(
+                r#"name="{}" classname="{}" time="{:.3}""#,
+                escape_xml(&result.name),
+                escape_xml(&package_name),
+                result.time.as_secs_f64()
+            );
+            let Some((message, diagnostic)) = failure(&result.status) else {
+                suite.test_cases.push(format!("<testcase {attributes}/>"));
+                continue;
+            };
+
+            // Compilation errors aren't assertion failures, JUnit reports these separately.
+            let element = if let TestStatus::CompileError(_) = result.status {
+                suite.errors += 1;
+                "error"
+            } else {
+                suite.failures += 1;
+                "failure"
+            };
+            let mut details = message.clone();
+            if let Some(location) = diagnostic.and_then(|diag| location(diag, file_manager)) {
+                details.push_str(&format!(
+                    "\nat {}:{}:{}",
+                    location.file, location.line, location.column
+                ));
+            }
+            let summary = message.lines().next().unwrap_or_default();
+            suite.test_cases.push(format!(
+                r#"<testcase {attributes}><{element} message="{}">{}</{element}></testcase>"#,
+                escape_xml(summary),
+                escape_xml(&details)
+            ));
+        }
+
+        self.suites.lock().expect("Failed to lock test suites").push(suite);
+    }
+
+    fn finish(&self) {
+        let mut suites = self.suites.lock().expect("Failed to lock test suites");
+        suites.sort_by(|a, b| a.name.cmp(&b.name));
+        print!("{}", render_junit(&suites));
+    }
+}
+
+fn render_junit(suites: &[JunitSuite]) -> String {
+    let count = |suite: &JunitSuite| suite.test_cases.len();
+    let tests: usize = suites.iter().map(count).sum();
+    let failures: usize = suites.iter().map(|suite| suite.failures).sum();
+    let errors: usize = suites.iter().map(|suite| suite.errors).sum();
+    let time: f64 = suites.iter().map(|suite| suite.time.as_secs_f64()).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"nargo test\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time:.3}\">\n"
+    ));
+    for suite in suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+            escape_xml(&suite.name),
+            count(suite),
+            suite.failures,
+            suite.errors,
+            suite.time.as_secs_f64()
+        ));
+        for test_case in &suite.test_cases {
+            xml.push_str(&format!("    {test_case}\n"));
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Returns the message and diagnostic of a test which didn't pass
+fn failure(status: &TestStatus) -> Option<(String, Option<&FileDiagnostic>)> {
+    match status {
+        TestStatus::Pass => None,
+        TestStatus::Fail { message, error_diagnostic } => {
+            Some((message.clone(), error_diagnostic.as_ref()))
+        }
+        TestStatus::CompileError(diagnostic) => {
+            Some((diagnostic.diagnostic.message.clone(), Some(diagnostic)))
+        }
+        TestStatus::LimitExceeded(limit) => Some((limit.to_string(), None)),
+    }
+}
+
+struct SourceLocation {
+    file: String,
+    /// One-based line number
+    line: usize,
+    /// One-based column number
+    column: usize,
+}
+
+/// Returns where the primary label of `diagnostic` points to
+fn location(diagnostic: &FileDiagnostic, file_manager: &FileManager) -> Option<SourceLocation> {
+    let span = diagnostic.diagnostic.secondaries.first()?.span;
+    let location =
+        file_manager.as_file_map().location(diagnostic.file_id, span.start() as usize).ok()?;
+    let file = file_manager.path(diagnostic.file_id)?.display().to_string();
+    Some(SourceLocation { file, line: location.line_number, column: location.column_number })
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Other control characters aren't allowed in XML 1.0 documents.
+            '\n' | '\r' | '\t' => escaped.push(char),
+            char if char.is_control() => {}
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{escape_xml, render_junit, JunitSuite};
+
+    #[test]
+    fn escapes_xml_special_characters() {
+        assert_eq!(
+            escape_xml("assert(a < b && c > \"d\")\u{1b}"),
+            "assert(a &lt; b &amp;&amp; c &gt; &quot;d&quot;)"
+        );
+    }
+
+    #[test]
+    fn renders_junit_report() {
+        let suite = JunitSuite {
+            name: "foo".to_string(),
+            time: Duration::from_millis(1500),
+            test_cases: vec![
+                r#"<testcase name="foo::a" classname="foo" time="0.500"/>"#.to_string(),
+                r#"<testcase name="foo::b" classname="foo" time="1.000"><failure message="oops">oops</failure></testcase>"#.to_string(),
+            ],
+            failures: 1,
+            errors: 0,
+        };
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="nargo test" tests="2" failures="1" errors="0" time="1.500">
+  <testsuite name="foo" tests="2" failures="1" errors="0" time="1.500">
+    <testcase name="foo::a" classname="foo" time="0.500"/>
+    <testcase name="foo::b" classname="foo" time="1.000"><failure message="oops">oops</failure></testcase>
+  </testsuite>
+</testsuites>
+"#;
+        assert_eq!(render_junit(&[suite]), expected);
+    }
+}
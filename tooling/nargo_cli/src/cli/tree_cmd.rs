@@ -0,0 +1,311 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Write,
+    path::Path,
+};
+
+use clap::Args;
+use nargo::{
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use noirc_frontend::graph::CrateName;
+
+use crate::errors::CliError;
+
+use super::NargoConfig;
+
+/// Display the dependency graph of the workspace's packages
+#[derive(Debug, Clone, Args)]
+pub(crate) struct TreeCommand {
+    /// The name of the package to display the dependencies of
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Display the dependencies of all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Only show packages which are pulled in from more than one source, along with the packages which depend on them
+    #[clap(long)]
+    duplicates: bool,
+}
+
+pub(crate) fn run(args: TreeCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.offline,
+    )?;
+
+    let graph = DependencyGraph::new(&workspace);
+    if args.duplicates {
+        print!("{}", graph.render_duplicates());
+    } else {
+        print!("{}", graph.render_trees(&workspace));
+    }
+
+    Ok(())
+}
+
+/// A package reachable from the selected workspace members.
+struct Node<'a> {
+    package: &'a Package,
+    /// Where the package was resolved from, either a git source or the directory containing it
+    source: String,
+    /// The directories of the packages which depend on this one
+    dependents: BTreeSet<&'a Path>,
+}
+
+/// Every package reachable from the selected workspace members, keyed by the directory it resolved to.
+///
+/// Two packages with the same name which resolve to different directories are duplicates: they come
+/// from different git references or paths and will be compiled into the program as separate crates.
+struct DependencyGraph<'a> {
+    nodes: BTreeMap<&'a Path, Node<'a>>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    fn new(workspace: &'a Workspace) -> Self {
+        let mut graph = Self { nodes: BTreeMap::new() };
+        for package in workspace {
+            graph.add_package(package, package.root_dir.display().to_string(), None);
+        }
+        graph
+    }
+
+    fn add_package(&mut self, package: &'a Package, source: String, dependent: Option<&'a Path>) {
+        let visited = self.nodes.contains_key(package.root_dir.as_path());
+        let node = self.nodes.entry(package.root_dir.as_path()).or_insert_with(|| Node {
+            package,
+            source,
+            dependents: BTreeSet::new(),
+        });
+        node.dependents.extend(dependent);
+        if visited {
+            return;
+        }
+
+        for dependency in package.dependencies.values() {
+            let source = dependency_source(dependency);
+            self.add_package(dependency.package(), source, Some(package.root_dir.as_path()));
+        }
+    }
+
+    /// Names of the packages which resolve to more than one directory
+    fn duplicate_names(&self) -> BTreeSet<&'a CrateName> {
+        let mut directories: BTreeMap<&CrateName, usize> = BTreeMap::new();
+        for node in self.nodes.values() {
+            *directories.entry(&node.package.name).or_default() += 1;
+        }
+        directories.into_iter().filter_map(|(name, count)| (count > 1).then_some(name)).collect()
+    }
+
+    fn label(&self, package: &Package, alias: Option<&CrateName>) -> String {
+        let node = &self.nodes[package.root_dir.as_path()];
+        let mut label = package.name.to_string();
+        if let Some(version) = &package.version {
+            write!(label, " v{version}").unwrap();
+        }
+        if let Some(alias) = alias.filter(|alias| *alias != &package.name) {
+            write!(label, " as {alias}").unwrap();
+        }
+        write!(label, " ({})", node.source).unwrap();
+        label
+    }
+
+    /// Renders the dependencies of each selected workspace member as a tree.
+    ///
+    /// The dependencies of a package are only expanded the first time it's shown, later
+    /// occurrences are marked with `(*)`.
+    fn render_trees(&self, workspace: &Workspace) -> String {
+        let duplicates = self.duplicate_names();
+        let mut expanded = HashSet::new();
+        let mut output = String::new();
+        for (index, package) in workspace.into_iter().enumerate() {
+            if index > 0 {
+                output.push('\n');
+            }
+            self.render_tree(package, None, "", &duplicates, &mut expanded, &mut output);
+        }
+        output
+    }
+
+    fn render_tree(
+        &self,
+        package: &'a Package,
+        alias: Option<&CrateName>,
+        prefix: &str,
+        duplicates: &BTreeSet<&CrateName>,
+        expanded: &mut HashSet<&'a Path>,
+        output: &mut String,
+    ) {
+        output.push_str(&self.label(package, alias));
+        if duplicates.contains(&package.name) {
+            output.push_str(" [duplicate]");
+        }
+        let first_occurrence = expanded.insert(&package.root_dir);
+        if !first_occurrence && !package.dependencies.is_empty() {
+            output.push_str(" (*)");
+        }
+        output.push('\n');
+        if !first_occurrence {
+            return;
+        }
+
+        let dependency_count = package.dependencies.len();
+        for (index, (alias, dependency)) in package.dependencies.iter().enumerate() {
+            let (branch, indent) = tree_branch(index + 1 == dependency_count);
+            output.push_str(prefix);
+            output.push_str(branch);
+            self.render_tree(
+                dependency.package(),
+                Some(alias),
+                &format!("{prefix}{indent}"),
+                duplicates,
+                expanded,
+                output,
+            );
+        }
+    }
+
+    /// Renders each package which was pulled in from more than one source, with an inverted
+    /// tree below it showing how each workspace member comes to depend on it.
+    fn render_duplicates(&self) -> String {
+        let duplicates = self.duplicate_names();
+        let mut output = String::new();
+        for (index, node) in
+            self.nodes.values().filter(|node| duplicates.contains(&node.package.name)).enumerate()
+        {
+            if index > 0 {
+                output.push('\n');
+            }
+            self.render_dependents(node, "", &mut output);
+        }
+        output
+    }
+
+    fn render_dependents(&self, node: &Node<'a>, prefix: &str, output: &mut String) {
+        output.push_str(&self.label(node.package, None));
+        output.push('\n');
+
+        let dependent_count = node.dependents.len();
+        for (index, dependent) in node.dependents.iter().enumerate() {
+            let (branch, indent) = tree_branch(index + 1 == dependent_count);
+            output.push_str(prefix);
+            output.push_str(branch);
+            self.render_dependents(&self.nodes[dependent], &format!("{prefix}{indent}"), output);
+        }
+    }
+}
+
+/// Returns the prefix for a node of a tree, and the indentation for the nodes below it.
+fn tree_branch(is_last: bool) -> (&'static str, &'static str) {
+    if is_last {
+        ("└── ", "    ")
+    } else {
+        ("├── ", "│   ")
+    }
+}
+
+fn dependency_source(dependency: &Dependency) -> String {
+    match dependency {
+        Dependency::Local { package } => package.root_dir.display().to_string(),
+        Dependency::Remote { source, .. } => source.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, str::FromStr};
+
+    use nargo::{
+        package::{Dependency, Package, PackageType},
+        workspace::Workspace,
+    };
+    use noirc_frontend::graph::CrateName;
+
+    use super::DependencyGraph;
+
+    fn package(
+        name: &str,
+        version: &str,
+        root_dir: &str,
+        dependencies: Vec<Dependency>,
+    ) -> Package {
+        Package {
+            version: Some(version.to_string()),
+            compiler_required_version: None,
+            root_dir: PathBuf::from(root_dir),
+            package_type: PackageType::Library,
+            entry_path: PathBuf::from(root_dir).join("src").join("lib.nr"),
+            name: CrateName::from_str(name).unwrap(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|dependency| (dependency.package_name().clone(), dependency))
+                .collect(),
+            test_config: Default::default(),
+        }
+    }
+
+    fn git_dependency(name: &str, tag: &str) -> Dependency {
+        Dependency::Remote {
+            package: package(name, &tag[1..], &format!("/nargo/{name}{tag}"), Vec::new()),
+            source: format!("https://github.com/noir-lang/{name} at tag `{tag}`"),
+        }
+    }
+
+    fn workspace() -> Workspace {
+        let helper =
+            package("helper", "0.1.0", "/work/helper", vec![git_dependency("ec", "v0.2.0")]);
+        let main = package(
+            "main",
+            "1.0.0",
+            "/work/main",
+            vec![git_dependency("ec", "v0.1.0"), Dependency::Local { package: helper }],
+        );
+        Workspace {
+            root_dir: PathBuf::from("/work"),
+            members: vec![main],
+            selected_package_index: Some(0),
+            is_assumed: false,
+        }
+    }
+
+    #[test]
+    fn renders_dependency_tree() {
+        let workspace = workspace();
+        let graph = DependencyGraph::new(&workspace);
+
+        let expected = "\
+main v1.0.0 (/work/main)
+├── ec v0.1.0 (https://github.com/noir-lang/ec at tag `v0.1.0`) [duplicate]
+└── helper v0.1.0 (/work/helper)
+    └── ec v0.2.0 (https://github.com/noir-lang/ec at tag `v0.2.0`) [duplicate]
+";
+        assert_eq!(graph.render_trees(&workspace), expected);
+    }
+
+    #[test]
+    fn renders_duplicates_with_their_dependents() {
+        let workspace = workspace();
+        let graph = DependencyGraph::new(&workspace);
+
+        let expected = "\
+ec v0.1.0 (https://github.com/noir-lang/ec at tag `v0.1.0`)
+└── main v1.0.0 (/work/main)
+
+ec v0.2.0 (https://github.com/noir-lang/ec at tag `v0.2.0`)
+└── helper v0.1.0 (/work/helper)
+    └── main v1.0.0 (/work/main)
+";
+        assert_eq!(graph.render_duplicates(), expected);
+    }
+}
@@ -0,0 +1,35 @@
+use clap::Args;
+use nargo_toml::{get_package_manifest, update_lockfile};
+
+use crate::errors::CliError;
+
+use super::NargoConfig;
+
+/// Update the commits pinned in Nargo.lock to the ones the workspace's git dependencies currently point to
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UpdateCommand;
+
+pub(crate) fn run(_args: UpdateCommand, config: NargoConfig) -> Result<(), CliError> {
+    if config.offline {
+        return Err(CliError::Generic(
+            "Cannot update git dependencies while offline, they must be fetched again".to_string(),
+        ));
+    }
+    let toml_path = get_package_manifest(&config.program_dir)?;
+
+    let updates = update_lockfile(&toml_path)?;
+    if updates.is_empty() {
+        println!("All git dependencies are up to date");
+    }
+    for update in updates {
+        match update.previous_commit {
+            Some(previous_commit) => println!(
+                "Updated {} at {}: {previous_commit} -> {}",
+                update.url, update.reference, update.commit
+            ),
+            None => println!("Locked {} at {} to {}", update.url, update.reference, update.commit),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use clap::Args;
+use nargo_toml::{get_package_manifest, vendor_dependencies};
+
+use crate::errors::CliError;
+
+use super::NargoConfig;
+
+/// Copy the workspace's git dependencies into a `vendor` directory so it can be built without network access
+#[derive(Debug, Clone, Args)]
+pub(crate) struct VendorCommand;
+
+pub(crate) fn run(_args: VendorCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+
+    let vendored = vendor_dependencies(&toml_path, config.offline)?;
+    if vendored.is_empty() {
+        println!("The workspace has no git dependencies to vendor");
+    }
+    for dependency in vendored {
+        println!(
+            "Vendored {} at {} into {}",
+            dependency.url,
+            dependency.reference,
+            dependency.location.display()
+        );
+    }
+
+    Ok(())
+}
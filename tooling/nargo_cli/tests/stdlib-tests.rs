@@ -7,7 +7,7 @@ use noirc_driver::{check_crate, compile_no_check, file_manager_with_stdlib, Comp
 use noirc_frontend::hir::FunctionNameMatch;
 
 use nargo::{
-    ops::{report_errors, run_test, TestStatus},
+    ops::{report_errors, run_test, ExecutionLimits, TestStatus},
     package::{Package, PackageType},
     parse_all, prepare_package,
 };
@@ -28,6 +28,7 @@ fn run_stdlib_tests() {
         entry_path: PathBuf::from("main.nr"),
         name: "stdlib".parse().unwrap(),
         dependencies: BTreeMap::new(),
+        test_config: Default::default(),
     };
 
     let (mut context, dummy_crate_id) =
@@ -62,6 +63,8 @@ fn run_stdlib_tests() {
                     false,
                     None,
                     &CompileOptions::default(),
+                    ExecutionLimits::default(),
+                    None,
                 )
             } else {
                 use noir_fuzzer::FuzzedExecutor;
@@ -78,9 +81,15 @@ fn run_stdlib_tests() {
                     Ok(compiled_program) => {
                         let runner = TestRunner::default();
 
-                        let fuzzer = FuzzedExecutor::new(compiled_program.into(), runner);
+                        let fuzzer = FuzzedExecutor::new(
+                            compiled_program.into(),
+                            runner,
+                            StubbedBlackBoxSolver,
+                        );
 
-                        let result = fuzzer.fuzz();
+                        let result = fuzzer.fuzz(|execution| {
+                            execution.map(|_| ()).map_err(|err| err.to_string())
+                        });
                         if result.success {
                             TestStatus::Pass
                         } else {
@@ -147,6 +156,12 @@ fn display_test_report(
                     compile_options.silence_warnings,
                 );
             }
+            TestStatus::LimitExceeded(limit) => {
+                writer
+                    .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
+                    .expect("Failed to set color");
+                writeln!(writer, "FAIL\n{limit}\n").expect("Failed to write to stderr");
+            }
         }
         writer.reset().expect("Failed to reset writer");
     }
@@ -161,6 +161,7 @@ pub(crate) fn rewrite(
 
             visitor.format_if(*if_expr)
         }
+        ExpressionKind::Match(match_expr) => visitor.format_match(*match_expr, span),
         ExpressionKind::Variable(path, generics) => {
             let path_string = visitor.slice(path.span);
 
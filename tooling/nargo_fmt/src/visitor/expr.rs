@@ -1,6 +1,7 @@
 use noirc_frontend::ast::Expression;
 use noirc_frontend::ast::{
-    BlockExpression, ConstructorExpression, ExpressionKind, IfExpression, Statement, StatementKind,
+    BlockExpression, ConstructorExpression, ExpressionKind, IfExpression, MatchExpression,
+    Statement, StatementKind,
 };
 use noirc_frontend::{hir::resolution::errors::Span, lexer::Lexer, token::Token};
 
@@ -8,7 +9,7 @@ use super::{ExpressionType, FmtVisitor, Shape};
 use crate::{
     items::{HasItem, Item, Items},
     rewrite,
-    utils::{first_line_width, FindToken},
+    utils::{changed_comment_content, first_line_width, FindToken},
     Config,
 };
 
@@ -42,6 +43,35 @@ impl FmtVisitor<'_> {
         result
     }
 
+    pub(crate) fn format_match(&self, match_expr: MatchExpression, span: Span) -> String {
+        let expression = rewrite::sub_expr(self, self.shape(), match_expr.expression);
+
+        let mut visitor = self.fork();
+        visitor.indent.block_indent(visitor.config);
+        let arm_indent = visitor.shape().indent.to_string_with_newline();
+
+        let mut result = format!("match {expression} {{");
+        for (pattern, branch) in match_expr.rules {
+            let is_block = matches!(branch.kind, ExpressionKind::Block(_));
+            let branch =
+                rewrite::expr(&visitor, branch, ExpressionType::Statement, visitor.shape());
+            let comma = if is_block { "" } else { "," };
+
+            result.push_str(&arm_indent);
+            result.push_str(&format!("{} => {branch}{comma}", self.slice(pattern.span())));
+        }
+        result.push_str(&self.shape().indent.to_string_with_newline());
+        result.push('}');
+
+        // Comments between the arms have nowhere to go, so keep such a match as written
+        let original = self.slice(span);
+        if changed_comment_content(original, &result) {
+            original.to_string()
+        } else {
+            result
+        }
+    }
+
     pub(crate) fn format_if_single_line(&self, if_expr: IfExpression) -> Option<String> {
         let condition_str = rewrite::sub_expr(self, self.shape(), if_expr.condition);
         let consequence_str =
@@ -217,6 +217,7 @@ impl super::FmtVisitor<'_> {
                     self.last_position = span.end();
                 }
                 ItemKind::Struct(_)
+                | ItemKind::Enum(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::TypeAlias(_)
@@ -4,6 +4,8 @@ use nargo::package::PackageType;
 use noirc_frontend::graph::CrateName;
 use thiserror::Error;
 
+use crate::git::GitReference;
+
 /// Errors covering situations where a package is either missing, malformed or does not pass semver
 /// validation checks.
 #[derive(Debug, Error)]
@@ -72,6 +74,33 @@ pub enum ManifestError {
 
     #[error("Cyclic package dependency found when processing {cycle}")]
     CyclicDependency { cycle: String },
+
+    #[error("Nargo.lock is badly formed, could not parse {0}. Run `nargo update` to regenerate it.\n\n {1}")]
+    MalformedLockfile(PathBuf, toml::de::Error),
+
+    #[error("Could not write {0}: {1}")]
+    WriteLockfileFailed(PathBuf, String),
+
+    #[error("Dependency {url} at {reference} resolved to commit {found} but Nargo.lock pins it to {expected}. If it was moved upstream on purpose, run `nargo update` to accept the new commit")]
+    LockedCommitMismatch { url: String, reference: GitReference, expected: String, found: String },
+
+    #[error("Contents of dependency {url} at {reference} in {} do not match the checksum in Nargo.lock. Run `nargo update` to fetch it again", location.display())]
+    LockedChecksumMismatch { url: String, reference: GitReference, location: PathBuf },
+
+    #[error("Git dependency {url} in {toml} must specify exactly one of `tag`, `branch` or `rev`")]
+    InvalidGitReference { toml: PathBuf, url: String },
+
+    #[error("Cannot fetch dependency {url} at {reference} while offline. Run `nargo vendor`, or build once without `--offline`, while connected to the network to make it available")]
+    OfflineDependencyUnavailable { url: String, reference: GitReference },
+
+    #[error("Dependency {url} at {reference} is vendored in {} but is not pinned in Nargo.lock. Run `nargo vendor` to vendor it again", location.display())]
+    UnlockedVendoredDependency { url: String, reference: GitReference, location: PathBuf },
+
+    #[error("Contents of vendored dependency {url} at {reference} in {} do not match the checksum in Nargo.lock. Run `nargo vendor` to vendor it again", location.display())]
+    VendoredChecksumMismatch { url: String, reference: GitReference, location: PathBuf },
+
+    #[error("Could not vendor dependency into {0}: {1}")]
+    VendorFailed(PathBuf, String),
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -87,4 +116,31 @@ pub enum SemverError {
     CouldNotParseRequiredVersion { package_name: String, error: String },
     #[error("Could not parse the package version for package {package_name} in Nargo.toml. Error: {error}")]
     CouldNotParsePackageVersion { package_name: String, error: String },
+    #[error("Could not parse the version required of dependency {dependency_name} by package {package_name}. Error: {error}")]
+    CouldNotParseDependencyVersion {
+        package_name: CrateName,
+        dependency_name: CrateName,
+        error: String,
+    },
+    #[error("Package {package_name} requires version {required} of {dependency_name} but {dependency_name} does not specify a version in its Nargo.toml")]
+    MissingDependencyVersion {
+        package_name: CrateName,
+        dependency_name: CrateName,
+        required: String,
+    },
+    #[error("Package {package_name} requires version {required} of {dependency_name} but version {found} was found")]
+    IncompatibleDependencyVersion {
+        package_name: CrateName,
+        dependency_name: CrateName,
+        required: String,
+        found: String,
+    },
+    #[error("Incompatible versions of {dependency_name} are required: package {package_name} requires {required} while package {other_package_name} requires {other_required}")]
+    ConflictingDependencyVersions {
+        dependency_name: CrateName,
+        package_name: CrateName,
+        required: String,
+        other_package_name: CrateName,
+        other_required: String,
+    },
 }
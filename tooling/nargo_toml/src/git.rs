@@ -1,11 +1,41 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Which revision of a git dependency to check out
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitReference {
+    Tag(String),
+    /// A branch, which stays at the commit pinned in `Nargo.lock` until `nargo update` is run
+    Branch(String),
+    /// A commit hash, which may be abbreviated
+    Rev(String),
+}
+
+impl Display for GitReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitReference::Tag(tag) => write!(f, "tag `{tag}`"),
+            GitReference::Branch(branch) => write!(f, "branch `{branch}`"),
+            GitReference::Rev(rev) => write!(f, "rev `{rev}`"),
+        }
+    }
+}
 
 /// Creates a unique folder name for a GitHub repo
-/// by using its URL and tag
-fn resolve_folder_name(base: &url::Url, tag: &str) -> String {
+/// by using its URL and the reference being checked out
+fn resolve_folder_name(base: &url::Url, reference: &GitReference) -> String {
     let mut folder_name = base.domain().unwrap().to_owned();
     folder_name.push_str(base.path());
-    folder_name.push_str(tag);
+    match reference {
+        GitReference::Tag(tag) => folder_name.push_str(tag),
+        GitReference::Branch(branch) => folder_name.push_str(&format!("@branch/{branch}")),
+        GitReference::Rev(rev) => folder_name.push_str(&format!("@rev/{rev}")),
+    }
     folder_name
 }
 
@@ -13,19 +43,39 @@ fn nargo_crates() -> PathBuf {
     dirs::home_dir().unwrap().join("nargo")
 }
 
-fn git_dep_location(base: &url::Url, tag: &str) -> PathBuf {
-    let folder_name = resolve_folder_name(base, tag);
+fn git_dep_location(base: &url::Url, reference: &GitReference) -> PathBuf {
+    let folder_name = resolve_folder_name(base, reference);
 
     nargo_crates().join(folder_name)
 }
 
+/// Returns the directory a `nargo vendor` copy of the GitHub repo at `reference` lives in under `vendor_dir`.
+pub(crate) fn vendored_git_dep_location(
+    vendor_dir: &Path,
+    url: &str,
+    reference: &GitReference,
+) -> Result<PathBuf, String> {
+    let base = url::Url::parse(url).map_err(|err| err.to_string())?;
+    Ok(vendor_dir.join(resolve_folder_name(&base, reference)))
+}
+
+/// Returns the cached checkout of the GitHub repo at `reference`, if it has been cloned before.
+pub(crate) fn cached_git_repo(
+    url: &str,
+    reference: &GitReference,
+) -> Result<Option<PathBuf>, String> {
+    let base = url::Url::parse(url).map_err(|err| err.to_string())?;
+    let loc = git_dep_location(&base, reference);
+    Ok(loc.exists().then_some(loc))
+}
+
 /// XXX: I'd prefer to use a GitHub library however, there
 /// does not seem to be an easy way to download a repo at a specific
 /// tag
 /// github-rs looks promising, however it seems to require an API token
 ///
 /// One advantage of using "git clone" is that there is effectively no rate limit
-pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
+pub(crate) fn clone_git_repo(url: &str, reference: &GitReference) -> Result<PathBuf, String> {
     use std::process::Command;
 
     let base = match url::Url::parse(url) {
@@ -33,23 +83,149 @@ pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
         Err(err) => return Err(err.to_string()),
     };
 
-    let loc = git_dep_location(&base, tag);
+    let loc = git_dep_location(&base, reference);
     if loc.exists() {
         return Ok(loc);
     }
 
-    Command::new("git")
+    match reference {
+        GitReference::Tag(name) | GitReference::Branch(name) => {
+            Command::new("git")
+                .arg("-c")
+                .arg("advice.detachedHead=false")
+                .arg("clone")
+                .arg("--depth")
+                .arg("1")
+                .arg("--branch")
+                .arg(name)
+                .arg(base.as_str())
+                .arg(&loc)
+                .status()
+                .expect("git clone command failed to start");
+        }
+        GitReference::Rev(rev) => {
+            // Arbitrary commits can't be shallow cloned so we need the full history to find `rev`.
+            Command::new("git")
+                .arg("clone")
+                .arg(base.as_str())
+                .arg(&loc)
+                .status()
+                .expect("git clone command failed to start");
+
+            let checked_out = Command::new("git")
+                .arg("-c")
+                .arg("advice.detachedHead=false")
+                .arg("-C")
+                .arg(&loc)
+                .arg("checkout")
+                .arg(rev)
+                .status()
+                .expect("git checkout command failed to start");
+            if !checked_out.success() {
+                // Don't leave the default branch behind where later builds would find it.
+                let _ = std::fs::remove_dir_all(&loc);
+                return Err(format!("Could not find revision `{rev}` in {url}"));
+            }
+        }
+    }
+
+    Ok(loc)
+}
+
+/// Discards any cached checkout of the GitHub repo at `reference` and clones it again,
+/// picking up its current commit even if a tag or branch has been moved upstream.
+pub(crate) fn update_git_repo(url: &str, reference: &GitReference) -> Result<PathBuf, String> {
+    let base = url::Url::parse(url).map_err(|err| err.to_string())?;
+
+    let loc = git_dep_location(&base, reference);
+    if loc.exists() {
+        std::fs::remove_dir_all(&loc)
+            .map_err(|err| format!("Could not remove {}: {err}", loc.display()))?;
+    }
+
+    clone_git_repo(url, reference)
+}
+
+/// Checks out `commit` in the clone of `url` at `loc`, fetching it first if it isn't available
+/// locally and `fetch` is set.
+pub(crate) fn checkout_git_commit(
+    loc: &Path,
+    url: &str,
+    commit: &str,
+    fetch: bool,
+) -> Result<(), String> {
+    use std::process::Command;
+
+    if git_commit_hash(loc)? == commit {
+        return Ok(());
+    }
+
+    let available = Command::new("git")
+        .arg("-C")
+        .arg(loc)
+        .arg("cat-file")
+        .arg("-e")
+        .arg(format!("{commit}^{{commit}}"))
+        .output()
+        .map_err(|err| format!("git cat-file command failed to start: {err}"))?
+        .status
+        .success();
+    if !available {
+        if !fetch {
+            return Err(format!("Commit `{commit}` of {url} is not available locally"));
+        }
+
+        // Shallow clones only contain the tag or branch's current commit, so fetch the pinned one.
+        let fetched = Command::new("git")
+            .arg("-C")
+            .arg(loc)
+            .arg("fetch")
+            .arg("--depth")
+            .arg("1")
+            .arg("origin")
+            .arg(commit)
+            .status()
+            .map_err(|err| format!("git fetch command failed to start: {err}"))?;
+        if !fetched.success() {
+            return Err(format!("Could not fetch commit `{commit}` from {url}"));
+        }
+    }
+
+    let checked_out = Command::new("git")
         .arg("-c")
         .arg("advice.detachedHead=false")
-        .arg("clone")
-        .arg("--depth")
-        .arg("1")
-        .arg("--branch")
-        .arg(tag)
-        .arg(base.as_str())
-        .arg(&loc)
+        .arg("-C")
+        .arg(loc)
+        .arg("checkout")
+        .arg(commit)
         .status()
-        .expect("git clone command failed to start");
+        .map_err(|err| format!("git checkout command failed to start: {err}"))?;
+    if !checked_out.success() {
+        return Err(format!("Could not check out commit `{commit}` of {url}"));
+    }
 
-    Ok(loc)
+    Ok(())
+}
+
+/// Returns the hash of the commit checked out at `loc`
+pub(crate) fn git_commit_hash(loc: &Path) -> Result<String, String> {
+    use std::process::Command;
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(loc)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .map_err(|err| format!("git rev-parse command failed to start: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Could not determine the commit checked out in {}: {}",
+            loc.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
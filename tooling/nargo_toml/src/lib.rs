@@ -6,12 +6,14 @@
 use std::{
     collections::BTreeMap,
     path::{Component, Path, PathBuf},
+    time::Duration,
 };
 
 use errors::SemverError;
 use fm::{NormalizePath, FILE_EXTENSION};
 use nargo::{
-    package::{Dependency, Package, PackageType},
+    constants::VENDOR_DIR,
+    package::{Dependency, Package, PackageType, TestConfig},
     workspace::Workspace,
 };
 use noirc_frontend::graph::CrateName;
@@ -19,10 +21,16 @@ use serde::Deserialize;
 
 mod errors;
 mod git;
+mod lockfile;
 mod semver;
+mod vendor;
 
 pub use errors::ManifestError;
-use git::clone_git_repo;
+pub use git::GitReference;
+pub use lockfile::GitDependencyUpdate;
+use lockfile::{DependencyLock, LockMode};
+use semver::VersionRequirement;
+pub use vendor::VendoredDependency;
 
 /// Searches for a `Nargo.toml` file in the current directory and all parent directories.
 /// For example, if the current directory is `/workspace/package/src`, then this function
@@ -117,6 +125,8 @@ struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
     dependencies: BTreeMap<String, DependencyConfig>,
+    #[serde(default)]
+    test: TestConfigMetadata,
 }
 
 impl PackageConfig {
@@ -124,6 +134,7 @@ impl PackageConfig {
         &self,
         root_dir: &Path,
         processed: &mut Vec<String>,
+        resolver: &mut DependencyResolver,
     ) -> Result<Package, ManifestError> {
         let name: CrateName = if let Some(name) = &self.package.name {
             name.parse().map_err(|_| ManifestError::InvalidPackageName {
@@ -134,13 +145,24 @@ impl PackageConfig {
             return Err(ManifestError::MissingNameField { toml: root_dir.join("Nargo.toml") });
         };
 
+        let package_name = &name;
         let mut dependencies: BTreeMap<CrateName, Dependency> = BTreeMap::new();
         for (name, dep_config) in self.dependencies.iter() {
             let name = name.parse().map_err(|_| ManifestError::InvalidDependencyName {
                 toml: root_dir.join("Nargo.toml"),
                 name: name.into(),
             })?;
-            let resolved_dep = dep_config.resolve_to_dependency(root_dir, processed)?;
+            let resolved_dep = dep_config.resolve_to_dependency(root_dir, processed, resolver)?;
+
+            if let Some(required) = dep_config.version() {
+                let package = resolved_dep.package();
+                resolver.version_requirements.push(VersionRequirement {
+                    package_name: package_name.clone(),
+                    dependency_name: package.name.clone(),
+                    required: required.clone(),
+                    found: package.version.clone(),
+                });
+            }
 
             dependencies.insert(name, resolved_dep);
         }
@@ -207,11 +229,17 @@ impl PackageConfig {
             package_type,
             name,
             dependencies,
+            test_config: TestConfig {
+                fuzz_runs: self.test.fuzz_runs,
+                timeout: self.test.timeout.map(Duration::from_secs),
+                max_steps: self.test.max_steps,
+            },
         })
     }
 }
 
 /// Contains all the information about a package, as loaded from a `Nargo.toml`.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
 enum Config {
@@ -277,24 +305,65 @@ struct PackageMetadata {
     license: Option<String>,
 }
 
+/// The `[test]` table of a `Nargo.toml`
+#[derive(Default, Debug, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+struct TestConfigMetadata {
+    /// How many inputs to try when fuzzing a `#[test]` function which takes arguments
+    fuzz_runs: Option<u32>,
+    /// The number of seconds a test may run for
+    timeout: Option<u64>,
+    /// The number of ACIR opcodes and Brillig instructions a test may execute
+    max_steps: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
 /// Enum representing the different types of ways to
 /// supply a source for the dependency
 enum DependencyConfig {
-    Github { git: String, tag: String, directory: Option<String> },
-    Path { path: String },
+    Github {
+        git: String,
+        tag: Option<String>,
+        branch: Option<String>,
+        rev: Option<String>,
+        directory: Option<String>,
+        version: Option<String>,
+    },
+    Path {
+        path: String,
+        version: Option<String>,
+    },
 }
 
 impl DependencyConfig {
+    /// The semver requirement the dependency's version must satisfy, if any
+    fn version(&self) -> Option<&String> {
+        match self {
+            Self::Github { version, .. } | Self::Path { version, .. } => version.as_ref(),
+        }
+    }
+
     fn resolve_to_dependency(
         &self,
         pkg_root: &Path,
         processed: &mut Vec<String>,
+        resolver: &mut DependencyResolver,
     ) -> Result<Dependency, ManifestError> {
         let dep = match self {
-            Self::Github { git, tag, directory } => {
-                let dir_path = clone_git_repo(git, tag).map_err(ManifestError::GitError)?;
+            Self::Github { git, tag, branch, rev, directory, .. } => {
+                let reference = match (tag, branch, rev) {
+                    (Some(tag), None, None) => GitReference::Tag(tag.clone()),
+                    (None, Some(branch), None) => GitReference::Branch(branch.clone()),
+                    (None, None, Some(rev)) => GitReference::Rev(rev.clone()),
+                    _ => {
+                        return Err(ManifestError::InvalidGitReference {
+                            toml: pkg_root.join("Nargo.toml"),
+                            url: git.clone(),
+                        })
+                    }
+                };
+                let dir_path = resolver.lock.resolve_git_dependency(git, &reference)?;
                 let project_path = if let Some(directory) = directory {
                     let internal_path = dir_path.join(directory).normalize();
                     if !internal_path.starts_with(&dir_path) {
@@ -308,13 +377,17 @@ impl DependencyConfig {
                     dir_path
                 };
                 let toml_path = project_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
-                Dependency::Remote { package }
+                let package = resolve_package_from_toml(&toml_path, processed, resolver)?;
+                let source = match directory {
+                    Some(directory) => format!("{git} at {reference} in `{directory}`"),
+                    None => format!("{git} at {reference}"),
+                };
+                Dependency::Remote { package, source }
             }
-            Self::Path { path } => {
+            Self::Path { path, .. } => {
                 let dir_path = pkg_root.join(path);
                 let toml_path = dir_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
+                let package = resolve_package_from_toml(&toml_path, processed, resolver)?;
                 Dependency::Local { package }
             }
         };
@@ -329,14 +402,39 @@ impl DependencyConfig {
     }
 }
 
+/// State which is shared while resolving every package reachable from a workspace.
+struct DependencyResolver {
+    lock: DependencyLock,
+    /// Version requirements placed on dependencies, these are checked once the whole graph is known
+    version_requirements: Vec<VersionRequirement>,
+}
+
+impl DependencyResolver {
+    fn new(root_dir: &Path, mode: LockMode, offline: bool) -> Result<Self, ManifestError> {
+        Ok(Self {
+            lock: DependencyLock::load(root_dir, mode, offline)?,
+            version_requirements: Vec::new(),
+        })
+    }
+
+    /// Checks the version requirements between packages and writes out the workspace's lockfile.
+    fn finish(self) -> Result<Vec<GitDependencyUpdate>, ManifestError> {
+        semver::semver_check_dependency_versions(&self.version_requirements)
+            .map_err(ManifestError::SemverError)?;
+        self.lock.write()
+    }
+}
+
 fn toml_to_workspace(
     nargo_toml: NargoToml,
     package_selection: PackageSelection,
+    resolver: &mut DependencyResolver,
 ) -> Result<Workspace, ManifestError> {
     let mut resolved = Vec::new();
     let workspace = match nargo_toml.config {
         Config::Package { package_config } => {
-            let member = package_config.resolve_to_package(&nargo_toml.root_dir, &mut resolved)?;
+            let member =
+                package_config.resolve_to_package(&nargo_toml.root_dir, &mut resolved, resolver)?;
             match &package_selection {
                 PackageSelection::Selected(selected_name) if selected_name != &member.name => {
                     return Err(ManifestError::MissingSelectedPackage(member.name))
@@ -355,7 +453,8 @@ fn toml_to_workspace(
             for (index, member_path) in workspace_config.members.into_iter().enumerate() {
                 let package_root_dir = nargo_toml.root_dir.join(&member_path);
                 let package_toml_path = package_root_dir.join("Nargo.toml");
-                let member = resolve_package_from_toml(&package_toml_path, &mut resolved)?;
+                let member =
+                    resolve_package_from_toml(&package_toml_path, &mut resolved, resolver)?;
 
                 match &package_selection {
                     PackageSelection::Selected(selected_name) => {
@@ -420,6 +519,7 @@ fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
 fn resolve_package_from_toml(
     toml_path: &Path,
     processed: &mut Vec<String>,
+    resolver: &mut DependencyResolver,
 ) -> Result<Package, ManifestError> {
     // Checks for cyclic dependencies
     let str_path = toml_path.to_str().expect("ICE - path is empty");
@@ -444,7 +544,7 @@ fn resolve_package_from_toml(
 
     let result = match nargo_toml.config {
         Config::Package { package_config } => {
-            package_config.resolve_to_package(&nargo_toml.root_dir, processed)
+            package_config.resolve_to_package(&nargo_toml.root_dir, processed, resolver)
         }
         Config::Workspace { .. } => {
             Err(ManifestError::UnexpectedWorkspace(toml_path.to_path_buf()))
@@ -464,19 +564,54 @@ pub enum PackageSelection {
 }
 
 /// Resolves a Nargo.toml file into a `Workspace` struct as defined by our `nargo` core.
+///
+/// Git dependencies are taken from the workspace's vendor directory when they've been vendored.
+/// When `offline` is set, any other git dependencies must already be cached as they won't be cloned.
 pub fn resolve_workspace_from_toml(
     toml_path: &Path,
     package_selection: PackageSelection,
     current_compiler_version: Option<String>,
+    offline: bool,
 ) -> Result<Workspace, ManifestError> {
     let nargo_toml = read_toml(toml_path)?;
-    let workspace = toml_to_workspace(nargo_toml, package_selection)?;
+    let mut resolver = DependencyResolver::new(&nargo_toml.root_dir, LockMode::Verify, offline)?;
+    let workspace = toml_to_workspace(nargo_toml, package_selection, &mut resolver)?;
+    resolver.finish()?;
     if let Some(current_compiler_version) = current_compiler_version {
         semver::semver_check_workspace(&workspace, current_compiler_version)?;
     }
     Ok(workspace)
 }
 
+/// Fetches the latest commit of every git dependency of the workspace at `toml_path`,
+/// discarding any cached checkouts, and pins them in the workspace's `Nargo.lock`.
+///
+/// Returns the dependencies whose pinned commit changed.
+pub fn update_lockfile(toml_path: &Path) -> Result<Vec<GitDependencyUpdate>, ManifestError> {
+    let nargo_toml = read_toml(toml_path)?;
+    let mut resolver = DependencyResolver::new(&nargo_toml.root_dir, LockMode::Update, false)?;
+    toml_to_workspace(nargo_toml, PackageSelection::All, &mut resolver)?;
+    resolver.finish()
+}
+
+/// Copies every git dependency of the workspace at `toml_path` into its vendor directory,
+/// so that the workspace can be resolved without access to the network or the git cache.
+///
+/// Dependencies are fetched afresh rather than taken from any existing vendored copies,
+/// and must match the workspace's `Nargo.lock`, which is written if needed.
+pub fn vendor_dependencies(
+    toml_path: &Path,
+    offline: bool,
+) -> Result<Vec<VendoredDependency>, ManifestError> {
+    let nargo_toml = read_toml(toml_path)?;
+    let vendor_dir = nargo_toml.root_dir.join(VENDOR_DIR);
+    let mut resolver = DependencyResolver::new(&nargo_toml.root_dir, LockMode::Vendor, offline)?;
+    toml_to_workspace(nargo_toml, PackageSelection::All, &mut resolver)?;
+    let vendored = vendor::vendor_git_dependencies(&resolver.lock, &vendor_dir)?;
+    resolver.finish()?;
+    Ok(vendored)
+}
+
 #[test]
 fn parse_standard_toml() {
     let src = r#"
@@ -531,3 +666,81 @@ fn parse_workspace_default_member_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_toml_with_test_config() {
+    let src = r#"
+        [package]
+        name = "test"
+        authors = ["kev", "foo"]
+        compiler_version = "*"
+
+        [test]
+        fuzz-runs = 1000
+        timeout = 30
+        max-steps = 1000000
+    "#;
+
+    let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+        panic!("Expected a package config");
+    };
+    assert_eq!(package_config.test.fuzz_runs, Some(1000));
+    assert_eq!(package_config.test.timeout, Some(30));
+    assert_eq!(package_config.test.max_steps, Some(1_000_000));
+}
+
+#[test]
+fn parse_git_dependency_references() {
+    let src = r#"
+        [package]
+        name = "test"
+        authors = ["kev", "foo"]
+        compiler_version = "*"
+
+        [dependencies]
+        by_tag = { git = "https://github.com/noir-lang/rand", tag = "v1.0.0" }
+        by_branch = { git = "https://github.com/noir-lang/rand", branch = "main", version = "^1.0" }
+        by_rev = { git = "https://github.com/noir-lang/rand", rev = "0123abcd" }
+        local = { path = "../local", version = "0.2.1" }
+    "#;
+
+    let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+        panic!("Expected a package config");
+    };
+    assert!(matches!(
+        &package_config.dependencies["by_branch"],
+        DependencyConfig::Github { branch: Some(branch), version: Some(version), .. }
+            if branch == "main" && version == "^1.0"
+    ));
+    assert!(matches!(
+        &package_config.dependencies["by_rev"],
+        DependencyConfig::Github { rev: Some(rev), tag: None, .. } if rev == "0123abcd"
+    ));
+    assert_eq!(package_config.dependencies["local"].version().map(String::as_str), Some("0.2.1"));
+}
+
+#[test]
+fn git_dependency_requires_a_single_reference() {
+    let src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+        authors = [""]
+
+        [dependencies]
+        rand = { git = "https://github.com/noir-lang/rand", tag = "v1.0.0", branch = "main" }
+    "#;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src").join("main.nr"), "fn main() {}").unwrap();
+    std::fs::write(dir.path().join("Nargo.toml"), src).unwrap();
+
+    let result = resolve_workspace_from_toml(
+        &dir.path().join("Nargo.toml"),
+        PackageSelection::DefaultOrAll,
+        None,
+        false,
+    );
+    assert!(matches!(result, Err(ManifestError::InvalidGitReference { .. })));
+}
@@ -0,0 +1,454 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use nargo::constants::{LOCK_FILE, VENDOR_DIR};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    git::{
+        cached_git_repo, checkout_git_commit, clone_git_repo, git_commit_hash, update_git_repo,
+        vendored_git_dep_location, GitReference,
+    },
+    ManifestError,
+};
+
+/// The current version of the `Nargo.lock` format
+const LOCKFILE_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str = "# This file is automatically generated by Nargo.\n# It is not intended for manual editing, run `nargo update` to refresh it.\n";
+
+/// Contents of a `Nargo.lock` file.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+struct Lockfile {
+    version: u32,
+    #[serde(default, rename = "git")]
+    git_dependencies: Vec<LockedGitDependency>,
+}
+
+/// A git dependency pinned to the commit it resolved to, along with a checksum of its contents.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+struct LockedGitDependency {
+    url: String,
+    #[serde(flatten)]
+    reference: GitReference,
+    commit: String,
+    checksum: String,
+}
+
+/// A git dependency whose pinned commit was changed by [`update_lockfile`][crate::update_lockfile].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitDependencyUpdate {
+    pub url: String,
+    pub reference: GitReference,
+    /// The commit which was previously pinned, `None` if the dependency wasn't in the lockfile
+    pub previous_commit: Option<String>,
+    pub commit: String,
+}
+
+/// Whether git dependencies are checked against the lockfile or re-fetched and re-pinned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LockMode {
+    Verify,
+    Update,
+    /// Like `Verify`, but ignores the workspace's vendor directory so that it can be rewritten
+    Vendor,
+}
+
+/// Resolves git dependencies while a workspace is being resolved, keeping track of
+/// the commit each one resolved to so that `Nargo.lock` can be checked and written.
+pub(crate) struct DependencyLock {
+    path: PathBuf,
+    mode: LockMode,
+    /// Whether git dependencies must be found without accessing the network
+    offline: bool,
+    /// The workspace's vendor directory, if it has one and it should be used
+    vendor_dir: Option<PathBuf>,
+    /// Dependencies pinned in the existing lockfile, keyed by url and reference
+    locked: BTreeMap<(String, GitReference), LockedGitDependency>,
+    /// Every git dependency which has been resolved so far, keyed by url and reference
+    resolved: BTreeMap<(String, GitReference), (PathBuf, LockedGitDependency)>,
+}
+
+impl DependencyLock {
+    /// Loads the `Nargo.lock` next to the workspace manifest in `root_dir`, if there is one.
+    pub(crate) fn load(
+        root_dir: &Path,
+        mode: LockMode,
+        offline: bool,
+    ) -> Result<Self, ManifestError> {
+        let path = root_dir.join(LOCK_FILE);
+        let vendor_dir = root_dir.join(VENDOR_DIR);
+        let vendor_dir = (mode == LockMode::Verify && vendor_dir.is_dir()).then_some(vendor_dir);
+
+        let locked = match fs::read_to_string(&path) {
+            Ok(contents) => {
+                let lockfile: Lockfile = toml::from_str(&contents)
+                    .map_err(|err| ManifestError::MalformedLockfile(path.clone(), err))?;
+                lockfile
+                    .git_dependencies
+                    .into_iter()
+                    .map(|dependency| {
+                        ((dependency.url.clone(), dependency.reference.clone()), dependency)
+                    })
+                    .collect()
+            }
+            Err(_) => BTreeMap::new(),
+        };
+
+        Ok(Self { path, mode, offline, vendor_dir, locked, resolved: BTreeMap::new() })
+    }
+
+    /// Fetches the git dependency at `url` and `reference`, returning the directory it's checked out in.
+    ///
+    /// When verifying, the commit pinned in the lockfile is checked out even if the tag or branch
+    /// has moved since, and its contents must match the pinned checksum. Dependencies which aren't
+    /// in the lockfile yet are pinned to whatever they resolve to.
+    ///
+    /// A copy of the dependency in the workspace's vendor directory is used in preference to fetching it.
+    pub(crate) fn resolve_git_dependency(
+        &mut self,
+        url: &str,
+        reference: &GitReference,
+    ) -> Result<PathBuf, ManifestError> {
+        let key = (url.to_string(), reference.clone());
+        if let Some((location, _)) = self.resolved.get(&key) {
+            return Ok(location.clone());
+        }
+
+        if let Some(vendor_dir) = &self.vendor_dir {
+            let location = vendored_git_dep_location(vendor_dir, url, reference)
+                .map_err(ManifestError::GitError)?;
+            if location.is_dir() {
+                return self.resolve_vendored_dependency(key, location);
+            }
+        }
+
+        let location = if self.offline {
+            cached_git_repo(url, reference).map_err(ManifestError::GitError)?.ok_or_else(|| {
+                ManifestError::OfflineDependencyUnavailable {
+                    url: url.to_string(),
+                    reference: reference.clone(),
+                }
+            })?
+        } else {
+            match self.mode {
+                LockMode::Verify | LockMode::Vendor => clone_git_repo(url, reference),
+                LockMode::Update => update_git_repo(url, reference),
+            }
+            .map_err(ManifestError::GitError)?
+        };
+
+        // Only `nargo update` moves a dependency off the commit pinned in the lockfile.
+        let locked = if self.mode == LockMode::Update { None } else { self.locked.get(&key) };
+        if let Some(locked) = locked {
+            checkout_git_commit(&location, url, &locked.commit, !self.offline)
+                .map_err(ManifestError::GitError)?;
+        }
+
+        let commit = git_commit_hash(&location).map_err(ManifestError::GitError)?;
+        let checksum = checksum_directory(&location).map_err(|err| {
+            ManifestError::GitError(format!("Could not read {}: {err}", location.display()))
+        })?;
+
+        if let Some(locked) = locked {
+            if locked.commit != commit {
+                return Err(ManifestError::LockedCommitMismatch {
+                    url: url.to_string(),
+                    reference: reference.clone(),
+                    expected: locked.commit.clone(),
+                    found: commit,
+                });
+            }
+            if locked.checksum != checksum {
+                return Err(ManifestError::LockedChecksumMismatch {
+                    url: url.to_string(),
+                    reference: reference.clone(),
+                    location,
+                });
+            }
+        }
+
+        let dependency = LockedGitDependency {
+            url: url.to_string(),
+            reference: reference.clone(),
+            commit,
+            checksum,
+        };
+        self.resolved.insert(key, (location.clone(), dependency));
+        Ok(location)
+    }
+
+    /// Vendored copies don't include git metadata, so they're trusted to be at the commit
+    /// pinned in the lockfile as long as their contents match its checksum.
+    fn resolve_vendored_dependency(
+        &mut self,
+        key: (String, GitReference),
+        location: PathBuf,
+    ) -> Result<PathBuf, ManifestError> {
+        let (url, reference) = key.clone();
+        let Some(locked) = self.locked.get(&key) else {
+            return Err(ManifestError::UnlockedVendoredDependency { url, reference, location });
+        };
+
+        let checksum = checksum_directory(&location).map_err(|err| {
+            ManifestError::GitError(format!("Could not read {}: {err}", location.display()))
+        })?;
+        if locked.checksum != checksum {
+            return Err(ManifestError::VendoredChecksumMismatch { url, reference, location });
+        }
+
+        self.resolved.insert(key, (location.clone(), locked.clone()));
+        Ok(location)
+    }
+
+    /// Every git dependency which has been resolved so far, along with the directory it resolved to.
+    pub(crate) fn resolved_git_dependencies(
+        &self,
+    ) -> impl Iterator<Item = (&str, &GitReference, &Path)> {
+        self.resolved
+            .iter()
+            .map(|((url, reference), (location, _))| (url.as_str(), reference, location.as_path()))
+    }
+
+    /// Writes out the lockfile if the resolved dependencies differ from those which were pinned.
+    ///
+    /// Returns the dependencies whose pinned commit changed.
+    pub(crate) fn write(self) -> Result<Vec<GitDependencyUpdate>, ManifestError> {
+        let lockfile = Lockfile {
+            version: LOCKFILE_VERSION,
+            git_dependencies: self.resolved.values().map(|(_, locked)| locked.clone()).collect(),
+        };
+
+        let updates = self
+            .resolved
+            .iter()
+            .filter_map(|(key, (_, dependency))| {
+                let previous_commit = self.locked.get(key).map(|locked| locked.commit.clone());
+                (previous_commit.as_ref() != Some(&dependency.commit)).then(|| {
+                    GitDependencyUpdate {
+                        url: dependency.url.clone(),
+                        reference: dependency.reference.clone(),
+                        previous_commit,
+                        commit: dependency.commit.clone(),
+                    }
+                })
+            })
+            .collect();
+
+        let unchanged = self.locked.len() == self.resolved.len()
+            && self
+                .resolved
+                .iter()
+                .all(|(key, (_, dependency))| self.locked.get(key) == Some(dependency));
+        // Workspaces without any git dependencies don't need a lockfile.
+        let unneeded = self.resolved.is_empty() && !self.path.exists();
+        if unchanged || unneeded {
+            return Ok(updates);
+        }
+
+        let contents = toml::to_string(&lockfile).expect("lockfile should always serialize");
+        fs::write(&self.path, format!("{LOCKFILE_HEADER}\n{contents}")).map_err(|err| {
+            ManifestError::WriteLockfileFailed(self.path.clone(), err.to_string())
+        })?;
+
+        Ok(updates)
+    }
+}
+
+/// Hashes every file inside `dir` (other than git's own metadata) along with its path relative to `dir`.
+fn checksum_directory(dir: &Path) -> std::io::Result<String> {
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if path.file_name().is_some_and(|name| name == ".git") {
+                    continue;
+                }
+                collect_files(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+
+    let mut files: Vec<_> = files
+        .into_iter()
+        .map(|file| {
+            let relative_path = file.strip_prefix(dir).unwrap_or(&file);
+            // Normalize separators so that checksums match across platforms.
+            let components: Vec<_> =
+                relative_path.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+            (components.join("/"), file)
+        })
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (relative_path, file) in files {
+        let contents = fs::read(&file)?;
+        hasher.update(relative_path.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use nargo::constants::{LOCK_FILE, VENDOR_DIR};
+
+    use crate::{
+        git::{vendored_git_dep_location, GitReference},
+        ManifestError,
+    };
+
+    use super::{
+        checksum_directory, DependencyLock, LockMode, LockedGitDependency, Lockfile,
+        LOCKFILE_VERSION,
+    };
+
+    fn locked_dependency(commit: &str) -> LockedGitDependency {
+        LockedGitDependency {
+            url: "https://github.com/noir-lang/example".to_string(),
+            reference: GitReference::Tag("v0.1.0".to_string()),
+            commit: commit.to_string(),
+            checksum: "ab".repeat(32),
+        }
+    }
+
+    #[test]
+    fn lockfile_round_trips() {
+        let lockfile = Lockfile {
+            version: LOCKFILE_VERSION,
+            git_dependencies: vec![locked_dependency("0123456789abcdef")],
+        };
+
+        let contents = toml::to_string(&lockfile).unwrap();
+        assert_eq!(toml::from_str::<Lockfile>(&contents).unwrap(), lockfile);
+    }
+
+    #[test]
+    fn checksum_depends_on_contents_and_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src").join("lib.nr"), "fn foo() {}").unwrap();
+        let original = checksum_directory(dir.path()).unwrap();
+
+        // Git metadata doesn't affect the checksum.
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git").join("HEAD"), "ref: refs/heads/main").unwrap();
+        assert_eq!(checksum_directory(dir.path()).unwrap(), original);
+
+        std::fs::write(dir.path().join("src").join("lib.nr"), "fn bar() {}").unwrap();
+        let modified = checksum_directory(dir.path()).unwrap();
+        assert_ne!(modified, original);
+
+        std::fs::rename(
+            dir.path().join("src").join("lib.nr"),
+            dir.path().join("src").join("foo.nr"),
+        )
+        .unwrap();
+        assert_ne!(checksum_directory(dir.path()).unwrap(), modified);
+    }
+
+    #[test]
+    fn lockfile_is_only_written_when_needed() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // A workspace without git dependencies doesn't get a lockfile.
+        let lock = DependencyLock::load(dir.path(), LockMode::Verify, false).unwrap();
+        assert!(lock.write().unwrap().is_empty());
+        assert!(!dir.path().join(LOCK_FILE).exists());
+
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Verify, false).unwrap();
+        let dependency = locked_dependency("0123456789abcdef");
+        let key = (dependency.url.clone(), dependency.reference.clone());
+        lock.resolved.insert(key.clone(), (PathBuf::new(), dependency.clone()));
+        let updates = lock.write().unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].previous_commit, None);
+
+        let lock = DependencyLock::load(dir.path(), LockMode::Verify, false).unwrap();
+        assert_eq!(lock.locked.get(&key), Some(&dependency));
+
+        // Re-pinning to a different commit reports the change.
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Update, false).unwrap();
+        lock.resolved.insert(key, (PathBuf::new(), locked_dependency("fedcba9876543210")));
+        let updates = lock.write().unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].previous_commit.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(updates[0].commit, "fedcba9876543210");
+    }
+
+    #[test]
+    fn offline_resolution_requires_a_local_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://example.invalid/noir-lang/missing";
+        let reference = GitReference::Tag("v0.1.0".to_string());
+
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Verify, true).unwrap();
+        assert!(matches!(
+            lock.resolve_git_dependency(url, &reference),
+            Err(ManifestError::OfflineDependencyUnavailable { .. })
+        ));
+    }
+
+    #[test]
+    fn vendored_dependencies_are_checked_against_the_lockfile() {
+        let dir = tempfile::tempdir().unwrap();
+        let dependency = locked_dependency("0123456789abcdef");
+        let vendored = vendored_git_dep_location(
+            &dir.path().join(VENDOR_DIR),
+            &dependency.url,
+            &dependency.reference,
+        )
+        .unwrap();
+        std::fs::create_dir_all(vendored.join("src")).unwrap();
+        std::fs::write(vendored.join("src").join("lib.nr"), "fn foo() {}").unwrap();
+
+        // Vendored copies must have been pinned.
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Verify, true).unwrap();
+        assert!(matches!(
+            lock.resolve_git_dependency(&dependency.url, &dependency.reference),
+            Err(ManifestError::UnlockedVendoredDependency { .. })
+        ));
+
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Verify, true).unwrap();
+        let checksum = checksum_directory(&vendored).unwrap();
+        let key = (dependency.url.clone(), dependency.reference.clone());
+        lock.resolved.insert(
+            key,
+            (vendored.clone(), LockedGitDependency { checksum, ..dependency.clone() }),
+        );
+        lock.write().unwrap();
+
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Verify, true).unwrap();
+        let location = lock.resolve_git_dependency(&dependency.url, &dependency.reference).unwrap();
+        assert_eq!(location, vendored);
+
+        std::fs::write(vendored.join("src").join("lib.nr"), "fn bar() {}").unwrap();
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Verify, true).unwrap();
+        assert!(matches!(
+            lock.resolve_git_dependency(&dependency.url, &dependency.reference),
+            Err(ManifestError::VendoredChecksumMismatch { .. })
+        ));
+
+        // Re-vendoring doesn't read the existing copies.
+        let mut lock = DependencyLock::load(dir.path(), LockMode::Vendor, true).unwrap();
+        assert!(matches!(
+            lock.resolve_git_dependency(&dependency.url, &dependency.reference),
+            Err(ManifestError::OfflineDependencyUnavailable { .. })
+        ));
+    }
+}
@@ -3,6 +3,7 @@ use nargo::{
     package::{Dependency, Package},
     workspace::Workspace,
 };
+use noirc_frontend::graph::CrateName;
 use semver::{Error, Version, VersionReq};
 
 // Parse a semver compatible version string
@@ -52,7 +53,7 @@ pub(crate) fn semver_check_package(
     // Check that all of this package's dependencies' compiler version requirements are satisfied
     for dep in package.dependencies.values() {
         match dep {
-            Dependency::Local { package } | Dependency::Remote { package } => {
+            Dependency::Local { package } | Dependency::Remote { package, .. } => {
                 semver_check_package(package, compiler_version)?;
             }
         }
@@ -61,6 +62,70 @@ pub(crate) fn semver_check_package(
     Ok(())
 }
 
+/// A `version` requirement placed by a package on one of its dependencies
+pub(crate) struct VersionRequirement {
+    pub(crate) package_name: CrateName,
+    pub(crate) dependency_name: CrateName,
+    pub(crate) required: String,
+    /// The version of the package the dependency resolved to
+    pub(crate) found: Option<String>,
+}
+
+// Check that every dependency satisfies the version required of it, and that packages which depend
+// on the same crate all accept the versions of it required elsewhere in the dependency graph
+pub(crate) fn semver_check_dependency_versions(
+    requirements: &[VersionRequirement],
+) -> Result<(), SemverError> {
+    for requirement in requirements {
+        let version_req = VersionReq::parse(&requirement.required).map_err(|err| {
+            SemverError::CouldNotParseDependencyVersion {
+                package_name: requirement.package_name.clone(),
+                dependency_name: requirement.dependency_name.clone(),
+                error: err.to_string(),
+            }
+        })?;
+
+        let Some(found) = &requirement.found else {
+            return Err(SemverError::MissingDependencyVersion {
+                package_name: requirement.package_name.clone(),
+                dependency_name: requirement.dependency_name.clone(),
+                required: requirement.required.clone(),
+            });
+        };
+        // Package versions are validated when the dependency's manifest is resolved.
+        let version = parse_semver_compatible_version(found)
+            .expect("package version should have been validated");
+        if !version_req.matches(&version) {
+            return Err(SemverError::IncompatibleDependencyVersion {
+                package_name: requirement.package_name.clone(),
+                dependency_name: requirement.dependency_name.clone(),
+                required: requirement.required.clone(),
+                found: found.clone(),
+            });
+        }
+
+        let conflict = requirements.iter().find(|other| {
+            other.dependency_name == requirement.dependency_name
+                && other.found.as_ref().is_some_and(|other_version| {
+                    let other_version = parse_semver_compatible_version(other_version)
+                        .expect("package version should have been validated");
+                    !version_req.matches(&other_version)
+                })
+        });
+        if let Some(conflict) = conflict {
+            return Err(SemverError::ConflictingDependencyVersions {
+                dependency_name: requirement.dependency_name.clone(),
+                package_name: requirement.package_name.clone(),
+                required: requirement.required.clone(),
+                other_package_name: conflict.package_name.clone(),
+                other_required: conflict.required.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 // Strip the build meta data from the version string since it is ignored by semver.
 fn strip_build_meta_data(version: &Version) -> String {
     let version_string = version.to_string();
@@ -89,6 +154,7 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            test_config: Default::default(),
         };
         if let Err(err) = semver_check_package(&package, &compiler_version) {
             panic!("semver check should have passed. compiler version is 0.1.0 and required version from the package is 0.1.0\n error: {err:?}")
@@ -120,6 +186,7 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            test_config: Default::default(),
         };
 
         let valid_dependency = Package {
@@ -130,6 +197,7 @@ mod tests {
             name: CrateName::from_str("good_dependency").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            test_config: Default::default(),
         };
         let invalid_dependency = Package {
             compiler_required_version: Some("0.2.0".to_string()),
@@ -139,6 +207,7 @@ mod tests {
             name: CrateName::from_str("bad_dependency").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            test_config: Default::default(),
         };
 
         package.dependencies.insert(
@@ -179,6 +248,7 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            test_config: Default::default(),
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -198,10 +268,64 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            test_config: Default::default(),
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
             panic!("semver check should have passed. compiler version is 0.1.0+build_data and required version from the package is 0.1.0\n The build data should be ignored\n error: {err:?}")
         };
     }
+
+    fn requirement(package: &str, required: &str, found: Option<&str>) -> VersionRequirement {
+        VersionRequirement {
+            package_name: CrateName::from_str(package).unwrap(),
+            dependency_name: CrateName::from_str("dep").unwrap(),
+            required: required.to_string(),
+            found: found.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn test_semver_dependency_versions() {
+        let compatible = [
+            requirement("a", "^0.3", Some("0.3.1")),
+            requirement("b", ">=0.3.0, <0.4.0", Some("0.3.5")),
+        ];
+        assert!(semver_check_dependency_versions(&compatible).is_ok());
+
+        let incompatible = [requirement("a", "^0.3", Some("0.4.0"))];
+        assert!(matches!(
+            semver_check_dependency_versions(&incompatible),
+            Err(SemverError::IncompatibleDependencyVersion { .. })
+        ));
+
+        let missing = [requirement("a", "^0.3", None)];
+        assert!(matches!(
+            semver_check_dependency_versions(&missing),
+            Err(SemverError::MissingDependencyVersion { .. })
+        ));
+
+        let unparseable = [requirement("a", "not a version", Some("0.3.0"))];
+        assert!(matches!(
+            semver_check_dependency_versions(&unparseable),
+            Err(SemverError::CouldNotParseDependencyVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_semver_conflicting_dependency_versions() {
+        let conflicting =
+            [requirement("a", "^0.3", Some("0.3.1")), requirement("b", "^0.4", Some("0.4.0"))];
+
+        let Err(SemverError::ConflictingDependencyVersions {
+            package_name,
+            other_package_name,
+            ..
+        }) = semver_check_dependency_versions(&conflicting)
+        else {
+            panic!("Expected conflicting versions of `dep` to be reported");
+        };
+        assert_eq!(package_name.to_string(), "a");
+        assert_eq!(other_package_name.to_string(), "b");
+    }
 }
@@ -0,0 +1,93 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    git::{vendored_git_dep_location, GitReference},
+    lockfile::DependencyLock,
+    ManifestError,
+};
+
+/// A git dependency which was copied into a workspace's vendor directory by [`vendor_dependencies`][crate::vendor_dependencies].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendoredDependency {
+    pub url: String,
+    pub reference: GitReference,
+    /// The directory the dependency was copied into
+    pub location: PathBuf,
+}
+
+/// Copies every git dependency resolved by `lock` into `vendor_dir`, replacing any earlier copies of them.
+pub(crate) fn vendor_git_dependencies(
+    lock: &DependencyLock,
+    vendor_dir: &Path,
+) -> Result<Vec<VendoredDependency>, ManifestError> {
+    let mut vendored = Vec::new();
+    for (url, reference, source) in lock.resolved_git_dependencies() {
+        let location = vendored_git_dep_location(vendor_dir, url, reference)
+            .map_err(ManifestError::GitError)?;
+        let vendor_failed =
+            |err: std::io::Error| ManifestError::VendorFailed(location.clone(), err.to_string());
+
+        if location.exists() {
+            fs::remove_dir_all(&location).map_err(vendor_failed)?;
+        }
+        copy_directory(source, &location).map_err(vendor_failed)?;
+
+        vendored.push(VendoredDependency {
+            url: url.to_string(),
+            reference: reference.clone(),
+            location,
+        });
+    }
+    Ok(vendored)
+}
+
+/// Recursively copies `from` into `to`, leaving out git's own metadata.
+fn copy_directory(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        if path.is_dir() {
+            if file_name == ".git" {
+                continue;
+            }
+            copy_directory(&path, &to.join(file_name))?;
+        } else {
+            fs::copy(&path, to.join(file_name))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::copy_directory;
+
+    #[test]
+    fn copies_everything_but_git_metadata() {
+        let from = tempfile::tempdir().unwrap();
+        fs::create_dir_all(from.path().join("src").join("nested")).unwrap();
+        fs::write(from.path().join("Nargo.toml"), "[package]").unwrap();
+        fs::write(from.path().join("src").join("nested").join("foo.nr"), "fn foo() {}").unwrap();
+        fs::create_dir_all(from.path().join(".git")).unwrap();
+        fs::write(from.path().join(".git").join("HEAD"), "ref: refs/heads/main").unwrap();
+
+        let to = tempfile::tempdir().unwrap();
+        let destination = to.path().join("github.com").join("noir-lang").join("example");
+        copy_directory(from.path(), &destination).unwrap();
+
+        assert_eq!(fs::read_to_string(destination.join("Nargo.toml")).unwrap(), "[package]");
+        assert_eq!(
+            fs::read_to_string(destination.join("src").join("nested").join("foo.nr")).unwrap(),
+            "fn foo() {}"
+        );
+        assert!(!destination.join(".git").exists());
+    }
+}
@@ -11,7 +11,8 @@ export type AbiType =
   | { kind: 'integer'; sign: Sign; width: number }
   | { kind: 'array'; length: number; type: AbiType }
   | { kind: 'tuple'; fields: AbiType[] }
-  | { kind: 'struct'; path: string; fields: { name: string; type: AbiType }[] };
+  | { kind: 'struct'; path: string; fields: { name: string; type: AbiType }[] }
+  | { kind: 'enum'; path: string; variants: { name: string; fields: AbiType[] }[] };
 
 export type AbiParameter = {
   name: string;
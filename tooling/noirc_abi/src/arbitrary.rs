@@ -77,6 +77,26 @@ fn arb_value_from_abi_type(abi_type: &AbiType) -> SBoxedStrategy<InputValue> {
             let fields: Vec<_> = fields.iter().map(arb_value_from_abi_type).collect();
             fields.prop_map(InputValue::Vec).sboxed()
         }
+
+        AbiType::Enum { variants, .. } => {
+            let variants: Vec<SBoxedStrategy<InputValue>> = variants
+                .iter()
+                .map(|(name, fields)| {
+                    let name = name.clone();
+                    let fields: Vec<_> = fields.iter().map(arb_value_from_abi_type).collect();
+                    fields
+                        .prop_map(move |fields| {
+                            InputValue::Struct(BTreeMap::from([(
+                                name.clone(),
+                                InputValue::Vec(fields),
+                            )]))
+                        })
+                        .sboxed()
+                })
+                .collect();
+
+            proptest::strategy::Union::new(variants).sboxed()
+        }
     }
 }
 
@@ -110,13 +130,22 @@ pub(super) fn arb_abi_type() -> BoxedStrategy<AbiType> {
                 prop::collection::vec(inner.clone(), 1..10)
                     .prop_map(|fields| { AbiType::Tuple { fields } })
                     .boxed(),
-                (".*", prop::collection::vec((".+", inner), 1..10))
+                (".*", prop::collection::vec((".+", inner.clone()), 1..10))
                     .prop_map(|(path, mut fields)| {
                         // Require that all field names are unique.
                         ensure_unique_strings(fields.iter_mut().map(|(field_name, _)| field_name));
                         AbiType::Struct { path, fields }
                     })
                     .boxed(),
+                (".*", prop::collection::vec((".+", prop::collection::vec(inner, 0..4)), 1..10))
+                    .prop_map(|(path, mut variants)| {
+                        // Require that all variant names are unique.
+                        ensure_unique_strings(
+                            variants.iter_mut().map(|(variant_name, _)| variant_name),
+                        );
+                        AbiType::Enum { path, variants }
+                    })
+                    .boxed(),
             ]
         },
     )
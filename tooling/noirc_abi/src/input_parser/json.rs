@@ -114,6 +114,19 @@ impl JsonTypes {
                 JsonTypes::Array(fields)
             }
 
+            (InputValue::Struct(map), AbiType::Enum { variants, .. }) => {
+                let map_with_json_types = try_btree_map(map, |(variant_name, value)| {
+                    let (_, fields) = variants
+                        .iter()
+                        .find(|(name, _)| name == variant_name)
+                        .ok_or_else(|| InputParserError::AbiTypeMismatch(abi_type.clone()))?;
+                    let fields = AbiType::Tuple { fields: fields.clone() };
+                    JsonTypes::try_from_input_value(value, &fields)
+                        .map(|json_value| (variant_name.to_owned(), json_value))
+                })?;
+                JsonTypes::Table(map_with_json_types)
+            }
+
             _ => return Err(InputParserError::AbiTypeMismatch(abi_type.clone())),
         };
         Ok(json_value)
@@ -186,6 +199,20 @@ impl InputValue {
                 InputValue::Vec(tuple_fields)
             }
 
+            (JsonTypes::Table(table), AbiType::Enum { variants, .. }) if table.len() == 1 => {
+                // Enum values are written as a table holding a single variant, e.g. `{ Some = [1] }`.
+                let (variant_name, value) = table.into_iter().next().unwrap();
+                let (_, fields) = variants
+                    .iter()
+                    .find(|(name, _)| *name == variant_name)
+                    .ok_or_else(|| InputParserError::AbiTypeMismatch(param_type.clone()))?;
+                let field_id = format!("{arg_name}.{variant_name}");
+                let fields = AbiType::Tuple { fields: fields.clone() };
+                let value = InputValue::try_from_json(value, &fields, &field_id)?;
+
+                InputValue::Struct(BTreeMap::from([(variant_name, value)]))
+            }
+
             (_, _) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
         };
 
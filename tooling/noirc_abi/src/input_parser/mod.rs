@@ -169,6 +169,22 @@ impl InputValue {
                 Ok(())
             }
 
+            (InputValue::Struct(map), AbiType::Enum { variants, .. }) if map.len() == 1 => {
+                let (variant_name, variant_value) = map.iter().next().unwrap();
+                let Some((_, fields)) = variants.iter().find(|(name, _)| name == variant_name)
+                else {
+                    return Err(InputTypecheckingError::UnexpectedField {
+                        path,
+                        typ: abi_param.clone(),
+                        extra_field: variant_name.to_string(),
+                    });
+                };
+
+                let mut path = path.clone();
+                path.push_str(&format!(".{variant_name}"));
+                variant_value.find_type_mismatch(&AbiType::Tuple { fields: fields.clone() }, path)
+            }
+
             // All other InputValue-AbiType combinations are fundamentally incompatible.
             _ => Err(InputTypecheckingError::IncompatibleTypes {
                 path,
@@ -262,6 +278,17 @@ mod serialization_tests {
                     },
                     visibility: AbiVisibility::Private,
                 },
+                AbiParameter {
+                    name: "baz".into(),
+                    typ: AbiType::Enum {
+                        path: "MyEnum".into(),
+                        variants: vec![
+                            ("Empty".into(), vec![]),
+                            ("Pair".into(), vec![AbiType::Field, AbiType::Boolean]),
+                        ],
+                    },
+                    visibility: AbiVisibility::Private,
+                },
             ],
             return_type: Some(AbiReturnType {
                 abi_type: AbiType::String { length: 5 },
@@ -285,6 +312,16 @@ mod serialization_tests {
                     ),
                 ])),
             ),
+            (
+                "baz".into(),
+                InputValue::Struct(BTreeMap::from([(
+                    "Pair".into(),
+                    InputValue::Vec(vec![
+                        InputValue::Field(42u128.into()),
+                        InputValue::Field(true.into()),
+                    ]),
+                )])),
+            ),
             (MAIN_RETURN_NAME.into(), InputValue::String("hello".to_owned())),
         ]);
 
@@ -112,6 +112,19 @@ impl TomlTypes {
                 TomlTypes::Array(fields)
             }
 
+            (InputValue::Struct(map), AbiType::Enum { variants, .. }) => {
+                let map_with_toml_types = try_btree_map(map, |(variant_name, value)| {
+                    let (_, fields) = variants
+                        .iter()
+                        .find(|(name, _)| name == variant_name)
+                        .ok_or_else(|| InputParserError::AbiTypeMismatch(abi_type.clone()))?;
+                    let fields = AbiType::Tuple { fields: fields.clone() };
+                    TomlTypes::try_from_input_value(value, &fields)
+                        .map(|toml_value| (variant_name.to_owned(), toml_value))
+                })?;
+                TomlTypes::Table(map_with_toml_types)
+            }
+
             _ => return Err(InputParserError::AbiTypeMismatch(abi_type.clone())),
         };
         Ok(toml_value)
@@ -173,6 +186,20 @@ impl InputValue {
                 InputValue::Vec(tuple_fields)
             }
 
+            (TomlTypes::Table(table), AbiType::Enum { variants, .. }) if table.len() == 1 => {
+                // Enum values are written as a table holding a single variant, e.g. `{ Some = [1] }`.
+                let (variant_name, value) = table.into_iter().next().unwrap();
+                let (_, fields) = variants
+                    .iter()
+                    .find(|(name, _)| *name == variant_name)
+                    .ok_or_else(|| InputParserError::AbiTypeMismatch(param_type.clone()))?;
+                let field_id = format!("{arg_name}.{variant_name}");
+                let fields = AbiType::Tuple { fields: fields.clone() };
+                let value = InputValue::try_from_toml(value, &fields, &field_id)?;
+
+                InputValue::Struct(BTreeMap::from([(variant_name, value)]))
+            }
+
             (_, _) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
         };
 
@@ -75,6 +75,14 @@ pub enum AbiType {
     String {
         length: u32,
     },
+    Enum {
+        path: String,
+        #[serde(
+            serialize_with = "serialization::serialize_enum_variants",
+            deserialize_with = "serialization::deserialize_enum_variants"
+        )]
+        variants: Vec<(String, Vec<AbiType>)>,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -110,6 +118,11 @@ impl AbiType {
                 fields.iter().fold(0, |acc, field_typ| acc + field_typ.field_count())
             }
             AbiType::String { length } => *length,
+            // An enum is encoded as its tag followed by the fields of every variant,
+            // with the variants which aren't in use being filled with zeroes.
+            AbiType::Enum { variants, .. } => variants.iter().fold(1, |acc, (_, fields)| {
+                acc + fields.iter().map(AbiType::field_count).sum::<u32>()
+            }),
         }
     }
 }
@@ -142,6 +155,16 @@ impl From<&AbiType> for PrintableType {
             AbiType::Integer { sign: Sign::Signed, width } => {
                 PrintableType::SignedInteger { width: *width }
             }
+            AbiType::Enum { path, variants } => {
+                let variants = variants
+                    .iter()
+                    .map(|(name, fields)| (name.clone(), fields.iter().map(Into::into).collect()))
+                    .collect();
+                PrintableType::Enum {
+                    name: path.split("::").last().unwrap_or_default().to_string(),
+                    variants,
+                }
+            }
         }
     }
 }
@@ -302,6 +325,29 @@ impl Abi {
                     encoded_value.extend(Self::encode_value(value, typ)?);
                 }
             }
+            (InputValue::Struct(object), AbiType::Enum { variants, .. }) => {
+                let (variant_name, variant_value) =
+                    object.into_iter().next().expect("enum values should hold a single variant");
+                let tag = variants
+                    .iter()
+                    .position(|(name, _)| *name == variant_name)
+                    .expect("value should have already been checked to match abi type");
+                encoded_value.push(FieldElement::from(tag as u128));
+
+                for (index, (_, fields)) in variants.iter().enumerate() {
+                    if index == tag {
+                        let InputValue::Vec(values) = &variant_value else {
+                            unreachable!("value should have already been checked to match abi type")
+                        };
+                        for (value, typ) in values.iter().zip(fields) {
+                            encoded_value.extend(Self::encode_value(value.clone(), typ)?);
+                        }
+                    } else {
+                        let field_count = fields.iter().map(AbiType::field_count).sum::<u32>();
+                        encoded_value.extend((0..field_count).map(|_| FieldElement::zero()));
+                    }
+                }
+            }
             _ => unreachable!("value should have already been checked to match abi type"),
         }
         Ok(encoded_value)
@@ -414,6 +460,28 @@ pub fn decode_value(
 
             InputValue::Vec(tuple_elements)
         }
+        AbiType::Enum { path, variants } => {
+            let tag = field_iterator.next().unwrap();
+
+            // Every variant's fields are present in the encoding so we need to decode all of them
+            // to advance the iterator, but we only keep the ones of the variant that is in use.
+            let mut decoded_variants = Vec::with_capacity(variants.len());
+            for (name, fields) in variants {
+                let mut variant_elements = Vec::with_capacity(fields.len());
+                for field_typ in fields {
+                    variant_elements.push(decode_value(field_iterator, field_typ)?);
+                }
+                decoded_variants.push((name.clone(), InputValue::Vec(variant_elements)));
+            }
+
+            let variant = tag
+                .try_to_u64()
+                .and_then(|tag| usize::try_from(tag).ok())
+                .filter(|tag| *tag < decoded_variants.len())
+                .ok_or_else(|| AbiError::InvalidEnumTag { path: path.clone(), tag })?;
+
+            InputValue::Struct(BTreeMap::from([decoded_variants.swap_remove(variant)]))
+        }
     };
 
     Ok(value)
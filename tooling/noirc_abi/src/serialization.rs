@@ -71,6 +71,40 @@ where
     Ok(vecmap(fields_vector, |StructFieldValue { name, value }| (name, value)))
 }
 
+// Enum variants are serialized in the same way as struct fields, as the ordering of the variants determines the
+// tag of each variant.
+
+#[derive(Serialize, Deserialize)]
+struct EnumVariant {
+    name: String,
+    fields: Vec<AbiType>,
+}
+
+pub(crate) fn serialize_enum_variants<S>(
+    variants: &[(String, Vec<AbiType>)],
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let variants_vector = vecmap(variants, |(name, fields)| EnumVariant {
+        name: name.to_owned(),
+        fields: fields.to_owned(),
+    });
+
+    variants_vector.serialize(s)
+}
+
+pub(crate) fn deserialize_enum_variants<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(String, Vec<AbiType>)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let variants_vector = Vec::<EnumVariant>::deserialize(deserializer)?;
+    Ok(vecmap(variants_vector, |EnumVariant { name, fields }| (name, fields)))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{AbiParameter, AbiType, AbiVisibility, Sign};
@@ -163,5 +197,45 @@ mod tests {
         };
         let deserialized_struct: AbiParameter = serde_json::from_str(serialized_struct).unwrap();
         assert_eq!(deserialized_struct, expected_struct);
+
+        let serialized_enum = "{
+            \"name\":\"thing4\",
+            \"type\": {
+                \"kind\":\"enum\",
+                \"path\": \"MyEnum\",
+                \"variants\": [
+                    {
+                        \"name\": \"Empty\",
+                        \"fields\": []
+                    },
+                    {
+                        \"name\":\"Pair\",
+                        \"fields\": [
+                            {
+                                \"kind\":\"field\"
+                            },
+                            {
+                                \"kind\":\"boolean\"
+                            }
+                        ]
+                    }
+                ]
+            },
+            \"visibility\":\"private\"
+        }";
+
+        let expected_enum = AbiParameter {
+            name: "thing4".to_string(),
+            typ: AbiType::Enum {
+                path: "MyEnum".to_string(),
+                variants: vec![
+                    ("Empty".to_string(), vec![]),
+                    ("Pair".to_string(), vec![AbiType::Field, AbiType::Boolean]),
+                ],
+            },
+            visibility: AbiVisibility::Private,
+        };
+        let deserialized_enum: AbiParameter = serde_json::from_str(serialized_enum).unwrap();
+        assert_eq!(deserialized_enum, expected_enum);
     }
 }